@@ -0,0 +1,24 @@
+//! Times the built-in workloads on small, medium and wide synthetic datasets
+//!
+//! Run with `cargo bench --features bench`.
+
+use tsetlin::bench::{SyntheticConfig, Workload, WorkloadKind};
+
+fn main() {
+    let configs = [
+        SyntheticConfig::default(),
+        SyntheticConfig { samples: 5000, ..Default::default() },
+        SyntheticConfig { features: 512, clauses: 200, ..Default::default() },
+    ];
+    let kinds = [
+        WorkloadKind::ClauseEvaluation,
+        WorkloadKind::EpochTraining,
+        WorkloadKind::BatchPrediction,
+    ];
+
+    for config in configs {
+        for kind in kinds {
+            println!("{}", Workload::new(kind, config).measure(10));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! One-class Tsetlin machine for anomaly detection
+//!
+//! The detector is trained on normal samples only. Every epoch it draws
+//! uniformly random samples as a synthetic negative class, so the clauses learn
+//! patterns that distinguish normal data from noise. New samples are scored by
+//! their vote sum: low scores mean the sample looks unlike the training data.
+
+use crate::machine::TsetlinMachine;
+use ndarray::{concatenate, s, Array1, Array2, Axis};
+use rand::{Rng, SeedableRng};
+
+/// Tsetlin machine that flags samples unlike its normal training data
+#[derive(Debug, Clone)]
+pub struct AnomalyTsetlinMachine {
+    /// Underlying binary machine (normal vs. noise)
+    machine: TsetlinMachine,
+    /// Number of noise samples generated per normal sample each epoch
+    noise_ratio: f64,
+    /// Samples scoring below this vote sum are anomalies
+    anomaly_threshold: i32,
+    /// Random number generator for noise generation
+    rng: crate::rng::TsetlinRng,
+}
+
+impl AnomalyTsetlinMachine {
+    /// Create a new anomaly detector
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `num_clauses` - Number of clauses (must be even)
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold of the underlying machine
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::anomaly::AnomalyTsetlinMachine;
+    /// let detector = AnomalyTsetlinMachine::new(10, 100, 2.0, 10.0);
+    /// assert_eq!(detector.anomaly_threshold(), 0);
+    /// ```
+    pub fn new(num_features: usize, num_clauses: usize, specificity: f64, threshold: f64) -> Self {
+        Self {
+            machine: TsetlinMachine::new(num_features, num_clauses, specificity, threshold),
+            noise_ratio: 1.0,
+            anomaly_threshold: 0,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a new anomaly detector with default parameters
+    pub fn with_defaults(num_features: usize, num_clauses: usize) -> Self {
+        Self::new(num_features, num_clauses, 2.0, 1.0)
+    }
+
+    /// Seed the noise generator and the underlying machine for reproducible training
+    ///
+    /// The machine is seeded with `seed + 1`, so noise and feedback draw from
+    /// separate streams.
+    pub fn seed(self, seed: u64) -> Self {
+        Self {
+            machine: self.machine.seed(seed.wrapping_add(1)),
+            rng: crate::rng::TsetlinRng::seed_from_u64(seed),
+            ..self
+        }
+    }
+
+    /// Set how many noise samples are generated per normal sample (default: 1.0)
+    pub fn with_noise_ratio(mut self, noise_ratio: f64) -> Self {
+        assert!(noise_ratio > 0.0, "Noise ratio must be positive");
+        self.noise_ratio = noise_ratio;
+        self
+    }
+
+    /// Train on normal samples only
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::anomaly::AnomalyTsetlinMachine;
+    /// use ndarray::Array2;
+    ///
+    /// let normal = Array2::from_shape_vec((3, 3), vec![
+    ///     true, true, false,
+    ///     true, true, true,
+    ///     true, true, false,
+    /// ]).unwrap();
+    ///
+    /// let mut detector = AnomalyTsetlinMachine::with_defaults(3, 20);
+    /// detector.fit(&normal, 50);
+    /// let scores = detector.score(&normal);
+    /// assert_eq!(scores.len(), 3);
+    /// ```
+    pub fn fit(&mut self, normal: &Array2<bool>, epochs: usize) {
+        assert_eq!(normal.ncols(), self.machine.num_features());
+
+        let num_noise = ((normal.nrows() as f64) * self.noise_ratio).round().max(1.0) as usize;
+        let mut labels = Array1::from_elem(normal.nrows() + num_noise, false);
+        labels.slice_mut(s![..normal.nrows()]).fill(true);
+
+        for _ in 0..epochs {
+            let noise = self.generate_noise(num_noise);
+            let combined = concatenate(Axis(0), &[normal.view(), noise.view()])
+                .expect("noise has the same number of features");
+            self.machine.fit(&combined, &labels, 1);
+        }
+    }
+
+    /// Anomaly score (vote sum) for each sample; lower means more anomalous
+    pub fn score(&self, features: &Array2<bool>) -> Array1<i32> {
+        self.machine.predict_votes(features)
+    }
+
+    /// Anomaly score (vote sum) for a single sample
+    pub fn score_single(&self, features: &[bool]) -> i32 {
+        self.machine.vote_single(features)
+    }
+
+    /// Flag anomalies in a dataset (true = anomaly)
+    pub fn predict(&self, features: &Array2<bool>) -> Array1<bool> {
+        self.score(features).mapv(|score| score < self.anomaly_threshold)
+    }
+
+    /// Check whether a single sample is an anomaly
+    pub fn is_anomaly(&self, features: &[bool]) -> bool {
+        self.score_single(features) < self.anomaly_threshold
+    }
+
+    /// Choose the anomaly threshold from a validation split of normal samples
+    ///
+    /// The threshold is set so that at most `false_positive_rate` of the
+    /// validation samples are flagged as anomalies. Returns the new threshold.
+    pub fn fit_threshold(&mut self, validation: &Array2<bool>, false_positive_rate: f64) -> i32 {
+        assert!(validation.nrows() > 0, "Validation set must not be empty");
+        assert!(
+            (0.0..1.0).contains(&false_positive_rate),
+            "False positive rate must be in [0, 1)"
+        );
+
+        let mut scores = self.score(validation).to_vec();
+        scores.sort_unstable();
+
+        let index = (false_positive_rate * scores.len() as f64).floor() as usize;
+        self.anomaly_threshold = scores[index];
+        self.anomaly_threshold
+    }
+
+    /// Set the anomaly threshold directly
+    pub fn set_anomaly_threshold(&mut self, anomaly_threshold: i32) {
+        self.anomaly_threshold = anomaly_threshold;
+    }
+
+    /// Current anomaly threshold
+    pub fn anomaly_threshold(&self) -> i32 {
+        self.anomaly_threshold
+    }
+
+    /// Underlying binary machine
+    pub fn machine(&self) -> &TsetlinMachine {
+        &self.machine
+    }
+
+    /// Draw uniformly random samples used as the negative class
+    fn generate_noise(&mut self, num_samples: usize) -> Array2<bool> {
+        let num_features = self.machine.num_features();
+        Array2::from_shape_fn((num_samples, num_features), |_| self.rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normal_dataset() -> Array2<bool> {
+        Array2::from_shape_vec((4, 4), vec![
+            true, true, false, false,
+            true, true, false, true,
+            true, true, true, false,
+            true, true, false, false,
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_anomaly_training() {
+        let normal = normal_dataset();
+        let mut detector = AnomalyTsetlinMachine::new(4, 16, 10.0, 10.0).seed(1).with_noise_ratio(2.0);
+        crate::testing::spread_bank_literals(detector.machine.clause_bank_mut());
+        detector.fit(&normal, 100);
+
+        // Normal samples always have the first two features set
+        let planted = Array2::from_shape_vec((3, 4), vec![
+            false, false, true, true,
+            false, false, false, false,
+            false, false, true, false,
+        ]).unwrap();
+        let normal_scores = detector.score(&normal);
+        let planted_scores = detector.score(&planted);
+        let lowest_normal = normal_scores.iter().min().unwrap();
+        assert!(
+            planted_scores.iter().all(|score| score < lowest_normal),
+            "planted {planted_scores} vs normal {normal_scores}"
+        );
+        assert_eq!(detector.predict(&normal).len(), 4);
+    }
+
+    #[test]
+    fn test_seeded_training_is_reproducible() {
+        let normal = normal_dataset();
+        let train = || {
+            let mut detector = AnomalyTsetlinMachine::with_defaults(4, 20).seed(7);
+            detector.fit(&normal, 20);
+            detector
+        };
+        let (first, second) = (train(), train());
+        assert_eq!(first.machine().rules(), second.machine().rules());
+        assert_eq!(first.score(&normal), second.score(&normal));
+    }
+
+    #[test]
+    fn test_fit_threshold() {
+        let normal = normal_dataset();
+        let mut detector = AnomalyTsetlinMachine::with_defaults(4, 20);
+        detector.fit(&normal, 20);
+
+        // With a zero false positive rate no validation sample is flagged
+        let threshold = detector.fit_threshold(&normal, 0.0);
+        assert_eq!(detector.anomaly_threshold(), threshold);
+        assert!(detector.predict(&normal).iter().all(|&anomaly| !anomaly));
+    }
+
+    #[test]
+    fn test_manual_threshold() {
+        let mut detector = AnomalyTsetlinMachine::with_defaults(4, 20);
+        // Untrained clauses all fire and cancel out to a score of zero
+        assert_eq!(detector.score_single(&[true, false, true, false]), 0);
+
+        detector.set_anomaly_threshold(1);
+        assert!(detector.is_anomaly(&[true, false, true, false]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Noise ratio must be positive")]
+    fn test_invalid_noise_ratio() {
+        AnomalyTsetlinMachine::with_defaults(4, 20).with_noise_ratio(0.0);
+    }
+}
@@ -0,0 +1,105 @@
+//! Data augmentation for boolean inputs
+
+use ndarray::Array2;
+use rand::Rng;
+
+/// Randomly flip input bits
+///
+/// Used during training (see [`TsetlinMachine::augment`]) every sample is
+/// perturbed afresh in each epoch, which makes clauses less sensitive to
+/// single noisy features. The machine's random number generator drives the
+/// flips, so seeded machines stay reproducible.
+///
+/// [`TsetlinMachine::augment`]: crate::TsetlinMachine::augment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitFlip {
+    /// Probability of flipping each bit
+    pub probability: f64,
+}
+
+impl BitFlip {
+    /// Create a bit-flip augmentation
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use rand::SeedableRng;
+    /// use tsetlin::augment::BitFlip;
+    ///
+    /// let mut rng = tsetlin::rng::TsetlinRng::seed_from_u64(0);
+    /// let flipped = BitFlip::new(1.0).apply(&array![[true, false]], &mut rng);
+    /// assert_eq!(flipped, array![[false, true]]);
+    /// ```
+    pub fn new(probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "Flip probability must be between 0 and 1"
+        );
+
+        Self { probability }
+    }
+
+    /// Flip the bits of one sample in place
+    pub fn flip_sample<R: Rng>(&self, sample: &mut [bool], rng: &mut R) {
+        if self.probability <= 0.0 {
+            return;
+        }
+        for bit in sample.iter_mut() {
+            if rng.gen::<f64>() < self.probability {
+                *bit = !*bit;
+            }
+        }
+    }
+
+    /// Return a perturbed copy of a feature matrix
+    pub fn apply<R: Rng>(&self, features: &Array2<bool>, rng: &mut R) -> Array2<bool> {
+        let mut augmented = features.clone();
+        if self.probability > 0.0 {
+            for bit in augmented.iter_mut() {
+                if rng.gen::<f64>() < self.probability {
+                    *bit = !*bit;
+                }
+            }
+        }
+        augmented
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_zero_probability_is_identity() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(1);
+        let mut sample = vec![true, false, true];
+        BitFlip::new(0.0).flip_sample(&mut sample, &mut rng);
+        assert_eq!(sample, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_flip_rate() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(2);
+        let features = Array2::from_elem((100, 100), false);
+        let flipped = BitFlip::new(0.2).apply(&features, &mut rng);
+
+        let rate = flipped.iter().filter(|&&bit| bit).count() as f64 / 10_000.0;
+        assert!((rate - 0.2).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_seeded_flips_are_reproducible() {
+        let features = Array2::from_elem((10, 10), true);
+        let flip = BitFlip::new(0.5);
+        let a = flip.apply(&features, &mut crate::rng::TsetlinRng::seed_from_u64(7));
+        let b = flip.apply(&features, &mut crate::rng::TsetlinRng::seed_from_u64(7));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Flip probability must be between 0 and 1")]
+    fn test_invalid_probability() {
+        BitFlip::new(1.5);
+    }
+}
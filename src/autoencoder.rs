@@ -0,0 +1,328 @@
+//! Self-supervised Tsetlin machine autoencoder
+//!
+//! The autoencoder learns to reconstruct every input bit from the remaining
+//! bits of the sample. Each reconstructed feature owns a clause bank that sees
+//! the input with that feature masked out, so its clauses have to capture how
+//! the feature relates to the rest of the sample. The firing pattern of all
+//! clauses forms a boolean embedding that can be fed to a downstream
+//! classifier or compared between samples.
+
+use crate::clause::ClauseBank;
+use ndarray::{Array1, Array2};
+use rand::{prelude::SliceRandom, SeedableRng};
+
+/// Tsetlin machine that reconstructs masked input bits
+#[derive(Debug, Clone)]
+pub struct TsetlinAutoencoder {
+    /// One clause bank per reconstructed feature
+    banks: Vec<ClauseBank>,
+    /// Number of input features
+    num_features: usize,
+    /// Number of clauses in each bank
+    clauses_per_feature: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Decision threshold
+    threshold: f64,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl TsetlinAutoencoder {
+    /// Create a new autoencoder
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `clauses_per_feature` - Number of clauses reconstructing each feature (must be even)
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::autoencoder::TsetlinAutoencoder;
+    /// let autoencoder = TsetlinAutoencoder::new(8, 10, 2.0, 5.0);
+    /// assert_eq!(autoencoder.embedding_dim(), 80);
+    /// ```
+    pub fn new(
+        num_features: usize,
+        clauses_per_feature: usize,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        assert!(
+            clauses_per_feature.is_multiple_of(2),
+            "Number of clauses per feature must be even"
+        );
+
+        let banks = (0..num_features)
+            .map(|_| ClauseBank::new(num_features, clauses_per_feature, 100))
+            .collect();
+        let rng = crate::rng::TsetlinRng::from_entropy();
+
+        Self {
+            banks,
+            num_features,
+            clauses_per_feature,
+            specificity,
+            threshold,
+            rng,
+        }
+    }
+
+    /// Create a new autoencoder with default parameters
+    pub fn with_defaults(num_features: usize, clauses_per_feature: usize) -> Self {
+        Self::new(num_features, clauses_per_feature, 2.0, 1.0)
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Train the autoencoder on unlabeled samples
+    ///
+    /// Every epoch visits the samples in random order and, for each sample,
+    /// trains every feature's clause bank to predict that feature from the
+    /// sample with the feature masked.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::autoencoder::TsetlinAutoencoder;
+    /// use ndarray::Array2;
+    ///
+    /// // The second feature always copies the first one
+    /// let features = Array2::from_shape_vec((2, 2), vec![
+    ///     true, true, false, false
+    /// ]).unwrap();
+    ///
+    /// let mut autoencoder = TsetlinAutoencoder::with_defaults(2, 10);
+    /// autoencoder.fit(&features, 50);
+    /// let embeddings = autoencoder.embed(&features);
+    /// assert_eq!(embeddings.ncols(), 20);
+    /// ```
+    pub fn fit(&mut self, features: &Array2<bool>, epochs: usize) {
+        assert_eq!(features.ncols(), self.num_features);
+
+        let mut indices: Vec<usize> = (0..features.nrows()).collect();
+        let mut buffer = vec![false; self.num_features];
+
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+
+            for &idx in &indices {
+                let row = features.row(idx);
+                for (feature, bank) in self.banks.iter_mut().enumerate() {
+                    copy_masked(&mut buffer, row.iter().copied(), feature);
+                    bank.update(
+                        &buffer,
+                        row[feature],
+                        self.threshold,
+                        self.specificity,
+                        &mut self.rng,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Reconstruct every feature of a single sample from the remaining features
+    pub fn reconstruct_single(&self, sample: &[bool]) -> Vec<bool> {
+        assert_eq!(sample.len(), self.num_features);
+
+        let mut buffer = vec![false; self.num_features];
+        self.banks
+            .iter()
+            .enumerate()
+            .map(|(feature, bank)| {
+                copy_masked(&mut buffer, sample.iter().copied(), feature);
+                bank.vote(&buffer) > 0
+            })
+            .collect()
+    }
+
+    /// Reconstruct a dataset (samples x features)
+    pub fn reconstruct(&self, features: &Array2<bool>) -> Array2<bool> {
+        assert_eq!(features.ncols(), self.num_features);
+
+        let mut reconstructed = Array2::from_elem(features.raw_dim(), false);
+        for (row, mut out) in features.rows().into_iter().zip(reconstructed.rows_mut()) {
+            let sample = row.to_vec();
+            out.assign(&Array1::from_vec(self.reconstruct_single(&sample)));
+        }
+        reconstructed
+    }
+
+    /// Fraction of bits reconstructed correctly (0.0 to 1.0)
+    pub fn reconstruction_accuracy(&self, features: &Array2<bool>) -> f64 {
+        let reconstructed = self.reconstruct(features);
+        let correct = reconstructed
+            .iter()
+            .zip(features.iter())
+            .filter(|(&predicted, &actual)| predicted == actual)
+            .count();
+
+        correct as f64 / features.len() as f64
+    }
+
+    /// Clause-based embedding of a single sample
+    ///
+    /// The embedding holds the output of every clause in every bank, evaluated
+    /// on the sample with the bank's feature masked.
+    pub fn embed_single(&self, sample: &[bool]) -> Vec<bool> {
+        assert_eq!(sample.len(), self.num_features);
+
+        let mut buffer = vec![false; self.num_features];
+        let mut embedding = Vec::with_capacity(self.embedding_dim());
+        for (feature, bank) in self.banks.iter().enumerate() {
+            copy_masked(&mut buffer, sample.iter().copied(), feature);
+            embedding.extend(bank.clause_outputs(&buffer));
+        }
+        embedding
+    }
+
+    /// Clause-based embeddings of a dataset (samples x `embedding_dim()`)
+    pub fn embed(&self, features: &Array2<bool>) -> Array2<bool> {
+        assert_eq!(features.ncols(), self.num_features);
+
+        let mut embeddings = Array2::from_elem((features.nrows(), self.embedding_dim()), false);
+        for (row, mut out) in features.rows().into_iter().zip(embeddings.rows_mut()) {
+            let sample = row.to_vec();
+            out.assign(&Array1::from_vec(self.embed_single(&sample)));
+        }
+        embeddings
+    }
+
+    /// Jaccard similarity between the embeddings of two samples
+    ///
+    /// Returns 1.0 when no clause fires for either sample.
+    pub fn similarity(&self, a: &[bool], b: &[bool]) -> f64 {
+        let embedding_a = self.embed_single(a);
+        let embedding_b = self.embed_single(b);
+
+        let mut intersection = 0;
+        let mut union = 0;
+        for (&x, &y) in embedding_a.iter().zip(embedding_b.iter()) {
+            if x && y {
+                intersection += 1;
+            }
+            if x || y {
+                union += 1;
+            }
+        }
+
+        if union == 0 {
+            1.0
+        } else {
+            intersection as f64 / union as f64
+        }
+    }
+
+    /// Length of the embedding produced by `embed`
+    pub fn embedding_dim(&self) -> usize {
+        self.num_features * self.clauses_per_feature
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Number of clauses in each feature's bank
+    pub fn clauses_per_feature(&self) -> usize {
+        self.clauses_per_feature
+    }
+}
+
+/// Copy a sample into `buffer`, hiding the value of `masked_feature`
+fn copy_masked(buffer: &mut [bool], sample: impl Iterator<Item = bool>, masked_feature: usize) {
+    for (slot, value) in buffer.iter_mut().zip(sample) {
+        *slot = value;
+    }
+    buffer[masked_feature] = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copy_dataset() -> Array2<bool> {
+        // Feature 1 always equals feature 0, feature 2 is its negation
+        Array2::from_shape_vec((4, 3), vec![
+            true, true, false,
+            false, false, true,
+            true, true, false,
+            false, false, true,
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_autoencoder_creation() {
+        let autoencoder = TsetlinAutoencoder::new(3, 10, 2.0, 1.0);
+        assert_eq!(autoencoder.num_features(), 3);
+        assert_eq!(autoencoder.clauses_per_feature(), 10);
+        assert_eq!(autoencoder.embedding_dim(), 30);
+    }
+
+    #[test]
+    fn test_autoencoder_shapes() {
+        let features = copy_dataset();
+        let mut autoencoder = TsetlinAutoencoder::with_defaults(3, 10);
+        autoencoder.fit(&features, 10);
+
+        assert_eq!(autoencoder.reconstruct(&features).dim(), (4, 3));
+        assert_eq!(autoencoder.embed(&features).dim(), (4, 30));
+
+        let accuracy = autoencoder.reconstruction_accuracy(&features);
+        assert!((0.0..=1.0).contains(&accuracy));
+    }
+
+    #[test]
+    fn test_seeded_training_is_reproducible() {
+        let features = copy_dataset();
+        let train = || {
+            let mut autoencoder = TsetlinAutoencoder::with_defaults(3, 10).seed(5);
+            autoencoder.fit(&features, 10);
+            autoencoder
+        };
+        let (first, second) = (train(), train());
+        assert_eq!(first.embed(&features), second.embed(&features));
+        assert_eq!(first.reconstruct(&features), second.reconstruct(&features));
+    }
+
+    #[test]
+    fn test_reconstructs_copied_features() {
+        let features = copy_dataset();
+        let mut autoencoder = TsetlinAutoencoder::new(3, 12, 4.0, 4.0).seed(7);
+        for bank in &mut autoencoder.banks {
+            crate::testing::spread_bank_literals(bank);
+        }
+        autoencoder.fit(&features, 50);
+
+        // Every feature is determined by either of the other two
+        assert_eq!(autoencoder.reconstruct(&features), features);
+        assert_eq!(autoencoder.reconstruction_accuracy(&features), 1.0);
+    }
+
+    #[test]
+    fn test_untrained_embedding_fires_everywhere() {
+        // Empty clauses always fire, so untrained embeddings are all true
+        let autoencoder = TsetlinAutoencoder::with_defaults(3, 4);
+        let embedding = autoencoder.embed_single(&[true, false, true]);
+        assert!(embedding.iter().all(|&fired| fired));
+        assert_eq!(autoencoder.similarity(&[true, false, true], &[false, true, false]), 1.0);
+    }
+
+    #[test]
+    fn test_copy_masked() {
+        let mut buffer = vec![false; 3];
+        copy_masked(&mut buffer, [true, true, true].into_iter(), 1);
+        assert_eq!(buffer, vec![true, false, true]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of clauses per feature must be even")]
+    fn test_autoencoder_odd_clauses() {
+        TsetlinAutoencoder::with_defaults(3, 5);
+    }
+}
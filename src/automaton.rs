@@ -32,6 +32,22 @@ impl TsetlinAutomaton {
         }
     }
 
+    /// Create a new automaton starting in a specific state
+    ///
+    /// The state is clamped to `-num_states..=num_states`.
+    pub fn with_state(num_states: u32, state: i32) -> Self {
+        let bound = num_states as i32;
+        Self {
+            state: state.clamp(-bound, bound),
+            num_states,
+        }
+    }
+
+    /// Current state counter (positive for Include, non-positive for Exclude)
+    pub fn state(&self) -> i32 {
+        self.state
+    }
+
     /// Get the current action of the automaton
     pub fn action(&self) -> Action {
         if self.state > 0 {
@@ -41,6 +57,11 @@ impl TsetlinAutomaton {
         }
     }
 
+    /// Number of states in each action
+    pub fn num_states(&self) -> u32 {
+        self.num_states
+    }
+
     /// Reward the automaton (reinforce current action)
     pub fn reward(&mut self) {
         if self.state > 0 {
@@ -116,9 +137,18 @@ mod tests {
         assert_eq!(automaton.action(), Action::Include);
     }
 
+    #[test]
+    fn test_with_state() {
+        let automaton = TsetlinAutomaton::with_state(10, 3);
+        assert_eq!(automaton.state(), 3);
+        assert_eq!(automaton.action(), Action::Include);
+
+        assert_eq!(TsetlinAutomaton::with_state(10, -50).state(), -10);
+    }
+
     #[test]
     fn test_update_with_probability() {
-        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(42);
         let mut automaton = TsetlinAutomaton::new(100);
         let initial_state = automaton.state;
         
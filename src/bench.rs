@@ -0,0 +1,249 @@
+//! Reusable benchmark workloads (requires the `bench` feature)
+//!
+//! Each [`Workload`] owns a machine and a synthetic dataset sized by a
+//! [`SyntheticConfig`], and [`Workload::run`] performs one unit of work:
+//! evaluating every clause on every sample, training one epoch, or scoring
+//! the whole dataset. The `workloads` bench target times the default
+//! configurations with [`Workload::measure`]; a criterion harness can call
+//! `run` inside `b.iter` to benchmark other configurations.
+//!
+//! ```text
+//! cargo bench --features bench
+//! ```
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use crate::rng::TsetlinRng;
+use ndarray::{Array1, Array2};
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Size and difficulty of a synthetic dataset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyntheticConfig {
+    /// Number of samples
+    pub samples: usize,
+    /// Number of boolean features (at least 3)
+    pub features: usize,
+    /// Number of clauses of the benchmarked machine
+    pub clauses: usize,
+    /// Probability of flipping each label
+    pub label_noise: f64,
+    /// Seed of the data and the machine
+    pub seed: u64,
+}
+
+impl Default for SyntheticConfig {
+    fn default() -> Self {
+        Self {
+            samples: 1000,
+            features: 64,
+            clauses: 100,
+            label_noise: 0.05,
+            seed: 42,
+        }
+    }
+}
+
+/// Random features labelled by the planted rule `(x0 AND NOT x1) OR x2`
+///
+/// # Example
+/// ```
+/// use tsetlin::bench::{synthetic_dataset, SyntheticConfig};
+///
+/// let config = SyntheticConfig { samples: 10, features: 8, label_noise: 0.0, ..Default::default() };
+/// let (features, labels) = synthetic_dataset(&config);
+/// assert_eq!(features.dim(), (10, 8));
+/// let rule = |i: usize| (features[[i, 0]] && !features[[i, 1]]) || features[[i, 2]];
+/// assert!((0..10).all(|i| labels[i] == rule(i)));
+/// ```
+pub fn synthetic_dataset(config: &SyntheticConfig) -> (Array2<bool>, Array1<bool>) {
+    assert!(config.features >= 3, "Synthetic data needs at least 3 features");
+
+    let mut rng = TsetlinRng::seed_from_u64(config.seed);
+    let features = Array2::from_shape_simple_fn((config.samples, config.features), || rng.gen());
+    let labels = features
+        .rows()
+        .into_iter()
+        .map(|row| {
+            let label = (row[0] && !row[1]) || row[2];
+            label != (rng.gen::<f64>() < config.label_noise)
+        })
+        .collect();
+    (features, labels)
+}
+
+/// Unit of work timed by a [`Workload`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkloadKind {
+    /// Evaluate every clause on every sample
+    ClauseEvaluation,
+    /// Train one epoch
+    EpochTraining,
+    /// Compute the vote sums of all samples
+    BatchPrediction,
+}
+
+/// A machine and dataset prepared for repeated timing
+#[derive(Debug, Clone)]
+pub struct Workload {
+    /// What `run` does
+    kind: WorkloadKind,
+    /// Dataset size and machine configuration
+    config: SyntheticConfig,
+    /// Benchmarked machine
+    machine: TsetlinMachine,
+    /// Synthetic features
+    features: Array2<bool>,
+    /// Synthetic labels
+    labels: Array1<bool>,
+}
+
+impl Workload {
+    /// Prepare a workload
+    ///
+    /// Evaluation and prediction run on a machine trained for a few epochs,
+    /// so clauses hold literals as they would in practice.
+    pub fn new(kind: WorkloadKind, config: SyntheticConfig) -> Self {
+        let (features, labels) = synthetic_dataset(&config);
+        let mut machine = TsetlinMachine::new(config.features, config.clauses, 3.9, 15.0)
+            .seed(config.seed);
+        if kind != WorkloadKind::EpochTraining {
+            machine.fit(&features, &labels, 3);
+        }
+
+        Self {
+            kind,
+            config,
+            machine,
+            features,
+            labels,
+        }
+    }
+
+    /// Perform one unit of work and return a checksum of its result
+    pub fn run(&mut self) -> u64 {
+        match self.kind {
+            WorkloadKind::ClauseEvaluation => {
+                let mut sample = Vec::with_capacity(self.config.features);
+                let mut fired = 0;
+                for i in 0..self.features.num_samples() {
+                    self.features.sample_into(i, &mut sample);
+                    fired += self
+                        .machine
+                        .clause_bank()
+                        .clauses()
+                        .iter()
+                        .filter(|clause| clause.evaluate(&sample))
+                        .count() as u64;
+                }
+                fired
+            }
+            WorkloadKind::EpochTraining => {
+                self.machine.fit(&self.features, &self.labels, 1);
+                self.machine.num_included_literals() as u64
+            }
+            WorkloadKind::BatchPrediction => {
+                let votes = self.machine.predict_votes(&self.features);
+                votes.iter().map(|&vote| u64::from(vote as u32)).sum()
+            }
+        }
+    }
+
+    /// Time `iterations` runs after one warm-up run
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::bench::{SyntheticConfig, Workload, WorkloadKind};
+    ///
+    /// let config = SyntheticConfig { samples: 20, features: 8, clauses: 10, ..Default::default() };
+    /// let mut workload = Workload::new(WorkloadKind::BatchPrediction, config);
+    /// let measurement = workload.measure(3);
+    /// assert_eq!(measurement.iterations, 3);
+    /// assert!(measurement.min <= measurement.mean);
+    /// ```
+    pub fn measure(&mut self, iterations: usize) -> Measurement {
+        assert!(iterations > 0, "At least one iteration is needed");
+
+        std::hint::black_box(self.run());
+        let mut times = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            std::hint::black_box(self.run());
+            times.push(start.elapsed());
+        }
+
+        Measurement {
+            name: self.name(),
+            iterations,
+            mean: times.iter().sum::<Duration>() / iterations as u32,
+            min: times.iter().copied().min().unwrap_or_default(),
+        }
+    }
+
+    /// Workload name with its dataset and machine size
+    pub fn name(&self) -> String {
+        let kind = match self.kind {
+            WorkloadKind::ClauseEvaluation => "clause evaluation",
+            WorkloadKind::EpochTraining => "epoch training",
+            WorkloadKind::BatchPrediction => "batch prediction",
+        };
+        format!(
+            "{kind} ({} samples x {} features, {} clauses)",
+            self.config.samples, self.config.features, self.config.clauses
+        )
+    }
+}
+
+/// Timing of a workload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    /// Workload name
+    pub name: String,
+    /// Number of timed runs
+    pub iterations: usize,
+    /// Mean time per run
+    pub mean: Duration,
+    /// Fastest run
+    pub min: Duration,
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<60} mean {:>10.3?}  min {:>10.3?}  ({} runs)",
+            self.name, self.mean, self.min, self.iterations
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small() -> SyntheticConfig {
+        SyntheticConfig { samples: 30, features: 6, clauses: 10, label_noise: 0.1, seed: 1 }
+    }
+
+    #[test]
+    fn test_synthetic_dataset_is_seeded() {
+        let (a, labels_a) = synthetic_dataset(&small());
+        let (b, labels_b) = synthetic_dataset(&small());
+        assert_eq!((a, labels_a), (b, labels_b));
+    }
+
+    #[test]
+    fn test_workloads_run() {
+        for kind in [
+            WorkloadKind::ClauseEvaluation,
+            WorkloadKind::EpochTraining,
+            WorkloadKind::BatchPrediction,
+        ] {
+            let mut workload = Workload::new(kind, small());
+            let measurement = workload.measure(2);
+            assert!(measurement.to_string().contains("30 samples x 6 features"));
+        }
+    }
+}
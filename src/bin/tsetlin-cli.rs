@@ -0,0 +1,366 @@
+//! Command line interface for training and scoring Tsetlin machines
+//!
+//! ```text
+//! tsetlin-cli train    --config <config.toml> --data <train.csv> --model <model.json>
+//! tsetlin-cli predict  --model <model.json> --data <samples.csv>
+//! tsetlin-cli evaluate --model <model.json> --data <test.csv>
+//! tsetlin-cli inspect  --model <model.json>
+//! tsetlin-cli experiment --config <experiment.toml> [--output <results.json>]
+//! ```
+//!
+//! CSV files hold one sample per line with 0/1 (or true/false) values and an
+//! optional header. For `train` and `evaluate` the last column is the label
+//! and the header, if present, names the features. Both commands also accept
+//! a `.json` file written by `Dataset::to_json`.
+//! The config file uses the [`TsetlinConfig`] TOML format:
+//!
+//! ```toml
+//! [machine]
+//! num_clauses = 100
+//! specificity = 3.9
+//! threshold = 15.0
+//!
+//! [training]
+//! epochs = 200
+//! ```
+//!
+//! The CSV input is already boolean, so binarization must be left at `none`.
+//! `experiment` runs an [`Experiment`](tsetlin::experiments::Experiment) file
+//! on a numeric CSV dataset and prints (or writes) its JSON results.
+
+use ndarray::Array2;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use tsetlin::config::Binarization;
+use tsetlin::dataset::Dataset;
+use tsetlin::experiments::Experiment;
+use tsetlin::{TsetlinConfig, TsetlinMachine};
+
+const USAGE: &str = "usage:
+  tsetlin-cli train    --config <config.toml> --data <train.csv> --model <model.json>
+  tsetlin-cli predict  --model <model.json> --data <samples.csv>
+  tsetlin-cli evaluate --model <model.json> --data <test.csv>
+  tsetlin-cli inspect  --model <model.json>
+  tsetlin-cli experiment --config <experiment.toml> [--output <results.json>]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (command, options) = args.split_first().ok_or(USAGE)?;
+    let options = parse_options(options)?;
+
+    match command.as_str() {
+        "train" => train(&options),
+        "predict" => predict(&options),
+        "evaluate" => evaluate(&options),
+        "inspect" => inspect(&options),
+        "experiment" => experiment(&options),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        other => Err(format!("unknown command `{other}`\n{USAGE}")),
+    }
+}
+
+fn train(options: &HashMap<String, String>) -> Result<(), String> {
+    let config_path = required(options, "config")?;
+    let config = TsetlinConfig::from_toml(config_path).map_err(|error| error.to_string())?;
+    if config.binarization != Binarization::None {
+        return Err(format!("{config_path}: binarization is not supported for boolean CSV input"));
+    }
+    let dataset = read_dataset(required(options, "data")?)?;
+
+    let mut machine = config.build_machine(dataset.features().ncols());
+    machine.fit_dataset(&dataset, config.epochs);
+
+    let model_path = required(options, "model")?;
+    std::fs::write(model_path, machine.to_json())
+        .map_err(|error| format!("cannot write `{model_path}`: {error}"))?;
+    println!(
+        "trained on {} samples, training accuracy {:.4}",
+        dataset.len(),
+        machine.evaluate_dataset(&dataset)
+    );
+    Ok(())
+}
+
+fn predict(options: &HashMap<String, String>) -> Result<(), String> {
+    let machine = load_model(required(options, "model")?)?;
+    let (_, mut rows) = read_csv(required(options, "data")?)?;
+
+    // Accept labelled files by ignoring the trailing label column
+    if rows.ncols() == machine.num_features() + 1 {
+        rows = rows.slice(ndarray::s![.., ..machine.num_features()]).to_owned();
+    }
+    check_width(&rows, machine.num_features())?;
+
+    for prediction in machine.predict(&rows) {
+        println!("{}", u8::from(prediction));
+    }
+    Ok(())
+}
+
+fn evaluate(options: &HashMap<String, String>) -> Result<(), String> {
+    let machine = load_model(required(options, "model")?)?;
+    let dataset = read_dataset(required(options, "data")?)?;
+    check_width(dataset.features(), machine.num_features())?;
+
+    println!("samples: {}", dataset.len());
+    println!("accuracy: {:.4}", machine.evaluate_dataset(&dataset));
+    Ok(())
+}
+
+fn inspect(options: &HashMap<String, String>) -> Result<(), String> {
+    let machine = load_model(required(options, "model")?)?;
+
+    println!("{machine}");
+    println!();
+    println!("rules:");
+    for rule in machine.rules_simplified() {
+        let sign = if rule.polarity { '+' } else { '-' };
+        println!("  {sign} {rule}");
+    }
+    Ok(())
+}
+
+fn experiment(options: &HashMap<String, String>) -> Result<(), String> {
+    let config_path = required(options, "config")?;
+    let experiment = Experiment::from_toml(config_path).map_err(|error| error.to_string())?;
+    let results = experiment.run().map_err(|error| error.to_string())?;
+
+    match options.get("output") {
+        Some(path) => {
+            std::fs::write(path, results.to_json())
+                .map_err(|error| format!("cannot write `{path}`: {error}"))?;
+            let (mean, std) = results.mean_std(|run| run.test_accuracy);
+            println!("{} runs, test accuracy {mean:.4} +- {std:.4}", results.runs.len());
+        }
+        None => print!("{}", results.to_json()),
+    }
+    Ok(())
+}
+
+/// Collect `--name value` pairs
+fn parse_options(args: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut options = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let name = arg
+            .strip_prefix("--")
+            .ok_or_else(|| format!("unexpected argument `{arg}`"))?;
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("missing value for `--{name}`"))?;
+        options.insert(name.to_string(), value.clone());
+    }
+    Ok(options)
+}
+
+fn required<'a>(options: &'a HashMap<String, String>, name: &str) -> Result<&'a str, String> {
+    options
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| format!("missing `--{name}`\n{USAGE}"))
+}
+
+fn load_model(path: &str) -> Result<TsetlinMachine, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|error| format!("cannot read `{path}`: {error}"))?;
+    TsetlinMachine::from_json(&json).map_err(|error| format!("{path}: {error}"))
+}
+
+fn check_width(features: &Array2<bool>, num_features: usize) -> Result<(), String> {
+    if features.ncols() != num_features {
+        return Err(format!(
+            "data has {} feature columns but the model expects {num_features}",
+            features.ncols()
+        ));
+    }
+    Ok(())
+}
+
+/// Read a boolean CSV file and its non-numeric header line, if any
+fn read_csv(path: &str) -> Result<(Option<Vec<String>>, Array2<bool>), String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| format!("cannot read `{path}`: {error}"))?;
+
+    let mut header = None;
+    let mut values = Vec::new();
+    let mut width = None;
+    let mut num_rows = 0;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+        let parsed: Option<Vec<bool>> = cells.iter().map(|cell| parse_bool(cell)).collect();
+        let row = match parsed {
+            Some(row) => row,
+            None if number == 0 => {
+                header = Some(cells.iter().map(|cell| cell.to_string()).collect());
+                continue;
+            }
+            None => return Err(format!("{path}:{}: expected 0/1 values", number + 1)),
+        };
+
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(format!("{path}:{}: inconsistent number of columns", number + 1));
+        }
+        values.extend(row);
+        num_rows += 1;
+    }
+
+    let width = width.ok_or_else(|| format!("`{path}` contains no samples"))?;
+    let rows =
+        Array2::from_shape_vec((num_rows, width), values).map_err(|error| error.to_string())?;
+    Ok((header, rows))
+}
+
+/// Read a labelled dataset from JSON or from a CSV file whose last column is the label
+fn read_dataset(path: &str) -> Result<Dataset, String> {
+    if path.ends_with(".json") {
+        let json = std::fs::read_to_string(path)
+            .map_err(|error| format!("cannot read `{path}`: {error}"))?;
+        return Dataset::from_json(&json).map_err(|error| format!("{path}: {error}"));
+    }
+
+    let (header, rows) = read_csv(path)?;
+    if rows.ncols() < 2 {
+        return Err(format!("`{path}` needs at least one feature and a label column"));
+    }
+
+    let num_features = rows.ncols() - 1;
+    let features = rows.slice(ndarray::s![.., ..num_features]).to_owned();
+    let labels = rows.column(num_features).to_owned();
+    let dataset = Dataset::new(features, labels);
+    match header {
+        Some(mut names) if names.len() == num_features + 1 => {
+            names.pop();
+            Ok(dataset.with_feature_names(names))
+        }
+        Some(_) => Err(format!("{path}:1: header does not match the number of columns")),
+        None => Ok(dataset),
+    }
+}
+
+fn parse_bool(cell: &str) -> Option<bool> {
+    match cell {
+        "1" | "true" | "True" | "TRUE" => Some(true),
+        "0" | "false" | "False" | "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn path(dir: &Path, name: &str) -> String {
+        dir.join(name).to_string_lossy().into_owned()
+    }
+
+    /// Fresh scratch directory, unique per test and process
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tsetlin-cli-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(&args("--model m.json --data d.csv")).unwrap();
+        assert_eq!(options["model"], "m.json");
+        assert_eq!(options["data"], "d.csv");
+        assert_eq!(required(&options, "model"), Ok("m.json"));
+        assert!(required(&options, "config").unwrap_err().starts_with("missing `--config`"));
+
+        assert_eq!(
+            parse_options(&args("model m.json")).unwrap_err(),
+            "unexpected argument `model`"
+        );
+        assert_eq!(parse_options(&args("--model")).unwrap_err(), "missing value for `--model`");
+    }
+
+    #[test]
+    fn test_run_rejects_bad_commands() {
+        assert_eq!(run(&[]).unwrap_err(), USAGE);
+        assert!(run(&args("fit --model m.json")).unwrap_err().starts_with("unknown command `fit`"));
+        assert!(run(&args("inspect")).unwrap_err().starts_with("missing `--model`"));
+        assert!(run(&args("help")).is_ok());
+    }
+
+    #[test]
+    fn test_read_csv_and_dataset() {
+        let dir = scratch_dir("csv");
+        let data = path(&dir, "data.csv");
+        std::fs::write(&data, "hot,wet,label\n1,0,1\n0, 1 ,0\n\ntrue,true,1\n").unwrap();
+
+        let dataset = read_dataset(&data).unwrap();
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.features().ncols(), 2);
+        assert_eq!(dataset.labels().to_vec(), vec![true, false, true]);
+        assert_eq!(dataset.feature_names(), ["hot", "wet"]);
+
+        std::fs::write(&data, "1,0,1\n0,2,0\n").unwrap();
+        assert_eq!(read_csv(&data).unwrap_err(), format!("{data}:2: expected 0/1 values"));
+        std::fs::write(&data, "1,0,1\n0,1\n").unwrap();
+        assert_eq!(
+            read_csv(&data).unwrap_err(),
+            format!("{data}:2: inconsistent number of columns")
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_train_then_score_round_trip() {
+        let dir = scratch_dir("round-trip");
+        let (config, data, model) =
+            (path(&dir, "config.toml"), path(&dir, "train.csv"), path(&dir, "model.json"));
+        std::fs::write(
+            &config,
+            "[machine]\nnum_clauses = 10\nthreshold = 5.0\n\n[training]\nepochs = 20\nseed = 3\n",
+        )
+        .unwrap();
+        // The label copies the first feature
+        std::fs::write(&data, "a,b,label\n1,0,1\n0,1,0\n1,1,1\n0,0,0\n").unwrap();
+
+        run(&args(&format!("train --config {config} --data {data} --model {model}"))).unwrap();
+        let machine = load_model(&model).unwrap();
+        assert_eq!((machine.num_features(), machine.num_clauses()), (2, 10));
+
+        run(&args(&format!("predict --model {model} --data {data}"))).unwrap();
+        run(&args(&format!("evaluate --model {model} --data {data}"))).unwrap();
+        run(&args(&format!("inspect --model {model}"))).unwrap();
+
+        // Inputs of the wrong width are refused
+        let wide = path(&dir, "wide.csv");
+        std::fs::write(&wide, "1,0,1,1\n").unwrap();
+        assert_eq!(
+            run(&args(&format!("evaluate --model {model} --data {wide}"))).unwrap_err(),
+            "data has 3 feature columns but the model expects 2"
+        );
+        // Boolean CSV input cannot be binarized
+        std::fs::write(&config, "[binarization]\nmethod = \"thermometer\"\n").unwrap();
+        let error = run(&args(&format!("train --config {config} --data {data} --model {model}")))
+            .unwrap_err();
+        assert!(error.ends_with("binarization is not supported for boolean CSV input"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,882 @@
+//! Binarization of numeric features
+//!
+//! Tsetlin machines operate on boolean inputs, so numeric columns have to be
+//! encoded first. Encoders implement [`Transform`]: they are fitted on raw
+//! training data and then map raw samples to boolean feature matrices.
+//!
+//! [`auto`] picks an encoding for every column by probing small machines.
+
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use rand::{Rng, SeedableRng};
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// Encoder from numeric features to boolean features
+pub trait Transform: Debug {
+    /// Learn the encoding from raw training features (samples x columns)
+    fn fit(&mut self, features: &Array2<f64>);
+
+    /// Encode raw features into boolean features
+    fn transform(&self, features: &Array2<f64>) -> Array2<bool>;
+
+    /// Number of boolean features produced per sample
+    fn output_features(&self) -> usize;
+
+    /// Fit the encoder and encode the same features
+    fn fit_transform(&mut self, features: &Array2<f64>) -> Array2<bool> {
+        self.fit(features);
+        self.transform(features)
+    }
+}
+
+/// Thermometer encoding with quantile thresholds
+///
+/// Each column is split into `bins` quantile bins. Every bin boundary becomes
+/// one boolean feature that is true when the value reaches the boundary, so a
+/// value in bin `k` sets the first `k` features of its column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermometerEncoder {
+    /// Number of quantile bins per column
+    bins: usize,
+    /// Sorted, distinct bin boundaries per column
+    thresholds: Vec<Vec<f64>>,
+}
+
+impl ThermometerEncoder {
+    /// Create an unfitted encoder with the given number of bins per column
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::binarize::{ThermometerEncoder, Transform};
+    ///
+    /// let mut encoder = ThermometerEncoder::new(4);
+    /// let encoded = encoder.fit_transform(&array![[1.0], [2.0], [3.0], [4.0]]);
+    /// assert_eq!(encoded.row(2).to_vec(), vec![true, true, false]);
+    /// ```
+    pub fn new(bins: usize) -> Self {
+        assert!(bins >= 2, "At least two bins are required");
+
+        Self {
+            bins,
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Create an encoder from known bin boundaries per column
+    pub fn from_thresholds(thresholds: Vec<Vec<f64>>) -> Self {
+        let thresholds: Vec<Vec<f64>> = thresholds
+            .into_iter()
+            .map(|mut column| {
+                column.sort_by(f64::total_cmp);
+                column.dedup();
+                column
+            })
+            .collect();
+        let bins = thresholds.iter().map(Vec::len).max().unwrap_or(1) + 1;
+
+        Self { bins, thresholds }
+    }
+
+    /// Number of quantile bins per column
+    pub fn bins(&self) -> usize {
+        self.bins
+    }
+
+    /// Bin boundaries per column (empty before fitting)
+    pub fn thresholds(&self) -> &[Vec<f64>] {
+        &self.thresholds
+    }
+
+    /// Range of output features encoding each column (empty before fitting)
+    ///
+    /// Pass these to
+    /// [`TsetlinMachine::ordinal_groups`](crate::TsetlinMachine::ordinal_groups)
+    /// to keep learned rules consistent with the ordering of every column.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::binarize::ThermometerEncoder;
+    ///
+    /// let encoder = ThermometerEncoder::from_thresholds(vec![vec![1.0, 2.0], vec![0.5]]);
+    /// assert_eq!(encoder.feature_groups(), vec![0..2, 2..3]);
+    /// ```
+    pub fn feature_groups(&self) -> Vec<Range<usize>> {
+        let mut offset = 0;
+        self.thresholds
+            .iter()
+            .map(|column| {
+                offset += column.len();
+                offset - column.len()..offset
+            })
+            .collect()
+    }
+
+    /// Fit from rows streamed in one pass
+    ///
+    /// Each column is summarized by a [`QuantileSketch`] with rank error
+    /// `epsilon`, so out-of-core datasets can be fitted without materializing
+    /// any column. Thresholds match [`Transform::fit`] up to that error.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::binarize::ThermometerEncoder;
+    ///
+    /// let rows = (0..10_000).map(|i| vec![(i % 100) as f64]);
+    /// let mut encoder = ThermometerEncoder::new(4);
+    /// encoder.fit_stream(rows, 0.001);
+    /// assert_eq!(encoder.thresholds()[0].len(), 3);
+    /// ```
+    pub fn fit_stream<I, R>(&mut self, rows: I, epsilon: f64)
+    where
+        I: IntoIterator<Item = R>,
+        R: AsRef<[f64]>,
+    {
+        let mut sketches: Vec<QuantileSketch> = Vec::new();
+        for row in rows {
+            let row = row.as_ref();
+            if sketches.is_empty() {
+                sketches = vec![QuantileSketch::new(epsilon); row.len()];
+            }
+            assert_eq!(row.len(), sketches.len(), "All rows must have the same number of columns");
+            for (sketch, &value) in sketches.iter_mut().zip(row) {
+                sketch.insert(value);
+            }
+        }
+
+        self.thresholds = sketches
+            .iter()
+            .map(|sketch| {
+                let Some(min) = sketch.quantile(0.0) else {
+                    return Vec::new();
+                };
+                let mut thresholds: Vec<f64> = (1..self.bins)
+                    .filter_map(|k| sketch.quantile(k as f64 / self.bins as f64))
+                    .filter(|&threshold| threshold > min)
+                    .collect();
+                thresholds.dedup();
+                thresholds
+            })
+            .collect();
+    }
+}
+
+/// Streaming quantile summary (Greenwald-Khanna)
+///
+/// Keeps a small sorted sample of the stream with rank bounds so that any
+/// quantile can be answered with a rank error of at most `epsilon * count`,
+/// using `O(log(epsilon * count) / epsilon)` memory. NaN values are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantileSketch {
+    /// Allowed rank error as a fraction of the count
+    epsilon: f64,
+    /// Number of inserted values
+    count: usize,
+    /// Sorted `(value, g, delta)` tuples: `g` is the rank gap to the previous
+    /// tuple and `delta` the uncertainty of the tuple's maximum rank
+    tuples: Vec<(f64, usize, usize)>,
+}
+
+impl QuantileSketch {
+    /// Create an empty sketch with the given rank error
+    pub fn new(epsilon: f64) -> Self {
+        assert!(epsilon > 0.0 && epsilon < 1.0, "Epsilon must be between 0 and 1");
+
+        Self {
+            epsilon,
+            count: 0,
+            tuples: Vec::new(),
+        }
+    }
+
+    /// Add a value to the stream
+    pub fn insert(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+
+        let position = self.tuples.partition_point(|&(v, _, _)| v <= value);
+        let delta = if position == 0 || position == self.tuples.len() {
+            0
+        } else {
+            self.band()
+        };
+        self.tuples.insert(position, (value, 1, delta));
+        self.count += 1;
+
+        let period = ((1.0 / (2.0 * self.epsilon)) as usize).max(1);
+        if self.count.is_multiple_of(period) {
+            self.compress();
+        }
+    }
+
+    /// Largest rank uncertainty allowed for the current count
+    fn band(&self) -> usize {
+        (2.0 * self.epsilon * self.count as f64) as usize
+    }
+
+    /// Merge neighbouring tuples whose combined rank bounds stay within the band
+    fn compress(&mut self) {
+        let band = self.band();
+        let mut i = self.tuples.len().saturating_sub(2);
+        while i >= 1 {
+            let (_, g, _) = self.tuples[i];
+            let (_, next_g, next_delta) = self.tuples[i + 1];
+            if g + next_g + next_delta <= band {
+                self.tuples[i + 1].1 += g;
+                self.tuples.remove(i);
+            }
+            i -= 1;
+        }
+    }
+
+    /// Value at quantile `phi` (0.0 = minimum), `None` for an empty stream
+    pub fn quantile(&self, phi: f64) -> Option<f64> {
+        let last = self.tuples.last()?.0;
+        let rank = ((phi.clamp(0.0, 1.0) * self.count as f64) as usize + 1).min(self.count);
+        let bound = rank as f64 + self.epsilon * self.count as f64;
+
+        let mut min_rank = 0;
+        for (i, &(value, g, delta)) in self.tuples.iter().enumerate() {
+            min_rank += g;
+            if (min_rank + delta) as f64 > bound {
+                return Some(if i == 0 { value } else { self.tuples[i - 1].0 });
+            }
+        }
+        Some(last)
+    }
+
+    /// Number of inserted values
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Number of stored tuples
+    pub fn size(&self) -> usize {
+        self.tuples.len()
+    }
+}
+
+/// Distinct quantile boundaries splitting a column into at most `bins` bins
+fn quantile_thresholds(column: ArrayView1<'_, f64>, bins: usize) -> Vec<f64> {
+    let mut values: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+    if values.is_empty() {
+        return Vec::new();
+    }
+    values.sort_by(f64::total_cmp);
+
+    let mut thresholds: Vec<f64> = (1..bins)
+        .map(|k| values[(k * values.len() / bins).min(values.len() - 1)])
+        .filter(|&threshold| threshold > values[0])
+        .collect();
+    thresholds.dedup();
+    thresholds
+}
+
+impl Transform for ThermometerEncoder {
+    fn fit(&mut self, features: &Array2<f64>) {
+        self.thresholds = features
+            .columns()
+            .into_iter()
+            .map(|column| quantile_thresholds(column, self.bins))
+            .collect();
+    }
+
+    fn transform(&self, features: &Array2<f64>) -> Array2<bool> {
+        assert_eq!(
+            features.ncols(),
+            self.thresholds.len(),
+            "Encoder was fitted on a different number of columns"
+        );
+
+        let mut encoded = Array2::from_elem((features.nrows(), self.output_features()), false);
+        for (row, mut out) in features.rows().into_iter().zip(encoded.rows_mut()) {
+            let mut offset = 0;
+            for (&value, thresholds) in row.iter().zip(self.thresholds.iter()) {
+                for (i, &threshold) in thresholds.iter().enumerate() {
+                    out[offset + i] = value >= threshold;
+                }
+                offset += thresholds.len();
+            }
+        }
+        encoded
+    }
+
+    fn output_features(&self) -> usize {
+        self.thresholds.iter().map(Vec::len).sum()
+    }
+}
+
+/// Compact binary encoding of quantile bins
+///
+/// Columns are split into quantile bins like [`ThermometerEncoder`], but the
+/// bin index is written with `ceil(log2(bins))` bits instead of one feature
+/// per boundary. In Gray code neighbouring bins differ in exactly one bit, so
+/// clauses can still pick out ranges of similar values; the plain binary
+/// count is also available.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrayCodeEncoder {
+    /// Number of quantile bins per column
+    bins: usize,
+    /// Gray code (true) or plain binary count (false)
+    gray: bool,
+    /// Sorted, distinct bin boundaries per column
+    thresholds: Vec<Vec<f64>>,
+}
+
+impl GrayCodeEncoder {
+    /// Create an unfitted Gray-code encoder with the given number of bins per column
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::binarize::{GrayCodeEncoder, Transform};
+    ///
+    /// let mut encoder = GrayCodeEncoder::new(4);
+    /// let encoded = encoder.fit_transform(&array![[1.0], [2.0], [3.0], [4.0]]);
+    /// // Bins 0..3 as Gray codes 00, 01, 11, 10 (least significant bit first)
+    /// assert_eq!(encoded.row(2).to_vec(), vec![true, true]);
+    /// assert_eq!(encoded.row(3).to_vec(), vec![false, true]);
+    /// ```
+    pub fn new(bins: usize) -> Self {
+        assert!(bins >= 2, "At least two bins are required");
+
+        Self {
+            bins,
+            gray: true,
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Create an unfitted encoder that writes bin indices as plain binary counts
+    pub fn binary_count(bins: usize) -> Self {
+        Self {
+            gray: false,
+            ..Self::new(bins)
+        }
+    }
+
+    /// Number of quantile bins per column
+    pub fn bins(&self) -> usize {
+        self.bins
+    }
+
+    /// Bin boundaries per column (empty before fitting)
+    pub fn thresholds(&self) -> &[Vec<f64>] {
+        &self.thresholds
+    }
+
+}
+
+/// Bits needed to number the bins between `num_boundaries` boundaries
+fn code_bits(num_boundaries: usize) -> usize {
+    (usize::BITS - num_boundaries.leading_zeros()) as usize
+}
+
+impl Transform for GrayCodeEncoder {
+    fn fit(&mut self, features: &Array2<f64>) {
+        self.thresholds = features
+            .columns()
+            .into_iter()
+            .map(|column| quantile_thresholds(column, self.bins))
+            .collect();
+    }
+
+    fn transform(&self, features: &Array2<f64>) -> Array2<bool> {
+        assert_eq!(
+            features.ncols(),
+            self.thresholds.len(),
+            "Encoder was fitted on a different number of columns"
+        );
+
+        let mut encoded = Array2::from_elem((features.nrows(), self.output_features()), false);
+        for (row, mut out) in features.rows().into_iter().zip(encoded.rows_mut()) {
+            let mut offset = 0;
+            for (&value, thresholds) in row.iter().zip(self.thresholds.iter()) {
+                let bin = thresholds.iter().filter(|&&threshold| value >= threshold).count();
+                let code = if self.gray { bin ^ (bin >> 1) } else { bin };
+                let bits = code_bits(thresholds.len());
+                for i in 0..bits {
+                    out[offset + i] = code & (1 << i) != 0;
+                }
+                offset += bits;
+            }
+        }
+        encoded
+    }
+
+    fn output_features(&self) -> usize {
+        self.thresholds.iter().map(|column| code_bits(column.len())).sum()
+    }
+}
+
+/// Thermometer encoding combined with random-projection features
+///
+/// Quantile thresholds resolve each column on its own, which wastes bins on
+/// skewed or high-cardinality columns. The hybrid encoder appends
+/// `dimensions` hyperdimensional features: each is the sign of a random
+/// Gaussian projection of the standardized sample plus a random offset, so
+/// together they capture the position of a sample in the joint feature space.
+/// The output is the thermometer features followed by the projection
+/// features.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridEncoder {
+    /// Per-column thermometer encoding
+    thermometer: ThermometerEncoder,
+    /// Number of random-projection features
+    dimensions: usize,
+    /// Seed of the random projections
+    seed: u64,
+    /// Column means and standard deviations used for standardization
+    scaling: Vec<(f64, f64)>,
+    /// Projection weights (dimensions x columns)
+    weights: Array2<f64>,
+    /// Projection offsets
+    offsets: Array1<f64>,
+}
+
+impl HybridEncoder {
+    /// Create an unfitted encoder
+    ///
+    /// # Arguments
+    /// * `bins` - Thermometer bins per column
+    /// * `dimensions` - Number of random-projection features
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::binarize::{HybridEncoder, Transform};
+    ///
+    /// let mut encoder = HybridEncoder::new(4, 16).with_seed(3);
+    /// let encoded = encoder.fit_transform(&array![[1.0, 10.0], [2.0, 0.0], [3.0, 1e6], [4.0, 5.0]]);
+    /// assert_eq!(encoded.ncols(), encoder.output_features());
+    /// ```
+    pub fn new(bins: usize, dimensions: usize) -> Self {
+        Self {
+            thermometer: ThermometerEncoder::new(bins),
+            dimensions,
+            seed: 0,
+            scaling: Vec::new(),
+            weights: Array2::zeros((0, 0)),
+            offsets: Array1::zeros(0),
+        }
+    }
+
+    /// Seed the random projections (default 0)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The thermometer part of the encoding
+    pub fn thermometer(&self) -> &ThermometerEncoder {
+        &self.thermometer
+    }
+
+    /// Number of random-projection features
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Projection bit of a standardized sample
+    fn project(&self, standardized: &[f64], dimension: usize) -> bool {
+        let projection: f64 = self
+            .weights
+            .row(dimension)
+            .iter()
+            .zip(standardized)
+            .map(|(weight, value)| weight * value)
+            .sum();
+        projection + self.offsets[dimension] >= 0.0
+    }
+}
+
+/// Standard normal sample (Box-Muller)
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+impl Transform for HybridEncoder {
+    fn fit(&mut self, features: &Array2<f64>) {
+        self.thermometer.fit(features);
+        self.scaling = features
+            .columns()
+            .into_iter()
+            .map(|column| {
+                let values: Vec<f64> = column.iter().copied().filter(|v| !v.is_nan()).collect();
+                if values.is_empty() {
+                    return (0.0, 1.0);
+                }
+                let mean = values.iter().sum::<f64>() / values.len() as f64;
+                let variance =
+                    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+                (mean, if variance > 0.0 { variance.sqrt() } else { 1.0 })
+            })
+            .collect();
+
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(self.seed);
+        let columns = features.ncols();
+        self.weights = Array2::from_shape_simple_fn((self.dimensions, columns), || {
+            standard_normal(&mut rng)
+        });
+        self.offsets = Array1::from_shape_simple_fn(self.dimensions, || standard_normal(&mut rng));
+    }
+
+    fn transform(&self, features: &Array2<f64>) -> Array2<bool> {
+        let thermometer = self.thermometer.transform(features);
+        let offset = thermometer.ncols();
+
+        let mut encoded = Array2::from_elem((features.nrows(), self.output_features()), false);
+        let mut standardized = vec![0.0; features.ncols()];
+        for ((row, bits), mut out) in features
+            .rows()
+            .into_iter()
+            .zip(thermometer.rows())
+            .zip(encoded.rows_mut())
+        {
+            for (i, &bit) in bits.iter().enumerate() {
+                out[i] = bit;
+            }
+            // Missing values sit at the column mean
+            for ((z, &value), &(mean, std)) in standardized.iter_mut().zip(row).zip(&self.scaling) {
+                *z = if value.is_nan() { 0.0 } else { (value - mean) / std };
+            }
+            for dimension in 0..self.dimensions {
+                out[offset + dimension] = self.project(&standardized, dimension);
+            }
+        }
+        encoded
+    }
+
+    fn output_features(&self) -> usize {
+        self.thermometer.output_features() + self.dimensions
+    }
+}
+
+/// Encoding of a single column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnEncoding {
+    /// Quantile thermometer encoding, one feature per bin boundary
+    Thermometer {
+        /// Number of quantile bins
+        bins: usize,
+    },
+    /// Gray-coded quantile bins, `ceil(log2(bins))` features
+    Gray {
+        /// Number of quantile bins
+        bins: usize,
+    },
+}
+
+impl ColumnEncoding {
+    /// Number of quantile bins
+    pub fn bins(&self) -> usize {
+        match *self {
+            ColumnEncoding::Thermometer { bins } | ColumnEncoding::Gray { bins } => bins,
+        }
+    }
+
+    /// Boolean features produced for a column with `bins` distinct bins
+    fn num_features(&self) -> usize {
+        match *self {
+            ColumnEncoding::Thermometer { bins } => bins - 1,
+            ColumnEncoding::Gray { bins } => code_bits(bins - 1),
+        }
+    }
+}
+
+/// Encoder with its own [`ColumnEncoding`] per column
+///
+/// Usually produced by [`auto`]; the output is the encoded columns in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnEncoder {
+    /// Encoding of each column
+    columns: Vec<ColumnEncoding>,
+    /// Sorted, distinct bin boundaries per column
+    thresholds: Vec<Vec<f64>>,
+}
+
+impl ColumnEncoder {
+    /// Create an unfitted encoder from per-column encodings
+    pub fn new(columns: Vec<ColumnEncoding>) -> Self {
+        assert!(
+            columns.iter().all(|column| column.bins() >= 2),
+            "At least two bins are required"
+        );
+
+        Self {
+            columns,
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Encoding of each column
+    pub fn columns(&self) -> &[ColumnEncoding] {
+        &self.columns
+    }
+
+    /// Features produced for column `index`
+    fn column_features(&self, index: usize) -> usize {
+        let thresholds = &self.thresholds[index];
+        match self.columns[index] {
+            ColumnEncoding::Thermometer { .. } => thresholds.len(),
+            ColumnEncoding::Gray { .. } => code_bits(thresholds.len()),
+        }
+    }
+}
+
+impl Transform for ColumnEncoder {
+    fn fit(&mut self, features: &Array2<f64>) {
+        assert_eq!(
+            features.ncols(),
+            self.columns.len(),
+            "Encoder was fitted on a different number of columns"
+        );
+
+        self.thresholds = features
+            .columns()
+            .into_iter()
+            .zip(&self.columns)
+            .map(|(column, encoding)| quantile_thresholds(column, encoding.bins()))
+            .collect();
+    }
+
+    fn transform(&self, features: &Array2<f64>) -> Array2<bool> {
+        assert_eq!(
+            features.ncols(),
+            self.thresholds.len(),
+            "Encoder was fitted on a different number of columns"
+        );
+
+        let mut encoded = Array2::from_elem((features.nrows(), self.output_features()), false);
+        for (row, mut out) in features.rows().into_iter().zip(encoded.rows_mut()) {
+            let mut offset = 0;
+            for (index, &value) in row.iter().enumerate() {
+                let thresholds = &self.thresholds[index];
+                let bin = thresholds.iter().filter(|&&threshold| value >= threshold).count();
+                let bits = self.column_features(index);
+                for i in 0..bits {
+                    out[offset + i] = match self.columns[index] {
+                        ColumnEncoding::Thermometer { .. } => i < bin,
+                        ColumnEncoding::Gray { .. } => (bin ^ (bin >> 1)) & (1 << i) != 0,
+                    };
+                }
+                offset += bits;
+            }
+        }
+        encoded
+    }
+
+    fn output_features(&self) -> usize {
+        (0..self.thresholds.len()).map(|index| self.column_features(index)).sum()
+    }
+}
+
+/// Pick an encoding for every column by probing small machines
+///
+/// Each column is tried on its own with thermometer and Gray encodings of
+/// 2, 4, 8, 16 and 32 bins, keeping only candidates that produce at most
+/// `budget` boolean features. A candidate is scored by the 3-fold
+/// cross-validated accuracy of a small seeded machine trained on that column
+/// alone; ties go to the candidate with fewer features. The returned encoder
+/// is fitted on `features`.
+///
+/// # Arguments
+/// * `features` - Raw training features (samples x columns)
+/// * `labels` - Training labels
+/// * `budget` - Maximum number of boolean features per column (at least 1)
+///
+/// # Example
+/// ```
+/// use ndarray::{Array1, Array2};
+/// use tsetlin::binarize::{self, Transform};
+///
+/// let features = Array2::from_shape_fn((12, 2), |(i, j)| (i * (j + 1)) as f64);
+/// let labels = Array1::from_shape_fn(12, |i| i >= 6);
+/// let encoder = binarize::auto(&features, &labels, 4);
+/// assert_eq!(encoder.columns().len(), 2);
+/// assert!(encoder.transform(&features).ncols() <= 2 * 4);
+/// ```
+pub fn auto(features: &Array2<f64>, labels: &Array1<bool>, budget: usize) -> ColumnEncoder {
+    assert_eq!(features.nrows(), labels.len());
+    assert!(budget > 0, "The feature budget must be positive");
+
+    let candidates: Vec<ColumnEncoding> = [2, 4, 8, 16, 32]
+        .into_iter()
+        .flat_map(|bins| [ColumnEncoding::Gray { bins }, ColumnEncoding::Thermometer { bins }])
+        .filter(|candidate| candidate.num_features() <= budget)
+        .collect();
+
+    let columns = features
+        .columns()
+        .into_iter()
+        .map(|column| {
+            let column = column.insert_axis(Axis(1)).to_owned();
+            let mut best: Option<(f64, usize, ColumnEncoding)> = None;
+            for &candidate in &candidates {
+                let mut encoder = ColumnEncoder::new(vec![candidate]);
+                let encoded = encoder.fit_transform(&column);
+                let score = probe_accuracy(&encoded, labels);
+                let size = encoded.ncols();
+                if best.is_none_or(|(best_score, best_size, _)| {
+                    score > best_score || (score == best_score && size < best_size)
+                }) {
+                    best = Some((score, size, candidate));
+                }
+            }
+            best.map_or(ColumnEncoding::Gray { bins: 2 }, |(_, _, encoding)| encoding)
+        })
+        .collect();
+
+    let mut encoder = ColumnEncoder::new(columns);
+    encoder.fit(features);
+    encoder
+}
+
+/// Cross-validated accuracy of a small machine on encoded features
+fn probe_accuracy(encoded: &Array2<bool>, labels: &Array1<bool>) -> f64 {
+    const FOLDS: usize = 3;
+    if encoded.ncols() == 0 || labels.len() < FOLDS {
+        return 0.0;
+    }
+
+    let mut total = 0.0;
+    for fold in 0..FOLDS {
+        let (test, train): (Vec<usize>, Vec<usize>) =
+            (0..labels.len()).partition(|&i| i % FOLDS == fold);
+        let mut machine = TsetlinMachine::new(encoded.ncols(), 10, 3.0, 5.0).seed(fold as u64);
+        machine.fit(&encoded.select(Axis(0), &train), &labels.select(Axis(0), &train), 10);
+        total += machine.evaluate(&encoded.select(Axis(0), &test), &labels.select(Axis(0), &test));
+    }
+    total / FOLDS as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_thermometer_thresholds() {
+        let mut encoder = ThermometerEncoder::new(4);
+        encoder.fit(&array![[1.0, 5.0], [2.0, 5.0], [3.0, 5.0], [4.0, 5.0]]);
+
+        assert_eq!(encoder.thresholds()[0], vec![2.0, 3.0, 4.0]);
+        // A constant column carries no information
+        assert!(encoder.thresholds()[1].is_empty());
+        assert_eq!(encoder.output_features(), 3);
+    }
+
+    #[test]
+    fn test_thermometer_transform() {
+        let encoder = ThermometerEncoder::from_thresholds(vec![vec![3.0, 1.0], vec![0.5]]);
+        assert_eq!(encoder.bins(), 3);
+
+        let encoded = encoder.transform(&array![[0.0, 1.0], [2.0, 0.0], [3.5, 0.5]]);
+        assert_eq!(encoded, array![
+            [false, false, true],
+            [true, false, false],
+            [true, true, true],
+        ]);
+    }
+
+    #[test]
+    fn test_quantile_sketch_error() {
+        let mut sketch = QuantileSketch::new(0.01);
+        // A scrambled permutation of 0..10_000
+        for i in 0..10_000u64 {
+            sketch.insert(((i * 7_919) % 10_000) as f64);
+        }
+
+        assert_eq!(sketch.count(), 10_000);
+        assert!(sketch.size() < 1_000);
+        for phi in [0.1, 0.25, 0.5, 0.9] {
+            let value = sketch.quantile(phi).unwrap();
+            assert!((value - phi * 10_000.0).abs() <= 100.0, "phi {phi}: {value}");
+        }
+        assert_eq!(QuantileSketch::new(0.1).quantile(0.5), None);
+    }
+
+    #[test]
+    fn test_fit_stream_matches_batch() {
+        let features = array![[1.0, 5.0], [2.0, 5.0], [3.0, 5.0], [4.0, 5.0]];
+        let mut batch = ThermometerEncoder::new(4);
+        batch.fit(&features);
+
+        let mut streamed = ThermometerEncoder::new(4);
+        streamed.fit_stream(features.rows().into_iter().map(|row| row.to_vec()), 0.01);
+        assert_eq!(streamed, batch);
+    }
+
+    #[test]
+    fn test_gray_code_encoder() {
+        let features = array![[0.0, 1.0], [1.0, 1.0], [2.0, 1.0], [3.0, 1.0], [4.0, 1.0]];
+        let mut gray = GrayCodeEncoder::new(5);
+        let encoded = gray.fit_transform(&features);
+
+        // Five bins need three bits; the constant column needs none
+        assert_eq!(gray.output_features(), 3);
+        for pair in encoded.rows().into_iter().collect::<Vec<_>>().windows(2) {
+            let changed = pair[0].iter().zip(pair[1].iter()).filter(|(a, b)| a != b).count();
+            assert_eq!(changed, 1);
+        }
+
+        let mut binary = GrayCodeEncoder::binary_count(5);
+        let encoded = binary.fit_transform(&features);
+        assert_eq!(encoded.row(3).to_vec(), vec![true, true, false]);
+        assert_eq!(encoded.row(4).to_vec(), vec![false, false, true]);
+    }
+
+    #[test]
+    fn test_column_encoder() {
+        let features = array![[0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]];
+        let mut encoder = ColumnEncoder::new(vec![
+            ColumnEncoding::Thermometer { bins: 4 },
+            ColumnEncoding::Gray { bins: 4 },
+        ]);
+        let encoded = encoder.fit_transform(&features);
+
+        assert_eq!(encoded.ncols(), 3 + 2);
+        assert_eq!(encoded.row(2).to_vec(), vec![true, true, false, true, true]);
+    }
+
+    #[test]
+    fn test_auto_respects_budget() {
+        let features = Array2::from_shape_fn((9, 3), |(i, j)| ((i * 7 + j * 3) % 9) as f64);
+        let labels = Array1::from_shape_fn(9, |i| i % 2 == 0);
+
+        let encoder = auto(&features, &labels, 2);
+        assert_eq!(encoder.columns().len(), 3);
+        assert!(encoder.columns().iter().all(|column| column.num_features() <= 2));
+        assert_eq!(encoder.transform(&features).nrows(), 9);
+    }
+
+    #[test]
+    fn test_hybrid_encoder() {
+        let features = array![[1.0, 10.0], [2.0, 0.0], [3.0, 1e6], [4.0, 5.0]];
+        let mut encoder = HybridEncoder::new(4, 8).with_seed(11);
+        let encoded = encoder.fit_transform(&features);
+
+        assert_eq!(encoder.output_features(), encoder.thermometer().output_features() + 8);
+        assert_eq!(encoded.ncols(), encoder.output_features());
+        // Thermometer part first
+        let thermometer = encoder.thermometer().transform(&features);
+        for (row, expected) in encoded.rows().into_iter().zip(thermometer.rows()) {
+            assert_eq!(row.slice(ndarray::s![..thermometer.ncols()]), expected);
+        }
+
+        // Same seed, same projections
+        let mut again = HybridEncoder::new(4, 8).with_seed(11);
+        assert_eq!(again.fit_transform(&features), encoded);
+    }
+
+    #[test]
+    #[should_panic(expected = "Encoder was fitted on a different number of columns")]
+    fn test_transform_column_mismatch() {
+        let encoder = ThermometerEncoder::from_thresholds(vec![vec![1.0]]);
+        encoder.transform(&array![[1.0, 2.0]]);
+    }
+}
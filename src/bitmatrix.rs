@@ -0,0 +1,179 @@
+//! Bit-packed boolean matrix
+//!
+//! `Array2<bool>` spends a byte per value. [`BitMatrix`] stores each row as
+//! 64-bit words with the same layout as [`pack_bits`](crate::compiled::pack_bits),
+//! using an eighth of the memory, and can be passed to `fit`/`predict`
+//! directly through [`AsBoolSamples`].
+
+use crate::compiled::pack_bits;
+use crate::input::AsBoolSamples;
+use ndarray::Array2;
+
+/// Row-major boolean matrix packed into 64-bit words
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    /// Number of rows
+    nrows: usize,
+    /// Number of columns
+    ncols: usize,
+    /// Words per row
+    words_per_row: usize,
+    /// Packed rows, `words_per_row` words each
+    words: Vec<u64>,
+}
+
+impl BitMatrix {
+    /// Create an all-false matrix
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::bitmatrix::BitMatrix;
+    ///
+    /// let mut matrix = BitMatrix::new(2, 100);
+    /// matrix.set(1, 70, true);
+    /// assert!(matrix.get(1, 70));
+    /// assert_eq!(matrix.row(0), vec![false; 100]);
+    /// ```
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        let words_per_row = ncols.div_ceil(64);
+        Self {
+            nrows,
+            ncols,
+            words_per_row,
+            words: vec![0; nrows * words_per_row],
+        }
+    }
+
+    /// Pack a boolean array
+    pub fn from_array(array: &Array2<bool>) -> Self {
+        let mut matrix = Self::new(0, array.ncols());
+        for row in array.rows() {
+            matrix.push_row(&row.to_vec());
+        }
+        matrix
+    }
+
+    /// Append a row
+    pub fn push_row(&mut self, row: &[bool]) {
+        assert_eq!(row.len(), self.ncols, "Row length must match the number of columns");
+
+        self.words.extend(pack_bits(row));
+        self.nrows += 1;
+    }
+
+    /// Value at `(row, col)`
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        assert!(row < self.nrows && col < self.ncols, "Index out of bounds");
+
+        self.row_words(row)[col / 64] & (1 << (col % 64)) != 0
+    }
+
+    /// Set the value at `(row, col)`
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        assert!(row < self.nrows && col < self.ncols, "Index out of bounds");
+
+        let word = &mut self.words[row * self.words_per_row + col / 64];
+        if value {
+            *word |= 1 << (col % 64);
+        } else {
+            *word &= !(1 << (col % 64));
+        }
+    }
+
+    /// Packed words of a row
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        let start = row * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+
+    /// Unpacked row
+    pub fn row(&self, row: usize) -> Vec<bool> {
+        let mut values = Vec::with_capacity(self.ncols);
+        self.sample_into(row, &mut values);
+        values
+    }
+
+    /// Number of rows
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Number of columns
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Bytes used by the packed data
+    pub fn memory_bytes(&self) -> usize {
+        self.words.len() * std::mem::size_of::<u64>()
+    }
+}
+
+impl From<&Array2<bool>> for BitMatrix {
+    fn from(array: &Array2<bool>) -> Self {
+        Self::from_array(array)
+    }
+}
+
+impl AsBoolSamples for BitMatrix {
+    fn num_samples(&self) -> usize {
+        self.nrows
+    }
+
+    fn num_features(&self) -> usize {
+        self.ncols
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        let words = self.row_words(index);
+        buffer.clear();
+        buffer.extend((0..self.ncols).map(|col| words[col / 64] & (1 << (col % 64)) != 0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TsetlinMachine;
+    use ndarray::array;
+
+    #[test]
+    fn test_round_trip() {
+        let array = Array2::from_shape_fn((3, 130), |(i, j)| (i * 7 + j) % 5 == 0);
+        let matrix = BitMatrix::from(&array);
+
+        assert_eq!(matrix.nrows(), 3);
+        assert_eq!(matrix.ncols(), 130);
+        assert_eq!(matrix.to_array(), array);
+        assert_eq!(matrix.memory_bytes(), 3 * 3 * 8);
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut matrix = BitMatrix::new(2, 3);
+        matrix.set(0, 2, true);
+        matrix.set(1, 0, true);
+        matrix.set(1, 0, false);
+
+        assert_eq!(matrix.row(0), vec![false, false, true]);
+        assert_eq!(matrix.row_words(0), &[0b100]);
+        assert!(!matrix.get(1, 0));
+    }
+
+    #[test]
+    fn test_machine_accepts_bit_matrix() {
+        let array = array![[true, false], [false, true], [true, true]];
+        let labels = array![true, false, true];
+        let matrix = BitMatrix::from(&array);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        machine.fit(&matrix, &labels, 2);
+        assert_eq!(machine.predict_votes(&matrix), machine.predict_votes(&array));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    fn test_out_of_bounds() {
+        BitMatrix::new(1, 64).get(0, 64);
+    }
+}
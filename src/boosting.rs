@@ -0,0 +1,212 @@
+//! AdaBoost over small Tsetlin machines
+//!
+//! [`BoostedTsetlinMachine`] trains a sequence of copies of a small template
+//! machine. Each copy sees the training samples drawn in proportion to their
+//! current weights (through a [`Weighted`] sampler), samples it gets wrong
+//! gain weight for the next copy, and the copies vote with weights that grow
+//! with their accuracy. A handful of tiny machines can match one large
+//! machine while every member stays small enough to read.
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use crate::rng::TsetlinRng;
+use crate::sampler::Weighted;
+use ndarray::Array1;
+use rand::{RngCore, SeedableRng};
+
+/// Smallest weighted error used for the vote weight, so a perfect member
+/// gets a large but finite weight
+const MIN_ERROR: f64 = 1e-10;
+
+/// Weighted vote of boosted Tsetlin machines
+#[derive(Debug, Clone)]
+pub struct BoostedTsetlinMachine {
+    /// Untrained machine copied for every boosting round
+    template: TsetlinMachine,
+    /// Maximum number of members
+    num_rounds: usize,
+    /// Trained members, in boosting order
+    machines: Vec<TsetlinMachine>,
+    /// Vote weight of each member
+    alphas: Vec<f64>,
+}
+
+impl BoostedTsetlinMachine {
+    /// Boost up to `num_rounds` copies of `template`
+    ///
+    /// The template keeps all of its options; a seeded template gives
+    /// member `m` the seed `seed + m`.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::boosting::BoostedTsetlinMachine;
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let template = TsetlinMachine::with_defaults(2, 4).seed(1);
+    /// let mut boosted = BoostedTsetlinMachine::new(template, 5);
+    /// boosted.fit(&features, &labels, 10);
+    /// assert!((1..=5).contains(&boosted.num_machines()));
+    /// assert_eq!(boosted.predict(&features).len(), 4);
+    /// ```
+    pub fn new(template: TsetlinMachine, num_rounds: usize) -> Self {
+        assert!(num_rounds > 0, "Boosting needs at least one round");
+
+        Self {
+            template,
+            num_rounds,
+            machines: Vec::new(),
+            alphas: Vec::new(),
+        }
+    }
+
+    /// Train a new sequence of members, `epochs` epochs each
+    ///
+    /// Boosting stops early once a member's weighted error reaches 0.5 (that
+    /// member is dropped unless it is the first) or falls to 0.
+    pub fn fit<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<bool>,
+        epochs: usize,
+    ) {
+        assert_eq!(features.num_samples(), labels.len());
+        assert!(!labels.is_empty(), "Boosting needs at least one sample");
+
+        let base_seed = self
+            .template
+            .random_seed()
+            .unwrap_or_else(|| TsetlinRng::from_entropy().next_u64());
+        let mut weights = vec![1.0 / labels.len() as f64; labels.len()];
+        self.machines.clear();
+        self.alphas.clear();
+
+        for round in 0..self.num_rounds {
+            let mut machine = self
+                .template
+                .clone()
+                .seed(base_seed.wrapping_add(round as u64))
+                .sampler(Weighted::new(weights.clone()));
+            machine.fit(features, labels, epochs);
+
+            let wrong: Vec<bool> = machine
+                .predict(features)
+                .iter()
+                .zip(labels)
+                .map(|(prediction, label)| prediction != label)
+                .collect();
+            let error: f64 = weights
+                .iter()
+                .zip(&wrong)
+                .filter(|(_, &wrong)| wrong)
+                .map(|(weight, _)| weight)
+                .sum();
+            if error >= 0.5 {
+                if self.machines.is_empty() {
+                    self.machines.push(machine);
+                    self.alphas.push(1.0);
+                }
+                break;
+            }
+
+            let alpha = 0.5 * ((1.0 - error) / error.max(MIN_ERROR)).ln();
+            self.machines.push(machine);
+            self.alphas.push(alpha);
+            if error == 0.0 {
+                break;
+            }
+
+            for (weight, &wrong) in weights.iter_mut().zip(&wrong) {
+                *weight *= if wrong { alpha.exp() } else { (-alpha).exp() };
+            }
+            let total: f64 = weights.iter().sum();
+            weights.iter_mut().for_each(|weight| *weight /= total);
+        }
+    }
+
+    /// Weighted vote of the members (positive for the positive class)
+    pub fn decision_function<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<f64> {
+        let mut scores = Array1::zeros(features.num_samples());
+        for (machine, &alpha) in self.machines.iter().zip(&self.alphas) {
+            for (score, prediction) in scores.iter_mut().zip(machine.predict(features)) {
+                *score += if prediction { alpha } else { -alpha };
+            }
+        }
+        scores
+    }
+
+    /// Predict the class of every sample
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<bool> {
+        self.decision_function(features).mapv(|score| score > 0.0)
+    }
+
+    /// Fraction of samples classified correctly
+    pub fn evaluate<S: AsBoolSamples + ?Sized>(&self, features: &S, labels: &Array1<bool>) -> f64 {
+        let predictions = self.predict(features);
+        let correct = predictions.iter().zip(labels).filter(|(p, l)| p == l).count();
+        correct as f64 / labels.len() as f64
+    }
+
+    /// Trained members, in boosting order
+    pub fn machines(&self) -> &[TsetlinMachine] {
+        &self.machines
+    }
+
+    /// Vote weight of each member
+    pub fn alphas(&self) -> &[f64] {
+        &self.alphas
+    }
+
+    /// Number of trained members
+    pub fn num_machines(&self) -> usize {
+        self.machines.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    /// Machine whose positive clause fires on `literal`
+    fn stump(literal: Literal) -> TsetlinMachine {
+        let mut machine = TsetlinMachine::with_defaults(2, 2);
+        machine.clause_bank_mut().clause_mut(0).set_included(literal, true);
+        let never = [Literal { feature: 0, negated: false }, Literal { feature: 0, negated: true }];
+        for literal in never {
+            machine.clause_bank_mut().clause_mut(1).set_included(literal, true);
+        }
+        machine
+    }
+
+    #[test]
+    fn test_weighted_vote() {
+        let x0 = stump(Literal { feature: 0, negated: false });
+        let x1 = stump(Literal { feature: 1, negated: false });
+        let boosted = BoostedTsetlinMachine {
+            template: x0.clone(),
+            num_rounds: 2,
+            machines: vec![x0, x1],
+            alphas: vec![2.0, 1.0],
+        };
+
+        let features = array![[true, false], [false, true], [true, true]];
+        assert_eq!(boosted.decision_function(&features), array![1.0, -1.0, 3.0]);
+        assert_eq!(boosted.predict(&features), array![true, false, true]);
+    }
+
+    #[test]
+    fn test_perfect_member_stops_boosting() {
+        // The stump on x0 already predicts the labels and keeps doing so
+        let features = array![[true, false], [false, true], [true, true], [false, false]];
+        let labels = array![true, false, true, false];
+        let template = stump(Literal { feature: 0, negated: false });
+        let mut boosted = BoostedTsetlinMachine::new(template, 4);
+        boosted.fit(&features, &labels, 2);
+
+        assert_eq!(boosted.num_machines(), 1);
+        assert!(boosted.alphas()[0] > 10.0);
+        assert_eq!(boosted.evaluate(&features, &labels), 1.0);
+    }
+}
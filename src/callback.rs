@@ -0,0 +1,224 @@
+//! Hooks run by [`TsetlinMachine::fit_with_callback`] after every epoch
+//!
+//! A [`Callback`] sees the machine and the training data at the end of each
+//! epoch and may stop training early. [`ProgressCallback`] computes the usual
+//! per-epoch metrics and hands them to a closure, which can drive a progress
+//! bar or a log without this crate depending on any UI library.
+//! [`ChurnStopping`] stops training once the clauses stop changing, which
+//! needs no validation data.
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2};
+use std::ops::ControlFlow;
+
+/// Hook called after every training epoch
+pub trait Callback {
+    /// Inspect the machine after an epoch; `Break` stops training
+    fn on_epoch_end(
+        &mut self,
+        machine: &TsetlinMachine,
+        features: &dyn AsBoolSamples,
+        labels: &Array1<bool>,
+    ) -> ControlFlow<()>;
+}
+
+/// Metrics of one epoch reported by [`ProgressCallback`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochProgress {
+    /// Epochs trained so far, across calls to `fit`
+    pub epoch: usize,
+    /// Accuracy on the training data
+    pub train_accuracy: f64,
+    /// Accuracy on the validation data, if given
+    pub validation_accuracy: Option<f64>,
+}
+
+/// Report train and validation accuracy to a closure after every epoch
+///
+/// # Example
+/// ```
+/// use tsetlin::callback::ProgressCallback;
+/// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let mut history = Vec::new();
+/// let mut progress = ProgressCallback::new(|epoch| history.push(epoch.train_accuracy))
+///     .with_validation(&features, &labels);
+///
+/// let mut machine = TsetlinMachine::with_defaults(2, 10);
+/// machine.fit_with_callback(&features, &labels, 5, &mut progress);
+/// assert_eq!(history.len(), 5);
+/// ```
+pub struct ProgressCallback<'a, F> {
+    /// Receives the metrics of every epoch
+    report: F,
+    /// Validation features and labels, if any
+    validation: Option<(&'a Array2<bool>, &'a Array1<bool>)>,
+}
+
+impl<'a, F: FnMut(&EpochProgress)> ProgressCallback<'a, F> {
+    /// Report training accuracy to `report`
+    pub fn new(report: F) -> Self {
+        Self { report, validation: None }
+    }
+
+    /// Report accuracy on validation data as well
+    pub fn with_validation(mut self, features: &'a Array2<bool>, labels: &'a Array1<bool>) -> Self {
+        assert_eq!(features.nrows(), labels.len(), "Validation features and labels must match");
+        self.validation = Some((features, labels));
+        self
+    }
+}
+
+impl<F: FnMut(&EpochProgress)> Callback for ProgressCallback<'_, F> {
+    fn on_epoch_end(
+        &mut self,
+        machine: &TsetlinMachine,
+        features: &dyn AsBoolSamples,
+        labels: &Array1<bool>,
+    ) -> ControlFlow<()> {
+        let progress = EpochProgress {
+            epoch: machine.epochs_trained(),
+            train_accuracy: machine.evaluate(features, labels),
+            validation_accuracy: self
+                .validation
+                .map(|(features, labels)| machine.evaluate(features, labels)),
+        };
+        (self.report)(&progress);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Stop once few literals cross the include boundary per epoch
+///
+/// Training stops after `patience` consecutive epochs in which fewer than
+/// `threshold` literals were included or excluded (see
+/// [`TsetlinMachine::churn_history`]). The clauses have then converged
+/// structurally, so further epochs change little; unlike validation-based
+/// stopping this needs no held-out data.
+///
+/// # Example
+/// ```
+/// use tsetlin::callback::ChurnStopping;
+/// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1);
+/// let mut stopping = ChurnStopping::new(1, 3);
+/// let epochs = machine.fit_with_callback(&features, &labels, 100, &mut stopping);
+/// assert!(epochs <= 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChurnStopping {
+    /// Epochs with fewer crossings than this count as converged
+    threshold: usize,
+    /// Converged epochs in a row needed to stop
+    patience: usize,
+    /// Converged epochs in a row so far
+    calm_epochs: usize,
+}
+
+impl ChurnStopping {
+    /// Stop after `patience` epochs in a row with churn below `threshold`
+    pub fn new(threshold: usize, patience: usize) -> Self {
+        assert!(patience > 0, "Patience must be at least one epoch");
+
+        Self { threshold, patience, calm_epochs: 0 }
+    }
+
+    /// Whether the last epochs met the stopping rule
+    pub fn converged(&self) -> bool {
+        self.calm_epochs >= self.patience
+    }
+}
+
+impl Callback for ChurnStopping {
+    fn on_epoch_end(
+        &mut self,
+        machine: &TsetlinMachine,
+        _features: &dyn AsBoolSamples,
+        _labels: &Array1<bool>,
+    ) -> ControlFlow<()> {
+        match machine.churn_history().last() {
+            Some(&churn) if churn < self.threshold => self.calm_epochs += 1,
+            _ => self.calm_epochs = 0,
+        }
+        if self.converged() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    /// Stops after a fixed number of epochs
+    struct StopAfter(usize);
+
+    impl Callback for StopAfter {
+        fn on_epoch_end(
+            &mut self,
+            machine: &TsetlinMachine,
+            _features: &dyn AsBoolSamples,
+            _labels: &Array1<bool>,
+        ) -> ControlFlow<()> {
+            if machine.epochs_trained() >= self.0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_callback_stops_training() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        assert_eq!(machine.fit_with_callback(&features, &labels, 10, &mut StopAfter(3)), 3);
+        assert_eq!(machine.epochs_trained(), 3);
+    }
+
+    #[test]
+    fn test_progress_reports_accuracies() {
+        // Positive clause on x0 predicts the label perfectly
+        let mut machine = TsetlinMachine::with_defaults(1, 2).batch_size(2);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 0, negated: true }, true);
+        let features = array![[true], [false]];
+        let labels = array![true, false];
+        let flipped = array![false, true];
+
+        let mut reports = Vec::new();
+        let mut progress = ProgressCallback::new(|epoch: &EpochProgress| reports.push(*epoch))
+            .with_validation(&features, &flipped);
+        machine.fit_with_callback(&features, &labels, 2, &mut progress);
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(
+            reports[1],
+            EpochProgress { epoch: 2, train_accuracy: 1.0, validation_accuracy: Some(0.0) }
+        );
+    }
+
+    #[test]
+    fn test_churn_stopping() {
+        // Untrained clauses never change, so every epoch is calm
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let mut stopping = ChurnStopping::new(1, 3);
+        assert_eq!(machine.fit_with_callback(&features, &labels, 10, &mut stopping), 3);
+        assert!(stopping.converged());
+
+        // A zero threshold is never reached
+        let mut never = ChurnStopping::new(0, 1);
+        assert_eq!(machine.fit_with_callback(&features, &labels, 4, &mut never), 4);
+        assert!(!never.converged());
+    }
+}
@@ -0,0 +1,307 @@
+//! Checkpoints for resuming training
+//!
+//! A checkpoint holds everything `fit` changes: every automaton state and
+//! clause polarity, weight, frozen flag and feature subset, plus the training
+//! progress (epoch counter, random generator state, sample order, auto-grow
+//! plateau, annealed threshold, include churn, fairness history and counters). Builder settings
+//! such as the sampler or prune-and-regrow are not stored: load a checkpoint
+//! into a machine built the same way, and the resumed run continues exactly
+//! like an uninterrupted one.
+
+use crate::clause::Literal;
+use crate::compiled::{read_i32, read_usize, read_varint, write_varint, zigzag, DecodeError};
+use crate::machine::{FairnessEpoch, Progress, TsetlinMachine};
+
+/// Leading bytes of a serialized checkpoint
+const MAGIC: &[u8; 4] = b"TMK1";
+
+impl TsetlinMachine {
+    /// Serialize the clauses and training progress
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let build = || TsetlinMachine::with_defaults(2, 10).seed(1);
+    ///
+    /// let mut uninterrupted = build();
+    /// uninterrupted.fit(&features, &labels, 10);
+    ///
+    /// let mut first = build();
+    /// first.fit(&features, &labels, 4);
+    /// let mut resumed = build();
+    /// resumed.load_checkpoint(&first.save_checkpoint()).unwrap();
+    /// resumed.fit(&features, &labels, 6);
+    ///
+    /// assert_eq!(resumed.epochs_trained(), 10);
+    /// assert_eq!(resumed.state_hash(), uninterrupted.state_hash());
+    /// ```
+    pub fn save_checkpoint(&self) -> Vec<u8> {
+        let bank = self.clause_bank();
+        let mut out = MAGIC.to_vec();
+        write_varint(&mut out, self.num_features() as u64);
+        write_varint(&mut out, u64::from(bank.num_states()));
+        write_varint(&mut out, bank.num_clauses() as u64);
+
+        for (index, clause) in bank.clauses().iter().enumerate() {
+            let flags = u64::from(bank.polarities()[index]) | (u64::from(bank.is_frozen(index)) << 1);
+            write_varint(&mut out, flags);
+            write_varint(&mut out, u64::from(bank.weights()[index]));
+            match clause.feature_subset() {
+                Some(subset) => {
+                    write_varint(&mut out, subset.len() as u64 + 1);
+                    for &feature in subset {
+                        write_varint(&mut out, feature as u64);
+                    }
+                }
+                None => write_varint(&mut out, 0),
+            }
+            for feature in 0..self.num_features() {
+                for negated in [false, true] {
+                    let state = clause.literal_state(Literal { feature, negated });
+                    write_varint(&mut out, zigzag(state));
+                }
+            }
+        }
+
+        let progress = self.progress();
+        write_varint(&mut out, progress.epochs_trained as u64);
+        for word in progress.rng_state {
+            write_varint(&mut out, word);
+        }
+        write_varint(&mut out, progress.sample_order.len() as u64);
+        for &index in &progress.sample_order {
+            write_varint(&mut out, index as u64);
+        }
+        write_varint(&mut out, progress.growth_state.0.to_bits());
+        write_varint(&mut out, progress.growth_state.1 as u64);
+        write_varint(&mut out, progress.threshold.to_bits());
+        write_varint(&mut out, progress.threshold_history.len() as u64);
+        for threshold in &progress.threshold_history {
+            write_varint(&mut out, threshold.to_bits());
+        }
+        for churn in [&progress.churn_history, &progress.clause_churn] {
+            write_varint(&mut out, churn.len() as u64);
+            for &count in churn {
+                write_varint(&mut out, count as u64);
+            }
+        }
+        write_varint(&mut out, progress.privacy_spent.to_bits());
+        write_varint(&mut out, progress.num_regrown as u64);
+        write_varint(&mut out, progress.num_flipped as u64);
+        write_varint(&mut out, progress.fairness_history.len() as u64);
+        for epoch in &progress.fairness_history {
+            for value in [epoch.disparity, epoch.accuracy, epoch.strength] {
+                write_varint(&mut out, value.to_bits());
+            }
+        }
+        out
+    }
+
+    /// Restore the clauses and training progress of a checkpoint
+    ///
+    /// The machine must have the checkpoint's number of features and
+    /// automaton states; its number of clauses follows the checkpoint. On
+    /// error the machine is left unchanged.
+    pub fn load_checkpoint(&mut self, bytes: &[u8]) -> Result<(), DecodeError> {
+        let mut reader = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| DecodeError::new("missing checkpoint header"))?;
+        let num_features = read_usize(&mut reader)?;
+        let num_states = read_varint(&mut reader)?;
+        if num_features != self.num_features()
+            || num_states != u64::from(self.clause_bank().num_states())
+        {
+            return Err(DecodeError::new(
+                "checkpoint does not match the machine's features and automaton states",
+            ));
+        }
+
+        let num_clauses = read_usize(&mut reader)?;
+        if num_clauses > reader.len() {
+            return Err(DecodeError::new("clause count exceeds the data"));
+        }
+        let mut bank = self.clause_bank().clone();
+        bank.resize(num_clauses);
+        for index in 0..num_clauses {
+            let flags = read_varint(&mut reader)?;
+            if flags > 3 {
+                return Err(DecodeError::new("invalid clause flags"));
+            }
+            bank.set_polarity(index, flags & 1 == 1);
+            bank.set_frozen(index, flags & 2 == 2);
+            let weight = u32::try_from(read_varint(&mut reader)?)
+                .ok()
+                .filter(|&weight| weight > 0)
+                .ok_or_else(|| DecodeError::new("invalid weight"))?;
+            bank.set_weight(index, weight);
+
+            let subset = match read_usize(&mut reader)? {
+                0 => None,
+                length => {
+                    let mut subset = Vec::with_capacity((length - 1).min(reader.len()));
+                    for _ in 1..length {
+                        let feature = read_index(&mut reader, num_features, "feature out of range")?;
+                        subset.push(feature);
+                    }
+                    Some(subset)
+                }
+            };
+            let clause = bank.clause_mut(index);
+            clause.set_feature_subset(subset);
+            for feature in 0..num_features {
+                for negated in [false, true] {
+                    clause.set_literal_state(Literal { feature, negated }, read_i32(&mut reader)?);
+                }
+            }
+        }
+
+        let epochs_trained = read_usize(&mut reader)?;
+        let mut rng_state = [0; 4];
+        for word in &mut rng_state {
+            *word = read_varint(&mut reader)?;
+        }
+        let order_length = read_usize(&mut reader)?;
+        let mut sample_order = Vec::with_capacity(order_length.min(reader.len()));
+        for _ in 0..order_length {
+            sample_order.push(read_index(&mut reader, order_length, "sample out of range")?);
+        }
+        let growth_state = (read_f64(&mut reader)?, read_usize(&mut reader)?);
+        let threshold = read_f64(&mut reader)?;
+        if threshold.is_nan() || threshold <= 0.0 {
+            return Err(DecodeError::new("invalid threshold"));
+        }
+        let history_length = read_usize(&mut reader)?;
+        let mut threshold_history = Vec::with_capacity(history_length.min(reader.len()));
+        for _ in 0..history_length {
+            threshold_history.push(read_f64(&mut reader)?);
+        }
+        let churn_history = read_counts(&mut reader)?;
+        let clause_churn = read_counts(&mut reader)?;
+        let mut progress = Progress {
+            epochs_trained,
+            rng_state,
+            sample_order,
+            growth_state,
+            threshold,
+            threshold_history,
+            churn_history,
+            clause_churn,
+            privacy_spent: read_f64(&mut reader)?,
+            num_regrown: read_usize(&mut reader)?,
+            num_flipped: read_usize(&mut reader)?,
+            fairness_history: Vec::new(),
+        };
+        let length = read_usize(&mut reader)?;
+        progress.fairness_history.reserve(length.min(reader.len()));
+        for _ in 0..length {
+            progress.fairness_history.push(FairnessEpoch {
+                disparity: read_f64(&mut reader)?,
+                accuracy: read_f64(&mut reader)?,
+                strength: read_f64(&mut reader)?,
+            });
+        }
+        if !reader.is_empty() {
+            return Err(DecodeError::new("trailing bytes"));
+        }
+
+        self.restore(bank, progress);
+        Ok(())
+    }
+}
+
+/// Read an index below `bound`
+fn read_index(reader: &mut &[u8], bound: usize, message: &str) -> Result<usize, DecodeError> {
+    let index = read_usize(reader)?;
+    if index < bound {
+        Ok(index)
+    } else {
+        Err(DecodeError::new(message))
+    }
+}
+
+/// Read a length-prefixed list of counts
+fn read_counts(reader: &mut &[u8]) -> Result<Vec<usize>, DecodeError> {
+    let length = read_usize(reader)?;
+    let mut counts = Vec::with_capacity(length.min(reader.len()));
+    for _ in 0..length {
+        counts.push(read_usize(reader)?);
+    }
+    Ok(counts)
+}
+
+/// Read a float stored as its bit pattern
+fn read_f64(reader: &mut &[u8]) -> Result<f64, DecodeError> {
+    read_varint(reader).map(f64::from_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Machine with included literals, since feedback only moves automata
+    /// once some clause holds a literal
+    fn seeded(builder: TsetlinMachine) -> TsetlinMachine {
+        let mut machine = builder;
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 1, negated: true }, true);
+        machine
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let build = || {
+            seeded(TsetlinMachine::with_defaults(2, 4).seed(5).auto_grow(10, 1).feature_bagging(2))
+        };
+        let mut machine = build();
+        machine.fit(&features, &labels, 6);
+
+        let mut restored = build();
+        restored.load_checkpoint(&machine.save_checkpoint()).unwrap();
+        assert_eq!(restored.num_clauses(), machine.num_clauses());
+        assert_eq!(restored.state_hash(), machine.state_hash());
+        assert_eq!(restored.save_checkpoint(), machine.save_checkpoint());
+
+        machine.fit(&features, &labels, 3);
+        restored.fit(&features, &labels, 3);
+        assert_eq!(restored.state_hash(), machine.state_hash());
+    }
+
+    #[test]
+    fn test_split_fit_matches_single_fit() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let build = || seeded(TsetlinMachine::with_defaults(2, 6).seed(9));
+        let mut split = build();
+        split.fit(&features, &labels, 5);
+        split.fit(&features, &labels, 3);
+        let mut single = build();
+        single.fit(&features, &labels, 8);
+        assert_ne!(single.state_hash(), build().state_hash());
+        assert_eq!(split.state_hash(), single.state_hash());
+    }
+
+    #[test]
+    fn test_mismatched_checkpoint() {
+        let bytes = TsetlinMachine::with_defaults(2, 4).save_checkpoint();
+        let mut other = TsetlinMachine::with_defaults(3, 4);
+        assert!(other.load_checkpoint(&bytes).is_err());
+        let mut same = TsetlinMachine::with_defaults(2, 4);
+        assert!(same.load_checkpoint(&bytes[..bytes.len() - 1]).is_err());
+        assert!(same.load_checkpoint(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_fairness_history_round_trip() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4).fairness_constraint(0, 0.1);
+        machine.fit(&features, &labels, 3);
+        let bytes = machine.save_checkpoint();
+
+        let mut restored = TsetlinMachine::with_defaults(2, 4);
+        restored.load_checkpoint(&bytes).unwrap();
+        assert_eq!(restored.fairness_history(), machine.fairness_history());
+    }
+}
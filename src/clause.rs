@@ -3,7 +3,19 @@
 //! A clause is a conjunction of literals that can be positive or negative features.
 
 use crate::automaton::{Action, TsetlinAutomaton};
+use crate::input::AsBoolSamples;
+use ndarray::{Array1, Array2};
 use rand::Rng;
+use std::ops::Range;
+
+/// A feature or its negation as used inside a clause
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Literal {
+    /// Index of the input feature
+    pub feature: usize,
+    /// True for the negated literal (feature must be false)
+    pub negated: bool,
+}
 
 /// Represents a single clause in a Tsetlin machine
 #[derive(Debug, Clone)]
@@ -12,6 +24,17 @@ pub struct Clause {
     positive_automata: Vec<TsetlinAutomaton>,
     /// Automata for negative literals (one per feature)
     negative_automata: Vec<TsetlinAutomaton>,
+    /// Features the clause may use, ascending (all features when `None`)
+    feature_subset: Option<Vec<usize>>,
+}
+
+/// Indices of the features a clause looks at
+fn feature_indices(
+    subset: Option<&[usize]>,
+    num_features: usize,
+) -> impl Iterator<Item = usize> + '_ {
+    let all = if subset.is_none() { 0..num_features } else { 0..0 };
+    subset.unwrap_or(&[]).iter().copied().chain(all)
 }
 
 impl Clause {
@@ -20,19 +43,315 @@ impl Clause {
         Self {
             positive_automata: vec![TsetlinAutomaton::new(num_states); num_features],
             negative_automata: vec![TsetlinAutomaton::new(num_states); num_features],
+            feature_subset: None,
+        }
+    }
+
+    /// Create a clause that only ever uses the given features
+    ///
+    /// Evaluation and feedback skip every other feature, so a clause over a
+    /// small subset of a wide input is cheap.
+    pub fn with_feature_subset(num_features: usize, num_states: u32, features: Vec<usize>) -> Self {
+        let mut clause = Self::new(num_features, num_states);
+        clause.set_feature_subset(Some(features));
+        clause
+    }
+
+    /// Features the clause may use, or `None` if it uses all of them
+    pub fn feature_subset(&self) -> Option<&[usize]> {
+        self.feature_subset.as_deref()
+    }
+
+    /// Restrict the clause to a subset of features (`None` lifts the restriction)
+    ///
+    /// Literals on features outside the subset are excluded.
+    pub fn set_feature_subset(&mut self, features: Option<Vec<usize>>) {
+        if let Some(mut features) = features {
+            features.sort_unstable();
+            features.dedup();
+            assert!(
+                features.last().is_none_or(|&last| last < self.num_features()),
+                "Feature subset index out of range"
+            );
+            for feature in 0..self.num_features() {
+                if features.binary_search(&feature).is_err() {
+                    let num_states = self.positive_automata[feature].num_states();
+                    self.positive_automata[feature] = TsetlinAutomaton::new(num_states);
+                    self.negative_automata[feature] = TsetlinAutomaton::new(num_states);
+                }
+            }
+            self.feature_subset = Some(features);
+        } else {
+            self.feature_subset = None;
+        }
+    }
+
+    /// Create a clause with randomly initialized exclude states
+    ///
+    /// Every automaton starts at a random depth of the exclude side, so some
+    /// literals are only a few penalties away from being included.
+    pub fn random<R: Rng>(num_features: usize, num_states: u32, rng: &mut R) -> Self {
+        let bound = num_states as i32;
+        let mut random_automata = || -> Vec<TsetlinAutomaton> {
+            (0..num_features)
+                .map(|_| TsetlinAutomaton::with_state(num_states, rng.gen_range(-bound..=0)))
+                .collect()
+        };
+
+        Self {
+            positive_automata: random_automata(),
+            negative_automata: random_automata(),
+            feature_subset: None,
+        }
+    }
+
+    /// Number of features the clause is defined over
+    pub fn num_features(&self) -> usize {
+        self.positive_automata.len()
+    }
+
+    /// Append fresh exclude-state automata for `additional` new features
+    pub fn extend_features(&mut self, additional: usize, num_states: u32) {
+        let fresh = TsetlinAutomaton::new(num_states);
+        self.positive_automata.extend(std::iter::repeat_n(fresh.clone(), additional));
+        self.negative_automata.extend(std::iter::repeat_n(fresh, additional));
+    }
+
+    /// Literals currently included in the clause, ordered by feature
+    pub fn included_literals(&self) -> Vec<Literal> {
+        let mut literals = Vec::new();
+        for (feature, (positive, negative)) in self
+            .positive_automata
+            .iter()
+            .zip(self.negative_automata.iter())
+            .enumerate()
+        {
+            if positive.action() == Action::Include {
+                literals.push(Literal { feature, negated: false });
+            }
+            if negative.action() == Action::Include {
+                literals.push(Literal { feature, negated: true });
+            }
+        }
+        literals
+    }
+
+    /// Fraction of the clause's automata in their deepest include or exclude state
+    ///
+    /// Saturated automata need many opposing feedback steps to change their
+    /// action, so a clause close to 1 has settled; a low value means its
+    /// literals are still moving around the include boundary.
+    pub fn saturation(&self) -> f64 {
+        let automata = self.positive_automata.iter().chain(&self.negative_automata);
+        let num_automata = 2 * self.positive_automata.len();
+        let saturated = automata
+            .filter(|automaton| automaton.state().unsigned_abs() == automaton.num_states())
+            .count();
+        if num_automata == 0 {
+            0.0
+        } else {
+            saturated as f64 / num_automata as f64
+        }
+    }
+
+    /// Jaccard similarity between the included-literal sets of two clauses
+    ///
+    /// Two clauses without included literals are considered identical.
+    pub fn jaccard_similarity(&self, other: &Clause) -> f64 {
+        let ours = self.included_literals();
+        let theirs = other.included_literals();
+        if ours.is_empty() && theirs.is_empty() {
+            return 1.0;
+        }
+
+        let intersection = ours.iter().filter(|literal| theirs.contains(literal)).count();
+        let union = ours.len() + theirs.len() - intersection;
+        intersection as f64 / union as f64
+    }
+
+    /// Force a literal into the shallowest include or exclude state
+    pub fn set_included(&mut self, literal: Literal, included: bool) {
+        assert!(
+            !included
+                || self
+                    .feature_subset()
+                    .is_none_or(|subset| subset.binary_search(&literal.feature).is_ok()),
+            "Feature is outside the clause's feature subset"
+        );
+        let automaton = if literal.negated {
+            &mut self.negative_automata[literal.feature]
+        } else {
+            &mut self.positive_automata[literal.feature]
+        };
+        let num_states = automaton.num_states();
+        *automaton = TsetlinAutomaton::with_state(num_states, if included { 1 } else { 0 });
+    }
+
+    /// State counter of a literal's automaton (positive when included)
+    pub fn literal_state(&self, literal: Literal) -> i32 {
+        self.automaton(literal).state()
+    }
+
+    /// Set the state counter of a literal's automaton
+    ///
+    /// The state is clamped to the automaton's range.
+    pub fn set_literal_state(&mut self, literal: Literal, state: i32) {
+        let automaton = if literal.negated {
+            &mut self.negative_automata[literal.feature]
+        } else {
+            &mut self.positive_automata[literal.feature]
+        };
+        *automaton = TsetlinAutomaton::with_state(automaton.num_states(), state);
+    }
+
+    /// Automaton of a literal
+    fn automaton(&self, literal: Literal) -> &TsetlinAutomaton {
+        if literal.negated {
+            &self.negative_automata[literal.feature]
+        } else {
+            &self.positive_automata[literal.feature]
+        }
+    }
+
+    /// Check the clause's internal invariants
+    ///
+    /// Every automaton must have `num_states` states per action and a state
+    /// within `-num_states..=num_states`, both automaton vectors must cover
+    /// `num_features` features, and a feature subset must be ascending and in
+    /// range.
+    pub fn debug_validate(&self, num_features: usize, num_states: u32) -> Result<(), String> {
+        if self.positive_automata.len() != num_features
+            || self.negative_automata.len() != num_features
+        {
+            return Err(format!(
+                "clause has {}/{} automata for {num_features} features",
+                self.positive_automata.len(),
+                self.negative_automata.len()
+            ));
+        }
+        let bound = num_states as i32;
+        for automaton in self.positive_automata.iter().chain(&self.negative_automata) {
+            if automaton.num_states() != num_states {
+                return Err(format!(
+                    "automaton has {} states instead of {num_states}",
+                    automaton.num_states()
+                ));
+            }
+            if !(-bound..=bound).contains(&automaton.state()) {
+                return Err(format!("automaton state {} is outside ±{num_states}", automaton.state()));
+            }
+        }
+        if let Some(subset) = self.feature_subset() {
+            if subset.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err("feature subset is not strictly ascending".to_string());
+            }
+            if subset.last().is_some_and(|&feature| feature >= num_features) {
+                return Err("feature subset index out of range".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Push an included literal one step towards exclusion
+    pub fn penalize_literal(&mut self, literal: Literal) {
+        let automaton = if literal.negated {
+            &mut self.negative_automata[literal.feature]
+        } else {
+            &mut self.positive_automata[literal.feature]
+        };
+        if automaton.action() == Action::Include {
+            automaton.penalize();
+        }
+    }
+
+    /// Exclude literals until the thermometer bits in `group` are consistent
+    ///
+    /// See [`ClauseBank::set_ordinal_groups`].
+    fn resolve_ordinal_conflicts(&mut self, group: Range<usize>) {
+        loop {
+            let lower = group.clone().rev().find(|&feature| {
+                self.positive_automata[feature].action() == Action::Include
+            });
+            let upper = group
+                .clone()
+                .find(|&feature| self.negative_automata[feature].action() == Action::Include);
+            let (Some(lower), Some(upper)) = (lower, upper) else {
+                return;
+            };
+            if upper > lower {
+                return;
+            }
+
+            let plain = Literal { feature: lower, negated: false };
+            let negated = Literal { feature: upper, negated: true };
+            let weaker = if self.literal_state(plain) < self.literal_state(negated) {
+                plain
+            } else {
+                negated
+            };
+            self.set_included(weaker, false);
         }
     }
 
+    /// Number of literals currently included in the clause
+    pub fn num_included(&self) -> usize {
+        self.positive_automata
+            .iter()
+            .chain(self.negative_automata.iter())
+            .filter(|automaton| automaton.action() == Action::Include)
+            .count()
+    }
+
     /// Evaluate the clause for a given input
     pub fn evaluate(&self, input: &[bool]) -> bool {
-        for i in 0..input.len() {
+        for i in feature_indices(self.feature_subset(), input.len()) {
+            let value = input[i];
             // Check positive literals
-            if self.positive_automata[i].action() == Action::Include && !input[i] {
+            if self.positive_automata[i].action() == Action::Include && !value {
                 return false;
             }
             
             // Check negative literals
-            if self.negative_automata[i].action() == Action::Include && input[i] {
+            if self.negative_automata[i].action() == Action::Include && value {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Evaluate the clause on up to 64 inputs at once
+    ///
+    /// Bit `j` of `columns[i]` holds feature `i` of input `j`; bit `j` of the
+    /// result is the output on input `j`. Bits outside `valid` stay clear.
+    pub(crate) fn evaluate_packed(&self, columns: &[u64], valid: u64) -> u64 {
+        let mut output = valid;
+        for i in feature_indices(self.feature_subset(), columns.len()) {
+            if self.positive_automata[i].action() == Action::Include {
+                output &= columns[i];
+            }
+            if self.negative_automata[i].action() == Action::Include {
+                output &= !columns[i];
+            }
+            if output == 0 {
+                break;
+            }
+        }
+        output
+    }
+
+    /// Evaluate the clause when only the features with `mask[i]` set are known
+    ///
+    /// A literal on an unknown feature is never satisfied, so any clause that
+    /// uses a masked-out feature does not fire.
+    pub fn evaluate_masked(&self, input: &[bool], mask: &[bool]) -> bool {
+        for i in feature_indices(self.feature_subset(), input.len()) {
+            let (value, known) = (input[i], mask[i]);
+            let positive = self.positive_automata[i].action() == Action::Include;
+            let negative = self.negative_automata[i].action() == Action::Include;
+            if !known && (positive || negative) {
+                return false;
+            }
+            if (positive && !value) || (negative && value) {
                 return false;
             }
         }
@@ -48,13 +367,17 @@ impl Clause {
         specificity: f64,
         rng: &mut R,
     ) {
+        let subset = self.feature_subset.take();
+        let features = || feature_indices(subset.as_deref(), input.len());
+
         if target {
             // Type I feedback (positive target)
             if clause_output {
                 // Clause fired correctly, reward included literals
-                for i in 0..input.len() {
+                for i in features() {
+                    let value = input[i];
                     if self.positive_automata[i].action() == Action::Include {
-                        if input[i] {
+                        if value {
                             self.positive_automata[i].reward();
                         } else {
                             self.positive_automata[i].penalize();
@@ -62,7 +385,7 @@ impl Clause {
                     }
                     
                     if self.negative_automata[i].action() == Action::Include {
-                        if !input[i] {
+                        if !value {
                             self.negative_automata[i].reward();
                         } else {
                             self.negative_automata[i].penalize();
@@ -71,8 +394,9 @@ impl Clause {
                 }
             } else {
                 // Clause didn't fire, include more literals with probability
-                for i in 0..input.len() {
-                    if self.positive_automata[i].action() == Action::Exclude && input[i] {
+                for i in features() {
+                    let value = input[i];
+                    if self.positive_automata[i].action() == Action::Exclude && value {
                         self.positive_automata[i].update_with_probability(
                             false,
                             specificity / (specificity + 1.0),
@@ -80,7 +404,7 @@ impl Clause {
                         );
                     }
                     
-                    if self.negative_automata[i].action() == Action::Exclude && !input[i] {
+                    if self.negative_automata[i].action() == Action::Exclude && !value {
                         self.negative_automata[i].update_with_probability(
                             false,
                             specificity / (specificity + 1.0),
@@ -93,7 +417,7 @@ impl Clause {
             // Type II feedback (negative target)
             if clause_output {
                 // Clause fired incorrectly, penalize randomly
-                for i in 0..input.len() {
+                for i in features() {
                     if self.positive_automata[i].action() == Action::Include {
                         self.positive_automata[i].update_with_probability(
                             false,
@@ -112,6 +436,137 @@ impl Clause {
                 }
             }
         }
+
+        self.feature_subset = subset;
+    }
+}
+
+/// How clause outputs over a bag of inputs are combined into one vote sum
+///
+/// A bag holds several inputs that share a label, such as the patches of an
+/// image or the instances of a multi-instance sample. A bag with a single
+/// input votes the same under every aggregation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Add up the vote sums of all inputs
+    #[default]
+    Sum,
+    /// A clause votes once if it fires on any input
+    OrPool,
+    /// Vote sum of the single most decisive input (largest absolute vote)
+    MaxMargin,
+}
+
+/// Which clauses of a bank vote for the positive class
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PolarityScheme {
+    /// First half positive, second half negative
+    #[default]
+    Halves,
+    /// Even-indexed clauses positive, odd-indexed clauses negative
+    Interleaved,
+    /// The first `fraction` of the clauses (rounded) positive, the rest negative
+    ///
+    /// Skewed problems often need more clauses for the minority class.
+    Ratio(f64),
+    /// Explicit polarity of every clause (true for positive)
+    Custom(Vec<bool>),
+}
+
+impl PolarityScheme {
+    /// Polarity of each of `num_clauses` clauses
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::PolarityScheme;
+    ///
+    /// assert_eq!(PolarityScheme::Interleaved.polarities(4), vec![true, false, true, false]);
+    /// assert_eq!(PolarityScheme::Ratio(0.75).polarities(4), vec![true, true, true, false]);
+    /// ```
+    pub fn polarities(&self, num_clauses: usize) -> Vec<bool> {
+        match self {
+            PolarityScheme::Halves => (0..num_clauses).map(|i| i < num_clauses / 2).collect(),
+            PolarityScheme::Interleaved => (0..num_clauses).map(|i| i.is_multiple_of(2)).collect(),
+            PolarityScheme::Ratio(fraction) => {
+                assert!(
+                    (0.0..=1.0).contains(fraction),
+                    "Polarity ratio must be between 0 and 1"
+                );
+                let num_positive = (fraction * num_clauses as f64).round() as usize;
+                (0..num_clauses).map(|i| i < num_positive).collect()
+            }
+            PolarityScheme::Custom(polarities) => {
+                assert_eq!(
+                    polarities.len(),
+                    num_clauses,
+                    "Custom polarities must cover every clause"
+                );
+                polarities.clone()
+            }
+        }
+    }
+}
+
+/// How clause vote weights are learned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WeightLearning {
+    /// Every clause votes with weight 1
+    #[default]
+    None,
+    /// Integer weights adjusted during feedback by a stochastic search
+    ///
+    /// A firing clause that receives feedback steps its weight by one unit,
+    /// up when its vote was correct and down when it was wrong, staying
+    /// between 1 and `max_weight`. The step is taken with probability
+    /// `(T - v) / 2T`, where `v` is the vote sum in favour of the target
+    /// clipped to `[-T, T]`: samples the machine gets badly wrong move weights
+    /// almost surely, nearly settled ones rarely. Strong clauses end up
+    /// carrying several votes, so fewer clauses are needed for the same
+    /// accuracy.
+    Integer {
+        /// Upper bound on any clause weight
+        max_weight: u32,
+    },
+}
+
+/// How an input that gets feedback is treated
+#[derive(Debug, Clone, Copy)]
+struct Decision {
+    /// The vote sum already reaches the threshold for the opposite class
+    suspected_mislabel: bool,
+    /// Shortfall of the vote sum from the threshold, from 0.0 to 1.0
+    vote_error: f64,
+}
+
+/// Firing statistics of a clause over a labelled dataset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClauseStats {
+    /// Number of samples on which the clause fired
+    pub fired: usize,
+    /// Number of those samples whose label matches the clause's polarity
+    pub correct: usize,
+}
+
+impl ClauseStats {
+    /// Fraction of firings that voted for the correct class (0.0 if never fired)
+    pub fn precision(&self) -> f64 {
+        if self.fired == 0 {
+            0.0
+        } else {
+            self.correct as f64 / self.fired as f64
+        }
+    }
+
+    /// Correct minus wrong firings per firing, from -1.0 to 1.0 (0.0 if never fired)
+    ///
+    /// Negative when the clause votes for the wrong class more often than for
+    /// the right one, i.e. when its precision is below 0.5.
+    pub fn net_precision(&self) -> f64 {
+        if self.fired == 0 {
+            0.0
+        } else {
+            (2.0 * self.correct as f64 - self.fired as f64) / self.fired as f64
+        }
     }
 }
 
@@ -122,6 +577,31 @@ pub struct ClauseBank {
     clauses: Vec<Clause>,
     /// Polarity of each clause (true for positive, false for negative)
     polarities: Vec<bool>,
+    /// Frozen clauses still vote but receive no feedback
+    frozen: Vec<bool>,
+    /// Number of states per action of every automaton
+    num_states: u32,
+    /// Number of input features
+    num_features: usize,
+    /// Probability of withholding Type II feedback from suspected mislabels
+    noise_tolerance: f64,
+    /// Vote weight of each clause
+    weights: Vec<u32>,
+    /// How weights are learned
+    weight_learning: WeightLearning,
+    /// Size of the random feature subset of every clause (all features when `None`)
+    features_per_clause: Option<usize>,
+    /// How clause outputs over a bag of inputs are combined
+    aggregation: Aggregation,
+    /// Literals no clause may include, sorted
+    forbidden: Vec<Literal>,
+    /// Feature ranges holding thermometer bits of one ordinal column
+    ordinal_groups: Vec<Range<usize>>,
+    /// Clause outputs on the input being trained on, bit `i % 64` of word
+    /// `i / 64` for clause `i` (scratch buffer)
+    outputs: Vec<u64>,
+    /// Vote sums of the entries of a mini-batch (scratch buffer)
+    votes: Vec<i32>,
 }
 
 impl ClauseBank {
@@ -131,95 +611,1579 @@ impl ClauseBank {
             .map(|_| Clause::new(num_features, num_states))
             .collect();
             
-        let polarities = (0..num_clauses)
-            .map(|i| i < num_clauses / 2) // First half positive, second half negative
-            .collect();
+        let polarities = PolarityScheme::Halves.polarities(num_clauses);
             
         Self {
             clauses,
             polarities,
+            frozen: vec![false; num_clauses],
+            num_states,
+            num_features,
+            noise_tolerance: 0.0,
+            weights: vec![1; num_clauses],
+            weight_learning: WeightLearning::None,
+            features_per_clause: None,
+            aggregation: Aggregation::Sum,
+            forbidden: Vec::new(),
+            ordinal_groups: Vec::new(),
+            outputs: Vec::new(),
+            votes: Vec::new(),
         }
     }
 
-    /// Evaluate all clauses and return the vote sum
-    pub fn vote(&self, input: &[bool]) -> i32 {
-        let mut vote_sum = 0;
-        
-        for (clause, &polarity) in self.clauses.iter().zip(self.polarities.iter()) {
-            if clause.evaluate(input) {
-                if polarity {
-                    vote_sum += 1;
-                } else {
-                    vote_sum -= 1;
+    /// Create a clause bank with separate budgets per polarity
+    ///
+    /// The first `num_positive` clauses vote for the positive class and the
+    /// remaining `num_negative` against it. Votes and thresholds need no
+    /// adjustment: the vote sum simply has more terms on one side, so the
+    /// larger side must still be outvoted by the features of a sample.
+    pub fn with_polarity_budget(
+        num_features: usize,
+        num_positive: usize,
+        num_negative: usize,
+        num_states: u32,
+    ) -> Self {
+        assert!(
+            num_positive > 0 && num_negative > 0,
+            "Each polarity needs at least one clause"
+        );
+        let num_clauses = num_positive + num_negative;
+        let mut bank = Self::new(num_features, num_clauses, num_states);
+        bank.polarities = (0..num_clauses).map(|i| i < num_positive).collect();
+        bank
+    }
+
+    /// Number of positive and negative clauses
+    pub fn polarity_counts(&self) -> (usize, usize) {
+        let num_positive = self.polarities.iter().filter(|&&polarity| polarity).count();
+        (num_positive, self.polarities.len() - num_positive)
+    }
+
+    /// Number of clauses in the bank
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// All clauses in the bank
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+
+    /// Mutable access to a single clause
+    pub fn clause_mut(&mut self, index: usize) -> &mut Clause {
+        &mut self.clauses[index]
+    }
+
+    /// Polarity of each clause (true for positive, false for negative)
+    pub fn polarities(&self) -> &[bool] {
+        &self.polarities
+    }
+
+    /// Change the polarity of a clause
+    pub fn set_polarity(&mut self, index: usize, polarity: bool) {
+        self.polarities[index] = polarity;
+    }
+
+    /// Reassign the polarity of every clause
+    pub fn set_polarity_scheme(&mut self, scheme: &PolarityScheme) {
+        self.polarities = scheme.polarities(self.num_clauses());
+    }
+
+    /// Vote weight of each clause
+    pub fn weights(&self) -> &[u32] {
+        &self.weights
+    }
+
+    /// Set the vote weight of a clause
+    pub fn set_weight(&mut self, index: usize, weight: u32) {
+        assert!(weight > 0, "Clause weights must be positive");
+        self.weights[index] = weight;
+    }
+
+    /// Signed vote of a clause when it fires
+    pub fn signed_weight(&self, index: usize) -> i32 {
+        let weight = self.weights[index] as i32;
+        if self.polarities[index] {
+            weight
+        } else {
+            -weight
+        }
+    }
+
+    /// Choose how clause weights are learned during feedback
+    pub fn set_weight_learning(&mut self, weight_learning: WeightLearning) {
+        if let WeightLearning::Integer { max_weight } = weight_learning {
+            assert!(max_weight > 0, "Maximum weight must be positive");
+        }
+        self.weight_learning = weight_learning;
+    }
+
+    /// How clause weights are learned during feedback
+    pub fn weight_learning(&self) -> WeightLearning {
+        self.weight_learning
+    }
+
+    /// Restrict every clause to its own random subset of features
+    ///
+    /// Like the feature subsampling of a random forest, this decorrelates the
+    /// clauses and makes evaluation on very wide data cheaper. Subsets are
+    /// drawn once here; clauses that are reinitialized or added later draw
+    /// their own. `None` lets every clause use all features again.
+    pub fn set_feature_bagging<R: Rng>(&mut self, features_per_clause: Option<usize>, rng: &mut R) {
+        if let Some(size) = features_per_clause {
+            assert!(
+                size > 0 && size <= self.num_features,
+                "Features per clause must be between 1 and the number of features"
+            );
+        }
+        self.features_per_clause = features_per_clause;
+        for index in 0..self.clauses.len() {
+            let subset = self.random_feature_subset(rng);
+            self.clauses[index].set_feature_subset(subset);
+        }
+    }
+
+    /// Size of the random feature subset of every clause, if bagging is enabled
+    pub fn features_per_clause(&self) -> Option<usize> {
+        self.features_per_clause
+    }
+
+    /// Draw a feature subset for a new clause
+    fn random_feature_subset<R: Rng>(&self, rng: &mut R) -> Option<Vec<usize>> {
+        self.features_per_clause
+            .map(|size| rand::seq::index::sample(rng, self.num_features, size).into_vec())
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Number of states per action of every automaton
+    pub fn num_states(&self) -> u32 {
+        self.num_states
+    }
+
+    /// Grow every clause to accept `additional` new features
+    ///
+    /// The new literals start in the deepest exclude state, so existing
+    /// clauses keep their outputs until training includes them. Clauses with
+    /// a feature subset do not see the new features.
+    pub fn extend_features(&mut self, additional: usize) {
+        for clause in &mut self.clauses {
+            clause.extend_features(additional, self.num_states);
+        }
+        self.num_features += additional;
+    }
+
+    /// Automaton saturation of every clause (see [`Clause::saturation`])
+    pub fn saturation(&self) -> Vec<f64> {
+        self.clauses.iter().map(Clause::saturation).collect()
+    }
+
+    /// Literals crossing the include boundary in each clause since `before`
+    ///
+    /// `before` holds the included literals of every clause, as returned by
+    /// [`Clause::included_literals`]; clauses beyond it count all their
+    /// included literals.
+    pub fn include_churn(&self, before: &[Vec<Literal>]) -> Vec<usize> {
+        self.clauses
+            .iter()
+            .enumerate()
+            .map(|(index, clause)| {
+                let now = clause.included_literals();
+                let then = before.get(index).map_or(&[][..], Vec::as_slice);
+                let kept = now.iter().filter(|literal| then.binary_search(literal).is_ok()).count();
+                now.len() + then.len() - 2 * kept
+            })
+            .collect()
+    }
+
+    /// Compute firing statistics of every clause on a labelled dataset
+    pub fn clause_stats<S: AsBoolSamples + ?Sized>(
+        &self,
+        features: &S,
+        labels: &Array1<bool>,
+    ) -> Vec<ClauseStats> {
+        assert_eq!(features.num_samples(), labels.len());
+
+        let mut stats = vec![ClauseStats::default(); self.clauses.len()];
+        let mut input = Vec::with_capacity(self.num_features);
+        for (index, &label) in labels.iter().enumerate() {
+            features.sample_into(index, &mut input);
+            for ((clause, &polarity), stat) in self
+                .clauses
+                .iter()
+                .zip(self.polarities.iter())
+                .zip(stats.iter_mut())
+            {
+                if clause.evaluate(&input) {
+                    stat.fired += 1;
+                    if polarity == label {
+                        stat.correct += 1;
+                    }
                 }
             }
         }
-        
-        vote_sum
+        stats
     }
 
-    /// Update all clauses based on feedback
-    pub fn update<R: Rng>(
-        &mut self,
-        input: &[bool],
-        target: bool,
-        threshold: f64,
-        specificity: f64,
-        rng: &mut R,
-    ) {
-        let vote_sum = self.vote(input);
-        
-        for (clause, &polarity) in self.clauses.iter_mut().zip(self.polarities.iter()) {
-            let clause_output = clause.evaluate(input);
-            
-            let should_update = if target {
-                vote_sum < threshold as i32
-            } else {
-                vote_sum > -(threshold as i32)
+    /// Replace a clause with a randomly initialized one, keeping its polarity
+    pub fn reinitialize<R: Rng>(&mut self, index: usize, rng: &mut R) {
+        let subset = self.random_feature_subset(rng);
+        self.clauses[index] = Clause::random(self.num_features, self.num_states, rng);
+        self.clauses[index].set_feature_subset(subset);
+        self.frozen[index] = false;
+        self.weights[index] = 1;
+    }
+
+    /// Reset clauses that duplicate another clause of the same polarity
+    ///
+    /// Clauses with identical included-literal sets and polarity always fire
+    /// together, so only the first one is kept and every duplicate is reset to
+    /// a fresh all-exclude clause that can learn a new pattern. The weight of a
+    /// duplicate is added to the kept clause, up to the maximum weight. Vote
+    /// sums still change: a reset clause is empty, so it fires on every input
+    /// and adds one vote of its polarity until it learns a pattern. Clauses
+    /// without included literals are untrained and never count as
+    /// duplicates. Returns the number of reset clauses.
+    pub fn deduplicate(&mut self) -> usize {
+        let mut seen = std::collections::HashMap::new();
+        let mut reset = 0;
+
+        for index in 0..self.clauses.len() {
+            let literals = self.clauses[index].included_literals();
+            if literals.is_empty() {
+                continue;
+            }
+            match seen.entry((self.polarities[index], literals)) {
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+                std::collections::hash_map::Entry::Occupied(entry) => {
+                    let kept = *entry.get();
+                    self.weights[kept] = self.weights[kept]
+                        .saturating_add(self.weights[index])
+                        .min(self.max_weight());
+                    let subset = self.clauses[index].feature_subset().map(<[usize]>::to_vec);
+                    self.clauses[index] = Clause::new(self.num_features, self.num_states);
+                    self.clauses[index].set_feature_subset(subset);
+                    self.frozen[index] = false;
+                    self.weights[index] = 1;
+                    reset += 1;
+                }
+            }
+        }
+        reset
+    }
+
+    /// Pairwise Jaccard similarity between the literal sets of all clauses
+    pub fn similarity_matrix(&self) -> Array2<f64> {
+        let n = self.clauses.len();
+        let mut similarity = Array2::from_elem((n, n), 1.0);
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let value = self.clauses[i].jaccard_similarity(&self.clauses[j]);
+                similarity[[i, j]] = value;
+                similarity[[j, i]] = value;
+            }
+        }
+        similarity
+    }
+
+    /// Overall diversity of the bank (0.0 to 1.0)
+    ///
+    /// Defined as one minus the mean pairwise Jaccard similarity, so a bank of
+    /// identical clauses scores 0.0 and a bank of disjoint clauses 1.0.
+    pub fn diversity(&self) -> f64 {
+        let n = self.clauses.len();
+        if n < 2 {
+            return 1.0;
+        }
+
+        let similarity = self.similarity_matrix();
+        let mut total = 0.0;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                total += similarity[[i, j]];
+            }
+        }
+        1.0 - total / (n * (n - 1) / 2) as f64
+    }
+
+    /// Discourage redundant clauses of the same polarity
+    ///
+    /// Every trained clause is compared with its most similar other clause of
+    /// the same polarity. Each literal the two share is pushed towards exclusion
+    /// with probability `strength * similarity`, nudging near-duplicates apart.
+    /// Returns the number of penalized literals.
+    pub fn apply_diversity_penalty<R: Rng>(&mut self, strength: f64, rng: &mut R) -> usize {
+        let similarity = self.similarity_matrix();
+        let mut penalized = 0;
+
+        for i in 0..self.clauses.len() {
+            if self.frozen[i] || self.clauses[i].num_included() == 0 {
+                continue;
+            }
+
+            let nearest = (0..self.clauses.len())
+                .filter(|&j| j != i && self.polarities[j] == self.polarities[i])
+                .filter(|&j| self.clauses[j].num_included() > 0)
+                .max_by(|&a, &b| similarity[[i, a]].total_cmp(&similarity[[i, b]]));
+            let Some(nearest) = nearest else {
+                continue;
             };
-            
-            if should_update {
-                let clause_target = if polarity { target } else { !target };
-                clause.update(input, clause_target, clause_output, specificity, rng);
+
+            let probability = strength * similarity[[i, nearest]];
+            let own = self.clauses[i].included_literals();
+            let shared: Vec<Literal> = self.clauses[nearest]
+                .included_literals()
+                .into_iter()
+                .filter(|literal| own.contains(literal))
+                .collect();
+            for literal in shared {
+                if rng.gen::<f64>() < probability {
+                    self.clauses[i].penalize_literal(literal);
+                    penalized += 1;
+                }
             }
         }
+        penalized
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::SeedableRng;
+    /// Append a randomly initialized pair of clauses (one per polarity)
+    pub fn add_clause_pair<R: Rng>(&mut self, rng: &mut R) {
+        for polarity in [true, false] {
+            let subset = self.random_feature_subset(rng);
+            let mut clause = Clause::random(self.num_features, self.num_states, rng);
+            clause.set_feature_subset(subset);
+            self.clauses.push(clause);
+            self.polarities.push(polarity);
+            self.frozen.push(false);
+            self.weights.push(1);
+        }
+    }
 
-    #[test]
-    fn test_clause_evaluation() {
-        let clause = Clause::new(3, 100);
-        let input = vec![true, false, true];
-        
-        // Empty clause should always return true
-        assert!(clause.evaluate(&input));
+    /// Copies of the clauses at `indices` with their polarities
+    ///
+    /// Together with [`ClauseBank::insert`] this transplants clauses between
+    /// banks, e.g. the most precise clauses of a per-segment model into a
+    /// global one.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::{ClauseBank, Literal};
+    ///
+    /// let mut source = ClauseBank::new(3, 4, 100);
+    /// source.clause_mut(1).set_included(Literal { feature: 2, negated: false }, true);
+    ///
+    /// let mut target = ClauseBank::new(3, 2, 100);
+    /// let (clauses, polarities) = source.extract(&[1]);
+    /// target.insert(clauses, polarities);
+    /// assert_eq!(target.num_clauses(), 3);
+    /// let literals = target.clauses()[2].included_literals();
+    /// assert_eq!(literals, source.clauses()[1].included_literals());
+    /// ```
+    pub fn extract(&self, indices: &[usize]) -> (Vec<Clause>, Vec<bool>) {
+        indices
+            .iter()
+            .map(|&index| (self.clauses[index].clone(), self.polarities[index]))
+            .unzip()
     }
 
-    #[test]
-    fn test_clause_bank_voting() {
-        let bank = ClauseBank::new(3, 4, 100);
-        let input = vec![true, false, true];
-        
-        // With empty clauses, all should fire
-        // 2 positive clauses (+2) + 2 negative clauses (-2) = 0
-        assert_eq!(bank.vote(&input), 0);
+    /// Append clauses with the given polarities
+    ///
+    /// Every clause must match the bank's features and automaton states.
+    /// Inserted clauses are unfrozen, have weight 1 and lose any forbidden
+    /// literals.
+    pub fn insert(&mut self, clauses: Vec<Clause>, polarities: Vec<bool>) {
+        assert_eq!(clauses.len(), polarities.len(), "Every inserted clause needs a polarity");
+        for (index, clause) in clauses.iter().enumerate() {
+            if let Err(violation) = clause.debug_validate(self.num_features, self.num_states) {
+                panic!("Inserted clause {index} does not fit the bank: {violation}");
+            }
+        }
+
+        let num_inserted = clauses.len();
+        self.clauses.extend(clauses);
+        self.polarities.extend(polarities);
+        self.frozen.extend(std::iter::repeat_n(false, num_inserted));
+        self.weights.extend(std::iter::repeat_n(1, num_inserted));
+        for index in self.clauses.len() - num_inserted..self.clauses.len() {
+            self.enforce_constraints(index);
+        }
     }
 
-    #[test]
-    fn test_clause_bank_update() {
-        let mut bank = ClauseBank::new(3, 4, 100);
-        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
-        let input = vec![true, false, true];
-        
-        // Update with positive target
-        bank.update(&input, true, 1.0, 2.0, &mut rng);
-        
-        // Should not crash
-        assert_eq!(bank.clauses.len(), 4);
+    /// Forbid every clause from including `literal`
+    ///
+    /// The literal is excluded from all clauses now, and feedback pins it to
+    /// the deepest exclude state from then on, so the constraint holds for
+    /// the whole of training rather than being checked afterwards.
+    pub fn forbid_literal(&mut self, literal: Literal) {
+        assert!(literal.feature < self.num_features, "Constrained feature out of range");
+
+        if let Err(position) = self.forbidden.binary_search(&literal) {
+            self.forbidden.insert(position, literal);
+        }
+        for clause in &mut self.clauses {
+            clause.set_literal_state(literal, -(self.num_states as i32));
+        }
+    }
+
+    /// Literals no clause may include
+    pub fn forbidden_literals(&self) -> &[Literal] {
+        &self.forbidden
+    }
+
+    /// Keep clauses consistent with thermometer-encoded ordinal columns
+    ///
+    /// Each range holds the bits of one column in increasing threshold order,
+    /// as produced by
+    /// [`ThermometerEncoder`](crate::binarize::ThermometerEncoder): bit `k` is
+    /// set when the value reaches threshold `k`. A clause including bit `k`
+    /// together with the negation of bit `j <= k` can never fire. After every
+    /// feedback step such contradictions are resolved by excluding the less
+    /// confident of the two literals, so every clause describes a value range
+    /// of each column.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::{ClauseBank, Literal};
+    ///
+    /// let x2 = Literal { feature: 2, negated: false };
+    /// let mut bank = ClauseBank::new(3, 2, 100);
+    /// bank.clause_mut(0).set_literal_state(x2, 5);
+    /// bank.clause_mut(0).set_literal_state(Literal { feature: 1, negated: true }, 1);
+    /// bank.set_ordinal_groups([0..3]);
+    /// assert_eq!(bank.clauses()[0].included_literals(), vec![x2]);
+    /// ```
+    pub fn set_ordinal_groups(&mut self, groups: impl IntoIterator<Item = Range<usize>>) {
+        let groups: Vec<Range<usize>> = groups.into_iter().collect();
+        assert!(
+            groups.iter().all(|group| group.end <= self.num_features),
+            "Ordinal group out of range"
+        );
+
+        self.ordinal_groups = groups;
+        for index in 0..self.clauses.len() {
+            self.enforce_constraints(index);
+        }
+    }
+
+    /// Feature ranges of thermometer-encoded ordinal columns
+    pub fn ordinal_groups(&self) -> &[Range<usize>] {
+        &self.ordinal_groups
+    }
+
+    /// Apply forbidden literals and ordinal consistency to a clause
+    fn enforce_constraints(&mut self, index: usize) {
+        let clause = &mut self.clauses[index];
+        let deepest = -(self.num_states as i32);
+        for &literal in &self.forbidden {
+            if clause.literal_state(literal) > deepest {
+                clause.set_literal_state(literal, deepest);
+            }
+        }
+        for group in &self.ordinal_groups {
+            clause.resolve_ordinal_conflicts(group.clone());
+        }
+    }
+
+    /// Truncate or pad the bank to `num_clauses` clauses
+    ///
+    /// Added clauses are empty, positive, unfrozen and of weight 1.
+    pub(crate) fn resize(&mut self, num_clauses: usize) {
+        let (num_features, num_states) = (self.num_features, self.num_states);
+        self.clauses.resize_with(num_clauses, || Clause::new(num_features, num_states));
+        self.polarities.resize(num_clauses, true);
+        self.frozen.resize(num_clauses, false);
+        self.weights.resize(num_clauses, 1);
+    }
+
+    /// Freeze or unfreeze a clause
+    pub fn set_frozen(&mut self, index: usize, frozen: bool) {
+        self.frozen[index] = frozen;
+    }
+
+    /// Check whether a clause is frozen
+    pub fn is_frozen(&self, index: usize) -> bool {
+        self.frozen[index]
+    }
+
+    /// Number of frozen clauses
+    pub fn num_frozen(&self) -> usize {
+        self.frozen.iter().filter(|&&frozen| frozen).count()
+    }
+
+    /// Freeze the most specific `fraction` of the clauses of each polarity
+    ///
+    /// Clauses with more included literals have learned more structure, so
+    /// they are frozen first. Ties keep the clause order.
+    pub fn freeze_most_specific(&mut self, fraction: f64) {
+        assert!((0.0..=1.0).contains(&fraction), "Freeze fraction must be in [0, 1]");
+
+        for polarity in [true, false] {
+            let mut candidates: Vec<usize> = (0..self.clauses.len())
+                .filter(|&i| self.polarities[i] == polarity)
+                .collect();
+            candidates.sort_by_key(|&i| std::cmp::Reverse(self.clauses[i].num_included()));
+
+            let count = (fraction * candidates.len() as f64).round() as usize;
+            for &i in &candidates[..count] {
+                self.frozen[i] = true;
+            }
+        }
+    }
+
+    /// Unfreeze every clause
+    pub fn unfreeze_all(&mut self) {
+        self.frozen.fill(false);
+    }
+
+    /// Evaluate all clauses and return the vote sum
+    pub fn vote(&self, input: &[bool]) -> i32 {
+        self.clauses
+            .iter()
+            .enumerate()
+            .filter(|(_, clause)| clause.evaluate(input))
+            .map(|(index, _)| self.signed_weight(index))
+            .sum()
+    }
+
+    /// Sum of votes with masked-out features treated as unknown
+    pub fn vote_masked(&self, input: &[bool], mask: &[bool]) -> i32 {
+        self.clauses
+            .iter()
+            .enumerate()
+            .filter(|(_, clause)| clause.evaluate_masked(input, mask))
+            .map(|(index, _)| self.signed_weight(index))
+            .sum()
+    }
+
+    /// Evaluate every clause on the input, in clause order
+    pub fn clause_outputs(&self, input: &[bool]) -> Vec<bool> {
+        self.clauses.iter().map(|clause| clause.evaluate(input)).collect()
+    }
+
+    /// Choose how [`vote_bag`](Self::vote_bag) combines clause outputs
+    pub fn set_aggregation(&mut self, aggregation: Aggregation) {
+        self.aggregation = aggregation;
+    }
+
+    /// How clause outputs over a bag of inputs are combined
+    pub fn aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+
+    /// Vote sum of a bag of inputs under the bank's [`Aggregation`]
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::{Aggregation, ClauseBank};
+    ///
+    /// // Two empty clauses of each polarity always fire
+    /// let mut bank = ClauseBank::new(2, 4, 100);
+    /// let bag = vec![vec![true, false], vec![false, true], vec![true, true]];
+    /// assert_eq!(bank.vote_bag(&bag), 0);
+    ///
+    /// bank.set_aggregation(Aggregation::OrPool);
+    /// assert_eq!(bank.vote_bag(&bag), bank.vote(&bag[0]));
+    /// ```
+    pub fn vote_bag(&self, bag: &[Vec<bool>]) -> i32 {
+        match self.aggregation {
+            Aggregation::Sum => bag.iter().map(|input| self.vote(input)).sum(),
+            Aggregation::OrPool => (0..self.clauses.len())
+                .filter(|&index| bag.iter().any(|input| self.clauses[index].evaluate(input)))
+                .map(|index| self.signed_weight(index))
+                .sum(),
+            Aggregation::MaxMargin => self.decisive_input(bag).1,
+        }
+    }
+
+    /// Index and vote sum of the input with the largest absolute vote (first on ties)
+    fn decisive_input(&self, bag: &[Vec<bool>]) -> (usize, i32) {
+        let mut best = (0, 0_i32);
+        for (index, input) in bag.iter().enumerate() {
+            let vote = self.vote(input);
+            if vote.abs() > best.1.abs() {
+                best = (index, vote);
+            }
+        }
+        best
+    }
+
+    /// Update all clauses from a bag of inputs sharing one label
+    ///
+    /// Feedback is gated by [`vote_bag`](Self::vote_bag). Each clause then
+    /// learns from one input of the bag: under `OrPool` a random input it
+    /// fires on (a random input if none), under `Sum` a random input, and
+    /// under `MaxMargin` the most decisive input.
+    pub fn update_bag<R: Rng>(
+        &mut self,
+        bag: &[Vec<bool>],
+        target: bool,
+        threshold: f64,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        let vote_sum = self.vote_bag(bag);
+        self.update_bag_with_vote(bag, target, vote_sum, threshold, specificity, rng);
+    }
+
+    /// [`update_bag`](Self::update_bag) gated by an externally computed vote sum
+    ///
+    /// Used when several banks vote on the same sample together.
+    pub(crate) fn update_bag_with_vote<R: Rng>(
+        &mut self,
+        bag: &[Vec<bool>],
+        target: bool,
+        vote_sum: i32,
+        threshold: f64,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        assert!(!bag.is_empty(), "Bags must contain at least one input");
+
+        let Some(decision) = self.feedback_decision(target, vote_sum, threshold) else {
+            return;
+        };
+
+        let decisive = match self.aggregation {
+            Aggregation::MaxMargin => self.decisive_input(bag).0,
+            _ => 0,
+        };
+        self.outputs.resize(self.clauses.len().div_ceil(64), 0);
+        for index in 0..self.clauses.len() {
+            if self.frozen[index] {
+                continue;
+            }
+
+            let input = match self.aggregation {
+                Aggregation::OrPool => {
+                    let firing: Vec<&Vec<bool>> = bag
+                        .iter()
+                        .filter(|input| self.clauses[index].evaluate(input))
+                        .collect();
+                    if firing.is_empty() {
+                        &bag[crate::rng::gen_index(rng, bag.len())]
+                    } else {
+                        firing[crate::rng::gen_index(rng, firing.len())]
+                    }
+                }
+                Aggregation::Sum => &bag[crate::rng::gen_index(rng, bag.len())],
+                Aggregation::MaxMargin => &bag[decisive],
+            };
+            let clause_output = self.clauses[index].evaluate(input);
+            self.set_output(index, clause_output);
+            self.feedback(index, input, target, decision, specificity, rng);
+        }
+        self.validate_after_update();
+    }
+
+    /// Check the bank's internal invariants
+    ///
+    /// The per-clause vectors (polarities, frozen flags, weights) must match
+    /// the number of clauses, weights must lie within the weight-learning
+    /// range, and every clause must pass [`Clause::debug_validate`]. Returns
+    /// the first violation found.
+    pub fn debug_validate(&self) -> Result<(), String> {
+        let num_clauses = self.clauses.len();
+        for (name, len) in [
+            ("polarity", self.polarities.len()),
+            ("frozen flag", self.frozen.len()),
+            ("weight", self.weights.len()),
+        ] {
+            if len != num_clauses {
+                return Err(format!("{len} {name} entries for {num_clauses} clauses"));
+            }
+        }
+        let max_weight = self.max_weight();
+        for (index, clause) in self.clauses.iter().enumerate() {
+            let weight = self.weights[index];
+            if weight == 0 || weight > max_weight {
+                return Err(format!("clause {index} has weight {weight}"));
+            }
+            clause
+                .debug_validate(self.num_features, self.num_states)
+                .map_err(|violation| format!("clause {index}: {violation}"))?;
+        }
+        Ok(())
+    }
+
+    /// Largest weight a clause may carry
+    fn max_weight(&self) -> u32 {
+        match self.weight_learning {
+            WeightLearning::None => u32::MAX,
+            WeightLearning::Integer { max_weight } => max_weight,
+        }
+    }
+
+    /// Panic on invariant violations after an update when the `validate`
+    /// feature is enabled
+    fn validate_after_update(&self) {
+        #[cfg(feature = "validate")]
+        if let Err(violation) = self.debug_validate() {
+            panic!("Clause bank invariant violated after update: {violation}");
+        }
+    }
+
+    /// Set the probability of withholding Type II feedback from suspected mislabels
+    ///
+    /// A sample is suspected to be mislabelled when the vote sum already
+    /// reaches the threshold in favour of the opposite class.
+    pub fn set_noise_tolerance(&mut self, tolerance: f64) {
+        assert!(
+            (0.0..=1.0).contains(&tolerance),
+            "Noise tolerance must be between 0 and 1"
+        );
+        self.noise_tolerance = tolerance;
+    }
+
+    /// Probability of withholding Type II feedback from suspected mislabels
+    pub fn noise_tolerance(&self) -> f64 {
+        self.noise_tolerance
+    }
+
+    /// Update all clauses based on feedback
+    pub fn update<R: Rng>(
+        &mut self,
+        input: &[bool],
+        target: bool,
+        threshold: f64,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        self.update_scaled(input, target, 1.0, threshold, specificity, rng);
+    }
+
+    /// Update clauses, giving each one feedback with probability `strength`
+    ///
+    /// With `strength` 1.0 this is the same as [`ClauseBank::update`] and
+    /// draws no extra random numbers.
+    pub fn update_scaled<R: Rng>(
+        &mut self,
+        input: &[bool],
+        target: bool,
+        strength: f64,
+        threshold: f64,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        self.update_batch(&[(input, target, strength)], threshold, specificity, rng);
+    }
+
+    /// Update clauses from a mini-batch of `(input, target, strength)` entries
+    ///
+    /// The vote sums of all entries are computed before any feedback, so
+    /// every feedback decision in the batch is based on the same clause
+    /// state. Entries are then applied in order as in
+    /// [`update_scaled`](Self::update_scaled).
+    pub fn update_batch<R: Rng>(
+        &mut self,
+        batch: &[(&[bool], bool, f64)],
+        threshold: f64,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        // Evaluating the entries in reverse leaves the first entry's clause
+        // outputs in the buffer for its feedback
+        let mut votes = std::mem::take(&mut self.votes);
+        votes.clear();
+        votes.extend(batch.iter().rev().map(|&(input, _, _)| self.evaluate_outputs(input)));
+        votes.reverse();
+
+        let entries = batch.iter().zip(&votes).enumerate();
+        for (position, (&(input, target, strength), &vote_sum)) in entries {
+            let Some(decision) = self.feedback_decision(target, vote_sum, threshold) else {
+                continue;
+            };
+            if position > 0 {
+                // Feedback on the earlier entries may have changed the clauses
+                self.evaluate_outputs(input);
+            }
+            self.feedback_all(input, target, strength, decision, specificity, rng);
+        }
+        self.votes = votes;
+        self.validate_after_update();
+    }
+
+    /// Update clauses from a mini-batch whose clause outputs are computed bit-parallel
+    ///
+    /// Every clause is evaluated on 64 entries at a time, which touches each
+    /// automaton once per 64 inputs instead of once per input. Unlike
+    /// [`update_batch`](Self::update_batch), the feedback of every entry also
+    /// uses these pre-batch clause outputs, so the whole batch sees one
+    /// clause state. Entries are applied in order.
+    pub fn update_batch_packed<R: Rng>(
+        &mut self,
+        batch: &[(&[bool], bool, f64)],
+        threshold: f64,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        let num_features = self.num_features;
+        let num_words = batch.len().div_ceil(64);
+        let mut columns = vec![0u64; num_words * num_features];
+        for (position, &(input, _, _)) in batch.iter().enumerate() {
+            let word = &mut columns[position / 64 * num_features..][..num_features];
+            for (column, _) in word.iter_mut().zip(input).filter(|(_, &value)| value) {
+                *column |= 1 << (position % 64);
+            }
+        }
+
+        // Clause-major outputs: word `w` of clause `i` is at `i * num_words + w`
+        let mut packed = vec![0u64; self.clauses.len() * num_words];
+        let mut votes = std::mem::take(&mut self.votes);
+        votes.clear();
+        votes.resize(batch.len(), 0);
+        for (index, clause) in self.clauses.iter().enumerate() {
+            let weight = self.signed_weight(index);
+            for word in 0..num_words {
+                let remaining = batch.len() - word * 64;
+                let valid = if remaining >= 64 { u64::MAX } else { (1 << remaining) - 1 };
+                let columns = &columns[word * num_features..][..num_features];
+                let output = clause.evaluate_packed(columns, valid);
+                packed[index * num_words + word] = output;
+
+                let mut fired = output;
+                while fired != 0 {
+                    votes[word * 64 + fired.trailing_zeros() as usize] += weight;
+                    fired &= fired - 1;
+                }
+            }
+        }
+
+        self.outputs.resize(self.clauses.len().div_ceil(64), 0);
+        let entries = batch.iter().zip(&votes).enumerate();
+        for (position, (&(input, target, strength), &vote_sum)) in entries {
+            let Some(decision) = self.feedback_decision(target, vote_sum, threshold) else {
+                continue;
+            };
+            let (word, bit) = (position / 64, position % 64);
+            for index in 0..self.clauses.len() {
+                self.set_output(index, (packed[index * num_words + word] >> bit) & 1 == 1);
+            }
+            self.feedback_all(input, target, strength, decision, specificity, rng);
+        }
+        self.votes = votes;
+        self.validate_after_update();
+    }
+
+    /// Whether an input with this vote sum gets feedback, and if so how
+    fn feedback_decision(&self, target: bool, vote_sum: i32, threshold: f64) -> Option<Decision> {
+        let should_update = if target {
+            vote_sum < threshold as i32
+        } else {
+            vote_sum > -(threshold as i32)
+        };
+        let margin = if target { vote_sum } else { -vote_sum };
+        should_update.then(|| Decision {
+            suspected_mislabel: self.noise_tolerance > 0.0 && -margin >= threshold as i32,
+            vote_error: (threshold - f64::from(margin).clamp(-threshold, threshold))
+                / (2.0 * threshold),
+        })
+    }
+
+    /// Give every unfrozen clause feedback on one input with probability
+    /// `strength`, using the buffered clause outputs
+    fn feedback_all<R: Rng>(
+        &mut self,
+        input: &[bool],
+        target: bool,
+        strength: f64,
+        decision: Decision,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        for index in 0..self.clauses.len() {
+            if self.frozen[index] {
+                continue;
+            }
+            if strength >= 1.0 || rng.gen::<f64>() < strength {
+                self.feedback(index, input, target, decision, specificity, rng);
+            }
+        }
+    }
+
+    /// Evaluate every clause into the output buffer and return the vote sum
+    ///
+    /// Feedback to one clause never changes another clause's output, so the
+    /// buffer stays valid while the clauses of one input are updated.
+    fn evaluate_outputs(&mut self, input: &[bool]) -> i32 {
+        let mut outputs = std::mem::take(&mut self.outputs);
+        outputs.clear();
+        outputs.resize(self.clauses.len().div_ceil(64), 0);
+        let mut vote_sum = 0;
+        for (index, clause) in self.clauses.iter().enumerate() {
+            if clause.evaluate(input) {
+                outputs[index / 64] |= 1 << (index % 64);
+                vote_sum += self.signed_weight(index);
+            }
+        }
+        self.outputs = outputs;
+        vote_sum
+    }
+
+    /// Buffered output of clause `index`
+    fn output(&self, index: usize) -> bool {
+        (self.outputs[index / 64] >> (index % 64)) & 1 == 1
+    }
+
+    /// Store the output of clause `index` in the buffer
+    fn set_output(&mut self, index: usize, output: bool) {
+        let (word, bit) = (index / 64, index % 64);
+        self.outputs[word] = (self.outputs[word] & !(1 << bit)) | (u64::from(output) << bit);
+    }
+
+    /// Give one clause feedback, updating its weight if weights are learned
+    ///
+    /// The clause's output on `input` is read from the output buffer.
+    fn feedback<R: Rng>(
+        &mut self,
+        index: usize,
+        input: &[bool],
+        target: bool,
+        decision: Decision,
+        specificity: f64,
+        rng: &mut R,
+    ) {
+        let clause_output = self.output(index);
+        let clause_target = if self.polarities[index] { target } else { !target };
+        if !clause_target && decision.suspected_mislabel && rng.gen::<f64>() < self.noise_tolerance {
+            return;
+        }
+        if let (WeightLearning::Integer { max_weight }, true) = (self.weight_learning, clause_output) {
+            // Step with probability equal to the vote error (see `WeightLearning::Integer`)
+            if decision.vote_error >= 1.0 || rng.gen::<f64>() < decision.vote_error {
+                let weight = &mut self.weights[index];
+                *weight = if clause_target {
+                    (*weight + 1).min(max_weight)
+                } else {
+                    weight.saturating_sub(1).max(1)
+                };
+            }
+        }
+        self.clauses[index].update(input, clause_target, clause_output, specificity, rng);
+        self.enforce_constraints(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_clause_evaluation() {
+        let clause = Clause::new(3, 100);
+        let input = vec![true, false, true];
+        
+        // Empty clause should always return true
+        assert!(clause.evaluate(&input));
+    }
+
+    #[test]
+    fn test_clause_bank_voting() {
+        let bank = ClauseBank::new(3, 4, 100);
+        let input = vec![true, false, true];
+        
+        // With empty clauses, all should fire
+        // 2 positive clauses (+2) + 2 negative clauses (-2) = 0
+        assert_eq!(bank.vote(&input), 0);
+    }
+
+    #[test]
+    fn test_clause_bank_update() {
+        let mut bank = ClauseBank::new(3, 4, 100);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(42);
+        let input = vec![true, false, true];
+        
+        // Update with positive target
+        bank.update(&input, true, 1.0, 2.0, &mut rng);
+        
+        // Should not crash
+        assert_eq!(bank.clauses.len(), 4);
+    }
+
+    #[test]
+    fn test_extend_features() {
+        let mut bank = ClauseBank::new(2, 4, 100);
+        bank.extend_features(3);
+
+        assert!(bank.clauses().iter().all(|clause| clause.num_features() == 5));
+        assert!(bank.clauses().iter().all(|clause| clause.num_included() == 0));
+        assert_eq!(bank.vote(&[true, false, true, true, false]), 0);
+    }
+
+    #[test]
+    fn test_polarity_budget() {
+        let mut bank = ClauseBank::with_polarity_budget(2, 3, 1, 10);
+        assert_eq!(bank.polarity_counts(), (3, 1));
+        assert_eq!(bank.polarities(), &[true, true, true, false]);
+        // Every empty clause fires, so the untrained vote leans to the larger side
+        assert_eq!(bank.vote(&[true, false]), 2);
+
+        // Only the negative clause requires x0 to be false
+        bank.clause_mut(3).set_included(Literal { feature: 0, negated: true }, true);
+        assert_eq!(bank.vote(&[true, false]), 3);
+        assert_eq!(bank.vote(&[false, false]), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Each polarity needs at least one clause")]
+    fn test_empty_polarity_budget() {
+        ClauseBank::with_polarity_budget(2, 4, 0, 10);
+    }
+
+    #[test]
+    fn test_polarity_schemes() {
+        let mut bank = ClauseBank::new(2, 6, 10);
+        assert_eq!(bank.polarities(), PolarityScheme::Halves.polarities(6));
+
+        bank.set_polarity_scheme(&PolarityScheme::Ratio(1.0 / 3.0));
+        assert_eq!(bank.polarities(), &[true, true, false, false, false, false]);
+        let custom = vec![false, true, false, true, true, true];
+        bank.set_polarity_scheme(&PolarityScheme::Custom(custom.clone()));
+        assert_eq!(bank.polarities(), custom.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "Custom polarities must cover every clause")]
+    fn test_custom_polarities_length() {
+        ClauseBank::new(2, 4, 10).set_polarity_scheme(&PolarityScheme::Custom(vec![true; 3]));
+    }
+
+    #[test]
+    fn test_add_clause_pair() {
+        let mut bank = ClauseBank::new(3, 2, 10);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(42);
+        bank.add_clause_pair(&mut rng);
+
+        assert_eq!(bank.num_clauses(), 4);
+        assert_eq!(bank.polarities(), &[true, false, true, false]);
+        // Random initialization stays on the exclude side
+        assert_eq!(bank.vote(&[true, false, true]), 0);
+    }
+
+    #[test]
+    fn test_clause_stats() {
+        let bank = ClauseBank::new(2, 2, 100);
+        let features = Array2::from_shape_vec((3, 2), vec![
+            true, false, false, true, true, true
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, true, false]);
+
+        // Empty clauses fire on every sample
+        let stats = bank.clause_stats(&features, &labels);
+        assert_eq!(stats[0], ClauseStats { fired: 3, correct: 2 });
+        assert_eq!(stats[1], ClauseStats { fired: 3, correct: 1 });
+        assert!((stats[0].precision() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(ClauseStats::default().precision(), 0.0);
+    }
+
+    #[test]
+    fn test_reinitialize_keeps_polarity() {
+        let mut bank = ClauseBank::new(3, 4, 10);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(7);
+        bank.set_frozen(3, true);
+        bank.reinitialize(3, &mut rng);
+
+        assert!(!bank.is_frozen(3));
+        assert!(!bank.polarities()[3]);
+        assert_eq!(bank.clauses()[3].num_features(), 3);
+    }
+
+    #[test]
+    fn test_included_literals() {
+        let mut clause = Clause::new(3, 10);
+        assert!(clause.included_literals().is_empty());
+
+        clause.set_included(Literal { feature: 2, negated: false }, true);
+        clause.set_included(Literal { feature: 0, negated: true }, true);
+        assert_eq!(clause.included_literals(), vec![
+            Literal { feature: 0, negated: true },
+            Literal { feature: 2, negated: false },
+        ]);
+        assert_eq!(clause.num_included(), 2);
+
+        clause.set_included(Literal { feature: 2, negated: false }, false);
+        assert_eq!(clause.num_included(), 1);
+    }
+
+    #[test]
+    fn test_deduplicate() {
+        let mut bank = ClauseBank::new(3, 6, 10);
+        // Clauses 0 and 1 are identical positive clauses, clause 3 has the same
+        // literals but negative polarity
+        for index in [0, 1, 3] {
+            bank.clauses[index].positive_automata[1] = TsetlinAutomaton::with_state(10, 3);
+        }
+
+        assert_eq!(bank.deduplicate(), 1);
+        assert_eq!(bank.clauses[0].num_included(), 1);
+        assert_eq!(bank.clauses[1].num_included(), 0);
+        assert_eq!(bank.clauses[3].num_included(), 1);
+        assert_eq!(bank.deduplicate(), 0);
+    }
+
+    #[test]
+    fn test_similarity_and_diversity() {
+        let mut bank = ClauseBank::new(3, 4, 10);
+        // Untrained clauses are all identical
+        assert_eq!(bank.diversity(), 0.0);
+
+        bank.clauses[0].positive_automata[0] = TsetlinAutomaton::with_state(10, 1);
+        bank.clauses[0].positive_automata[1] = TsetlinAutomaton::with_state(10, 1);
+        bank.clauses[1].positive_automata[1] = TsetlinAutomaton::with_state(10, 1);
+
+        let similarity = bank.similarity_matrix();
+        assert_eq!(similarity[[0, 1]], 0.5);
+        assert_eq!(similarity[[1, 0]], 0.5);
+        assert_eq!(similarity[[0, 2]], 0.0);
+        assert_eq!(similarity[[2, 3]], 1.0);
+        assert!(bank.diversity() > 0.0 && bank.diversity() < 1.0);
+    }
+
+    #[test]
+    fn test_diversity_penalty() {
+        let mut bank = ClauseBank::new(3, 4, 10);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(42);
+        for index in [0, 1] {
+            bank.clauses[index].positive_automata[2] = TsetlinAutomaton::with_state(10, 1);
+        }
+
+        // The first of two identical clauses drops the shared literal, after
+        // which the second one has no similar clause left
+        assert_eq!(bank.apply_diversity_penalty(1.0, &mut rng), 1);
+        assert_eq!(bank.clauses[0].num_included(), 0);
+        assert_eq!(bank.clauses[1].num_included(), 1);
+        assert_eq!(bank.apply_diversity_penalty(1.0, &mut rng), 0);
+    }
+
+    #[test]
+    fn test_frozen_clauses() {
+        let mut bank = ClauseBank::new(3, 4, 100);
+        assert_eq!(bank.num_frozen(), 0);
+
+        bank.set_frozen(1, true);
+        bank.set_frozen(2, true);
+        bank.set_frozen(2, false);
+        assert!(bank.is_frozen(1));
+        assert!(!bank.is_frozen(2));
+        assert_eq!(bank.num_frozen(), 1);
+
+        bank.unfreeze_all();
+        assert_eq!(bank.num_frozen(), 0);
+
+        // One clause of each polarity
+        bank.freeze_most_specific(0.5);
+        assert_eq!(bank.num_frozen(), 2);
+        assert!(bank.is_frozen(0) && bank.is_frozen(2));
+    }
+
+    #[test]
+    fn test_update_scaled() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(3);
+        let literal = Literal { feature: 0, negated: false };
+        let mut bank = ClauseBank::new(1, 2, 100);
+        bank.clause_mut(0).set_included(literal, true);
+
+        // Zero strength withholds all feedback
+        for _ in 0..50 {
+            bank.update_scaled(&[true], false, 0.0, 1.0, 2.0, &mut rng);
+        }
+        assert_eq!(bank.clauses()[0].included_literals(), vec![literal]);
+
+        // Type II feedback on the firing clause eventually excludes the literal
+        for _ in 0..50 {
+            bank.update_scaled(&[true], false, 0.5, 1.0, 2.0, &mut rng);
+        }
+        assert!(bank.clauses()[0].included_literals().is_empty());
+    }
+
+    #[test]
+    fn test_noise_tolerance() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(5);
+        let literal = Literal { feature: 0, negated: false };
+        let mut bank = ClauseBank::new(1, 2, 100);
+        bank.set_noise_tolerance(1.0);
+        // The negative clause fires strongly on [true]: vote sum -1 reaches the threshold
+        bank.clause_mut(1).set_included(literal, true);
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: true }, true);
+
+        // A positive label there is suspected noise, so the negative clause is spared
+        for _ in 0..50 {
+            bank.update(&[true], true, 1.0, 2.0, &mut rng);
+        }
+        assert_eq!(bank.clauses()[1].included_literals(), vec![literal]);
+
+        bank.set_noise_tolerance(0.0);
+        for _ in 0..50 {
+            bank.update(&[true], true, 1.0, 2.0, &mut rng);
+        }
+        assert!(bank.clauses()[1].included_literals().is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_masked() {
+        let mut clause = Clause::new(3, 100);
+        clause.set_included(Literal { feature: 0, negated: false }, true);
+        clause.set_included(Literal { feature: 2, negated: true }, true);
+
+        let input = [true, true, false];
+        assert!(clause.evaluate_masked(&input, &[true, true, true]));
+        // Feature 1 is not used, so hiding it changes nothing
+        assert!(clause.evaluate_masked(&input, &[true, false, true]));
+        assert!(!clause.evaluate_masked(&input, &[true, true, false]));
+        assert!(Clause::new(3, 100).evaluate_masked(&input, &[false; 3]));
+    }
+
+    #[test]
+    fn test_integer_weight_learning() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(11);
+        let literal = Literal { feature: 0, negated: false };
+        let mut bank = ClauseBank::new(1, 2, 100);
+        bank.set_weight_learning(WeightLearning::Integer { max_weight: 3 });
+        bank.clause_mut(0).set_included(literal, true);
+
+        // Correct firing votes grow the positive clause's weight up to the cap
+        for _ in 0..10 {
+            bank.update(&[true], true, 100.0, 2.0, &mut rng);
+        }
+        assert_eq!(bank.weights()[0], 3);
+
+        // Wrong firing votes shrink it again, but never below 1
+        for _ in 0..10 {
+            bank.update(&[true], false, 100.0, 2.0, &mut rng);
+        }
+        assert_eq!(bank.weights()[0], 1);
+    }
+
+    #[test]
+    fn test_deduplicate_merges_weights() {
+        let literal = Literal { feature: 1, negated: true };
+        let mut bank = ClauseBank::new(2, 4, 100);
+        bank.clause_mut(0).set_included(literal, true);
+        bank.clause_mut(1).set_included(literal, true);
+        bank.set_weight(1, 2);
+
+        let input = [false, false];
+        let before = bank.vote(&input);
+        assert_eq!(bank.deduplicate(), 1);
+        assert_eq!(bank.weights()[..2], [3, 1]);
+        // The reset clause is empty and still fires, adding one extra vote
+        assert_eq!(bank.vote(&input), before + 1);
+    }
+
+    #[test]
+    fn test_deduplicate_caps_merged_weights() {
+        let literal = Literal { feature: 0, negated: false };
+        let mut bank = ClauseBank::new(2, 6, 100);
+        bank.set_weight_learning(WeightLearning::Integer { max_weight: 5 });
+        for index in 0..3 {
+            bank.clause_mut(index).set_included(literal, true);
+            bank.set_weight(index, 3);
+        }
+
+        assert_eq!(bank.deduplicate(), 2);
+        assert_eq!(bank.weights()[..3], [5, 1, 1]);
+        assert_eq!(bank.debug_validate(), Ok(()));
+
+        // Without weight learning the sum saturates instead of overflowing
+        let mut unbounded = ClauseBank::new(2, 4, 100);
+        for index in 0..2 {
+            unbounded.clause_mut(index).set_included(literal, true);
+            unbounded.set_weight(index, u32::MAX - 1);
+        }
+        assert_eq!(unbounded.deduplicate(), 1);
+        assert_eq!(unbounded.weights()[0], u32::MAX);
+    }
+
+    #[test]
+    fn test_feature_subset_ignores_other_features() {
+        let mut clause = Clause::new(4, 100);
+        clause.set_included(Literal { feature: 0, negated: true }, true);
+        clause.set_included(Literal { feature: 3, negated: false }, true);
+        clause.set_feature_subset(Some(vec![3, 1]));
+
+        // The literal on feature 0 falls outside the subset and is dropped
+        assert_eq!(clause.feature_subset(), Some(&[1, 3][..]));
+        assert_eq!(clause.included_literals(), vec![Literal { feature: 3, negated: false }]);
+        assert!(clause.evaluate(&[true, false, false, true]));
+
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(0);
+        for _ in 0..100 {
+            clause.update(&[true, true, true, true], true, false, 1.0, &mut rng);
+        }
+        assert!(clause.included_literals().iter().all(|literal| literal.feature % 2 == 1));
+    }
+
+    #[test]
+    fn test_feature_bagging() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(3);
+        let mut bank = ClauseBank::new(20, 6, 100);
+        bank.set_feature_bagging(Some(5), &mut rng);
+        bank.add_clause_pair(&mut rng);
+        bank.reinitialize(0, &mut rng);
+
+        assert_eq!(bank.features_per_clause(), Some(5));
+        for clause in bank.clauses() {
+            let subset = clause.feature_subset().unwrap();
+            assert_eq!(subset.len(), 5);
+            assert!(subset.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        bank.set_feature_bagging(None, &mut rng);
+        assert!(bank.clauses().iter().all(|clause| clause.feature_subset().is_none()));
+    }
+
+    #[test]
+    fn test_bag_aggregation() {
+        // Positive clause x0, negative clause x1, plus one empty clause of each polarity
+        let mut bank = ClauseBank::new(2, 4, 100);
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 1, negated: false }, true);
+        let bag = vec![vec![true, false], vec![true, false], vec![false, true], vec![false, false]];
+
+        assert_eq!(bank.vote_bag(&bag), 1);
+        bank.set_aggregation(Aggregation::OrPool);
+        assert_eq!(bank.vote_bag(&bag), 0);
+        bank.set_aggregation(Aggregation::MaxMargin);
+        assert_eq!(bank.vote_bag(&bag), 1);
+        assert_eq!(bank.vote_bag(&bag[2..]), -1);
+    }
+
+    #[test]
+    fn test_update_bag_respects_threshold() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(1);
+        let mut bank = ClauseBank::new(2, 4, 100);
+        bank.set_aggregation(Aggregation::OrPool);
+        bank.set_weight_learning(WeightLearning::Integer { max_weight: 10 });
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.set_weight(0, 3);
+        let bag = vec![vec![true, false], vec![false, false]];
+
+        // The vote of 2 already reaches the threshold, so nothing changes
+        bank.update_bag(&bag, true, 2.0, 2.0, &mut rng);
+        assert_eq!(bank.weights(), &[3, 1, 1, 1]);
+        // For the other class the vote error is 1, so every firing clause
+        // is rewarded or penalized
+        bank.update_bag(&bag, false, 2.0, 2.0, &mut rng);
+        assert_eq!(bank.weights(), &[2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_weight_steps_follow_vote_error() {
+        let mut bank = ClauseBank::new(1, 2, 100);
+        bank.set_weight_learning(WeightLearning::Integer { max_weight: 2000 });
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(5);
+        bank.evaluate_outputs(&[true]);
+
+        let decision = Decision { suspected_mislabel: false, vote_error: 0.25 };
+        for _ in 0..1000 {
+            bank.feedback(0, &[true], true, decision, 2.0, &mut rng);
+        }
+        // About a quarter of the 1000 correct votes step the weight up
+        assert!((200..300).contains(&bank.weights()[0]), "{}", bank.weights()[0]);
+    }
+
+    #[test]
+    fn test_update_batch_uses_pre_batch_votes() {
+        let bank = || {
+            let mut bank = ClauseBank::new(2, 4, 100);
+            bank.set_weight_learning(WeightLearning::Integer { max_weight: 10 });
+            bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+            bank.set_weight(0, 2);
+            bank
+        };
+        let input = [true, false];
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(1);
+
+        // One at a time, the first update drops the vote to the threshold
+        let mut sequential = bank();
+        sequential.update(&input, false, 1.0, 2.0, &mut rng);
+        sequential.update(&input, false, 1.0, 2.0, &mut rng);
+        assert_eq!(sequential.weights(), &[1, 1, 2, 2]);
+
+        // In a batch both entries see the starting vote of 1
+        let mut batched = bank();
+        batched.update_batch(&[(&input, false, 1.0), (&input, false, 1.0)], 1.0, 2.0, &mut rng);
+        assert_eq!(batched.weights(), &[1, 1, 3, 3]);
+    }
+
+    #[test]
+    fn test_debug_validate() {
+        let mut bank = ClauseBank::new(3, 4, 10);
+        assert_eq!(bank.debug_validate(), Ok(()));
+
+        bank.set_weight_learning(WeightLearning::Integer { max_weight: 4 });
+        bank.set_weight(2, 6);
+        assert_eq!(bank.debug_validate(), Err("clause 2 has weight 6".to_string()));
+
+        bank.set_weight(2, 1);
+        bank.clauses[1] = Clause::new(2, 10);
+        assert_eq!(
+            bank.debug_validate(),
+            Err("clause 1: clause has 2/2 automata for 3 features".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Feature is outside the clause's feature subset")]
+    fn test_include_outside_subset() {
+        let mut clause = Clause::with_feature_subset(3, 100, vec![0]);
+        clause.set_included(Literal { feature: 2, negated: false }, true);
+    }
+
+    #[test]
+    fn test_transplant_clauses() {
+        let x0 = Literal { feature: 0, negated: false };
+        let mut source = ClauseBank::new(2, 4, 10);
+        source.clause_mut(3).set_included(x0, true);
+        let mut target = ClauseBank::new(2, 2, 10);
+        target.set_weight(0, 3);
+
+        let (clauses, polarities) = source.extract(&[3, 0]);
+        assert_eq!(polarities, vec![false, true]);
+        target.insert(clauses, polarities);
+        assert_eq!(target.num_clauses(), 4);
+        assert_eq!(target.polarities(), &[true, false, false, true]);
+        assert_eq!(target.weights(), &[3, 1, 1, 1]);
+        assert_eq!(target.clauses()[2].included_literals(), vec![x0]);
+        assert_eq!(target.debug_validate(), Ok(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Inserted clause 0 does not fit the bank")]
+    fn test_insert_mismatched_clause() {
+        let mut bank = ClauseBank::new(3, 2, 10);
+        bank.insert(vec![Clause::new(2, 10)], vec![true]);
+    }
+
+    #[test]
+    fn test_ordinal_conflicts_resolved() {
+        let literal = |feature, negated| Literal { feature, negated };
+        let mut bank = ClauseBank::new(5, 2, 10);
+        let clause = bank.clause_mut(0);
+        clause.set_literal_state(literal(3, false), 3);
+        clause.set_literal_state(literal(1, true), 2);
+        clause.set_literal_state(literal(3, true), 5);
+        clause.set_literal_state(literal(0, false), 1);
+        clause.set_literal_state(literal(4, true), 1);
+        bank.set_ordinal_groups([0..4, 4..5]);
+
+        // x0 AND NOT x3 is the range [t0, t3); x4 forms its own group
+        let expected = vec![literal(0, false), literal(3, true), literal(4, true)];
+        assert_eq!(bank.clauses()[0].included_literals(), expected);
+    }
+
+    #[test]
+    fn test_feedback_keeps_ordinal_consistency() {
+        let mut bank = ClauseBank::new(2, 2, 10);
+        bank.set_ordinal_groups(std::iter::once(0..2));
+        let clause = bank.clause_mut(0);
+        clause.set_literal_state(Literal { feature: 1, negated: false }, 2);
+        clause.set_literal_state(Literal { feature: 0, negated: true }, 1);
+        assert_eq!(bank.clauses()[0].num_included(), 2);
+
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(0);
+        bank.update(&[true, true], true, 5.0, 2.0, &mut rng);
+        let included = bank.clauses()[0].included_literals();
+        assert_eq!(included, vec![Literal { feature: 1, negated: false }]);
+    }
+
+    #[test]
+    fn test_saturation_and_churn() {
+        let x0 = Literal { feature: 0, negated: false };
+        let mut bank = ClauseBank::new(2, 2, 10);
+        assert_eq!(bank.saturation(), vec![1.0, 1.0]);
+        bank.clause_mut(0).set_literal_state(x0, 10);
+        bank.clause_mut(0).set_literal_state(Literal { feature: 1, negated: true }, 3);
+        assert_eq!(bank.saturation()[0], 0.75);
+
+        let before: Vec<Vec<Literal>> =
+            bank.clauses().iter().map(Clause::included_literals).collect();
+        bank.clause_mut(0).set_included(x0, false);
+        bank.clause_mut(1).set_included(x0, true);
+        bank.clause_mut(1).set_included(Literal { feature: 1, negated: false }, true);
+        assert_eq!(bank.include_churn(&before), vec![1, 2]);
+        assert_eq!(bank.include_churn(&before[..1]), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_update_batch_reevaluates_later_entries() {
+        let mut bank = ClauseBank::new(1, 2, 100);
+        bank.set_weight_learning(WeightLearning::Integer { max_weight: 10 });
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.set_weight(0, 2);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(1);
+
+        // Clause 0 fires on the first entry and loses weight, but not on the
+        // second, whose feedback must not reuse the first entry's outputs
+        bank.update_batch(&[(&[true], false, 1.0), (&[false], true, 1.0)], 1.0, 2.0, &mut rng);
+        assert_eq!(bank.weights(), &[1, 1]);
+        assert_eq!(bank.outputs, vec![0b10]);
+        assert_eq!(bank.evaluate_outputs(&[true]), bank.vote(&[true]));
+    }
+
+    #[test]
+    fn test_output_bitset_spans_words() {
+        let mut bank = ClauseBank::new(2, 70, 100);
+        bank.clause_mut(65).set_included(Literal { feature: 0, negated: false }, true);
+
+        assert_eq!(bank.evaluate_outputs(&[false, true]), bank.vote(&[false, true]));
+        assert_eq!(bank.outputs.len(), 2);
+        assert!(bank.output(64) && !bank.output(65) && bank.output(69));
+        bank.set_output(65, true);
+        bank.set_output(64, false);
+        assert_eq!(bank.outputs[1], 0b11_1110);
+    }
+
+    #[test]
+    fn test_packed_batch_votes_span_words() {
+        let mut bank = ClauseBank::new(3, 6, 100);
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(4).set_included(Literal { feature: 2, negated: true }, true);
+        bank.set_weight(4, 2);
+        let inputs: Vec<Vec<bool>> =
+            (0..70).map(|i: usize| (0..3).map(|f| (i >> f) & 1 == 1).collect()).collect();
+        let expected: Vec<i32> = inputs.iter().map(|input| bank.vote(input)).collect();
+
+        let batch: Vec<(&[bool], bool, f64)> =
+            inputs.iter().map(|input| (input.as_slice(), true, 1.0)).collect();
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(2);
+        bank.update_batch_packed(&batch, 3.0, 2.0, &mut rng);
+        assert_eq!(bank.votes, expected);
+    }
+
+    #[test]
+    fn test_packed_single_entry_matches_update() {
+        let bank = || {
+            let mut bank = ClauseBank::new(3, 4, 100);
+            bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+            bank.clause_mut(2).set_included(Literal { feature: 1, negated: true }, true);
+            bank
+        };
+        let (mut plain, mut packed) = (bank(), bank());
+        let mut plain_rng = crate::rng::TsetlinRng::seed_from_u64(5);
+        let mut packed_rng = crate::rng::TsetlinRng::seed_from_u64(5);
+        for step in 0..50 {
+            let input = [step % 2 == 0, step % 3 == 0, step % 5 == 0];
+            let target = step % 4 < 2;
+            plain.update(&input, target, 5.0, 3.0, &mut plain_rng);
+            packed.update_batch_packed(&[(&input, target, 1.0)], 5.0, 3.0, &mut packed_rng);
+        }
+        let states = |bank: &ClauseBank| -> Vec<i32> {
+            let literals = (0..3).flat_map(|feature| {
+                [false, true].map(|negated| Literal { feature, negated })
+            });
+            let literals: Vec<Literal> = literals.collect();
+            bank.clauses()
+                .iter()
+                .flat_map(|clause| literals.iter().map(|&literal| clause.literal_state(literal)))
+                .collect()
+        };
+        assert_ne!(states(&plain), states(&bank()));
+        assert_eq!(states(&plain), states(&packed));
     }
 }
\ No newline at end of file
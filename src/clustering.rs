@@ -0,0 +1,258 @@
+//! Unsupervised clustering with competing clause banks
+//!
+//! Every cluster owns a bank of clauses, and a sample's score for a cluster
+//! is the number of its clauses that fire, capped at the threshold `T`.
+//! Training assigns each sample to the cluster with the highest score (ties
+//! broken at random, which also spreads the samples of an untrained model)
+//! and reinforces the winner: its clauses get Type I feedback with
+//! probability `(T - score) / T`, so they learn to match the sample. One
+//! other random cluster gets Type II feedback with probability
+//! `score / T`, so it lets go of the sample. Clauses without literals do
+//! not vote. Feedback follows the standard per-literal rules, as in
+//! [`RegressionTsetlinMachine`](crate::regression::RegressionTsetlinMachine).
+
+use crate::clause::Clause;
+use crate::input::AsBoolSamples;
+use crate::regression::{boundary_clause, fires, literal_feedback};
+use ndarray::{Array1, Array2};
+use rand::{prelude::SliceRandom, Rng, SeedableRng};
+
+/// Clause banks competing to own samples
+#[derive(Debug, Clone)]
+pub struct TsetlinClustering {
+    /// Clauses of each cluster, empty until `fit_unsupervised`
+    clusters: Vec<Vec<Clause>>,
+    /// Number of input features
+    num_features: usize,
+    /// Number of clauses per cluster
+    clauses_per_cluster: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Score at which a cluster stops reinforcing a sample
+    threshold: f64,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl TsetlinClustering {
+    /// Create an unfitted clustering model
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `clauses_per_cluster` - Number of clauses of each cluster
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Firing clauses at which a cluster fully owns a sample
+    pub fn new(
+        num_features: usize,
+        clauses_per_cluster: usize,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        assert!(clauses_per_cluster > 0, "Clusters need at least one clause");
+        assert!(threshold > 0.0, "Threshold must be positive");
+
+        Self {
+            clusters: Vec::new(),
+            num_features,
+            clauses_per_cluster,
+            specificity,
+            threshold,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a clustering model with specificity 3.0 and threshold half the clauses
+    pub fn with_defaults(num_features: usize, clauses_per_cluster: usize) -> Self {
+        let threshold = (clauses_per_cluster as f64 / 2.0).max(1.0);
+        Self::new(num_features, clauses_per_cluster, 3.0, threshold)
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Learn `k` clusters from unlabelled samples
+    ///
+    /// Any earlier clusters are discarded. Returns the final assignment of
+    /// the training samples, as [`assign`](Self::assign) would. Like k-means,
+    /// training can settle in a local optimum, so the clusters found depend
+    /// on the seed.
+    ///
+    /// # Arguments
+    /// * `features` - Training samples (samples x features)
+    /// * `k` - Number of clusters
+    /// * `epochs` - Number of training epochs
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::clustering::TsetlinClustering;
+    ///
+    /// let features = array![
+    ///     [true, true, false, false],
+    ///     [true, true, false, true],
+    ///     [false, false, true, true],
+    ///     [false, true, true, true],
+    /// ];
+    /// let mut clustering = TsetlinClustering::with_defaults(4, 10).seed(1);
+    /// let clusters = clustering.fit_unsupervised(&features, 2, 50);
+    /// assert!(clusters.iter().all(|&cluster| cluster < 2));
+    /// ```
+    pub fn fit_unsupervised<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        k: usize,
+        epochs: usize,
+    ) -> Array1<usize> {
+        assert!(k > 0, "At least one cluster is required");
+        assert_eq!(features.num_features(), self.num_features);
+
+        self.clusters = (0..k).map(|_| self.fresh_clauses()).collect();
+
+        let mut indices: Vec<usize> = (0..features.num_samples()).collect();
+        let mut sample = Vec::with_capacity(self.num_features);
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+            for &idx in &indices {
+                features.sample_into(idx, &mut sample);
+                self.update(&sample);
+            }
+            self.reseed_empty(features);
+        }
+        self.assign(features)
+    }
+
+    /// Assign one sample to its best cluster and reinforce the choice
+    fn update(&mut self, sample: &[bool]) {
+        let scores: Vec<usize> = self.clusters.iter().map(|c| self.score(c, sample)).collect();
+        let best = *scores.iter().max().expect("At least one cluster");
+        let tied: Vec<usize> = (0..scores.len()).filter(|&c| scores[c] == best).collect();
+        let winner = tied[crate::rng::gen_index(&mut self.rng, tied.len())];
+
+        let probability = (self.threshold - best as f64) / self.threshold;
+        self.feedback(winner, sample, true, probability);
+        if self.clusters.len() > 1 {
+            let mut other = crate::rng::gen_index(&mut self.rng, self.clusters.len() - 1);
+            if other >= winner {
+                other += 1;
+            }
+            let probability = scores[other] as f64 / self.threshold;
+            self.feedback(other, sample, false, probability);
+        }
+    }
+
+    /// Restart clusters that own no sample from the worst-fitting sample
+    ///
+    /// As in k-means, a cluster that lost every sample would otherwise stay
+    /// empty; it is rebuilt around the sample its owner scores lowest.
+    fn reseed_empty<S: AsBoolSamples + ?Sized>(&mut self, features: &S) {
+        let scores = self.scores(features);
+        let owners = self.assign(features);
+        let mut sample = Vec::with_capacity(self.num_features);
+        for cluster in 0..self.clusters.len() {
+            if owners.iter().any(|&owner| owner == cluster) {
+                continue;
+            }
+            let Some(worst) = (0..owners.len()).min_by_key(|&i| scores[[i, owners[i]]]) else {
+                return;
+            };
+            features.sample_into(worst, &mut sample);
+            self.clusters[cluster] = self.fresh_clauses();
+            self.feedback(cluster, &sample, true, 1.0);
+        }
+    }
+
+    /// Untrained clauses for one cluster
+    fn fresh_clauses(&self) -> Vec<Clause> {
+        (0..self.clauses_per_cluster).map(|_| boundary_clause(self.num_features)).collect()
+    }
+
+    /// Give each clause of `cluster` feedback with the given probability
+    fn feedback(&mut self, cluster: usize, sample: &[bool], type_i: bool, probability: f64) {
+        for clause in &mut self.clusters[cluster] {
+            if self.rng.gen::<f64>() < probability {
+                literal_feedback(clause, sample, type_i, self.specificity, &mut self.rng);
+            }
+        }
+    }
+
+    /// Firing clauses of a cluster, capped at the threshold
+    fn score(&self, clauses: &[Clause], sample: &[bool]) -> usize {
+        let votes = clauses.iter().filter(|clause| fires(clause, sample)).count();
+        votes.min(self.threshold as usize)
+    }
+
+    /// Score of every cluster for every sample (samples x clusters)
+    pub fn scores<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array2<usize> {
+        assert_eq!(features.num_features(), self.num_features);
+
+        let mut scores = Array2::zeros((features.num_samples(), self.clusters.len()));
+        let mut sample = Vec::with_capacity(self.num_features);
+        for (idx, mut row) in scores.rows_mut().into_iter().enumerate() {
+            features.sample_into(idx, &mut sample);
+            for (out, clauses) in row.iter_mut().zip(&self.clusters) {
+                *out = self.score(clauses, &sample);
+            }
+        }
+        scores
+    }
+
+    /// Cluster with the highest score for every sample, lowest index on ties
+    pub fn assign<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<usize> {
+        assert!(!self.clusters.is_empty(), "Clustering must be fitted first");
+
+        self.scores(features)
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let best = row.iter().max().copied().unwrap_or(0);
+                row.iter().position(|&score| score == best).unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Clauses of a cluster, which describe the samples it owns
+    pub fn clauses(&self, cluster: usize) -> &[Clause] {
+        &self.clusters[cluster]
+    }
+
+    /// Number of clusters, zero before fitting
+    pub fn num_clusters(&self) -> usize {
+        self.clusters.len()
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_separates_two_groups() {
+        // Two prototypes, each sample with one flipped bit
+        let features = Array2::from_shape_fn((16, 12), |(i, j)| {
+            ((j < 6) == (i < 8)) != (j == (i * 5) % 12)
+        });
+        let mut clustering = TsetlinClustering::with_defaults(12, 10).seed(1);
+        let clusters = clustering.fit_unsupervised(&features, 2, 50);
+
+        assert_eq!(clustering.num_clusters(), 2);
+        assert!(clusters.iter().take(8).all(|&cluster| cluster == clusters[0]));
+        assert!(clusters.iter().skip(8).all(|&cluster| cluster == clusters[8]));
+        assert_ne!(clusters[0], clusters[8]);
+        assert_eq!(clustering.scores(&features).dim(), (16, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Clustering must be fitted first")]
+    fn test_assign_before_fit() {
+        TsetlinClustering::with_defaults(2, 4).assign(&array![[true, false]]);
+    }
+}
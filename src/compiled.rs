@@ -0,0 +1,431 @@
+//! Compiled, inference-only Tsetlin machines
+//!
+//! Training needs the full automaton state of every literal, but inference
+//! only needs to know which literals are included. A [`CompiledMachine`]
+//! keeps each trained clause as a pair of bit masks over the packed input and
+//! folds the constant votes of untrained (always-firing) clauses into a bias,
+//! so it predicts exactly like the machine it was compiled from.
+//!
+//! [`CompiledMachine::to_bytes`] stores a compiled machine as a compact
+//! deployment artifact holding only clause weights and included literals.
+
+use crate::bitmatrix::BitMatrix;
+use crate::clause::Literal;
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2};
+use std::fmt;
+
+/// Leading bytes of a compressed model
+const MAGIC: &[u8; 4] = b"TMC1";
+
+/// Error raised when bytes cannot be read as a compressed model or delta
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    message: String,
+}
+
+impl DecodeError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid binary model data: {}", self.message)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Pack boolean features into 64-bit words (feature `i` is bit `i % 64` of word `i / 64`)
+pub fn pack_bits(features: &[bool]) -> Vec<u64> {
+    let mut words = vec![0u64; features.len().div_ceil(64)];
+    for (i, _) in features.iter().enumerate().filter(|(_, &value)| value) {
+        words[i / 64] |= 1 << (i % 64);
+    }
+    words
+}
+
+/// A trained clause stored as bit masks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledClause {
+    /// Vote added when the clause fires
+    pub weight: i32,
+    /// Features that must be true
+    pub include_mask: Vec<u64>,
+    /// Features that must be false
+    pub exclude_mask: Vec<u64>,
+}
+
+impl CompiledClause {
+    /// Build a clause from its included literals
+    pub fn from_literals(weight: i32, literals: &[Literal], num_features: usize) -> Self {
+        let num_words = num_features.div_ceil(64);
+        let mut include_mask = vec![0u64; num_words];
+        let mut exclude_mask = vec![0u64; num_words];
+        for literal in literals {
+            let mask = if literal.negated {
+                &mut exclude_mask
+            } else {
+                &mut include_mask
+            };
+            mask[literal.feature / 64] |= 1 << (literal.feature % 64);
+        }
+
+        Self {
+            weight,
+            include_mask,
+            exclude_mask,
+        }
+    }
+
+    /// Check whether the clause fires on packed input
+    pub fn matches(&self, packed: &[u64]) -> bool {
+        packed
+            .iter()
+            .zip(self.include_mask.iter().zip(self.exclude_mask.iter()))
+            .all(|(&word, (&include, &exclude))| word & include == include && word & exclude == 0)
+    }
+
+    /// Included literals, ordered by feature
+    pub fn literals(&self) -> Vec<Literal> {
+        let mut literals = Vec::new();
+        for word in 0..self.include_mask.len() {
+            for bit in 0..64 {
+                let feature = word * 64 + bit;
+                if self.include_mask[word] & (1 << bit) != 0 {
+                    literals.push(Literal { feature, negated: false });
+                }
+                if self.exclude_mask[word] & (1 << bit) != 0 {
+                    literals.push(Literal { feature, negated: true });
+                }
+            }
+        }
+        literals
+    }
+}
+
+/// Inference-only machine holding included literals as bit masks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledMachine {
+    /// Number of input features
+    num_features: usize,
+    /// Summed votes of the clauses that always fire
+    bias: i32,
+    /// Trained clauses
+    clauses: Vec<CompiledClause>,
+}
+
+impl CompiledMachine {
+    /// Compile a trained machine
+    pub fn new(machine: &TsetlinMachine) -> Self {
+        let bank = machine.clause_bank();
+        let num_features = machine.num_features();
+        let mut bias = 0;
+        let mut clauses = Vec::new();
+
+        for (index, clause) in bank.clauses().iter().enumerate() {
+            let weight = bank.signed_weight(index);
+            let literals = clause.included_literals();
+            if literals.is_empty() {
+                bias += weight;
+            } else {
+                clauses.push(CompiledClause::from_literals(weight, &literals, num_features));
+            }
+        }
+
+        Self {
+            num_features,
+            bias,
+            clauses,
+        }
+    }
+
+    /// Approximate memory held by the compiled model, in bytes
+    pub fn memory_bytes(&self) -> usize {
+        let mask_words: usize = self
+            .clauses
+            .iter()
+            .map(|clause| clause.include_mask.len() + clause.exclude_mask.len())
+            .sum();
+        std::mem::size_of::<Self>()
+            + self.clauses.len() * std::mem::size_of::<CompiledClause>()
+            + mask_words * std::mem::size_of::<u64>()
+    }
+
+    /// Vote sum for a single sample
+    pub fn vote_single(&self, features: &[bool]) -> i32 {
+        assert_eq!(features.len(), self.num_features);
+
+        let packed = pack_bits(features);
+        self.vote_packed(&packed)
+    }
+
+    /// Vote sum for a sample packed with [`pack_bits`]
+    pub fn vote_packed(&self, packed: &[u64]) -> i32 {
+        self.bias
+            + self
+                .clauses
+                .iter()
+                .filter(|clause| clause.matches(packed))
+                .map(|clause| clause.weight)
+                .sum::<i32>()
+    }
+
+    /// Prediction for a single sample
+    pub fn predict_single(&self, features: &[bool]) -> bool {
+        self.vote_single(features) > 0
+    }
+
+    /// Vote sums for a dataset
+    pub fn predict_votes(&self, features: &Array2<bool>) -> Array1<i32> {
+        assert_eq!(features.ncols(), self.num_features);
+
+        #[cfg(feature = "parallel")]
+        if features.nrows() >= crate::parallel::MIN_PARALLEL_ROWS {
+            return crate::parallel::vote_rows(features, |row| self.vote_single(row));
+        }
+
+        features
+            .rows()
+            .into_iter()
+            .map(|row| self.vote_single(&row.to_vec()))
+            .collect()
+    }
+
+    /// Predictions for a dataset
+    pub fn predict(&self, features: &Array2<bool>) -> Array1<bool> {
+        self.predict_votes(features).mapv(|vote| vote > 0)
+    }
+
+    /// Vote sums for a bit-packed dataset, without unpacking its rows
+    pub fn predict_votes_bits(&self, features: &BitMatrix) -> Array1<i32> {
+        assert_eq!(features.ncols(), self.num_features);
+
+        (0..features.nrows())
+            .map(|row| self.vote_packed(features.row_words(row)))
+            .collect()
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Summed votes of the clauses that always fire
+    pub fn bias(&self) -> i32 {
+        self.bias
+    }
+
+    /// Trained clauses
+    pub fn clauses(&self) -> &[CompiledClause] {
+        &self.clauses
+    }
+
+    /// Serialize the model as a compact byte string
+    ///
+    /// Only the bias, clause weights and included literals are stored, as
+    /// variable-length integers with literals delta-encoded per clause, so the
+    /// artifact is usually orders of magnitude smaller than the training state.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::compiled::CompiledMachine;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(100, 20);
+    /// let bytes = machine.compress();
+    /// assert!(bytes.len() < 16);
+    /// assert_eq!(CompiledMachine::from_bytes(&bytes).unwrap(), machine.compile());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        write_varint(&mut out, self.num_features as u64);
+        write_varint(&mut out, zigzag(self.bias));
+        write_varint(&mut out, self.clauses.len() as u64);
+        for clause in &self.clauses {
+            write_varint(&mut out, zigzag(clause.weight));
+            let literals = clause.literals();
+            write_varint(&mut out, literals.len() as u64);
+            let mut previous = 0;
+            for literal in literals {
+                let code = 2 * literal.feature as u64 + u64::from(literal.negated);
+                write_varint(&mut out, code - previous);
+                previous = code;
+            }
+        }
+        out
+    }
+
+    /// Load a model written by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| DecodeError::new("missing header"))?;
+
+        let num_features = read_usize(&mut reader)?;
+        let bias = read_i32(&mut reader)?;
+        let num_clauses = read_usize(&mut reader)?;
+        let mut clauses = Vec::with_capacity(num_clauses.min(reader.len()));
+        for _ in 0..num_clauses {
+            let weight = read_i32(&mut reader)?;
+            let num_literals = read_usize(&mut reader)?;
+            let mut literals = Vec::with_capacity(num_literals.min(reader.len()));
+            let mut code = 0u64;
+            for _ in 0..num_literals {
+                code = code
+                    .checked_add(read_varint(&mut reader)?)
+                    .ok_or_else(|| DecodeError::new("literal out of range"))?;
+                let feature = usize::try_from(code / 2)
+                    .ok()
+                    .filter(|&feature| feature < num_features)
+                    .ok_or_else(|| DecodeError::new("literal out of range"))?;
+                literals.push(Literal { feature, negated: code % 2 == 1 });
+            }
+            clauses.push(CompiledClause::from_literals(weight, &literals, num_features));
+        }
+        if !reader.is_empty() {
+            return Err(DecodeError::new("trailing bytes"));
+        }
+
+        Ok(Self {
+            num_features,
+            bias,
+            clauses,
+        })
+    }
+}
+
+/// Map signed to unsigned integers so small magnitudes stay small
+pub(crate) fn zigzag(value: i32) -> u64 {
+    u64::from(((value << 1) ^ (value >> 31)) as u32)
+}
+
+/// Append an unsigned LEB128 variable-length integer
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Read an unsigned LEB128 integer, advancing the reader
+pub(crate) fn read_varint(reader: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = reader
+            .split_first()
+            .ok_or_else(|| DecodeError::new("unexpected end of data"))?;
+        *reader = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DecodeError::new("integer too long"))
+}
+
+/// Read a count or feature index
+pub(crate) fn read_usize(reader: &mut &[u8]) -> Result<usize, DecodeError> {
+    usize::try_from(read_varint(reader)?).map_err(|_| DecodeError::new("count too large"))
+}
+
+/// Read a zigzag-encoded vote weight
+pub(crate) fn read_i32(reader: &mut &[u8]) -> Result<i32, DecodeError> {
+    let value = u32::try_from(read_varint(reader)?)
+        .map_err(|_| DecodeError::new("weight out of range"))?;
+    Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+}
+
+impl TsetlinMachine {
+    /// Compile the machine into an inference-only [`CompiledMachine`]
+    pub fn compile(&self) -> CompiledMachine {
+        CompiledMachine::new(self)
+    }
+
+    /// Compile the machine and serialize it with [`CompiledMachine::to_bytes`]
+    pub fn compress(&self) -> Vec<u8> {
+        self.compile().to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_bits() {
+        let mut features = vec![false; 70];
+        features[0] = true;
+        features[65] = true;
+        assert_eq!(pack_bits(&features), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_clause_masks() {
+        let literals = vec![
+            Literal { feature: 1, negated: false },
+            Literal { feature: 66, negated: true },
+        ];
+        let clause = CompiledClause::from_literals(-1, &literals, 70);
+        assert_eq!(clause.literals(), literals);
+
+        let mut features = vec![false; 70];
+        features[1] = true;
+        assert!(clause.matches(&pack_bits(&features)));
+        features[66] = true;
+        assert!(!clause.matches(&pack_bits(&features)));
+    }
+
+    #[test]
+    fn test_compressed_round_trip() {
+        let mut machine = TsetlinMachine::with_defaults(200, 6);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: true }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 150, negated: false }, true);
+        bank.clause_mut(4).set_included(Literal { feature: 199, negated: true }, true);
+        bank.set_weight(4, 300);
+
+        let compiled = machine.compile();
+        let bytes = machine.compress();
+        assert_eq!(CompiledMachine::from_bytes(&bytes).unwrap(), compiled);
+        assert!(bytes.len() * 100 < machine.estimated_memory_bytes());
+
+        assert!(CompiledMachine::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(CompiledMachine::from_bytes(b"JSON").is_err());
+    }
+
+    #[test]
+    fn test_varint_codes() {
+        for value in [0, 1, -1, 63, -64, i32::MAX, i32::MIN] {
+            let mut out = Vec::new();
+            write_varint(&mut out, zigzag(value));
+            assert_eq!(read_i32(&mut out.as_slice()).unwrap(), value);
+        }
+        assert_eq!(zigzag(-1), 1);
+    }
+
+    #[test]
+    fn test_compiled_matches_machine() {
+        let mut machine = TsetlinMachine::with_defaults(3, 6);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(4).set_included(Literal { feature: 2, negated: true }, true);
+
+        let compiled = machine.compile();
+        assert_eq!(compiled.bias(), 0);
+        assert_eq!(compiled.clauses().len(), 2);
+
+        let mut samples = BitMatrix::new(0, 3);
+        for bits in 0..8u8 {
+            let sample: Vec<bool> = (0..3).map(|i| bits & (1 << i) != 0).collect();
+            assert_eq!(compiled.vote_single(&sample), machine.vote_single(&sample));
+            samples.push_row(&sample);
+        }
+        assert_eq!(compiled.predict_votes_bits(&samples), machine.predict_votes(&samples));
+    }
+}
@@ -0,0 +1,775 @@
+//! Experiment configuration loaded from TOML
+//!
+//! A [`TsetlinConfig`] describes the machine hyperparameters, the training
+//! schedule and how raw numeric features are binarized, so an experiment can
+//! be reproduced from a single file. The same file format is read by the
+//! `tsetlin-cli` binary.
+//!
+//! ```toml
+//! [machine]
+//! num_clauses = 100
+//! specificity = 3.9
+//! threshold = 15.0
+//! threshold_fraction = 0.1      # optional, T = fraction * num_clauses / 2
+//! num_states = 100
+//! positive_clauses = 60         # optional, otherwise half of num_clauses
+//! features_per_clause = 50      # optional feature bagging
+//!
+//! [training]
+//! epochs = 200
+//! auto_grow_max_clauses = 400   # optional, with auto_grow_patience
+//! auto_grow_patience = 5
+//! prune_min_precision = 0.5     # optional, with prune_interval
+//! prune_interval = 10
+//! polarity_flip = true          # flip mostly-wrong clauses instead of pruning
+//! diversity_penalty = 0.1       # optional
+//! noise_tolerance = 0.05        # optional
+//! bit_flip_probability = 0.02   # optional augmentation
+//! batch_size = 32               # optional stratified mini-batches
+//! privacy_epsilon = 2.0         # optional label-private feedback
+//! fairness_feature = 3          # optional protected group column, with
+//! fairness_max_disparity = 0.1  # the demographic parity bound
+//! seed = 42                     # optional, for reproducible runs
+//! weight_learning = "integer"   # or "none" (default)
+//! max_weight = 32               # cap for integer weights
+//!
+//! [binarization]
+//! method = "thermometer"        # "gray", "hybrid", or "none" for boolean input
+//! bins = 8
+//! dimensions = 64               # random projections of the hybrid method
+//! ```
+//!
+//! Only the subset of TOML needed for such files is supported: tables,
+//! `key = value` pairs with strings, integers, floats and booleans, and
+//! comments. Every key is optional and unknown keys are rejected.
+
+use crate::augment::BitFlip;
+use crate::binarize::{GrayCodeEncoder, HybridEncoder, ThermometerEncoder, Transform};
+use crate::clause::{ClauseBank, WeightLearning};
+use crate::machine::{AutoGrow, FairnessConstraint, PruneRegrow, TsetlinMachine};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// Error raised when a configuration cannot be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    message: String,
+}
+
+impl ConfigError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration: {}", self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// How raw numeric features are turned into boolean features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binarization {
+    /// Features are already boolean; non-zero values are true
+    None,
+    /// Quantile thermometer encoding with the given number of bins
+    Thermometer {
+        /// Number of quantile bins per column
+        bins: usize,
+    },
+    /// Gray-coded quantile bins, `ceil(log2(bins))` features per column
+    Gray {
+        /// Number of quantile bins per column
+        bins: usize,
+    },
+    /// Thermometer encoding plus random-projection features
+    Hybrid {
+        /// Number of quantile bins per column
+        bins: usize,
+        /// Number of random-projection features
+        dimensions: usize,
+    },
+}
+
+impl Binarization {
+    /// Build the encoder for this setting, if any
+    pub fn encoder(&self) -> Option<Box<dyn Transform>> {
+        match *self {
+            Binarization::None => None,
+            Binarization::Thermometer { bins } => Some(Box::new(ThermometerEncoder::new(bins))),
+            Binarization::Gray { bins } => Some(Box::new(GrayCodeEncoder::new(bins))),
+            Binarization::Hybrid { bins, dimensions } => {
+                Some(Box::new(HybridEncoder::new(bins, dimensions)))
+            }
+        }
+    }
+}
+
+/// Hyperparameters, training schedule and binarization of an experiment
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsetlinConfig {
+    /// Number of clauses (must be even unless `positive_clauses` is set)
+    pub num_clauses: usize,
+    /// Clauses voting for the positive class (half of `num_clauses` when `None`)
+    pub positive_clauses: Option<usize>,
+    /// Specificity parameter
+    pub specificity: f64,
+    /// Decision threshold
+    pub threshold: f64,
+    /// Threshold as a fraction of `num_clauses / 2`, overriding `threshold`
+    pub threshold_fraction: Option<f64>,
+    /// Number of states per automaton action
+    pub num_states: u32,
+    /// Random features per clause (capped at the number of features)
+    pub features_per_clause: Option<usize>,
+    /// Number of training epochs
+    pub epochs: usize,
+    /// Clause growth on accuracy plateaus
+    pub auto_grow: Option<AutoGrow>,
+    /// Periodic replacement of low-precision clauses
+    pub prune_regrow: Option<PruneRegrow>,
+    /// Flip mostly-wrong clauses instead of replacing them
+    pub polarity_flip: bool,
+    /// Strength of the per-epoch diversity penalty
+    pub diversity_penalty: Option<f64>,
+    /// Probability of withholding Type II feedback from suspected mislabels
+    pub noise_tolerance: Option<f64>,
+    /// Bit-flip augmentation rate of training samples
+    pub bit_flip_probability: Option<f64>,
+    /// Size of stratified training mini-batches
+    pub batch_size: Option<usize>,
+    /// Randomized-response epsilon of label-private feedback
+    pub privacy_epsilon: Option<f64>,
+    /// Demographic parity bound on a protected feature
+    pub fairness: Option<FairnessConstraint>,
+    /// Random seed
+    pub seed: Option<u64>,
+    /// How clause weights are learned
+    pub weight_learning: WeightLearning,
+    /// Binarization of raw features
+    pub binarization: Binarization,
+}
+
+impl Default for TsetlinConfig {
+    fn default() -> Self {
+        Self {
+            num_clauses: 100,
+            positive_clauses: None,
+            specificity: 2.0,
+            threshold: 1.0,
+            threshold_fraction: None,
+            num_states: 100,
+            features_per_clause: None,
+            epochs: 100,
+            auto_grow: None,
+            prune_regrow: None,
+            polarity_flip: false,
+            diversity_penalty: None,
+            noise_tolerance: None,
+            bit_flip_probability: None,
+            batch_size: None,
+            privacy_epsilon: None,
+            fairness: None,
+            seed: None,
+            weight_learning: WeightLearning::None,
+            binarization: Binarization::None,
+        }
+    }
+}
+
+impl TsetlinConfig {
+    /// Load a configuration from a TOML file
+    pub fn from_toml(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| ConfigError::new(format!("cannot read {}: {error}", path.display())))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Parse a configuration from TOML text
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::config::{Binarization, TsetlinConfig};
+    ///
+    /// let config = TsetlinConfig::from_toml_str(
+    ///     "[machine]\nnum_clauses = 40\n\n[binarization]\nmethod = \"thermometer\"\nbins = 4\n",
+    /// ).unwrap();
+    /// assert_eq!(config.num_clauses, 40);
+    /// assert_eq!(config.binarization, Binarization::Thermometer { bins: 4 });
+    /// ```
+    pub fn from_toml_str(text: &str) -> Result<Self, ConfigError> {
+        Self::from_tables(parse_toml(text)?)
+    }
+
+    /// Read the configuration tables, rejecting any other table
+    pub(crate) fn from_tables(mut tables: BTreeMap<String, Table>) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+
+        if let Some(mut machine) = tables.remove("machine") {
+            if let Some(value) = machine.take("num_clauses")? {
+                config.num_clauses = value.as_usize("num_clauses")?;
+            }
+            if let Some(value) = machine.take("positive_clauses")? {
+                config.positive_clauses = Some(value.as_usize("positive_clauses")?);
+            }
+            if let Some(value) = machine.take("specificity")? {
+                config.specificity = value.as_f64("specificity")?;
+            }
+            if let Some(value) = machine.take("threshold")? {
+                config.threshold = value.as_f64("threshold")?;
+            }
+            if let Some(value) = machine.take("threshold_fraction")? {
+                config.threshold_fraction = Some(value.as_f64("threshold_fraction")?);
+            }
+            if let Some(value) = machine.take("num_states")? {
+                config.num_states = u32::try_from(value.as_usize("num_states")?)
+                    .map_err(|_| ConfigError::new("`num_states` is too large"))?;
+            }
+            if let Some(value) = machine.take("features_per_clause")? {
+                config.features_per_clause = Some(value.as_usize("features_per_clause")?);
+            }
+            machine.finish()?;
+        }
+
+        if let Some(mut training) = tables.remove("training") {
+            if let Some(value) = training.take("epochs")? {
+                config.epochs = value.as_usize("epochs")?;
+            }
+
+            let max_clauses = training.take("auto_grow_max_clauses")?;
+            let patience = training.take("auto_grow_patience")?;
+            config.auto_grow = match (max_clauses, patience) {
+                (Some(max_clauses), Some(patience)) => Some(AutoGrow {
+                    max_clauses: max_clauses.as_usize("auto_grow_max_clauses")?,
+                    patience: patience.as_usize("auto_grow_patience")?,
+                }),
+                (None, None) => None,
+                _ => {
+                    return Err(ConfigError::new(
+                        "`auto_grow_max_clauses` and `auto_grow_patience` must be set together",
+                    ))
+                }
+            };
+
+            let min_precision = training.take("prune_min_precision")?;
+            let interval = training.take("prune_interval")?;
+            config.prune_regrow = match (min_precision, interval) {
+                (Some(min_precision), Some(interval)) => Some(PruneRegrow {
+                    min_precision: min_precision.as_f64("prune_min_precision")?,
+                    interval: interval.as_usize("prune_interval")?,
+                }),
+                (None, None) => None,
+                _ => {
+                    return Err(ConfigError::new(
+                        "`prune_min_precision` and `prune_interval` must be set together",
+                    ))
+                }
+            };
+            if let Some(value) = training.take("polarity_flip")? {
+                config.polarity_flip = value.as_bool("polarity_flip")?;
+            }
+
+            if let Some(value) = training.take("diversity_penalty")? {
+                config.diversity_penalty = Some(value.as_f64("diversity_penalty")?);
+            }
+            if let Some(value) = training.take("noise_tolerance")? {
+                config.noise_tolerance = Some(value.as_f64("noise_tolerance")?);
+            }
+            if let Some(value) = training.take("bit_flip_probability")? {
+                config.bit_flip_probability = Some(value.as_f64("bit_flip_probability")?);
+            }
+            if let Some(value) = training.take("batch_size")? {
+                config.batch_size = Some(value.as_usize("batch_size")?);
+            }
+            if let Some(value) = training.take("privacy_epsilon")? {
+                config.privacy_epsilon = Some(value.as_f64("privacy_epsilon")?);
+            }
+
+            let protected_feature = training.take("fairness_feature")?;
+            let max_disparity = training.take("fairness_max_disparity")?;
+            config.fairness = match (protected_feature, max_disparity) {
+                (Some(protected_feature), Some(max_disparity)) => Some(FairnessConstraint {
+                    protected_feature: protected_feature.as_usize("fairness_feature")?,
+                    max_disparity: max_disparity.as_f64("fairness_max_disparity")?,
+                }),
+                (None, None) => None,
+                _ => {
+                    return Err(ConfigError::new(
+                        "`fairness_feature` and `fairness_max_disparity` must be set together",
+                    ))
+                }
+            };
+            if let Some(value) = training.take("seed")? {
+                config.seed = Some(value.as_usize("seed")? as u64);
+            }
+            let method = match training.take("weight_learning")? {
+                Some(value) => value.as_str("weight_learning")?.to_string(),
+                None => "none".to_string(),
+            };
+            let max_weight = training.take("max_weight")?;
+            config.weight_learning = match (method.as_str(), max_weight) {
+                ("none", None) => WeightLearning::None,
+                ("none", Some(_)) => {
+                    return Err(ConfigError::new(
+                        "`max_weight` requires `weight_learning = \"integer\"`",
+                    ))
+                }
+                ("integer", max_weight) => WeightLearning::Integer {
+                    max_weight: match max_weight {
+                        Some(value) => u32::try_from(value.as_usize("max_weight")?)
+                            .map_err(|_| ConfigError::new("`max_weight` is too large"))?,
+                        None => 100,
+                    },
+                },
+                (other, _) => {
+                    return Err(ConfigError::new(format!("unknown weight learning `{other}`")))
+                }
+            };
+            training.finish()?;
+        }
+
+        if let Some(mut binarization) = tables.remove("binarization") {
+            let method = match binarization.take("method")? {
+                Some(value) => value.as_str("method")?.to_string(),
+                None => "none".to_string(),
+            };
+            let bins = match binarization.take("bins")? {
+                Some(value) => value.as_usize("bins")?,
+                None => 8,
+            };
+            let dimensions = match binarization.take("dimensions")? {
+                Some(value) => value.as_usize("dimensions")?,
+                None => 64,
+            };
+            config.binarization = match method.as_str() {
+                "none" => Binarization::None,
+                "thermometer" => Binarization::Thermometer { bins },
+                "gray" => Binarization::Gray { bins },
+                "hybrid" => Binarization::Hybrid { bins, dimensions },
+                other => {
+                    return Err(ConfigError::new(format!("unknown binarization method `{other}`")))
+                }
+            };
+            binarization.finish()?;
+        }
+
+        if let Some(name) = tables.keys().next() {
+            return Err(ConfigError::new(format!("unknown table `{name}`")));
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that the settings can build a machine
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match self.positive_clauses {
+            Some(positive) if positive == 0 || positive >= self.num_clauses => {
+                return Err(ConfigError::new(
+                    "`positive_clauses` must leave at least one clause of each polarity",
+                ));
+            }
+            None if !self.num_clauses.is_multiple_of(2) => {
+                return Err(ConfigError::new("`num_clauses` must be even"));
+            }
+            _ => {}
+        }
+        if !(self.specificity >= 1.0 && self.specificity.is_finite()) {
+            return Err(ConfigError::new("`specificity` must be at least 1"));
+        }
+        if !(self.threshold > 0.0 && self.threshold.is_finite()) {
+            return Err(ConfigError::new("`threshold` must be positive"));
+        }
+        if let Some(fraction) = self.threshold_fraction {
+            if !(fraction > 0.0 && fraction.is_finite()) {
+                return Err(ConfigError::new("`threshold_fraction` must be positive"));
+            }
+        }
+        if self.num_states == 0 {
+            return Err(ConfigError::new("`num_states` must be positive"));
+        }
+        if self.features_per_clause == Some(0) {
+            return Err(ConfigError::new("`features_per_clause` must be positive"));
+        }
+        if self.batch_size == Some(0) {
+            return Err(ConfigError::new("`batch_size` must be positive"));
+        }
+        if let Some(epsilon) = self.privacy_epsilon {
+            if !(epsilon > 0.0 && epsilon.is_finite()) {
+                return Err(ConfigError::new("`privacy_epsilon` must be positive"));
+            }
+        }
+        if let Some(constraint) = self.fairness {
+            if !(0.0..=1.0).contains(&constraint.max_disparity) {
+                return Err(ConfigError::new("`fairness_max_disparity` must be in [0, 1]"));
+            }
+        }
+        if let Some(growth) = self.auto_grow {
+            if !growth.max_clauses.is_multiple_of(2) || growth.max_clauses < self.num_clauses {
+                return Err(ConfigError::new(
+                    "`auto_grow_max_clauses` must be even and at least `num_clauses`",
+                ));
+            }
+            if growth.patience == 0 {
+                return Err(ConfigError::new("`auto_grow_patience` must be positive"));
+            }
+        }
+        if let Some(regrow) = self.prune_regrow {
+            if !(0.0..=1.0).contains(&regrow.min_precision) || regrow.interval == 0 {
+                return Err(ConfigError::new(
+                    "`prune_min_precision` must be in [0, 1] and `prune_interval` positive",
+                ));
+            }
+        }
+        if let Some(strength) = self.diversity_penalty {
+            if !(0.0..=1.0).contains(&strength) {
+                return Err(ConfigError::new("`diversity_penalty` must be in [0, 1]"));
+            }
+        }
+        if let Some(tolerance) = self.noise_tolerance {
+            if !(0.0..=1.0).contains(&tolerance) {
+                return Err(ConfigError::new("`noise_tolerance` must be in [0, 1]"));
+            }
+        }
+        if self.weight_learning == (WeightLearning::Integer { max_weight: 0 }) {
+            return Err(ConfigError::new("`max_weight` must be positive"));
+        }
+        if let Some(probability) = self.bit_flip_probability {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(ConfigError::new("`bit_flip_probability` must be in [0, 1]"));
+            }
+        }
+        if let Binarization::Thermometer { bins }
+        | Binarization::Gray { bins }
+        | Binarization::Hybrid { bins, .. } = self.binarization
+        {
+            if bins < 2 {
+                return Err(ConfigError::new("`bins` must be at least 2"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build an untrained machine for `num_features` boolean features
+    pub fn build_machine(&self, num_features: usize) -> TsetlinMachine {
+        self.validate().expect("invalid configuration");
+
+        let bank = match self.positive_clauses {
+            Some(positive) => ClauseBank::with_polarity_budget(
+                num_features,
+                positive,
+                self.num_clauses - positive,
+                self.num_states,
+            ),
+            None => ClauseBank::new(num_features, self.num_clauses, self.num_states),
+        };
+        let mut machine = TsetlinMachine::from_clause_bank(bank, self.specificity, self.threshold);
+        if let Some(fraction) = self.threshold_fraction {
+            machine = machine.threshold_auto(fraction);
+        }
+        if let Some(growth) = self.auto_grow {
+            machine = machine.auto_grow(growth.max_clauses, growth.patience);
+        }
+        if let Some(regrow) = self.prune_regrow {
+            machine = machine.prune_and_regrow(regrow.min_precision, regrow.interval);
+        }
+        if self.polarity_flip {
+            machine = machine.polarity_flip(true);
+        }
+        if let Some(strength) = self.diversity_penalty {
+            machine = machine.diversity_penalty(strength);
+        }
+        if let Some(tolerance) = self.noise_tolerance {
+            machine = machine.noise_tolerance(tolerance);
+        }
+        if let Some(probability) = self.bit_flip_probability {
+            machine = machine.augment(BitFlip::new(probability));
+        }
+        if let Some(batch_size) = self.batch_size {
+            machine = machine.batch_size(batch_size);
+        }
+        if let Some(epsilon) = self.privacy_epsilon {
+            machine = machine.differential_privacy(epsilon);
+        }
+        if let Some(constraint) = self.fairness {
+            machine =
+                machine.fairness_constraint(constraint.protected_feature, constraint.max_disparity);
+        }
+        if let Some(seed) = self.seed {
+            machine = machine.seed(seed);
+        }
+        if let Some(size) = self.features_per_clause {
+            machine = machine.feature_bagging(size.min(num_features));
+        }
+        machine.weight_learning(self.weight_learning)
+    }
+}
+
+/// A parsed TOML scalar
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TomlValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl TomlValue {
+    pub(crate) fn as_f64(&self, key: &str) -> Result<f64, ConfigError> {
+        match *self {
+            TomlValue::Float(value) => Ok(value),
+            TomlValue::Integer(value) => Ok(value as f64),
+            _ => Err(ConfigError::new(format!("`{key}` must be a number"))),
+        }
+    }
+
+    pub(crate) fn as_usize(&self, key: &str) -> Result<usize, ConfigError> {
+        match *self {
+            TomlValue::Integer(value) if value >= 0 => Ok(value as usize),
+            _ => Err(ConfigError::new(format!("`{key}` must be a non-negative integer"))),
+        }
+    }
+
+    pub(crate) fn as_bool(&self, key: &str) -> Result<bool, ConfigError> {
+        match *self {
+            TomlValue::Bool(value) => Ok(value),
+            _ => Err(ConfigError::new(format!("`{key}` must be a boolean"))),
+        }
+    }
+
+    pub(crate) fn as_str(&self, key: &str) -> Result<&str, ConfigError> {
+        match self {
+            TomlValue::String(value) => Ok(value),
+            _ => Err(ConfigError::new(format!("`{key}` must be a string"))),
+        }
+    }
+}
+
+/// Key/value pairs of one TOML table
+#[derive(Debug, Default)]
+pub(crate) struct Table {
+    name: String,
+    values: BTreeMap<String, TomlValue>,
+}
+
+impl Table {
+    /// Remove and return a key
+    pub(crate) fn take(&mut self, key: &str) -> Result<Option<TomlValue>, ConfigError> {
+        Ok(self.values.remove(key))
+    }
+
+    /// Fail if any key was not consumed
+    pub(crate) fn finish(self) -> Result<(), ConfigError> {
+        match self.values.keys().next() {
+            Some(key) => Err(ConfigError::new(format!("unknown key `{key}` in [{}]", self.name))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse TOML text into named tables
+pub(crate) fn parse_toml(text: &str) -> Result<BTreeMap<String, Table>, ConfigError> {
+    let mut tables: BTreeMap<String, Table> = BTreeMap::new();
+    let mut current = String::new();
+
+    for (number, raw_line) in text.lines().enumerate() {
+        let line = strip_comment(raw_line).trim();
+        let at_line = |message: &str| ConfigError::new(format!("line {}: {message}", number + 1));
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| at_line("unterminated table header"))?
+                .trim();
+            if tables.contains_key(name) {
+                return Err(at_line(&format!("duplicate table `{name}`")));
+            }
+            current = name.to_string();
+            tables.insert(
+                current.clone(),
+                Table {
+                    name: current.clone(),
+                    values: BTreeMap::new(),
+                },
+            );
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| at_line("expected `key = value`"))?;
+        let key = key.trim();
+        let value = parse_value(value.trim()).ok_or_else(|| at_line("invalid value"))?;
+        if current.is_empty() {
+            return Err(at_line(&format!("key `{key}` must be inside a table")));
+        }
+
+        let table = tables.get_mut(&current).expect("current table exists");
+        if table.values.insert(key.to_string(), value).is_some() {
+            return Err(at_line(&format!("duplicate key `{key}`")));
+        }
+    }
+    Ok(tables)
+}
+
+/// Remove a trailing comment that is not inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, character) in line.char_indices() {
+        match character {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parse a scalar TOML value
+fn parse_value(value: &str) -> Option<TomlValue> {
+    if let Some(inner) = value.strip_prefix('"') {
+        let inner = inner.strip_suffix('"')?;
+        return (!inner.contains('"')).then(|| TomlValue::String(inner.to_string()));
+    }
+    match value {
+        "true" => return Some(TomlValue::Bool(true)),
+        "false" => return Some(TomlValue::Bool(false)),
+        _ => {}
+    }
+
+    let digits = value.replace('_', "");
+    if let Ok(integer) = digits.parse::<i64>() {
+        return Some(TomlValue::Integer(integer));
+    }
+    digits.parse::<f64>().ok().map(TomlValue::Float)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = r#"
+# Experiment settings
+[machine]
+num_clauses = 40
+positive_clauses = 25
+specificity = 3.9
+threshold = 15  # integers are accepted for floats
+threshold_fraction = 0.25
+num_states = 200
+features_per_clause = 5
+
+[training]
+epochs = 1_000
+auto_grow_max_clauses = 80
+auto_grow_patience = 5
+prune_min_precision = 0.5
+prune_interval = 10
+polarity_flip = true
+diversity_penalty = 0.1
+noise_tolerance = 0.05
+bit_flip_probability = 0.02
+batch_size = 8
+privacy_epsilon = 1.5
+fairness_feature = 2
+fairness_max_disparity = 0.1
+seed = 7
+weight_learning = "integer"
+max_weight = 16
+
+[binarization]
+method = "thermometer"
+bins = 4
+"#;
+
+    #[test]
+    fn test_full_config() {
+        let config = TsetlinConfig::from_toml_str(FULL).unwrap();
+        assert_eq!(config.num_clauses, 40);
+        assert_eq!(config.positive_clauses, Some(25));
+        assert_eq!(config.specificity, 3.9);
+        assert_eq!(config.threshold, 15.0);
+        assert_eq!(config.threshold_fraction, Some(0.25));
+        assert_eq!(config.num_states, 200);
+        assert_eq!(config.features_per_clause, Some(5));
+        assert_eq!(config.epochs, 1000);
+        assert_eq!(config.auto_grow, Some(AutoGrow { max_clauses: 80, patience: 5 }));
+        assert_eq!(config.prune_regrow, Some(PruneRegrow { min_precision: 0.5, interval: 10 }));
+        assert!(config.polarity_flip);
+        assert_eq!(config.diversity_penalty, Some(0.1));
+        assert_eq!(config.noise_tolerance, Some(0.05));
+        assert_eq!(config.bit_flip_probability, Some(0.02));
+        assert_eq!(config.batch_size, Some(8));
+        assert_eq!(config.privacy_epsilon, Some(1.5));
+        let fairness = FairnessConstraint { protected_feature: 2, max_disparity: 0.1 };
+        assert_eq!(config.fairness, Some(fairness));
+        assert_eq!(config.seed, Some(7));
+        assert_eq!(config.weight_learning, WeightLearning::Integer { max_weight: 16 });
+        assert_eq!(config.binarization, Binarization::Thermometer { bins: 4 });
+
+        let machine = config.build_machine(6);
+        assert_eq!(machine.num_features(), 6);
+        assert_eq!(machine.num_clauses(), 40);
+        assert_eq!(machine.threshold(), 5.0);
+        assert_eq!(machine.clause_bank().polarity_counts(), (25, 15));
+        assert_eq!(machine.clause_bank().num_states(), 200);
+        assert_eq!(machine.clause_bank().noise_tolerance(), 0.05);
+        assert_eq!(machine.clause_bank().features_per_clause(), Some(5));
+        assert_eq!(machine.random_seed(), Some(7));
+        assert_eq!(machine.training_batch_size(), Some(8));
+        assert_eq!(machine.privacy_epsilon(), Some(1.5));
+        assert_eq!(machine.fairness(), Some(fairness));
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(TsetlinConfig::from_toml_str("").unwrap(), TsetlinConfig::default());
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        let error = TsetlinConfig::from_toml_str("[machine]\nnum_clause = 10\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid configuration: unknown key `num_clause` in [machine]"
+        );
+
+        assert!(TsetlinConfig::from_toml_str("[machine]\nnum_clauses = 9\n").is_err());
+        let odd = "[machine]\nnum_clauses = 9\npositive_clauses = 6\n";
+        assert_eq!(TsetlinConfig::from_toml_str(odd).unwrap().positive_clauses, Some(6));
+        let one_sided = "[machine]\nnum_clauses = 8\npositive_clauses = 8\n";
+        assert!(TsetlinConfig::from_toml_str(one_sided).is_err());
+        assert!(TsetlinConfig::from_toml_str("[machine]\nthreshold = 0\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("[machine]\nspecificity = 0.5\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("[machine]\nspecificity = 1e999\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("[training]\nauto_grow_patience = 3\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("epochs = 3\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("[model]\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("[training]\nmax_weight = 4\n").is_err());
+        assert!(TsetlinConfig::from_toml_str("[binarization]\nmethod = \"magic\"\n").is_err());
+        let hybrid = "[binarization]\nmethod = \"hybrid\"\ndimensions = 16\n";
+        assert_eq!(
+            TsetlinConfig::from_toml_str(hybrid).unwrap().binarization,
+            Binarization::Hybrid { bins: 8, dimensions: 16 }
+        );
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(parse_value("\"a # b\""), Some(TomlValue::String("a # b".to_string())));
+        assert_eq!(parse_value("-3"), Some(TomlValue::Integer(-3)));
+        assert_eq!(parse_value("2.5e-1"), Some(TomlValue::Float(0.25)));
+        assert_eq!(parse_value("true"), Some(TomlValue::Bool(true)));
+        assert_eq!(parse_value("yes"), None);
+        assert_eq!(strip_comment("bins = 4 # comment"), "bins = 4 ");
+    }
+}
@@ -0,0 +1,410 @@
+//! Convolutional Tsetlin machine with multigranular patches
+//!
+//! Images are flattened row-major boolean samples. Every clause slides over
+//! all square patches of its group's size and fires for an image if it fires
+//! on any patch. Several patch sizes can be combined in one model, each with
+//! its own clause group, so coarse and fine patterns vote together (the
+//! multigranular convolutional Tsetlin machine).
+//!
+//! Every group's clause bank uses [`Aggregation::OrPool`], so training follows
+//! the convolutional Tsetlin machine: a clause that fires learns from a random
+//! patch it fires on, otherwise from a random patch.
+//!
+//! With [`ConvolutionalTsetlinMachine::positional_literals`] every patch also
+//! carries thermometer-coded position bits, so clauses can require a pattern
+//! to appear in a particular region of the image.
+
+use crate::clause::{Aggregation, ClauseBank};
+use crate::input::AsBoolSamples;
+use ndarray::Array1;
+use rand::{prelude::SliceRandom, SeedableRng};
+
+/// All square patches of `size` in a row-major image, scanning rows first
+///
+/// Each patch is flattened row-major.
+///
+/// # Example
+/// ```
+/// use tsetlin::convolution::extract_patches;
+///
+/// let image = [true, false, false, true];
+/// let patches = extract_patches(&image, 2, 2, 1);
+/// assert_eq!(patches, vec![vec![true], vec![false], vec![false], vec![true]]);
+/// ```
+pub fn extract_patches(image: &[bool], height: usize, width: usize, size: usize) -> Vec<Vec<bool>> {
+    assert_eq!(image.len(), height * width, "Image length must be height * width");
+    assert!(size > 0 && size <= height.min(width), "Patch size must fit the image");
+
+    let mut patches = Vec::with_capacity((height - size + 1) * (width - size + 1));
+    for y in 0..=height - size {
+        for x in 0..=width - size {
+            let mut patch = Vec::with_capacity(size * size);
+            for row in y..y + size {
+                patch.extend_from_slice(&image[row * width + x..row * width + x + size]);
+            }
+            patches.push(patch);
+        }
+    }
+    patches
+}
+
+/// Thermometer-coded position of the patch at row `y`, column `x`
+///
+/// The first `height - size` bits are `y > i` and the next `width - size`
+/// bits are `x > i`, so a single literal can bound the patch position from
+/// either side.
+///
+/// # Example
+/// ```
+/// use tsetlin::convolution::position_bits;
+/// // Patch at row 1, column 0 of a 3x4 image, size 2: one row bit, two column bits
+/// assert_eq!(position_bits(1, 0, 3, 4, 2), vec![true, false, false]);
+/// ```
+pub fn position_bits(y: usize, x: usize, height: usize, width: usize, size: usize) -> Vec<bool> {
+    (0..height - size)
+        .map(|i| y > i)
+        .chain((0..width - size).map(|i| x > i))
+        .collect()
+}
+
+/// Clauses that scan patches of one size
+#[derive(Debug, Clone)]
+struct PatchGroup {
+    /// Side length of the square patches
+    patch_size: usize,
+    /// Clauses over flattened patches
+    clause_bank: ClauseBank,
+}
+
+/// Tsetlin machine over boolean images with one clause group per patch size
+#[derive(Debug, Clone)]
+pub struct ConvolutionalTsetlinMachine {
+    /// Clause groups, one per patch size
+    groups: Vec<PatchGroup>,
+    /// Image height in pixels
+    height: usize,
+    /// Image width in pixels
+    width: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Decision threshold
+    threshold: f64,
+    /// Whether patches carry position bits
+    positional: bool,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl ConvolutionalTsetlinMachine {
+    /// Create a new convolutional machine
+    ///
+    /// # Arguments
+    /// * `height` - Image height
+    /// * `width` - Image width
+    /// * `patch_sizes` - Side length of the square patches of each clause group
+    /// * `clauses_per_group` - Number of clauses of each group (must be even)
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::convolution::ConvolutionalTsetlinMachine;
+    /// let machine = ConvolutionalTsetlinMachine::new(28, 28, &[3, 5, 10], 100, 3.9, 25.0);
+    /// assert_eq!(machine.patch_sizes(), vec![3, 5, 10]);
+    /// assert_eq!(machine.num_clauses(), 300);
+    /// ```
+    pub fn new(
+        height: usize,
+        width: usize,
+        patch_sizes: &[usize],
+        clauses_per_group: usize,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        assert!(!patch_sizes.is_empty(), "At least one patch size is required");
+        assert!(
+            patch_sizes.iter().all(|&size| size > 0 && size <= height.min(width)),
+            "Patch size must fit the image"
+        );
+        assert!(
+            clauses_per_group > 0 && clauses_per_group.is_multiple_of(2),
+            "Number of clauses must be even and positive for every group"
+        );
+
+        let groups = patch_sizes
+            .iter()
+            .map(|&patch_size| {
+                let num_pixels = patch_size * patch_size;
+                let mut clause_bank = ClauseBank::new(num_pixels, clauses_per_group, 100);
+                clause_bank.set_aggregation(Aggregation::OrPool);
+                PatchGroup {
+                    patch_size,
+                    clause_bank,
+                }
+            })
+            .collect();
+
+        Self {
+            groups,
+            height,
+            width,
+            specificity,
+            threshold,
+            positional: false,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a single-granularity machine with default parameters
+    pub fn with_defaults(height: usize, width: usize, patch_size: usize, num_clauses: usize) -> Self {
+        Self::new(height, width, &[patch_size], num_clauses, 2.0, 1.0)
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Append position bits to every patch
+    ///
+    /// Clause features of a group become the `size * size` patch pixels
+    /// followed by the [`position_bits`] of the patch. The new literals start
+    /// excluded, so existing clauses keep their outputs.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::convolution::ConvolutionalTsetlinMachine;
+    /// let machine = ConvolutionalTsetlinMachine::with_defaults(28, 28, 10, 100).positional_literals();
+    /// // 100 pixels, 18 row and 18 column thresholds
+    /// assert_eq!(machine.clause_bank(0).num_features(), 136);
+    /// ```
+    pub fn positional_literals(mut self) -> Self {
+        if !self.positional {
+            for group in &mut self.groups {
+                let size = group.patch_size;
+                group
+                    .clause_bank
+                    .extend_features(self.height - size + self.width - size);
+            }
+            self.positional = true;
+        }
+        self
+    }
+
+    /// Whether patches carry position bits
+    pub fn has_positional_literals(&self) -> bool {
+        self.positional
+    }
+
+    /// Patches of an image for every group
+    fn group_patches(&self, image: &[bool]) -> Vec<Vec<Vec<bool>>> {
+        self.groups
+            .iter()
+            .map(|group| {
+                let size = group.patch_size;
+                let mut patches = extract_patches(image, self.height, self.width, size);
+                if self.positional {
+                    let columns = self.width - size + 1;
+                    for (index, patch) in patches.iter_mut().enumerate() {
+                        let (y, x) = (index / columns, index % columns);
+                        patch.extend(position_bits(y, x, self.height, self.width, size));
+                    }
+                }
+                patches
+            })
+            .collect()
+    }
+
+    /// Train the machine
+    ///
+    /// # Arguments
+    /// * `images` - Training images, flattened row-major (samples x height*width)
+    /// * `labels` - Training labels
+    /// * `epochs` - Number of training epochs
+    pub fn fit<S: AsBoolSamples + ?Sized>(&mut self, images: &S, labels: &Array1<bool>, epochs: usize) {
+        assert_eq!(images.num_samples(), labels.len());
+        assert_eq!(images.num_features(), self.height * self.width);
+
+        let mut indices: Vec<usize> = (0..images.num_samples()).collect();
+        let mut image = Vec::with_capacity(self.height * self.width);
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+
+            for &idx in &indices {
+                images.sample_into(idx, &mut image);
+                self.update(&image, labels[idx]);
+            }
+        }
+    }
+
+    /// Give feedback for one image
+    fn update(&mut self, image: &[bool], target: bool) {
+        let patches = self.group_patches(image);
+        let vote_sum: i32 = self
+            .groups
+            .iter()
+            .zip(&patches)
+            .map(|(group, patches)| group.clause_bank.vote_bag(patches))
+            .sum();
+
+        for (group, patches) in self.groups.iter_mut().zip(&patches) {
+            group.clause_bank.update_bag_with_vote(
+                patches,
+                target,
+                vote_sum,
+                self.threshold,
+                self.specificity,
+                &mut self.rng,
+            );
+        }
+    }
+
+    /// Vote sum of a single image over all groups
+    pub fn vote_single(&self, image: &[bool]) -> i32 {
+        self.groups
+            .iter()
+            .zip(self.group_patches(image))
+            .map(|(group, patches)| group.clause_bank.vote_bag(&patches))
+            .sum()
+    }
+
+    /// Predict the label of a single image
+    pub fn predict_single(&self, image: &[bool]) -> bool {
+        self.vote_single(image) > 0
+    }
+
+    /// Vote sum of each image
+    pub fn predict_votes<S: AsBoolSamples + ?Sized>(&self, images: &S) -> Array1<i32> {
+        assert_eq!(images.num_features(), self.height * self.width);
+
+        let mut image = Vec::with_capacity(self.height * self.width);
+        (0..images.num_samples())
+            .map(|idx| {
+                images.sample_into(idx, &mut image);
+                self.vote_single(&image)
+            })
+            .collect()
+    }
+
+    /// Predict the label of each image
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, images: &S) -> Array1<bool> {
+        self.predict_votes(images).mapv(|vote| vote > 0)
+    }
+
+    /// Accuracy on labelled images
+    pub fn evaluate<S: AsBoolSamples + ?Sized>(&self, images: &S, labels: &Array1<bool>) -> f64 {
+        let predictions = self.predict(images);
+        let correct = predictions
+            .iter()
+            .zip(labels.iter())
+            .filter(|(&pred, &actual)| pred == actual)
+            .count();
+
+        correct as f64 / labels.len() as f64
+    }
+
+    /// Side length of the patches of each group
+    pub fn patch_sizes(&self) -> Vec<usize> {
+        self.groups.iter().map(|group| group.patch_size).collect()
+    }
+
+    /// Clause bank of a group; clause features are the flattened patch pixels
+    /// (followed by the position bits with positional literals)
+    pub fn clause_bank(&self, group: usize) -> &ClauseBank {
+        &self.groups[group].clause_bank
+    }
+
+    /// Mutable access to the clause bank of a group
+    pub fn clause_bank_mut(&mut self, group: usize) -> &mut ClauseBank {
+        &mut self.groups[group].clause_bank
+    }
+
+    /// Total number of clauses over all groups
+    pub fn num_clauses(&self) -> usize {
+        self.groups.iter().map(|group| group.clause_bank.num_clauses()).sum()
+    }
+
+    /// Image height
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Image width
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::Array2;
+
+    #[test]
+    fn test_extract_patches() {
+        // 3x3 image with the centre pixel set
+        let mut image = vec![false; 9];
+        image[4] = true;
+        let patches = extract_patches(&image, 3, 3, 2);
+
+        assert_eq!(patches.len(), 4);
+        assert_eq!(patches[0], vec![false, false, false, true]);
+        assert_eq!(patches[3], vec![true, false, false, false]);
+        assert_eq!(extract_patches(&image, 3, 3, 3), vec![image]);
+    }
+
+    #[test]
+    fn test_multigranular_vote() {
+        let mut machine = ConvolutionalTsetlinMachine::new(4, 4, &[1, 2], 2, 2.0, 1.0);
+        // 1x1 group: positive clause fires on any set pixel
+        let literal = Literal { feature: 0, negated: false };
+        machine.clause_bank_mut(0).clause_mut(0).set_included(literal, true);
+        // 2x2 group: positive clause fires on a filled 2x2 square
+        for feature in 0..4 {
+            let literal = Literal { feature, negated: false };
+            machine.clause_bank_mut(1).clause_mut(0).set_included(literal, true);
+        }
+
+        let mut images = Array2::from_elem((3, 16), false);
+        images[[1, 5]] = true;
+        for pixel in [10, 11, 14, 15] {
+            images[[2, pixel]] = true;
+        }
+
+        // Empty negative clauses always fire, one per group
+        assert_eq!(machine.predict_votes(&images), ndarray::array![-2, -1, 0]);
+    }
+
+    #[test]
+    fn test_positional_literals() {
+        let mut machine = ConvolutionalTsetlinMachine::with_defaults(3, 3, 1, 2).positional_literals();
+        assert_eq!(machine.clause_bank(0).num_features(), 5);
+
+        // Pixel set AND row > 1 (bottom row only)
+        let bank = machine.clause_bank_mut(0);
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 2, negated: false }, true);
+
+        let mut images = Array2::from_elem((2, 9), false);
+        images[[0, 1]] = true;
+        images[[1, 7]] = true;
+        assert_eq!(machine.predict_votes(&images), ndarray::array![-1, 0]);
+    }
+
+    #[test]
+    fn test_fit_runs() {
+        let images = Array2::from_shape_fn((4, 9), |(i, j)| (i + j) % 3 == 0);
+        let labels = ndarray::array![true, false, true, false];
+        let mut machine = ConvolutionalTsetlinMachine::new(3, 3, &[2, 3], 4, 2.0, 2.0).seed(5);
+        machine.fit(&images, &labels, 3);
+        assert_eq!(machine.predict(&images).len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Patch size must fit the image")]
+    fn test_patch_too_large() {
+        ConvolutionalTsetlinMachine::new(4, 4, &[5], 2, 2.0, 1.0);
+    }
+}
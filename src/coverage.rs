@@ -0,0 +1,143 @@
+//! Clause coverage of a dataset
+//!
+//! A sample that no trained clause fires on is predicted from the constant
+//! votes of empty clauses alone, i.e. by default. [`CoverageReport`] counts
+//! the firing positive and negative clauses per sample so such samples can be
+//! found. Empty clauses fire on every input and are not counted.
+
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2};
+
+/// Per-sample counts of firing clauses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Number of firing positive clauses per sample
+    pub positive_fired: Array1<usize>,
+    /// Number of firing negative clauses per sample
+    pub negative_fired: Array1<usize>,
+}
+
+impl CoverageReport {
+    /// Number of samples in the report
+    pub fn num_samples(&self) -> usize {
+        self.positive_fired.len()
+    }
+
+    /// Number of firing clauses per sample, regardless of polarity
+    pub fn total_fired(&self) -> Array1<usize> {
+        &self.positive_fired + &self.negative_fired
+    }
+
+    /// Whether each sample is covered by at least one clause
+    pub fn covered(&self) -> Array1<bool> {
+        self.total_fired().mapv(|count| count > 0)
+    }
+
+    /// Indices of the samples no clause fires on
+    pub fn uncovered(&self) -> Vec<usize> {
+        self.total_fired()
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count == 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Fraction of samples covered by at least one clause
+    pub fn coverage(&self) -> f64 {
+        if self.num_samples() == 0 {
+            return 0.0;
+        }
+        let num_covered = self.num_samples() - self.uncovered().len();
+        num_covered as f64 / self.num_samples() as f64
+    }
+}
+
+impl TsetlinMachine {
+    /// Count the clauses that fire on each sample
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix (samples x features)
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// // An untrained machine has only empty clauses, so nothing is covered
+    /// let machine = TsetlinMachine::with_defaults(2, 10);
+    /// let report = machine.coverage_report(&array![[true, false], [false, true]]);
+    /// assert_eq!(report.uncovered(), vec![0, 1]);
+    /// ```
+    pub fn coverage_report(&self, features: &Array2<bool>) -> CoverageReport {
+        assert_eq!(features.ncols(), self.num_features());
+
+        let bank = self.clause_bank();
+        let trained: Vec<(usize, bool)> = bank
+            .clauses()
+            .iter()
+            .zip(bank.polarities())
+            .enumerate()
+            .filter(|(_, (clause, _))| clause.num_included() > 0)
+            .map(|(i, (_, &polarity))| (i, polarity))
+            .collect();
+
+        let mut positive_fired = Array1::zeros(features.nrows());
+        let mut negative_fired = Array1::zeros(features.nrows());
+        for (row, sample) in features.rows().into_iter().enumerate() {
+            let sample = sample.to_vec();
+            for &(i, polarity) in &trained {
+                if bank.clauses()[i].evaluate(&sample) {
+                    if polarity {
+                        positive_fired[row] += 1;
+                    } else {
+                        negative_fired[row] += 1;
+                    }
+                }
+            }
+        }
+
+        CoverageReport {
+            positive_fired,
+            negative_fired,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    fn machine() -> TsetlinMachine {
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let bank = machine.clause_bank_mut();
+        // Positive clause x0, negative clause NOT x0 AND x1
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 0, negated: true }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 1, negated: false }, true);
+        machine
+    }
+
+    #[test]
+    fn test_coverage_counts() {
+        let features = array![[true, false], [false, true], [true, true]];
+        let report = machine().coverage_report(&features);
+
+        assert_eq!(report.positive_fired, array![1, 0, 1]);
+        assert_eq!(report.negative_fired, array![0, 1, 0]);
+        assert_eq!(report.total_fired(), array![1, 1, 1]);
+        assert_eq!(report.coverage(), 1.0);
+    }
+
+    #[test]
+    fn test_uncovered_samples() {
+        let features = array![[false, false], [true, false], [false, false]];
+        let report = machine().coverage_report(&features);
+
+        assert_eq!(report.uncovered(), vec![0, 2]);
+        assert_eq!(report.covered(), array![false, true, false]);
+        assert!((report.coverage() - 1.0 / 3.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,368 @@
+//! Labelled boolean datasets with metadata
+//!
+//! A [`Dataset`] keeps features, labels, feature names, class names and the
+//! encoding that produced the boolean features together, so they cannot drift
+//! apart as data moves between training, evaluation and the command line. It
+//! implements [`AsBoolSamples`] and can be passed anywhere features are
+//! accepted.
+//!
+//! [`balance`] evens out the class counts of imbalanced datasets, and
+//! [`DatasetStats`] summarizes a dataset for [`TsetlinMachine::auto`].
+
+use crate::augment::BitFlip;
+use crate::config::Binarization;
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2, Axis};
+use rand::Rng;
+
+/// Boolean features with labels and descriptive metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    /// Feature matrix (samples x features)
+    features: Array2<bool>,
+    /// Label of each sample
+    labels: Array1<bool>,
+    /// Name of each feature
+    feature_names: Vec<String>,
+    /// Names of the negative and positive class
+    class_names: [String; 2],
+    /// Encoding that produced the boolean features from raw data
+    encoding: Binarization,
+}
+
+impl Dataset {
+    /// Bundle features and labels
+    ///
+    /// Features are named `x0`, `x1`, ... and the classes `negative` and
+    /// `positive` until other names are given.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::dataset::Dataset;
+    ///
+    /// let dataset = Dataset::new(array![[true, false], [false, true]], array![true, false])
+    ///     .with_feature_names(["rain", "sun"])
+    ///     .with_class_names("dry", "wet");
+    /// assert_eq!(dataset.feature_names()[1], "sun");
+    /// assert_eq!(dataset.class_name(true), "wet");
+    /// ```
+    pub fn new(features: Array2<bool>, labels: Array1<bool>) -> Self {
+        assert_eq!(
+            features.nrows(),
+            labels.len(),
+            "Features and labels must have the same number of samples"
+        );
+
+        let feature_names = (0..features.ncols()).map(|i| format!("x{i}")).collect();
+        Self {
+            features,
+            labels,
+            feature_names,
+            class_names: ["negative".to_string(), "positive".to_string()],
+            encoding: Binarization::None,
+        }
+    }
+
+    /// Name the features
+    pub fn with_feature_names<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        assert_eq!(
+            names.len(),
+            self.features.ncols(),
+            "There must be one name per feature"
+        );
+        self.feature_names = names;
+        self
+    }
+
+    /// Name the negative and positive class
+    pub fn with_class_names(mut self, negative: impl Into<String>, positive: impl Into<String>) -> Self {
+        self.class_names = [negative.into(), positive.into()];
+        self
+    }
+
+    /// Record the encoding that produced the boolean features
+    pub fn with_encoding(mut self, encoding: Binarization) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Feature matrix (samples x features)
+    pub fn features(&self) -> &Array2<bool> {
+        &self.features
+    }
+
+    /// Label of each sample
+    pub fn labels(&self) -> &Array1<bool> {
+        &self.labels
+    }
+
+    /// Name of each feature
+    pub fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    /// Names of the negative and positive class
+    pub fn class_names(&self) -> &[String; 2] {
+        &self.class_names
+    }
+
+    /// Name of the class of a label
+    pub fn class_name(&self, label: bool) -> &str {
+        &self.class_names[usize::from(label)]
+    }
+
+    /// Encoding that produced the boolean features
+    pub fn encoding(&self) -> Binarization {
+        self.encoding
+    }
+
+    /// Number of samples
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Whether the dataset has no samples
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Samples at the given indices, keeping all metadata
+    pub fn select(&self, indices: &[usize]) -> Dataset {
+        Dataset {
+            features: self.features.select(Axis(0), indices),
+            labels: self.labels.select(Axis(0), indices),
+            ..self.clone()
+        }
+    }
+
+    /// Number of samples of each class as `(negative, positive)`
+    pub fn class_counts(&self) -> (usize, usize) {
+        let positive = self.labels.iter().filter(|&&label| label).count();
+        (self.len() - positive, positive)
+    }
+
+    /// Size and class balance of the dataset
+    pub fn stats(&self) -> DatasetStats {
+        DatasetStats::from_labels(&self.labels)
+    }
+}
+
+/// Size and class balance of a training set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatasetStats {
+    /// Number of training samples
+    pub num_samples: usize,
+    /// Fraction of positive samples
+    pub positive_fraction: f64,
+}
+
+impl DatasetStats {
+    /// Statistics of a label vector
+    pub fn from_labels(labels: &Array1<bool>) -> Self {
+        let positive = labels.iter().filter(|&&label| label).count();
+        Self {
+            num_samples: labels.len(),
+            positive_fraction: if labels.is_empty() {
+                0.5
+            } else {
+                positive as f64 / labels.len() as f64
+            },
+        }
+    }
+}
+
+/// How [`balance`] evens out the class counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Balancing {
+    /// Add randomly drawn copies of minority samples until the classes match,
+    /// flipping each bit of a copy with probability `jitter`
+    Oversample {
+        /// Bit-flip probability applied to the added copies
+        jitter: f64,
+    },
+    /// Keep a random subset of the majority class as large as the minority
+    Undersample,
+}
+
+/// Return a dataset with the same number of samples in both classes
+///
+/// Oversampling keeps every original sample and appends jittered copies of
+/// the minority class; undersampling keeps the minority class and a random
+/// part of the majority class, in their original order. Metadata is kept.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use rand::SeedableRng;
+/// use tsetlin::dataset::{balance, Balancing, Dataset};
+///
+/// let dataset = Dataset::new(
+///     array![[true, false], [false, false], [false, true], [true, true]],
+///     array![true, false, false, false],
+/// );
+/// let mut rng = tsetlin::rng::TsetlinRng::seed_from_u64(0);
+///
+/// let over = balance(&dataset, Balancing::Oversample { jitter: 0.05 }, &mut rng);
+/// assert_eq!(over.class_counts(), (3, 3));
+/// let under = balance(&dataset, Balancing::Undersample, &mut rng);
+/// assert_eq!(under.class_counts(), (1, 1));
+/// ```
+pub fn balance<R: Rng>(dataset: &Dataset, strategy: Balancing, rng: &mut R) -> Dataset {
+    let (negative, positive) = dataset.class_counts();
+    assert!(negative > 0 && positive > 0, "Both classes need at least one sample");
+
+    let minority_label = positive < negative;
+    let (minority, majority): (Vec<usize>, Vec<usize>) =
+        (0..dataset.len()).partition(|&i| dataset.labels[i] == minority_label);
+
+    match strategy {
+        Balancing::Oversample { jitter } => {
+            let flip = BitFlip::new(jitter);
+            let mut features = dataset.features.clone();
+            let mut labels = dataset.labels.to_vec();
+            for _ in minority.len()..majority.len() {
+                let source = minority[crate::rng::gen_index(rng, minority.len())];
+                let mut sample = dataset.features.row(source).to_vec();
+                flip.flip_sample(&mut sample, rng);
+                features
+                    .push_row(Array1::from(sample).view())
+                    .expect("sample has one value per feature");
+                labels.push(minority_label);
+            }
+            Dataset {
+                features,
+                labels: Array1::from(labels),
+                ..dataset.clone()
+            }
+        }
+        Balancing::Undersample => {
+            let mut kept: Vec<usize> =
+                rand::seq::index::sample(rng, majority.len(), minority.len())
+                    .into_iter()
+                    .map(|i| majority[i])
+                    .chain(minority)
+                    .collect();
+            kept.sort_unstable();
+            dataset.select(&kept)
+        }
+    }
+}
+
+impl AsBoolSamples for Dataset {
+    fn num_samples(&self) -> usize {
+        self.features.nrows()
+    }
+
+    fn num_features(&self) -> usize {
+        self.features.ncols()
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        self.features.sample_into(index, buffer);
+    }
+
+    fn to_array(&self) -> Array2<bool> {
+        self.features.clone()
+    }
+}
+
+impl TsetlinMachine {
+    /// Train on a dataset's features and labels
+    pub fn fit_dataset(&mut self, dataset: &Dataset, epochs: usize) {
+        self.fit(dataset.features(), dataset.labels(), epochs);
+    }
+
+    /// Accuracy on a dataset
+    pub fn evaluate_dataset(&self, dataset: &Dataset) -> f64 {
+        self.evaluate(dataset.features(), dataset.labels())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use rand::SeedableRng;
+
+    fn dataset() -> Dataset {
+        Dataset::new(
+            array![[true, false], [false, true], [true, true]],
+            array![true, false, true],
+        )
+    }
+
+    #[test]
+    fn test_defaults_and_metadata() {
+        let dataset = dataset().with_encoding(Binarization::Thermometer { bins: 4 });
+
+        assert_eq!(dataset.feature_names(), &["x0", "x1"]);
+        assert_eq!(dataset.class_name(false), "negative");
+        assert_eq!(dataset.encoding(), Binarization::Thermometer { bins: 4 });
+        assert_eq!(dataset.class_counts(), (1, 2));
+        assert_eq!(dataset.stats(), DatasetStats { num_samples: 3, positive_fraction: 2.0 / 3.0 });
+    }
+
+    #[test]
+    fn test_select_keeps_metadata() {
+        let dataset = dataset().with_feature_names(["a", "b"]);
+        let subset = dataset.select(&[2, 1]);
+
+        assert_eq!(subset.len(), 2);
+        assert_eq!(subset.labels(), &array![true, false]);
+        assert_eq!(subset.feature_names(), dataset.feature_names());
+    }
+
+    #[test]
+    fn test_machine_accepts_dataset() {
+        let dataset = dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        machine.fit_dataset(&dataset, 2);
+
+        assert_eq!(machine.predict(&dataset), machine.predict(dataset.features()));
+        assert_eq!(machine.evaluate_dataset(&dataset), machine.evaluate(&dataset, dataset.labels()));
+    }
+
+    #[test]
+    fn test_balance() {
+        let dataset = Dataset::new(
+            array![[true, true], [false, true], [false, false], [true, false], [false, false]],
+            array![false, true, false, false, false],
+        )
+        .with_feature_names(["a", "b"]);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(5);
+
+        // Without jitter the added copies are exact duplicates of the minority
+        let over = balance(&dataset, Balancing::Oversample { jitter: 0.0 }, &mut rng);
+        assert_eq!(over.class_counts(), (4, 4));
+        assert_eq!(over.features().slice(ndarray::s![..5, ..]), dataset.features());
+        for row in 5..8 {
+            assert_eq!(over.features().row(row), dataset.features().row(1));
+        }
+        assert_eq!(over.feature_names(), dataset.feature_names());
+
+        let under = balance(&dataset, Balancing::Undersample, &mut rng);
+        assert_eq!(under.class_counts(), (1, 1));
+        assert!(under.labels().iter().any(|&label| label));
+    }
+
+    #[test]
+    #[should_panic(expected = "Both classes need at least one sample")]
+    fn test_balance_single_class() {
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(0);
+        let dataset = Dataset::new(array![[true], [false]], array![true, true]);
+        balance(&dataset, Balancing::Undersample, &mut rng);
+    }
+
+    #[test]
+    #[should_panic(expected = "There must be one name per feature")]
+    fn test_wrong_name_count() {
+        dataset().with_feature_names(["only"]);
+    }
+}
@@ -0,0 +1,341 @@
+//! Standard UCI benchmarks from the Tsetlin machine literature
+//!
+//! [`breast_cancer`], [`iris`] and [`connect4`] download their UCI files on
+//! first use, cache them in [`cache_dir`] and return them binarized the way
+//! the benchmark is usually run, so published results can be reproduced.
+//! The crate has no HTTP dependency: downloads run the `curl` command (and
+//! `gzip` for the compressed Connect-4 file). Without network access, place
+//! the files under the names below in the cache directory, or read them with
+//! the `parse_*` functions directly.
+//!
+//! | Loader | Cached file | Samples | Features | Classes |
+//! |---|---|---|---|---|
+//! | `breast_cancer` | `wdbc.data` | 569 | 120 (thermometer, 5 bins) | benign, malignant |
+//! | `iris` | `iris.data` | 150 | 16 (thermometer, 5 bins) | setosa, versicolor, virginica |
+//! | `connect4` | `connect-4.data` | 67 557 | 84 (x and o per cell) | win, loss, draw |
+
+use crate::config::Binarization;
+use crate::dataset::Dataset;
+use ndarray::{Array1, Array2};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// UCI repository directory of the benchmark files
+const UCI_BASE: &str = "https://archive.ics.uci.edu/ml/machine-learning-databases";
+
+/// Error raised when a benchmark cannot be fetched or parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetError {
+    message: String,
+}
+
+impl DatasetError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "benchmark dataset: {}", self.message)
+    }
+}
+
+impl std::error::Error for DatasetError {}
+
+/// Binarized benchmark with class indices
+#[derive(Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    /// Boolean features (samples x features)
+    pub features: Array2<bool>,
+    /// Class index of each sample
+    pub labels: Array1<usize>,
+    /// Name of each class
+    pub class_names: Vec<String>,
+    /// Encoding that produced the boolean features
+    pub encoding: Binarization,
+}
+
+impl Benchmark {
+    /// Number of classes
+    pub fn num_classes(&self) -> usize {
+        self.class_names.len()
+    }
+
+    /// One-vs-rest binary dataset with `positive` as the positive class
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::datasets::parse_iris;
+    ///
+    /// let iris = parse_iris("5.1,3.5,1.4,0.2,Iris-setosa\n6.3,3.3,6.0,2.5,Iris-virginica\n")
+    ///     .unwrap();
+    /// let virginica = iris.binary(2);
+    /// assert_eq!(virginica.class_name(true), "virginica");
+    /// assert_eq!(virginica.class_counts(), (1, 1));
+    /// ```
+    pub fn binary(&self, positive: usize) -> Dataset {
+        assert!(positive < self.num_classes(), "Class index out of range");
+
+        let rest = if self.num_classes() == 2 {
+            self.class_names[1 - positive].clone()
+        } else {
+            format!("not {}", self.class_names[positive])
+        };
+        Dataset::new(self.features.clone(), self.labels.mapv(|label| label == positive))
+            .with_class_names(rest, self.class_names[positive].clone())
+            .with_encoding(self.encoding)
+    }
+}
+
+/// Directory where downloaded benchmark files are kept
+///
+/// `TSETLIN_DATA_DIR` if set, otherwise `tsetlin` under `XDG_CACHE_HOME`,
+/// `~/.cache` or the system temporary directory.
+pub fn cache_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("TSETLIN_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("tsetlin")
+}
+
+/// Wisconsin Diagnostic Breast Cancer, malignant (class 1) against benign
+pub fn breast_cancer() -> Result<Benchmark, DatasetError> {
+    let url = format!("{UCI_BASE}/breast-cancer-wisconsin/wdbc.data");
+    parse_breast_cancer(&read_cached(&cache_dir(), "wdbc.data", &url, false)?)
+}
+
+/// Iris flowers with every measurement thermometer-encoded in 4 bits
+pub fn iris() -> Result<Benchmark, DatasetError> {
+    let url = format!("{UCI_BASE}/iris/iris.data");
+    parse_iris(&read_cached(&cache_dir(), "iris.data", &url, false)?)
+}
+
+/// Connect-4 positions, labelled with the outcome for the first player
+pub fn connect4() -> Result<Benchmark, DatasetError> {
+    let url = format!("{UCI_BASE}/connect-4/connect-4.data.Z");
+    parse_connect4(&read_cached(&cache_dir(), "connect-4.data", &url, true)?)
+}
+
+/// Parse `wdbc.data`: an id, `M`/`B` and 30 measurements per line
+pub fn parse_breast_cancer(text: &str) -> Result<Benchmark, DatasetError> {
+    let classes = ["B", "M"];
+    let (raw, labels) = parse_rows(text, 32, |cells| {
+        let label = class_index(&classes, cells[1])?;
+        Some((cells[2..].to_vec(), label))
+    })?;
+    Ok(encode(raw, labels, &["benign", "malignant"], Binarization::Thermometer { bins: 5 }))
+}
+
+/// Parse `iris.data`: 4 measurements and the species per line
+pub fn parse_iris(text: &str) -> Result<Benchmark, DatasetError> {
+    let classes = ["Iris-setosa", "Iris-versicolor", "Iris-virginica"];
+    let (raw, labels) = parse_rows(text, 5, |cells| {
+        Some((cells[..4].to_vec(), class_index(&classes, cells[4])?))
+    })?;
+    let names = ["setosa", "versicolor", "virginica"];
+    Ok(encode(raw, labels, &names, Binarization::Thermometer { bins: 5 }))
+}
+
+/// Parse `connect-4.data`: 42 cells (`x`, `o` or `b`) and the outcome per line
+///
+/// Every cell becomes two features, "x here" and "o here"; a blank cell
+/// sets neither.
+pub fn parse_connect4(text: &str) -> Result<Benchmark, DatasetError> {
+    let classes = ["win", "loss", "draw"];
+    let mut features = Vec::new();
+    let mut labels = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells: Vec<&str> = line.trim().split(',').collect();
+        let label = (cells.len() == 43).then(|| class_index(&classes, cells[42])).flatten();
+        let label = label.ok_or_else(|| malformed(number))?;
+        for cell in &cells[..42] {
+            match *cell {
+                "x" => features.extend([true, false]),
+                "o" => features.extend([false, true]),
+                "b" => features.extend([false, false]),
+                _ => return Err(malformed(number)),
+            }
+        }
+        labels.push(label);
+    }
+
+    let features = Array2::from_shape_vec((labels.len(), 84), features)
+        .map_err(|error| DatasetError::new(error.to_string()))?;
+    let class_names = classes.iter().map(|name| name.to_string()).collect();
+    let labels = Array1::from(labels);
+    Ok(Benchmark { features, labels, class_names, encoding: Binarization::None })
+}
+
+/// Numeric rows and labels of a comma-separated file with `width` columns per line
+///
+/// `split` picks the numeric cells and the class index of a line.
+fn parse_rows<'a>(
+    text: &'a str,
+    width: usize,
+    split: impl Fn(&[&'a str]) -> Option<(Vec<&'a str>, usize)>,
+) -> Result<(Array2<f64>, Vec<usize>), DatasetError> {
+    let mut values = Vec::new();
+    let mut labels = Vec::new();
+    let mut num_columns = 0;
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells: Vec<&str> = line.trim().split(',').map(str::trim).collect();
+        let (numbers, label) = (cells.len() == width)
+            .then(|| split(&cells))
+            .flatten()
+            .ok_or_else(|| malformed(number))?;
+        for cell in &numbers {
+            values.push(cell.parse::<f64>().map_err(|_| malformed(number))?);
+        }
+        num_columns = numbers.len();
+        labels.push(label);
+    }
+
+    let raw = Array2::from_shape_vec((labels.len(), num_columns), values)
+        .map_err(|error| DatasetError::new(error.to_string()))?;
+    Ok((raw, labels))
+}
+
+/// Binarize raw measurements with the benchmark's default encoding
+fn encode(
+    raw: Array2<f64>,
+    labels: Vec<usize>,
+    names: &[&str],
+    encoding: Binarization,
+) -> Benchmark {
+    let features = match encoding.encoder() {
+        Some(mut encoder) if raw.nrows() > 0 => encoder.fit_transform(&raw),
+        _ => raw.mapv(|value| value != 0.0),
+    };
+    let class_names = names.iter().map(|name| name.to_string()).collect();
+    Benchmark { features, labels: Array1::from(labels), class_names, encoding }
+}
+
+/// Index of `name` among the class labels of the file
+fn class_index(classes: &[&str], name: &str) -> Option<usize> {
+    classes.iter().position(|&class| class == name)
+}
+
+/// Error for an unreadable line
+fn malformed(number: usize) -> DatasetError {
+    DatasetError::new(format!("line {}: unexpected format", number + 1))
+}
+
+/// Contents of `name` in `dir`, downloading it from `url` first if missing
+///
+/// With `compressed`, the download is a `.Z` file that `gzip` unpacks.
+fn read_cached(
+    dir: &Path,
+    name: &str,
+    url: &str,
+    compressed: bool,
+) -> Result<String, DatasetError> {
+    let path = dir.join(name);
+    if !path.exists() {
+        download(&path, url, compressed)?;
+    }
+    std::fs::read_to_string(&path)
+        .map_err(|error| DatasetError::new(format!("cannot read {}: {error}", path.display())))
+}
+
+/// Download `url` to `path` with `curl`, through a temporary file
+fn download(path: &Path, url: &str, compressed: bool) -> Result<(), DatasetError> {
+    let manual = || format!("download {url} to {} manually", path.display());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|error| {
+            DatasetError::new(format!("cannot create {}: {error}", dir.display()))
+        })?;
+    }
+
+    let partial = path.with_extension("part");
+    let status = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--output"])
+        .arg(&partial)
+        .arg(url)
+        .status()
+        .map_err(|error| DatasetError::new(format!("cannot run curl ({error}); {}", manual())))?;
+    if !status.success() {
+        let _ = std::fs::remove_file(&partial);
+        return Err(DatasetError::new(format!("curl failed ({status}); {}", manual())));
+    }
+
+    if compressed {
+        let output = Command::new("gzip")
+            .arg("--decompress")
+            .arg("--stdout")
+            .arg(&partial)
+            .output()
+            .map_err(|error| DatasetError::new(format!("cannot run gzip ({error})")))?;
+        let _ = std::fs::remove_file(&partial);
+        if !output.status.success() {
+            return Err(DatasetError::new(format!("gzip failed; {}", manual())));
+        }
+        std::fs::write(&partial, output.stdout).map_err(|error| {
+            DatasetError::new(format!("cannot write {}: {error}", path.display()))
+        })?;
+    }
+    std::fs::rename(&partial, path)
+        .map_err(|error| DatasetError::new(format!("cannot write {}: {error}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_breast_cancer() {
+        let row = |id: u32, class: &str, value: f64| {
+            let values: Vec<String> = (0..30).map(|i| (value + i as f64).to_string()).collect();
+            format!("{id},{class},{}\n", values.join(","))
+        };
+        let text: String =
+            (0..10).map(|i| row(i, if i % 3 == 0 { "M" } else { "B" }, i as f64)).collect();
+        let cancer = parse_breast_cancer(&text).unwrap();
+
+        assert_eq!(cancer.features.dim(), (10, 120));
+        assert_eq!(cancer.labels.sum(), 4);
+        assert_eq!(cancer.binary(1).class_names(), &["benign", "malignant"]);
+        assert!(parse_breast_cancer("1,X,2\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_connect4() {
+        let mut cells = vec!["b"; 42];
+        cells[0] = "x";
+        cells[1] = "o";
+        let text = format!("{},draw\n\n", cells.join(","));
+        let connect4 = parse_connect4(&text).unwrap();
+
+        assert_eq!(connect4.features.dim(), (1, 84));
+        assert_eq!(connect4.labels, ndarray::array![2]);
+        let set: Vec<usize> = (0..84).filter(|&i| connect4.features[[0, i]]).collect();
+        assert_eq!(set, vec![0, 3]);
+        let error = parse_connect4(&text.replace("draw", "tie")).unwrap_err();
+        assert_eq!(error.to_string(), "benchmark dataset: line 1: unexpected format");
+    }
+
+    #[test]
+    fn test_cached_file_is_not_downloaded() {
+        let dir = std::env::temp_dir().join(format!("tsetlin-datasets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("iris.data"), "5.1,3.5,1.4,0.2,Iris-setosa\n").unwrap();
+
+        let text = read_cached(&dir, "iris.data", "http://invalid.invalid/iris", false).unwrap();
+        let iris = parse_iris(&text).unwrap();
+        assert_eq!((iris.features.nrows(), iris.num_classes()), (1, 3));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,318 @@
+//! Multi-class classification with binary machines
+//!
+//! Instead of the shared-label training of
+//! [`MultiClassTsetlinMachine`](crate::MultiClassTsetlinMachine), these
+//! wrappers split a multi-class problem into binary tasks, each learned by a
+//! copy of a template [`TsetlinMachine`]:
+//!
+//! * [`OneVsRest`] trains one machine per class against all other classes and
+//!   predicts the class whose machine votes highest.
+//! * [`OneVsOne`] trains one machine per pair of classes on the samples of
+//!   those two classes only and predicts the class winning most pairwise
+//!   duels, which tends to work better when each class has little data.
+//!
+//! Both encode labels through [`BinaryTask`].
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use crate::multiclass::argmax;
+use crate::rng::TsetlinRng;
+use ndarray::{Array1, Array2, Axis};
+use rand::{RngCore, SeedableRng};
+
+/// A binary task carved out of multi-class labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinaryTask {
+    /// Class mapped to `true`
+    pub positive: usize,
+    /// Class mapped to `false`, or every other class when `None`
+    pub negative: Option<usize>,
+}
+
+impl BinaryTask {
+    /// Indices of the samples taking part in the task and their binary labels
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::decomposition::BinaryTask;
+    ///
+    /// let labels = array![0, 2, 1, 2];
+    /// let rest = BinaryTask { positive: 2, negative: None };
+    /// assert_eq!(rest.encode(&labels), (vec![0, 1, 2, 3], array![false, true, false, true]));
+    /// let pair = BinaryTask { positive: 1, negative: Some(2) };
+    /// assert_eq!(pair.encode(&labels), (vec![1, 2, 3], array![false, true, false]));
+    /// ```
+    pub fn encode(&self, labels: &Array1<usize>) -> (Vec<usize>, Array1<bool>) {
+        let (indices, binary): (Vec<usize>, Vec<bool>) = labels
+            .iter()
+            .enumerate()
+            .filter(|&(_, &label)| {
+                label == self.positive || self.negative.is_none_or(|negative| label == negative)
+            })
+            .map(|(index, &label)| (index, label == self.positive))
+            .unzip();
+        (indices, Array1::from(binary))
+    }
+
+    /// Train a fresh copy of `template` on the task
+    fn train<S: AsBoolSamples + ?Sized>(
+        &self,
+        template: &TsetlinMachine,
+        seed: u64,
+        features: &S,
+        labels: &Array1<usize>,
+        epochs: usize,
+    ) -> TsetlinMachine {
+        let (indices, binary) = self.encode(labels);
+        assert!(!indices.is_empty(), "Every binary task needs at least one sample");
+
+        let mut machine = template.clone().seed(seed);
+        if self.negative.is_none() {
+            machine.fit(features, &binary, epochs);
+        } else {
+            machine.fit(&features.to_array().select(Axis(0), &indices), &binary, epochs);
+        }
+        machine
+    }
+}
+
+/// Seed of the first member; a seeded template gives member `m` its seed plus `m`
+fn base_seed(template: &TsetlinMachine) -> u64 {
+    template
+        .random_seed()
+        .unwrap_or_else(|| TsetlinRng::from_entropy().next_u64())
+}
+
+/// Check that features and labels fit `num_classes` classes
+fn check_labels<S: AsBoolSamples + ?Sized>(
+    features: &S,
+    labels: &Array1<usize>,
+    num_classes: usize,
+) {
+    assert_eq!(features.num_samples(), labels.len());
+    assert!(
+        labels.iter().all(|&label| label < num_classes),
+        "Labels must be smaller than the number of classes"
+    );
+}
+
+/// One binary machine per class, each against all other classes
+#[derive(Debug, Clone)]
+pub struct OneVsRest {
+    /// Untrained machine copied for every class
+    template: TsetlinMachine,
+    /// Number of classes
+    num_classes: usize,
+    /// Machine of each class (empty before training)
+    machines: Vec<TsetlinMachine>,
+}
+
+impl OneVsRest {
+    /// Wrap copies of `template` for `num_classes` classes
+    pub fn new(template: TsetlinMachine, num_classes: usize) -> Self {
+        assert!(num_classes >= 2, "At least two classes are required");
+
+        Self { template, num_classes, machines: Vec::new() }
+    }
+
+    /// Train one machine per class for `epochs` epochs
+    pub fn fit<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<usize>,
+        epochs: usize,
+    ) {
+        check_labels(features, labels, self.num_classes);
+
+        let seed = base_seed(&self.template);
+        self.machines = (0..self.num_classes)
+            .map(|class| {
+                let task = BinaryTask { positive: class, negative: None };
+                let seed = seed.wrapping_add(class as u64);
+                task.train(&self.template, seed, features, labels, epochs)
+            })
+            .collect();
+    }
+
+    /// Vote sum of every class's machine (samples x classes)
+    pub fn predict_votes<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array2<i32> {
+        assert!(!self.machines.is_empty(), "OneVsRest must be trained before prediction");
+
+        let mut votes = Array2::zeros((features.num_samples(), self.num_classes));
+        for (class, machine) in self.machines.iter().enumerate() {
+            votes.column_mut(class).assign(&machine.predict_votes(features));
+        }
+        votes
+    }
+
+    /// Class with the highest vote, preferring the lowest index on ties
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<usize> {
+        self.predict_votes(features).rows().into_iter().map(argmax).collect()
+    }
+
+    /// Fraction of samples classified correctly
+    pub fn evaluate<S: AsBoolSamples + ?Sized>(&self, features: &S, labels: &Array1<usize>) -> f64 {
+        accuracy(&self.predict(features), labels)
+    }
+
+    /// Machine of each class
+    pub fn machines(&self) -> &[TsetlinMachine] {
+        &self.machines
+    }
+
+    /// Number of classes
+    pub fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+}
+
+/// One binary machine per pair of classes, combined by tournament voting
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::decomposition::OneVsOne;
+/// use tsetlin::TsetlinMachine;
+///
+/// let features = array![[true, false], [false, true], [true, true], [false, false]];
+/// let labels = array![0, 1, 2, 0];
+/// let mut machine = OneVsOne::new(TsetlinMachine::with_defaults(2, 4).seed(1), 3);
+/// machine.fit(&features, &labels, 5);
+/// assert_eq!(machine.pairs(), &[(0, 1), (0, 2), (1, 2)]);
+/// assert_eq!(machine.predict(&features).len(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OneVsOne {
+    /// Untrained machine copied for every pair
+    template: TsetlinMachine,
+    /// Number of classes
+    num_classes: usize,
+    /// Class pairs `(a, b)` with `a < b`; the machine of a pair predicts `a`
+    pairs: Vec<(usize, usize)>,
+    /// Machine of each pair (empty before training)
+    machines: Vec<TsetlinMachine>,
+}
+
+impl OneVsOne {
+    /// Wrap copies of `template` for every pair of `num_classes` classes
+    pub fn new(template: TsetlinMachine, num_classes: usize) -> Self {
+        assert!(num_classes >= 2, "At least two classes are required");
+
+        let pairs = (0..num_classes)
+            .flat_map(|a| (a + 1..num_classes).map(move |b| (a, b)))
+            .collect();
+        Self { template, num_classes, pairs, machines: Vec::new() }
+    }
+
+    /// Train one machine per pair for `epochs` epochs on that pair's samples
+    pub fn fit<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<usize>,
+        epochs: usize,
+    ) {
+        check_labels(features, labels, self.num_classes);
+
+        let seed = base_seed(&self.template);
+        self.machines = self
+            .pairs
+            .iter()
+            .enumerate()
+            .map(|(index, &(a, b))| {
+                let task = BinaryTask { positive: a, negative: Some(b) };
+                let seed = seed.wrapping_add(index as u64);
+                task.train(&self.template, seed, features, labels, epochs)
+            })
+            .collect();
+    }
+
+    /// Duels won by every class (samples x classes)
+    pub fn predict_wins<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array2<i32> {
+        assert!(!self.machines.is_empty(), "OneVsOne must be trained before prediction");
+
+        let mut wins = Array2::zeros((features.num_samples(), self.num_classes));
+        for (&(a, b), machine) in self.pairs.iter().zip(&self.machines) {
+            for (mut row, first) in wins.rows_mut().into_iter().zip(machine.predict(features)) {
+                row[if first { a } else { b }] += 1;
+            }
+        }
+        wins
+    }
+
+    /// Class winning the most duels, preferring the lowest index on ties
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<usize> {
+        self.predict_wins(features).rows().into_iter().map(argmax).collect()
+    }
+
+    /// Fraction of samples classified correctly
+    pub fn evaluate<S: AsBoolSamples + ?Sized>(&self, features: &S, labels: &Array1<usize>) -> f64 {
+        accuracy(&self.predict(features), labels)
+    }
+
+    /// Class pairs in the order of `machines()`
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+
+    /// Machine of each pair
+    pub fn machines(&self) -> &[TsetlinMachine] {
+        &self.machines
+    }
+
+    /// Number of classes
+    pub fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+}
+
+/// Fraction of predictions equal to the labels
+fn accuracy(predictions: &Array1<usize>, labels: &Array1<usize>) -> f64 {
+    let correct = predictions.iter().zip(labels).filter(|(p, l)| p == l).count();
+    correct as f64 / labels.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::copier;
+    use ndarray::array;
+
+    #[test]
+    fn test_tournament_voting() {
+        // Pair (0, 1) follows x0, (0, 2) follows x1, (1, 2) follows x2
+        let mut machine = OneVsOne::new(TsetlinMachine::with_defaults(3, 2), 3);
+        machine.machines = vec![copier(3, 0), copier(3, 1), copier(3, 2)];
+
+        let features = array![[true, true, false], [false, true, true], [false, false, false]];
+        let wins = machine.predict_wins(&features);
+        assert_eq!(wins.row(0).to_vec(), vec![2, 0, 1]);
+        assert_eq!(wins.row(1).to_vec(), vec![1, 2, 0]);
+        assert_eq!(wins.row(2).to_vec(), vec![0, 1, 2]);
+        assert_eq!(machine.predict(&features), array![0, 1, 2]);
+        assert_eq!(machine.evaluate(&features, &array![0, 1, 1]), 2.0 / 3.0);
+    }
+
+    #[test]
+    fn test_one_vs_rest_argmax() {
+        let mut machine = OneVsRest::new(TsetlinMachine::with_defaults(3, 2), 3);
+        machine.machines = vec![copier(3, 0), copier(3, 1), copier(3, 2)];
+
+        let features = array![[true, false, false], [false, false, true], [false, false, false]];
+        assert_eq!(machine.predict_votes(&features).row(1).to_vec(), vec![-1, -1, 1]);
+        assert_eq!(machine.predict(&features), array![0, 2, 0]);
+    }
+
+    #[test]
+    fn test_fit_trains_every_pair() {
+        let features = array![[true, false], [false, true], [true, true], [false, false]];
+        let labels = array![0, 1, 2, 2];
+        let mut machine = OneVsOne::new(TsetlinMachine::with_defaults(2, 4).seed(3), 3);
+        machine.fit(&features, &labels, 3);
+
+        assert_eq!(machine.machines().len(), 3);
+        assert_eq!(machine.machines()[0].epochs_trained(), 3);
+        let task = BinaryTask { positive: 0, negative: Some(2) };
+        assert_eq!(task.encode(&labels), (vec![0, 2, 3], array![true, false, false]));
+    }
+}
@@ -0,0 +1,253 @@
+//! Deltas between training checkpoints
+//!
+//! In federated or distributed training the same machine is shipped back and
+//! forth between rounds, yet most automata do not move in a round. A
+//! [`ModelDelta`] records only the automaton states, weights and polarities
+//! that changed, and [`ModelDelta::to_bytes`] stores it compactly.
+
+use crate::clause::Literal;
+use crate::compiled::{read_i32, read_usize, read_varint, write_varint, zigzag, DecodeError};
+use crate::machine::TsetlinMachine;
+
+/// Leading bytes of a serialized delta
+const MAGIC: &[u8; 4] = b"TMD1";
+
+/// Changes that turn one checkpoint of a machine into a later one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelDelta {
+    /// Number of input features of both checkpoints
+    num_features: usize,
+    /// Number of clauses of both checkpoints
+    num_clauses: usize,
+    /// New automaton states as `(clause, literal, state)`
+    states: Vec<(usize, Literal, i32)>,
+    /// New clause weights as `(clause, weight)`
+    weights: Vec<(usize, u32)>,
+    /// New clause polarities as `(clause, polarity)`
+    polarities: Vec<(usize, bool)>,
+}
+
+/// Changes between two checkpoints of the same machine
+///
+/// # Example
+/// ```
+/// use tsetlin::delta::{apply_delta, diff};
+/// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let old = TsetlinMachine::with_defaults(2, 10).seed(1);
+/// let mut new = old.clone();
+/// new.fit(&features, &labels, 5);
+///
+/// let delta = diff(&old, &new);
+/// let mut replica = old.clone();
+/// apply_delta(&mut replica, &delta);
+/// assert_eq!(diff(&replica, &new).num_changes(), 0);
+/// ```
+pub fn diff(old: &TsetlinMachine, new: &TsetlinMachine) -> ModelDelta {
+    assert!(
+        old.num_features() == new.num_features() && old.num_clauses() == new.num_clauses(),
+        "Checkpoints must have the same number of features and clauses"
+    );
+
+    let (old_bank, new_bank) = (old.clause_bank(), new.clause_bank());
+    let mut delta = ModelDelta {
+        num_features: new.num_features(),
+        num_clauses: new.num_clauses(),
+        states: Vec::new(),
+        weights: Vec::new(),
+        polarities: Vec::new(),
+    };
+    for (index, (before, after)) in old_bank.clauses().iter().zip(new_bank.clauses()).enumerate() {
+        for feature in 0..delta.num_features {
+            for negated in [false, true] {
+                let literal = Literal { feature, negated };
+                let state = after.literal_state(literal);
+                if before.literal_state(literal) != state {
+                    delta.states.push((index, literal, state));
+                }
+            }
+        }
+        if old_bank.weights()[index] != new_bank.weights()[index] {
+            delta.weights.push((index, new_bank.weights()[index]));
+        }
+        if old_bank.polarities()[index] != new_bank.polarities()[index] {
+            delta.polarities.push((index, new_bank.polarities()[index]));
+        }
+    }
+    delta
+}
+
+/// Bring a machine at the old checkpoint of a delta to its new checkpoint
+pub fn apply_delta(machine: &mut TsetlinMachine, delta: &ModelDelta) {
+    assert!(
+        machine.num_features() == delta.num_features && machine.num_clauses() == delta.num_clauses,
+        "Delta does not match the machine's number of features and clauses"
+    );
+
+    let bank = machine.clause_bank_mut();
+    for &(index, literal, state) in &delta.states {
+        bank.clause_mut(index).set_literal_state(literal, state);
+    }
+    for &(index, weight) in &delta.weights {
+        bank.set_weight(index, weight);
+    }
+    for &(index, polarity) in &delta.polarities {
+        bank.set_polarity(index, polarity);
+    }
+}
+
+impl ModelDelta {
+    /// Number of changed automata, weights and polarities
+    pub fn num_changes(&self) -> usize {
+        self.states.len() + self.weights.len() + self.polarities.len()
+    }
+
+    /// Whether the checkpoints are identical
+    pub fn is_empty(&self) -> bool {
+        self.num_changes() == 0
+    }
+
+    /// New automaton states as `(clause, literal, state)`
+    pub fn states(&self) -> &[(usize, Literal, i32)] {
+        &self.states
+    }
+
+    /// Serialize the delta as a compact byte string
+    ///
+    /// Changed automata are addressed by their distance from the previous
+    /// change, so a delta costs a few bytes per changed state.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        write_varint(&mut out, self.num_features as u64);
+        write_varint(&mut out, self.num_clauses as u64);
+
+        write_varint(&mut out, self.states.len() as u64);
+        let mut previous = 0;
+        for &(index, literal, state) in &self.states {
+            let position = self.position(index, literal);
+            write_varint(&mut out, position - previous);
+            write_varint(&mut out, zigzag(state));
+            previous = position;
+        }
+
+        write_varint(&mut out, self.weights.len() as u64);
+        for &(index, weight) in &self.weights {
+            write_varint(&mut out, index as u64);
+            write_varint(&mut out, u64::from(weight));
+        }
+
+        write_varint(&mut out, self.polarities.len() as u64);
+        for &(index, polarity) in &self.polarities {
+            write_varint(&mut out, 2 * index as u64 + u64::from(polarity));
+        }
+        out
+    }
+
+    /// Load a delta written by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| DecodeError::new("missing delta header"))?;
+        let mut delta = ModelDelta {
+            num_features: read_usize(&mut reader)?,
+            num_clauses: read_usize(&mut reader)?,
+            states: Vec::new(),
+            weights: Vec::new(),
+            polarities: Vec::new(),
+        };
+        let literals_per_clause = 2 * delta.num_features as u64;
+        let num_positions = literals_per_clause.saturating_mul(delta.num_clauses as u64);
+        let clause_index = |value: u64| -> Result<usize, DecodeError> {
+            usize::try_from(value)
+                .ok()
+                .filter(|&index| index < delta.num_clauses)
+                .ok_or_else(|| DecodeError::new("clause out of range"))
+        };
+
+        let mut position = 0u64;
+        for i in 0..read_usize(&mut reader)? {
+            // Positions strictly increase after the first change
+            let step = read_varint(&mut reader)?;
+            position = position
+                .checked_add(step)
+                .filter(|&next| next < num_positions && (i == 0 || step > 0))
+                .ok_or_else(|| DecodeError::new("automaton out of range"))?;
+            let index = clause_index(position / literals_per_clause)?;
+            let code = position % literals_per_clause;
+            let literal = Literal { feature: (code / 2) as usize, negated: code % 2 == 1 };
+            delta.states.push((index, literal, read_i32(&mut reader)?));
+        }
+
+        for _ in 0..read_usize(&mut reader)? {
+            let index = clause_index(read_varint(&mut reader)?)?;
+            let weight = u32::try_from(read_varint(&mut reader)?)
+                .ok()
+                .filter(|&weight| weight > 0)
+                .ok_or_else(|| DecodeError::new("invalid weight"))?;
+            delta.weights.push((index, weight));
+        }
+
+        for _ in 0..read_usize(&mut reader)? {
+            let code = read_varint(&mut reader)?;
+            delta.polarities.push((clause_index(code / 2)?, code % 2 == 1));
+        }
+
+        if !reader.is_empty() {
+            return Err(DecodeError::new("trailing bytes"));
+        }
+        Ok(delta)
+    }
+
+    /// Position of an automaton in clause-major, feature-major order
+    fn position(&self, index: usize, literal: Literal) -> u64 {
+        ((index * self.num_features + literal.feature) * 2 + usize::from(literal.negated)) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_records_only_changes() {
+        let old = TsetlinMachine::with_defaults(3, 4);
+        let mut new = old.clone();
+        let bank = new.clause_bank_mut();
+        bank.clause_mut(1).set_included(Literal { feature: 2, negated: true }, true);
+        bank.clause_mut(3).set_literal_state(Literal { feature: 0, negated: false }, -40);
+        bank.set_weight(2, 7);
+        bank.set_polarity(0, false);
+
+        let delta = diff(&old, &new);
+        assert_eq!(delta.num_changes(), 4);
+        assert_eq!(delta.states()[0], (1, Literal { feature: 2, negated: true }, 1));
+
+        let restored = ModelDelta::from_bytes(&delta.to_bytes()).unwrap();
+        assert_eq!(restored, delta);
+
+        let mut replica = old.clone();
+        apply_delta(&mut replica, &restored);
+        assert!(diff(&replica, &new).is_empty());
+        assert_eq!(replica.clause_bank().weights(), new.clause_bank().weights());
+    }
+
+    #[test]
+    fn test_corrupt_delta() {
+        let old = TsetlinMachine::with_defaults(2, 2);
+        let mut new = old.clone();
+        new.clause_bank_mut().set_weight(1, 3);
+        let bytes = diff(&old, &new).to_bytes();
+
+        assert!(ModelDelta::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(ModelDelta::from_bytes(b"TMC1").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Delta does not match the machine's number of features and clauses")]
+    fn test_apply_to_other_shape() {
+        let machine = TsetlinMachine::with_defaults(2, 2);
+        let delta = diff(&machine, &machine);
+        apply_delta(&mut TsetlinMachine::with_defaults(3, 2), &delta);
+    }
+}
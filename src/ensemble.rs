@@ -0,0 +1,155 @@
+//! Ensembles of machines trained with different seeds
+//!
+//! Tsetlin machine results vary from run to run, so they are usually
+//! reported as the mean and standard deviation over several seeds.
+//! [`seed_ensemble`] does exactly that and keeps the trained machines, whose
+//! majority vote is often more accurate than any single run.
+
+use crate::config::TsetlinConfig;
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use ndarray::Array1;
+
+/// Machines of one configuration trained with consecutive seeds
+#[derive(Debug, Clone)]
+pub struct SeedEnsemble {
+    /// Trained machines, one per seed
+    pub machines: Vec<TsetlinMachine>,
+    /// Seed of each machine
+    pub seeds: Vec<u64>,
+    /// Evaluation accuracy of each machine
+    pub accuracies: Vec<f64>,
+    /// Mean of `accuracies`
+    pub mean_accuracy: f64,
+    /// Sample standard deviation of `accuracies` (0 for a single seed)
+    pub std_accuracy: f64,
+}
+
+impl SeedEnsemble {
+    /// Majority vote of the machines; ties predict the negative class
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<bool> {
+        let mut positive = Array1::<usize>::zeros(features.num_samples());
+        for machine in &self.machines {
+            for (count, prediction) in positive.iter_mut().zip(machine.predict(features)) {
+                *count += usize::from(prediction);
+            }
+        }
+        positive.mapv(|count| 2 * count > self.machines.len())
+    }
+
+    /// Accuracy of the majority vote
+    pub fn evaluate<S: AsBoolSamples + ?Sized>(&self, features: &S, labels: &Array1<bool>) -> f64 {
+        let predictions = self.predict(features);
+        let correct = predictions.iter().zip(labels).filter(|(p, l)| p == l).count();
+        correct as f64 / labels.len() as f64
+    }
+}
+
+/// Train `config` once per seed and report the spread of the accuracy
+///
+/// Machine `i` uses the seed `config.seed + i` (counting from 0 when the
+/// configuration has no seed) and trains for `config.epochs` epochs.
+///
+/// # Arguments
+/// * `config` - Machine hyperparameters and training schedule
+/// * `n_seeds` - Number of machines to train
+/// * `features` - Training features
+/// * `labels` - Training labels
+/// * `eval_x` - Features the accuracies are measured on
+/// * `eval_y` - Labels the accuracies are measured on
+///
+/// # Example
+/// ```
+/// use tsetlin::{ensemble, generate_xor_dataset, TsetlinConfig};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let config = TsetlinConfig { num_clauses: 10, epochs: 10, ..TsetlinConfig::default() };
+///
+/// let result = ensemble::seed_ensemble(&config, 3, &features, &labels, &features, &labels);
+/// assert_eq!(result.seeds, vec![0, 1, 2]);
+/// println!("accuracy {:.3} +- {:.3}", result.mean_accuracy, result.std_accuracy);
+/// assert_eq!(result.predict(&features).len(), 4);
+/// ```
+pub fn seed_ensemble<S, E>(
+    config: &TsetlinConfig,
+    n_seeds: usize,
+    features: &S,
+    labels: &Array1<bool>,
+    eval_x: &E,
+    eval_y: &Array1<bool>,
+) -> SeedEnsemble
+where
+    S: AsBoolSamples + ?Sized,
+    E: AsBoolSamples + ?Sized,
+{
+    assert!(n_seeds > 0, "Seed ensemble needs at least one seed");
+    assert_eq!(eval_x.num_samples(), eval_y.len(), "Evaluation features and labels must match");
+
+    let base = config.seed.unwrap_or(0);
+    let seeds: Vec<u64> = (0..n_seeds as u64).map(|i| base.wrapping_add(i)).collect();
+    let machines: Vec<TsetlinMachine> = seeds
+        .iter()
+        .map(|&seed| {
+            let config = TsetlinConfig { seed: Some(seed), ..config.clone() };
+            let mut machine = config.build_machine(features.num_features());
+            machine.fit(features, labels, config.epochs);
+            machine
+        })
+        .collect();
+
+    let accuracies: Vec<f64> =
+        machines.iter().map(|machine| machine.evaluate(eval_x, eval_y)).collect();
+    let mean_accuracy = accuracies.iter().sum::<f64>() / n_seeds as f64;
+    let std_accuracy = if n_seeds > 1 {
+        let squares: f64 = accuracies.iter().map(|a| (a - mean_accuracy).powi(2)).sum();
+        (squares / (n_seeds - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    SeedEnsemble { machines, seeds, accuracies, mean_accuracy, std_accuracy }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::copier;
+    use ndarray::array;
+
+    #[test]
+    fn test_majority_vote() {
+        let ensemble = SeedEnsemble {
+            machines: vec![copier(3, 0), copier(3, 1), copier(3, 2)],
+            seeds: vec![0, 1, 2],
+            accuracies: vec![0.5, 0.5, 1.0],
+            mean_accuracy: 2.0 / 3.0,
+            std_accuracy: 0.0,
+        };
+        let features = array![[true, true, false], [true, false, false], [false, true, true]];
+        assert_eq!(ensemble.predict(&features), array![true, false, true]);
+        assert_eq!(ensemble.evaluate(&features, &array![true, true, true]), 2.0 / 3.0);
+
+        let tied = SeedEnsemble { machines: vec![copier(3, 0), copier(3, 1)], ..ensemble };
+        assert_eq!(tied.predict(&features), array![true, false, false]);
+    }
+
+    #[test]
+    fn test_seed_statistics() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let config =
+            TsetlinConfig { num_clauses: 4, epochs: 3, seed: Some(7), ..Default::default() };
+        let result = seed_ensemble(&config, 4, &features, &labels, &features, &labels);
+
+        assert_eq!(result.seeds, vec![7, 8, 9, 10]);
+        assert_eq!(result.machines.len(), 4);
+        let mean = result.accuracies.iter().sum::<f64>() / 4.0;
+        assert!((result.mean_accuracy - mean).abs() < 1e-12);
+        let variance =
+            result.accuracies.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / 3.0;
+        assert!((result.std_accuracy - variance.sqrt()).abs() < 1e-12);
+
+        let single = seed_ensemble(&config, 1, &features, &labels, &features, &labels);
+        assert_eq!(single.std_accuracy, 0.0);
+        assert_eq!(single.accuracies[0], result.accuracies[0]);
+    }
+}
@@ -0,0 +1,420 @@
+//! Reproducible experiment runs
+//!
+//! An [`Experiment`] names a CSV dataset, a [`TsetlinConfig`] (hyperparameters
+//! and binarization) and the seeds to run. Every seed gets its own shuffled
+//! train/test split, encoder fit and training run, so results depend only on
+//! the experiment and the crate version. [`ExperimentResults`] collects the
+//! metrics, timings and model hash of every run; with the `json` feature,
+//! `ExperimentResults::to_json` writes them for comparing crate versions.
+//!
+//! Experiments load from the config TOML format with an extra table:
+//!
+//! ```toml
+//! [experiment]
+//! name = "breast-cancer"
+//! data = "data/breast_cancer.csv"   # relative to the config file
+//! test_fraction = 0.2               # default 0.2
+//! seed = 1                          # first seed, default [training] seed or 0
+//! runs = 5                          # seeds seed, seed + 1, ..., default 1
+//!
+//! [machine]
+//! num_clauses = 100
+//!
+//! [binarization]
+//! method = "thermometer"
+//! ```
+//!
+//! The CSV file holds one sample per line with numeric feature columns, a
+//! 0/1 (or true/false) label in the last column and an optional header. With
+//! binarization `none` the features must be 0/1 as well.
+
+use crate::config::{parse_toml, Binarization, ConfigError, TsetlinConfig};
+use crate::metrics::Metric;
+use crate::rng::{gen_index, TsetlinRng};
+use ndarray::{Array1, Array2, Axis};
+use rand::SeedableRng;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Error raised when an experiment cannot run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExperimentError {
+    message: String,
+}
+
+impl ExperimentError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "experiment failed: {}", self.message)
+    }
+}
+
+impl std::error::Error for ExperimentError {}
+
+/// Dataset, configuration and seeds of a reproducible experiment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Experiment {
+    /// Name recorded in the results
+    pub name: String,
+    /// CSV file with numeric features and a trailing label column
+    pub data: PathBuf,
+    /// Fraction of samples held out for testing in every run
+    pub test_fraction: f64,
+    /// Seed of every run; each one sets the split and the machine seed
+    pub seeds: Vec<u64>,
+    /// Hyperparameters, training schedule and binarization
+    pub config: TsetlinConfig,
+}
+
+impl Experiment {
+    /// Single-seed experiment holding out 20% of `data` for testing
+    pub fn new(name: impl Into<String>, data: impl Into<PathBuf>, config: TsetlinConfig) -> Self {
+        let seeds = vec![config.seed.unwrap_or(0)];
+        Self { name: name.into(), data: data.into(), test_fraction: 0.2, seeds, config }
+    }
+
+    /// Load an experiment file; a relative `data` path starts at the file's directory
+    pub fn from_toml(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| ConfigError::new(format!("cannot read {}: {error}", path.display())))?;
+        let mut experiment = Self::from_toml_str(&text)?;
+        if let Some(directory) = path.parent() {
+            experiment.data = directory.join(&experiment.data);
+        }
+        Ok(experiment)
+    }
+
+    /// Parse an experiment from TOML text
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::experiments::Experiment;
+    ///
+    /// let experiment = Experiment::from_toml_str(
+    ///     "[experiment]\ndata = \"train.csv\"\nseed = 3\nruns = 2\n\n[machine]\nnum_clauses = 20\n",
+    /// ).unwrap();
+    /// assert_eq!(experiment.seeds, vec![3, 4]);
+    /// assert_eq!(experiment.config.num_clauses, 20);
+    /// ```
+    pub fn from_toml_str(text: &str) -> Result<Self, ConfigError> {
+        let mut tables = parse_toml(text)?;
+        let mut table = tables
+            .remove("experiment")
+            .ok_or_else(|| ConfigError::new("missing [experiment] table"))?;
+        let config = TsetlinConfig::from_tables(tables)?;
+
+        let data = match table.take("data")? {
+            Some(value) => value.as_str("data")?.to_string(),
+            None => return Err(ConfigError::new("`data` is required in [experiment]")),
+        };
+        let name = match table.take("name")? {
+            Some(value) => value.as_str("name")?.to_string(),
+            None => "experiment".to_string(),
+        };
+        let mut experiment = Self::new(name, data, config);
+        if let Some(value) = table.take("test_fraction")? {
+            experiment.test_fraction = value.as_f64("test_fraction")?;
+        }
+        let first = match table.take("seed")? {
+            Some(value) => value.as_usize("seed")? as u64,
+            None => experiment.seeds[0],
+        };
+        let runs = match table.take("runs")? {
+            Some(value) => value.as_usize("runs")?,
+            None => 1,
+        };
+        table.finish()?;
+
+        experiment.seeds = (first..).take(runs).collect();
+        experiment.validate()?;
+        Ok(experiment)
+    }
+
+    /// Check the experiment settings and the configuration
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.test_fraction > 0.0 && self.test_fraction < 1.0) {
+            return Err(ConfigError::new("`test_fraction` must be between 0 and 1"));
+        }
+        if self.seeds.is_empty() {
+            return Err(ConfigError::new("`runs` must be positive"));
+        }
+        self.config.validate()
+    }
+
+    /// Load the dataset and run every seed
+    pub fn run(&self) -> Result<ExperimentResults, ExperimentError> {
+        let (features, labels) = read_csv(&self.data)?;
+        self.run_on(&features, &labels)
+    }
+
+    /// Run every seed on in-memory raw features and labels
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::{Array1, Array2};
+    /// use tsetlin::experiments::Experiment;
+    /// use tsetlin::TsetlinConfig;
+    ///
+    /// let features = Array2::from_shape_fn((40, 2), |(i, j)| ((i >> j) & 1) as f64);
+    /// let labels = Array1::from_shape_fn(40, |i| i % 2 == 1);
+    /// let mut experiment = Experiment::new("parity", "in-memory", TsetlinConfig::default());
+    /// experiment.seeds = vec![1, 2];
+    ///
+    /// let results = experiment.run_on(&features, &labels).unwrap();
+    /// assert_eq!(results.runs.len(), 2);
+    /// assert_eq!(results.num_test, 8);
+    /// ```
+    pub fn run_on(
+        &self,
+        features: &Array2<f64>,
+        labels: &Array1<bool>,
+    ) -> Result<ExperimentResults, ExperimentError> {
+        self.validate().map_err(|error| ExperimentError::new(error.to_string()))?;
+        assert_eq!(features.nrows(), labels.len());
+        let num_samples = labels.len();
+        let num_test = ((num_samples as f64 * self.test_fraction).round() as usize).max(1);
+        if num_test >= num_samples {
+            return Err(ExperimentError::new("too few samples for a train/test split"));
+        }
+        if self.config.binarization == Binarization::None
+            && features.iter().any(|&value| value != 0.0 && value != 1.0)
+        {
+            return Err(ExperimentError::new("features must be 0/1 without binarization"));
+        }
+
+        let mut runs = Vec::with_capacity(self.seeds.len());
+        let mut num_features = features.ncols();
+        for &seed in &self.seeds {
+            let (test, train) = shuffled_split(num_samples, num_test, seed);
+            let (train_features, test_features) = self.encode(features, &train, &test);
+            num_features = train_features.ncols();
+            let train_labels = labels.select(Axis(0), &train);
+            let test_labels = labels.select(Axis(0), &test);
+
+            let mut config = self.config.clone();
+            config.seed = Some(seed);
+            let mut machine = config.build_machine(num_features);
+            let start = Instant::now();
+            machine.fit(&train_features, &train_labels, config.epochs);
+            let fit_seconds = start.elapsed().as_secs_f64();
+
+            let start = Instant::now();
+            let predictions = machine.predict(&test_features);
+            let predict_seconds = start.elapsed().as_secs_f64();
+
+            runs.push(RunRecord {
+                seed,
+                train_accuracy: machine.evaluate(&train_features, &train_labels),
+                test_accuracy: Metric::Accuracy.score(&predictions, &test_labels),
+                test_f1: Metric::F1.score(&predictions, &test_labels),
+                test_balanced_accuracy: Metric::BalancedAccuracy.score(&predictions, &test_labels),
+                fit_seconds,
+                predict_seconds,
+                included_literals: machine.num_included_literals(),
+                model_hash: machine.state_hash(),
+            });
+        }
+
+        Ok(ExperimentResults {
+            name: self.name.clone(),
+            crate_version: env!("CARGO_PKG_VERSION"),
+            data: self.data.display().to_string(),
+            config: self.config.clone(),
+            num_samples,
+            num_features,
+            num_test,
+            runs,
+        })
+    }
+
+    /// Boolean train and test features of one split
+    fn encode(
+        &self,
+        features: &Array2<f64>,
+        train: &[usize],
+        test: &[usize],
+    ) -> (Array2<bool>, Array2<bool>) {
+        let train_raw = features.select(Axis(0), train);
+        let test_raw = features.select(Axis(0), test);
+        match self.config.binarization.encoder() {
+            Some(mut encoder) => (encoder.fit_transform(&train_raw), encoder.transform(&test_raw)),
+            None => (train_raw.mapv(|value| value == 1.0), test_raw.mapv(|value| value == 1.0)),
+        }
+    }
+}
+
+/// Metrics, timings and final model of one seed
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    /// Seed of the split and the machine
+    pub seed: u64,
+    /// Accuracy on the training split
+    pub train_accuracy: f64,
+    /// Accuracy on the test split
+    pub test_accuracy: f64,
+    /// Positive-class F1 score on the test split
+    pub test_f1: f64,
+    /// Mean recall of the two classes on the test split
+    pub test_balanced_accuracy: f64,
+    /// Wall-clock seconds spent in `fit`
+    pub fit_seconds: f64,
+    /// Wall-clock seconds spent predicting the test split
+    pub predict_seconds: f64,
+    /// Included literals of the trained machine
+    pub included_literals: usize,
+    /// `state_hash` of the trained machine; equal across crate versions that train identically
+    pub model_hash: u64,
+}
+
+/// Outcome of every run of an experiment
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentResults {
+    /// Experiment name
+    pub name: String,
+    /// Version of this crate that produced the results
+    pub crate_version: &'static str,
+    /// Dataset the runs used
+    pub data: String,
+    /// Hyperparameters, training schedule and binarization
+    pub config: TsetlinConfig,
+    /// Number of samples in the dataset
+    pub num_samples: usize,
+    /// Number of boolean features after binarization
+    pub num_features: usize,
+    /// Number of samples held out in each run
+    pub num_test: usize,
+    /// Record of every seed, in the experiment's order
+    pub runs: Vec<RunRecord>,
+}
+
+impl ExperimentResults {
+    /// Mean and standard deviation of a per-run value
+    pub fn mean_std(&self, value: impl Fn(&RunRecord) -> f64) -> (f64, f64) {
+        let values: Vec<f64> = self.runs.iter().map(value).collect();
+        let count = values.len().max(1) as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count;
+        (mean, variance.sqrt())
+    }
+}
+
+/// Split `0..num_samples` into `num_test` test indices and the rest, shuffled by `seed`
+///
+/// Uses `gen_index` so the split is the same on every platform.
+fn shuffled_split(num_samples: usize, num_test: usize, seed: u64) -> (Vec<usize>, Vec<usize>) {
+    let mut rng = TsetlinRng::seed_from_u64(seed);
+    let mut indices: Vec<usize> = (0..num_samples).collect();
+    for i in (1..num_samples).rev() {
+        indices.swap(i, gen_index(&mut rng, i + 1));
+    }
+    let train = indices.split_off(num_test);
+    (indices, train)
+}
+
+/// Read numeric features and a trailing boolean label column
+fn read_csv(path: &Path) -> Result<(Array2<f64>, Array1<bool>), ExperimentError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| ExperimentError::new(format!("cannot read {}: {error}", path.display())))?;
+    let at_line = |number: usize, message: &str| {
+        ExperimentError::new(format!("{}:{}: {message}", path.display(), number + 1))
+    };
+
+    let mut values = Vec::new();
+    let mut labels = Vec::new();
+    let mut width = None;
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells: Vec<&str> = line.trim().split(',').map(str::trim).collect();
+        let (label, row) = cells.split_last().expect("split yields at least one cell");
+        let row: Result<Vec<f64>, _> = row.iter().map(|cell| cell.parse::<f64>()).collect();
+        let label = match *label {
+            "1" | "true" => Some(true),
+            "0" | "false" => Some(false),
+            _ => None,
+        };
+        let (row, label) = match (row, label) {
+            (Ok(row), Some(label)) => (row, label),
+            // A non-numeric first line is a header
+            _ if number == 0 => continue,
+            _ => return Err(at_line(number, "expected numeric features and a 0/1 label")),
+        };
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(at_line(number, "inconsistent number of columns"));
+        }
+        values.extend(row);
+        labels.push(label);
+    }
+
+    let width = width.filter(|&width| width > 0).ok_or_else(|| {
+        ExperimentError::new(format!("{} has no samples with features", path.display()))
+    })?;
+    let features = Array2::from_shape_vec((labels.len(), width), values)
+        .map_err(|error| ExperimentError::new(error.to_string()))?;
+    Ok((features, Array1::from(labels)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_are_reproducible() {
+        let features = Array2::from_shape_fn((30, 3), |(i, j)| ((i * 7 + j * 5) % 11) as f64);
+        let labels = Array1::from_shape_fn(30, |i| (i * 7) % 11 > 5);
+        let config = TsetlinConfig {
+            num_clauses: 10,
+            epochs: 3,
+            binarization: Binarization::Thermometer { bins: 4 },
+            ..Default::default()
+        };
+        let mut experiment = Experiment::new("synthetic", "in-memory", config);
+        experiment.seeds = vec![5, 6];
+        experiment.test_fraction = 0.3;
+
+        let results = experiment.run_on(&features, &labels).unwrap();
+        assert_eq!((results.num_samples, results.num_features, results.num_test), (30, 9, 9));
+        let again = experiment.run_on(&features, &labels).unwrap();
+        let hashes = |results: &ExperimentResults| -> Vec<u64> {
+            results.runs.iter().map(|run| run.model_hash).collect()
+        };
+        assert_eq!(hashes(&results), hashes(&again));
+        let (mean, std) = results.mean_std(|run| run.test_accuracy);
+        assert!((0.0..=1.0).contains(&mean) && std >= 0.0);
+    }
+
+    #[test]
+    fn test_split_covers_every_sample() {
+        let (test, train) = shuffled_split(10, 3, 42);
+        assert_eq!((test.len(), train.len()), (3, 7));
+        let mut all = [test.clone(), train].concat();
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+        assert_eq!(shuffled_split(10, 3, 42).0, test);
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let name = format!("tsetlin-experiment-{}.csv", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, "a,b,label\n0.5,2,1\n-1,3,0\n\n").unwrap();
+        let (features, labels) = read_csv(&path).unwrap();
+        assert_eq!(features, ndarray::array![[0.5, 2.0], [-1.0, 3.0]]);
+        assert_eq!(labels, ndarray::array![true, false]);
+
+        std::fs::write(&path, "0.5,2,1\nx,3,0\n").unwrap();
+        let error = read_csv(&path).unwrap_err();
+        assert!(error.to_string().ends_with(":2: expected numeric features and a 0/1 label"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
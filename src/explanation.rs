@@ -0,0 +1,275 @@
+//! Per-sample explanations of a prediction
+//!
+//! A prediction is the sign of the weighted vote of every clause firing on
+//! the sample. [`Explanation`] lists the trained firing clauses that voted for
+//! the predicted class and those that voted against it, heaviest first, along
+//! with the net margin. Its `Display` output is a short plain-text block meant
+//! for audit logs.
+//!
+//! The same firing clauses also induce a similarity between samples: two
+//! samples are alike when the model recognises the same patterns in both,
+//! which [`TsetlinMachine::similarity`] measures as the Jaccard overlap of
+//! their firing clauses.
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use crate::rules::Rule;
+use std::fmt;
+
+/// A firing clause and its share of the vote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClauseContribution {
+    /// Index of the clause in the clause bank
+    pub clause: usize,
+    /// Vote weight of the clause, negative for negative clauses
+    pub vote: i32,
+    /// Literals of the clause
+    pub rule: Rule,
+}
+
+/// Why a machine predicted what it did for one sample
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// Predicted class
+    pub prediction: bool,
+    /// Net vote sum; the prediction is positive when it is above 0
+    pub margin: i32,
+    /// Heaviest firing clauses voting for the predicted class
+    pub supporting: Vec<ClauseContribution>,
+    /// Heaviest firing clauses voting against the predicted class
+    pub opposing: Vec<ClauseContribution>,
+    /// Number of trained clauses firing for the predicted class
+    pub num_supporting: usize,
+    /// Number of trained clauses firing against the predicted class
+    pub num_opposing: usize,
+}
+
+impl TsetlinMachine {
+    /// Explain the prediction for one sample by its top-k clauses
+    ///
+    /// Firing clauses are ranked by vote weight, ties in clause order.
+    /// Clauses without included literals fire on every input; they count in
+    /// the margin but are not listed.
+    ///
+    /// # Arguments
+    /// * `features` - A single sample
+    /// * `top_k` - Maximum number of clauses listed on each side
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::Literal;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// // One positive clause x0 of weight 2 against an empty negative clause
+    /// let mut machine = TsetlinMachine::with_defaults(2, 2);
+    /// let bank = machine.clause_bank_mut();
+    /// bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+    /// bank.set_weight(0, 2);
+    ///
+    /// let explanation = machine.explain(&[true, false], 3);
+    /// assert_eq!(explanation.margin, 1);
+    /// assert_eq!(explanation.supporting[0].rule.to_string(), "x0");
+    /// println!("{explanation}");
+    /// ```
+    pub fn explain(&self, features: &[bool], top_k: usize) -> Explanation {
+        assert_eq!(features.len(), self.num_features());
+
+        let bank = self.clause_bank();
+        let margin = bank.vote(features);
+        let prediction = margin > 0;
+
+        let mut supporting = Vec::new();
+        let mut opposing = Vec::new();
+        let clauses = bank.clauses().iter().zip(bank.polarities());
+        for (index, (clause, &polarity)) in clauses.enumerate() {
+            if clause.num_included() == 0 || !clause.evaluate(features) {
+                continue;
+            }
+            let contribution = ClauseContribution {
+                clause: index,
+                vote: bank.signed_weight(index),
+                rule: Rule { polarity, literals: clause.included_literals() },
+            };
+            if polarity == prediction {
+                supporting.push(contribution);
+            } else {
+                opposing.push(contribution);
+            }
+        }
+        let (num_supporting, num_opposing) = (supporting.len(), opposing.len());
+        for side in [&mut supporting, &mut opposing] {
+            side.sort_by_key(|contribution| std::cmp::Reverse(contribution.vote.abs()));
+            side.truncate(top_k);
+        }
+
+        Explanation { prediction, margin, supporting, opposing, num_supporting, num_opposing }
+    }
+
+    /// Indices of the trained clauses firing on one sample, in clause order
+    fn firing_clauses(&self, features: &[bool]) -> Vec<usize> {
+        assert_eq!(features.len(), self.num_features());
+
+        let clauses = self.clause_bank().clauses().iter().enumerate();
+        clauses
+            .filter(|(_, clause)| clause.num_included() > 0 && clause.evaluate(features))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Similarity of two samples as seen by the model
+    ///
+    /// The Jaccard index of the sets of trained clauses firing on `a` and
+    /// `b`, from 0.0 (no shared clause) to 1.0 (the same clauses). Clauses of
+    /// both polarities count, and clauses without included literals are
+    /// ignored as in [`explain`](Self::explain). Two samples on which no
+    /// trained clause fires are indistinguishable and get 1.0.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::Literal;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let mut machine = TsetlinMachine::with_defaults(2, 2);
+    /// let bank = machine.clause_bank_mut();
+    /// bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+    /// bank.clause_mut(1).set_included(Literal { feature: 1, negated: false }, true);
+    ///
+    /// assert_eq!(machine.similarity(&[true, true], &[true, false]), 0.5);
+    /// ```
+    pub fn similarity(&self, a: &[bool], b: &[bool]) -> f64 {
+        let (a, b) = (self.firing_clauses(a), self.firing_clauses(b));
+        jaccard(&a, &b)
+    }
+
+    /// The `k` samples of `features` most similar to `query`
+    ///
+    /// Returns `(sample index, similarity)` pairs, most similar first and
+    /// ties in sample order, for retrieval or for explaining a prediction by
+    /// the training samples the model treats alike.
+    ///
+    /// # Arguments
+    /// * `query` - A single sample
+    /// * `features` - Candidate samples (samples x features)
+    /// * `k` - Maximum number of neighbours returned
+    pub fn nearest_neighbors<S: AsBoolSamples + ?Sized>(
+        &self,
+        query: &[bool],
+        features: &S,
+        k: usize,
+    ) -> Vec<(usize, f64)> {
+        let firing = self.firing_clauses(query);
+        let mut sample = Vec::with_capacity(self.num_features());
+        let mut neighbors: Vec<(usize, f64)> = (0..features.num_samples())
+            .map(|idx| {
+                features.sample_into(idx, &mut sample);
+                (idx, jaccard(&firing, &self.firing_clauses(&sample)))
+            })
+            .collect();
+        neighbors.sort_by(|x, y| y.1.total_cmp(&x.1));
+        neighbors.truncate(k);
+        neighbors
+    }
+}
+
+/// Jaccard index of two sorted index sets, 1.0 when both are empty
+fn jaccard(a: &[usize], b: &[usize]) -> f64 {
+    let shared = a.iter().filter(|index| b.binary_search(index).is_ok()).count();
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        1.0
+    } else {
+        shared as f64 / union as f64
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let class = if self.prediction { "positive" } else { "negative" };
+        write!(f, "prediction: {class} (margin {:+})", self.margin)?;
+        let sides = [
+            ("for", &self.supporting, self.num_supporting),
+            ("against", &self.opposing, self.num_opposing),
+        ];
+        for (name, contributions, total) in sides {
+            write!(f, "\n{name} ({} of {total} firing clauses):", contributions.len())?;
+            for contribution in contributions {
+                write!(
+                    f,
+                    "\n  {:+} clause {}: {}",
+                    contribution.vote, contribution.clause, contribution.rule
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+
+    /// Positive clauses x1 and x0 (weight 3), negative clauses x0 AND x1 and NOT x1
+    fn machine() -> TsetlinMachine {
+        let x0 = Literal { feature: 0, negated: false };
+        let x1 = Literal { feature: 1, negated: false };
+        let not_x1 = Literal { feature: 1, negated: true };
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(x1, true);
+        bank.clause_mut(1).set_included(x0, true);
+        bank.set_weight(1, 3);
+        bank.clause_mut(2).set_included(x0, true);
+        bank.clause_mut(2).set_included(x1, true);
+        bank.clause_mut(3).set_included(not_x1, true);
+        machine
+    }
+
+    #[test]
+    fn test_contributions_ranked_by_weight() {
+        let machine = machine();
+        let explanation = machine.explain(&[true, true], 5);
+
+        assert!(explanation.prediction);
+        assert_eq!(explanation.margin, 3);
+        assert_eq!(explanation.margin, machine.vote_single(&[true, true]));
+        let clauses: Vec<usize> = explanation.supporting.iter().map(|c| c.clause).collect();
+        assert_eq!(clauses, vec![1, 0]);
+        assert_eq!(explanation.supporting[0].vote, 3);
+        assert_eq!(explanation.opposing[0].vote, -1);
+        assert_eq!(explanation.opposing[0].rule.to_string(), "x0 AND x1");
+
+        let top = machine.explain(&[true, true], 1);
+        assert_eq!(top.supporting.len(), 1);
+        assert_eq!(top.num_supporting, 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let explanation = machine().explain(&[false, true], 2);
+        let expected = "prediction: positive (margin +1)\n\
+                        for (1 of 1 firing clauses):\n  +1 clause 0: x1\n\
+                        against (0 of 0 firing clauses):";
+        assert_eq!(explanation.to_string(), expected);
+    }
+
+    #[test]
+    fn test_similarity_is_clause_overlap() {
+        // Firing clauses: [1, 1] -> {0, 1, 2}, [1, 0] -> {1, 3}, [0, 1] -> {0}, [0, 0] -> {3}
+        let machine = machine();
+        assert_eq!(machine.similarity(&[true, true], &[true, true]), 1.0);
+        assert_eq!(machine.similarity(&[true, true], &[true, false]), 0.25);
+        assert_eq!(machine.similarity(&[false, false], &[true, false]), 0.5);
+        assert_eq!(machine.similarity(&[false, false], &[false, true]), 0.0);
+
+        let untrained = TsetlinMachine::with_defaults(2, 4);
+        assert_eq!(untrained.similarity(&[true, false], &[false, true]), 1.0);
+    }
+
+    #[test]
+    fn test_nearest_neighbors() {
+        let features = ndarray::array![[false, false], [true, false], [false, true], [true, true]];
+        let neighbors = machine().nearest_neighbors(&[true, true], &features, 3);
+        assert_eq!(neighbors, vec![(3, 1.0), (2, 1.0 / 3.0), (1, 0.25)]);
+    }
+}
@@ -0,0 +1,608 @@
+//! Export of trained machines to external formats
+//!
+//! Exporters read the included literals of each trained clause and render
+//! them for other tools. Clauses without included literals are untrained and
+//! left out, matching [`TsetlinMachine::rules`]. Inference exporters fold the
+//! constant votes of those always-firing clauses into a bias term so the
+//! generated code predicts exactly like the machine.
+
+use crate::clause::Literal;
+use crate::compiled::CompiledMachine;
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use crate::rules::{DecisionList, Rule};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Render the learned logic as a Graphviz DOT graph
+///
+/// Each trained clause becomes a node linked to the literals it includes.
+/// Edges of positive clauses are green and edges of negative clauses red.
+/// Features are named `x0`, `x1`, ... unless `feature_names` is given.
+///
+/// # Example
+/// ```
+/// use tsetlin::{export, TsetlinMachine};
+///
+/// let machine = TsetlinMachine::with_defaults(2, 10);
+/// let dot = export::to_dot(&machine, Some(&["temperature", "humidity"]));
+/// assert!(dot.starts_with("digraph tsetlin {"));
+/// ```
+pub fn to_dot(machine: &TsetlinMachine, feature_names: Option<&[&str]>) -> String {
+    check_feature_names(machine, feature_names);
+
+    let bank = machine.clause_bank();
+    let mut dot = String::from("digraph tsetlin {\n    rankdir=LR;\n");
+    let mut used_literals = BTreeSet::new();
+    let mut edges = String::new();
+
+    for (index, (clause, &polarity)) in bank.clauses().iter().zip(bank.polarities()).enumerate() {
+        let literals = clause.included_literals();
+        if literals.is_empty() {
+            continue;
+        }
+
+        let (sign, color) = if polarity { ("+", "forestgreen") } else { ("-", "firebrick") };
+        let weight = match bank.weights()[index] {
+            1 => String::new(),
+            weight => weight.to_string(),
+        };
+        writeln!(
+            dot,
+            "    c{index} [shape=box, color={color}, label=\"clause {index} ({sign}{weight})\"];"
+        )
+        .unwrap();
+        for literal in literals {
+            writeln!(edges, "    c{index} -> {} [color={color}];", literal_node(literal)).unwrap();
+            used_literals.insert(literal);
+        }
+    }
+
+    for literal in used_literals {
+        writeln!(
+            dot,
+            "    {} [shape=ellipse, label=\"{}\"];",
+            literal_node(literal),
+            escape(&literal_label(literal, feature_names))
+        )
+        .unwrap();
+    }
+
+    dot.push_str(&edges);
+    dot.push_str("}\n");
+    dot
+}
+
+/// Generate a self-contained C99 inference routine
+///
+/// The output defines `tsetlin_pack`, which packs a `uint8_t` array of 0/1
+/// features into 32-bit words, plus `tsetlin_vote` and `tsetlin_predict`
+/// operating on packed input. Every trained clause is stored as a pair of
+/// bit masks: features that must be set and features that must be clear.
+///
+/// # Example
+/// ```
+/// use tsetlin::{export, TsetlinMachine};
+///
+/// let machine = TsetlinMachine::with_defaults(40, 10);
+/// let source = export::to_c_source(&machine);
+/// assert!(source.contains("int32_t tsetlin_vote(const uint32_t *input)"));
+/// ```
+pub fn to_c_source(machine: &TsetlinMachine) -> String {
+    let compiled = machine.compile();
+    let (bias, clauses) = (compiled.bias(), compiled.clauses());
+    let num_features = machine.num_features();
+    let num_words = num_features.div_ceil(32).max(1);
+
+    let mut c = String::new();
+    c.push_str("/* Tsetlin machine inference, generated by the tsetlin crate */\n");
+    c.push_str("#include <stdint.h>\n\n");
+    writeln!(c, "#define TSETLIN_NUM_FEATURES {num_features}").unwrap();
+    writeln!(c, "#define TSETLIN_NUM_WORDS {num_words}").unwrap();
+    writeln!(c, "#define TSETLIN_NUM_CLAUSES {}", clauses.len()).unwrap();
+    writeln!(c, "#define TSETLIN_BIAS {bias}\n").unwrap();
+
+    if !clauses.is_empty() {
+        let mask_table = |name: &str, negated: bool| {
+            let mut table = format!(
+                "static const uint32_t {name}[TSETLIN_NUM_CLAUSES][TSETLIN_NUM_WORDS] = {{\n"
+            );
+            for clause in clauses {
+                let mut words = vec![0u32; num_words];
+                for literal in clause.literals().iter().filter(|literal| literal.negated == negated) {
+                    words[literal.feature / 32] |= 1 << (literal.feature % 32);
+                }
+                let words: Vec<String> = words.iter().map(|word| format!("0x{word:08x}u")).collect();
+                writeln!(table, "    {{ {} }},", words.join(", ")).unwrap();
+            }
+            table.push_str("};\n\n");
+            table
+        };
+        c.push_str(&mask_table("tsetlin_include_masks", false));
+        c.push_str(&mask_table("tsetlin_exclude_masks", true));
+
+        let weights: Vec<String> = clauses.iter().map(|clause| clause.weight.to_string()).collect();
+        writeln!(
+            c,
+            "static const int32_t tsetlin_weights[TSETLIN_NUM_CLAUSES] = {{ {} }};\n",
+            weights.join(", ")
+        )
+        .unwrap();
+    }
+
+    c.push_str(
+        "void tsetlin_pack(const uint8_t *features, uint32_t *packed) {\n\
+         \x20   for (int w = 0; w < TSETLIN_NUM_WORDS; w++) {\n\
+         \x20       packed[w] = 0;\n\
+         \x20   }\n\
+         \x20   for (int i = 0; i < TSETLIN_NUM_FEATURES; i++) {\n\
+         \x20       if (features[i]) {\n\
+         \x20           packed[i / 32] |= (uint32_t)1 << (i % 32);\n\
+         \x20       }\n\
+         \x20   }\n\
+         }\n\n",
+    );
+
+    c.push_str("int32_t tsetlin_vote(const uint32_t *input) {\n");
+    c.push_str("    int32_t vote = TSETLIN_BIAS;\n");
+    if clauses.is_empty() {
+        c.push_str("    (void)input;\n");
+    } else {
+        c.push_str(
+            "    for (int c = 0; c < TSETLIN_NUM_CLAUSES; c++) {\n\
+             \x20       int fires = 1;\n\
+             \x20       for (int w = 0; w < TSETLIN_NUM_WORDS; w++) {\n\
+             \x20           if ((input[w] & tsetlin_include_masks[c][w]) != tsetlin_include_masks[c][w]\n\
+             \x20               || (input[w] & tsetlin_exclude_masks[c][w]) != 0) {\n\
+             \x20               fires = 0;\n\
+             \x20               break;\n\
+             \x20           }\n\
+             \x20       }\n\
+             \x20       if (fires) {\n\
+             \x20           vote += tsetlin_weights[c];\n\
+             \x20       }\n\
+             \x20   }\n",
+        );
+    }
+    c.push_str("    return vote;\n}\n\n");
+
+    c.push_str(
+        "int tsetlin_predict(const uint32_t *input) {\n\
+         \x20   return tsetlin_vote(input) > 0;\n\
+         }\n",
+    );
+    c
+}
+
+/// Generate a combinational Verilog module from a compiled machine
+///
+/// The module `tsetlin_machine` takes the features as a bit vector `x`. Each
+/// trained clause is an AND of literal gates (with NOT for negated literals),
+/// and the signed clause votes are summed by a balanced adder tree on top of
+/// the constant bias. The outputs are the signed vote sum and the prediction
+/// (`vote > 0`).
+///
+/// # Example
+/// ```
+/// use tsetlin::{export, TsetlinMachine};
+///
+/// let machine = TsetlinMachine::with_defaults(8, 10);
+/// let verilog = export::to_verilog(&machine.compile());
+/// assert!(verilog.contains("module tsetlin_machine"));
+/// ```
+pub fn to_verilog(compiled: &CompiledMachine) -> String {
+    let clauses = compiled.clauses();
+    let max_magnitude = compiled.bias().unsigned_abs() as u64
+        + clauses.iter().map(|clause| clause.weight.unsigned_abs() as u64).sum::<u64>();
+    // Enough bits for the magnitude plus a sign bit
+    let width = (u64::BITS - max_magnitude.leading_zeros()) as usize + 1;
+    let literal = |value: i64| {
+        if value < 0 {
+            format!("-{width}'sd{}", value.unsigned_abs())
+        } else {
+            format!("{width}'sd{value}")
+        }
+    };
+
+    let mut v = String::new();
+    v.push_str("// Tsetlin machine inference, generated by the tsetlin crate\n");
+    v.push_str("module tsetlin_machine (\n");
+    writeln!(v, "    input  wire [{}:0] x,", compiled.num_features().max(1) - 1).unwrap();
+    writeln!(v, "    output wire signed [{}:0] vote,", width - 1).unwrap();
+    v.push_str("    output wire prediction\n);\n\n");
+
+    // Clause outputs: AND of literal gates
+    for (index, clause) in clauses.iter().enumerate() {
+        let gates: Vec<String> = clause
+            .literals()
+            .iter()
+            .map(|literal| {
+                if literal.negated {
+                    format!("~x[{}]", literal.feature)
+                } else {
+                    format!("x[{}]", literal.feature)
+                }
+            })
+            .collect();
+        writeln!(v, "    wire clause_{index} = {};", gates.join(" & ")).unwrap();
+    }
+    if !clauses.is_empty() {
+        v.push('\n');
+    }
+
+    // Adder tree over the signed clause votes
+    let mut level: Vec<String> = Vec::new();
+    for (index, clause) in clauses.iter().enumerate() {
+        let name = format!("sum_0_{index}");
+        writeln!(
+            v,
+            "    wire signed [{}:0] {name} = clause_{index} ? {} : {};",
+            width - 1,
+            literal(clause.weight as i64),
+            literal(0)
+        )
+        .unwrap();
+        level.push(name);
+    }
+
+    let mut depth = 0;
+    while level.len() > 1 {
+        depth += 1;
+        let mut next = Vec::new();
+        for (index, pair) in level.chunks(2).enumerate() {
+            if let [left, right] = pair {
+                let name = format!("sum_{depth}_{index}");
+                writeln!(v, "    wire signed [{}:0] {name} = {left} + {right};", width - 1).unwrap();
+                next.push(name);
+            } else {
+                next.push(pair[0].clone());
+            }
+        }
+        level = next;
+    }
+
+    let bias = literal(compiled.bias() as i64);
+    match level.first() {
+        Some(root) => writeln!(v, "\n    assign vote = {root} + {bias};").unwrap(),
+        None => writeln!(v, "    assign vote = {bias};").unwrap(),
+    }
+    writeln!(v, "    assign prediction = vote > {};", literal(0)).unwrap();
+    v.push_str("\nendmodule\n");
+    v
+}
+
+/// Generate a SQL expression computing the vote sum of each row
+///
+/// `feature_columns` names the 0/1 column holding each feature, in feature
+/// order, and is inserted verbatim so it may contain quoted identifiers.
+///
+/// # Example
+/// ```
+/// use tsetlin::{export, TsetlinMachine};
+///
+/// let machine = TsetlinMachine::with_defaults(2, 4);
+/// assert_eq!(export::to_sql_vote(&machine, &["a", "b"]), "0");
+/// ```
+pub fn to_sql_vote(machine: &TsetlinMachine, feature_columns: &[&str]) -> String {
+    check_feature_names(machine, Some(feature_columns));
+
+    let compiled = machine.compile();
+    let mut sql = compiled.bias().to_string();
+    for clause in compiled.clauses() {
+        let conditions: Vec<String> = clause
+            .literals()
+            .iter()
+            .map(|literal| {
+                let value = if literal.negated { 0 } else { 1 };
+                format!("{} = {value}", feature_columns[literal.feature])
+            })
+            .collect();
+        write!(
+            sql,
+            "\n  + CASE WHEN {} THEN {} ELSE 0 END",
+            conditions.join(" AND "),
+            clause.weight
+        )
+        .unwrap();
+    }
+    sql
+}
+
+/// Generate a SQL `CASE` expression scoring each row as 1 (positive) or -1
+///
+/// Wraps [`to_sql_vote`] so trained models can score tables directly inside a
+/// data warehouse, e.g. `SELECT id, <expression> AS prediction FROM samples`.
+///
+/// # Example
+/// ```
+/// use tsetlin::{export, TsetlinMachine};
+///
+/// let machine = TsetlinMachine::with_defaults(2, 4);
+/// let sql = export::to_sql_case(&machine, &["a", "b"]);
+/// assert_eq!(sql, "CASE WHEN (0) > 0 THEN 1 ELSE -1 END");
+/// ```
+pub fn to_sql_case(machine: &TsetlinMachine, feature_columns: &[&str]) -> String {
+    format!(
+        "CASE WHEN ({}) > 0 THEN 1 ELSE -1 END",
+        to_sql_vote(machine, feature_columns)
+    )
+}
+
+/// Approximate the machine by a decision list fitted to its own predictions
+///
+/// The trained clauses become candidate rules, each predicting its
+/// polarity. Rules are picked greedily: the candidate with the highest
+/// precision against the machine's predictions on the still uncovered
+/// samples (ties broken by coverage) goes next, as long as it is right more
+/// often than wrong. The default class is the majority prediction among the
+/// samples no rule covers. The list is a global surrogate: `fidelity`
+/// reports how often it agrees with the machine on `features`, and unseen
+/// inputs may disagree more often.
+///
+/// # Example
+/// ```
+/// use tsetlin::clause::Literal;
+/// use tsetlin::{export, TsetlinMachine};
+/// use ndarray::array;
+///
+/// let mut machine = TsetlinMachine::with_defaults(2, 4);
+/// let bank = machine.clause_bank_mut();
+/// bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+/// bank.clause_mut(2).set_included(Literal { feature: 0, negated: true }, true);
+///
+/// let features = array![[true, false], [false, true], [true, true]];
+/// let list = export::to_decision_list(&machine, &features);
+/// assert_eq!(list.fidelity, 1.0);
+/// assert_eq!(list.rules.len(), 2);
+/// println!("{list}");
+/// ```
+pub fn to_decision_list<S: AsBoolSamples + ?Sized>(
+    machine: &TsetlinMachine,
+    features: &S,
+) -> DecisionList {
+    assert_eq!(features.num_features(), machine.num_features());
+
+    let samples: Vec<Vec<bool>> = (0..features.num_samples()).map(|i| features.sample(i)).collect();
+    let targets: Vec<bool> = samples.iter().map(|sample| machine.predict_single(sample)).collect();
+    let mut candidates: Vec<Rule> = machine.rules();
+
+    let mut uncovered: Vec<usize> = (0..samples.len()).collect();
+    let mut rules = Vec::new();
+    while !uncovered.is_empty() {
+        // (correct, covered, candidate) of the most precise useful rule
+        let mut best: Option<(usize, usize, usize)> = None;
+        for (index, rule) in candidates.iter().enumerate() {
+            let covered: Vec<usize> = uncovered
+                .iter()
+                .copied()
+                .filter(|&i| rule.matches(&samples[i]))
+                .collect();
+            let correct = covered.iter().filter(|&&i| targets[i] == rule.polarity).count();
+            if 2 * correct <= covered.len() {
+                continue;
+            }
+            let better = best.is_none_or(|(best_correct, best_covered, _)| {
+                // Compare correct / covered as cross products to stay exact
+                let (precision, best_precision) =
+                    (correct * best_covered, best_correct * covered.len());
+                precision > best_precision
+                    || (precision == best_precision && covered.len() > best_covered)
+            });
+            if better {
+                best = Some((correct, covered.len(), index));
+            }
+        }
+
+        let Some((_, _, index)) = best else { break };
+        let rule = candidates.remove(index);
+        uncovered.retain(|&i| !rule.matches(&samples[i]));
+        rules.push(rule);
+    }
+
+    let positive = uncovered.iter().filter(|&&i| targets[i]).count();
+    let mut list = DecisionList { rules, default: 2 * positive > uncovered.len(), fidelity: 0.0 };
+    let agreeing = samples
+        .iter()
+        .zip(&targets)
+        .filter(|(sample, &target)| list.predict_single(sample) == target)
+        .count();
+    list.fidelity = agreeing as f64 / samples.len().max(1) as f64;
+    list
+}
+
+/// Panic if the feature names do not match the machine's features
+fn check_feature_names(machine: &TsetlinMachine, feature_names: Option<&[&str]>) {
+    if let Some(names) = feature_names {
+        assert_eq!(
+            names.len(),
+            machine.num_features(),
+            "One feature name per feature is required"
+        );
+    }
+}
+
+/// Name of a feature, falling back to `x<index>`
+fn feature_name(feature: usize, feature_names: Option<&[&str]>) -> String {
+    match feature_names {
+        Some(names) => names[feature].to_string(),
+        None => format!("x{feature}"),
+    }
+}
+
+/// Human-readable literal such as `x3` or `NOT x3`
+fn literal_label(literal: Literal, feature_names: Option<&[&str]>) -> String {
+    let name = feature_name(literal.feature, feature_names);
+    if literal.negated {
+        format!("NOT {name}")
+    } else {
+        name
+    }
+}
+
+/// DOT node identifier of a literal
+fn literal_node(literal: Literal) -> String {
+    let suffix = if literal.negated { "n" } else { "p" };
+    format!("l{}{}", literal.feature, suffix)
+}
+
+/// Escape a label for use inside a quoted DOT string
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untrained_dot() {
+        let machine = TsetlinMachine::with_defaults(2, 4);
+        assert_eq!(to_dot(&machine, None), "digraph tsetlin {\n    rankdir=LR;\n}\n");
+    }
+
+    #[test]
+    fn test_trained_dot() {
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        machine
+            .clause_bank_mut()
+            .clause_mut(0)
+            .set_included(Literal { feature: 1, negated: true }, true);
+        machine
+            .clause_bank_mut()
+            .clause_mut(3)
+            .set_included(Literal { feature: 1, negated: true }, true);
+
+        let dot = to_dot(&machine, Some(&["a", "b"]));
+        assert!(dot.contains("c0 [shape=box, color=forestgreen, label=\"clause 0 (+)\"];"));
+        assert!(dot.contains("c3 [shape=box, color=firebrick, label=\"clause 3 (-)\"];"));
+        assert!(dot.contains("l1n [shape=ellipse, label=\"NOT b\"];"));
+        assert!(dot.contains("c0 -> l1n [color=forestgreen];"));
+        assert!(dot.contains("c3 -> l1n [color=firebrick];"));
+        assert!(!dot.contains("c1"));
+    }
+
+    #[test]
+    fn test_c_source() {
+        let mut machine = TsetlinMachine::with_defaults(34, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 33, negated: true }, true);
+
+        let source = to_c_source(&machine);
+        assert!(source.contains("#define TSETLIN_NUM_WORDS 2"));
+        assert!(source.contains("#define TSETLIN_NUM_CLAUSES 1"));
+        assert!(source.contains("#define TSETLIN_BIAS -1"));
+        assert!(source.contains("tsetlin_include_masks[TSETLIN_NUM_CLAUSES][TSETLIN_NUM_WORDS] = {\n    { 0x00000001u, 0x00000000u },"));
+        assert!(source.contains("tsetlin_exclude_masks[TSETLIN_NUM_CLAUSES][TSETLIN_NUM_WORDS] = {\n    { 0x00000000u, 0x00000002u },"));
+        assert!(source.contains("tsetlin_weights[TSETLIN_NUM_CLAUSES] = { 1 };"));
+    }
+
+    #[test]
+    fn test_verilog() {
+        let mut machine = TsetlinMachine::with_defaults(3, 6);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 2, negated: true }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 1, negated: false }, true);
+        bank.clause_mut(4).set_included(Literal { feature: 1, negated: true }, true);
+
+        // Magnitude 3 from clauses plus bias -1 needs three bits and a sign bit
+        let verilog = to_verilog(&machine.compile());
+        assert!(verilog.contains("input  wire [2:0] x,"));
+        assert!(verilog.contains("output wire signed [3:0] vote,"));
+        assert!(verilog.contains("wire clause_0 = x[0] & ~x[2];"));
+        assert!(verilog.contains("wire signed [3:0] sum_0_2 = clause_2 ? -4'sd1 : 4'sd0;"));
+        assert!(verilog.contains("wire signed [3:0] sum_1_0 = sum_0_0 + sum_0_1;"));
+        assert!(verilog.contains("wire signed [3:0] sum_2_0 = sum_1_0 + sum_0_2;"));
+        assert!(verilog.contains("assign vote = sum_2_0 + -4'sd1;"));
+        assert!(verilog.ends_with("endmodule\n"));
+    }
+
+    #[test]
+    fn test_untrained_verilog() {
+        let machine = TsetlinMachine::with_defaults(3, 4);
+        let verilog = to_verilog(&machine.compile());
+        assert!(verilog.contains("assign vote = 1'sd0;"));
+    }
+
+    #[test]
+    fn test_sql_case() {
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 1, negated: true }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 1, negated: false }, true);
+
+        assert_eq!(
+            to_sql_case(&machine, &["age_over_40", "\"has loan\""]),
+            "CASE WHEN (0\n\
+             \x20 + CASE WHEN age_over_40 = 1 AND \"has loan\" = 0 THEN 1 ELSE 0 END\n\
+             \x20 + CASE WHEN \"has loan\" = 1 THEN -1 ELSE 0 END) > 0 THEN 1 ELSE -1 END"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "One feature name per feature is required")]
+    fn test_sql_column_mismatch() {
+        let machine = TsetlinMachine::with_defaults(2, 4);
+        to_sql_case(&machine, &["a"]);
+    }
+
+    #[test]
+    fn test_untrained_c_source_has_no_tables() {
+        let machine = TsetlinMachine::with_defaults(3, 4);
+        let source = to_c_source(&machine);
+        assert!(source.contains("#define TSETLIN_BIAS 0"));
+        assert!(!source.contains("tsetlin_weights"));
+    }
+
+    #[test]
+    fn test_literal_labels() {
+        let literal = Literal { feature: 1, negated: true };
+        assert_eq!(literal_label(literal, None), "NOT x1");
+        assert_eq!(literal_label(literal, Some(&["a", "b"])), "NOT b");
+        assert_eq!(literal_node(literal), "l1n");
+        assert_eq!(escape("say \"hi\""), "say \\\"hi\\\"");
+    }
+
+    #[test]
+    #[should_panic(expected = "One feature name per feature is required")]
+    fn test_feature_name_mismatch() {
+        let machine = TsetlinMachine::with_defaults(2, 4);
+        to_dot(&machine, Some(&["only one"]));
+    }
+
+    #[test]
+    fn test_decision_list_orders_by_precision() {
+        // Vote x0 - 2 * x1: only [true, false] is positive
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 1, negated: false }, true);
+        bank.set_weight(2, 2);
+
+        let features = ndarray::array![[true, false], [false, true], [true, true], [false, false]];
+        let list = to_decision_list(&machine, &features);
+        assert_eq!(list.to_string(), "IF x1 THEN negative\nELSE IF x0 THEN positive\nELSE negative");
+        assert_eq!(list.fidelity, 1.0);
+        assert!(list.predict_single(&[true, false]));
+    }
+
+    #[test]
+    fn test_decision_list_reports_fidelity() {
+        // Vote x0 + x1 - 1 (an AND) cannot be written with the single-literal clauses
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 1, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 0, negated: true }, true);
+
+        let features = ndarray::array![[true, false], [false, true], [true, true], [false, false]];
+        let list = to_decision_list(&machine, &features);
+        assert!(list.rules.is_empty());
+        assert!(!list.default);
+        assert_eq!(list.fidelity, 0.75);
+        assert_eq!(list.to_string(), "negative");
+    }
+}
@@ -0,0 +1,151 @@
+//! Feature selection from clause statistics
+//!
+//! Features that no clause includes do not influence predictions. Recursive
+//! feature elimination trains a machine, drops the features with the fewest
+//! included literals and retrains on the rest until the requested number of
+//! features remains.
+
+use crate::config::TsetlinConfig;
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2, Axis};
+
+/// Outcome of recursive feature elimination
+#[derive(Debug, Clone)]
+pub struct RfeResult {
+    /// Indices of the kept features in the original feature matrix, ascending
+    pub selected: Vec<usize>,
+    /// Machine trained on the selected features only
+    pub machine: TsetlinMachine,
+}
+
+impl RfeResult {
+    /// Keep only the selected columns of a feature matrix
+    pub fn select(&self, features: &Array2<bool>) -> Array2<bool> {
+        features.select(Axis(1), &self.selected)
+    }
+}
+
+/// Recursive feature elimination
+///
+/// Each round trains a machine built from `config` for `config.epochs` epochs
+/// on the remaining features and removes the least-used tenth of them (at
+/// least one feature, never going below the target). Usage is the number of
+/// included literals, plain or negated, over all clauses; ties drop the
+/// feature with the higher index first.
+///
+/// # Arguments
+/// * `config` - Machine hyperparameters and training schedule
+/// * `features` - Training feature matrix (samples x features)
+/// * `labels` - Training labels
+/// * `target_n_features` - Number of features to keep
+///
+/// # Example
+/// ```
+/// use ndarray::{Array1, Array2};
+/// use tsetlin::{feature_selection, TsetlinConfig};
+///
+/// let features = Array2::from_shape_fn((8, 5), |(i, j)| (i >> (j % 3)) & 1 == 1);
+/// let labels = Array1::from_shape_fn(8, |i| i & 1 == 1);
+/// let config = TsetlinConfig { num_clauses: 10, epochs: 5, ..TsetlinConfig::default() };
+///
+/// let result = feature_selection::rfe(&config, &features, &labels, 2);
+/// assert_eq!(result.selected.len(), 2);
+/// assert_eq!(result.machine.num_features(), 2);
+/// ```
+pub fn rfe(
+    config: &TsetlinConfig,
+    features: &Array2<bool>,
+    labels: &Array1<bool>,
+    target_n_features: usize,
+) -> RfeResult {
+    assert!(target_n_features > 0, "At least one feature must be kept");
+    assert!(
+        target_n_features <= features.ncols(),
+        "Cannot keep more features than the data has"
+    );
+
+    let mut selected: Vec<usize> = (0..features.ncols()).collect();
+    loop {
+        let subset = features.select(Axis(1), &selected);
+        let mut machine = config.build_machine(selected.len());
+        machine.fit(&subset, labels, config.epochs);
+
+        if selected.len() == target_n_features {
+            return RfeResult { selected, machine };
+        }
+
+        let excess = selected.len() - target_n_features;
+        let num_dropped = (selected.len() / 10).clamp(1, excess);
+        let usage = machine.feature_usage();
+        let mut order: Vec<usize> = (0..selected.len()).collect();
+        order.sort_by_key(|&i| (usage[i], std::cmp::Reverse(i)));
+
+        let mut dropped: Vec<usize> = order[..num_dropped].to_vec();
+        dropped.sort_unstable();
+        for &i in dropped.iter().rev() {
+            selected.remove(i);
+        }
+    }
+}
+
+impl TsetlinMachine {
+    /// Number of included literals, plain or negated, per feature
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// let machine = TsetlinMachine::with_defaults(3, 10);
+    /// assert_eq!(machine.feature_usage(), vec![0, 0, 0]);
+    /// ```
+    pub fn feature_usage(&self) -> Vec<usize> {
+        let mut usage = vec![0; self.num_features()];
+        for clause in self.clause_bank().clauses() {
+            for literal in clause.included_literals() {
+                usage[literal.feature] += 1;
+            }
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+
+    #[test]
+    fn test_feature_usage() {
+        let mut machine = TsetlinMachine::with_defaults(3, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 2, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 2, negated: true }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 0, negated: false }, true);
+
+        assert_eq!(machine.feature_usage(), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_rfe_keeps_target_count() {
+        let features = Array2::from_shape_fn((6, 12), |(i, j)| (i + j) % 3 == 0);
+        let labels = Array1::from_shape_fn(6, |i| i % 2 == 0);
+        let config = TsetlinConfig {
+            num_clauses: 4,
+            epochs: 2,
+            ..TsetlinConfig::default()
+        };
+
+        let result = rfe(&config, &features, &labels, 3);
+        assert_eq!(result.selected.len(), 3);
+        assert!(result.selected.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(result.select(&features).ncols(), 3);
+        assert_eq!(result.machine.num_features(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot keep more features than the data has")]
+    fn test_rfe_target_too_large() {
+        let features = Array2::from_elem((2, 2), false);
+        let labels = Array1::from_elem(2, false);
+        rfe(&TsetlinConfig::default(), &features, &labels, 3);
+    }
+}
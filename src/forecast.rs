@@ -0,0 +1,204 @@
+//! Time-series forecasting with a regression Tsetlin machine
+//!
+//! A [`Forecaster`] predicts the next value of a series from its last `lags`
+//! values and the `lags - 1` differences between them. Both are
+//! thermometer-encoded with quantile thresholds fitted on the training
+//! series, and a [`RegressionTsetlinMachine`] learns the next value from the
+//! encoded lags. Forecasts further ahead are recursive: each predicted value
+//! is appended to the lags of the next step.
+
+use crate::binarize::{ThermometerEncoder, Transform};
+use crate::regression::RegressionTsetlinMachine;
+use ndarray::{Array1, Array2};
+
+/// Next-step regression machine over thermometer-encoded lags and deltas
+///
+/// # Example
+/// ```
+/// use tsetlin::forecast::Forecaster;
+///
+/// let series: Vec<f64> = (0..60).map(|t| (t % 6) as f64).collect();
+/// let mut forecaster = Forecaster::new(3, 40).seed(1);
+/// forecaster.fit(&series, 30);
+///
+/// let forecast = forecaster.forecast(4);
+/// assert_eq!(forecast.len(), 4);
+/// assert!(forecast.iter().all(|&value| (0.0..=5.0).contains(&value)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Forecaster {
+    /// Number of past values the next value is predicted from
+    lags: usize,
+    /// Quantile bins of the thermometer encoding
+    bins: usize,
+    /// Number of clauses of the regression machine
+    num_clauses: usize,
+    /// Specificity of the regression machine
+    specificity: f64,
+    /// Threshold of the regression machine, or half the clauses if unset
+    threshold: Option<f64>,
+    /// Seed of the regression machine
+    seed: Option<u64>,
+    /// Encoder of lagged values and deltas, fitted by `fit`
+    encoder: ThermometerEncoder,
+    /// Regression machine, built by `fit`
+    machine: Option<RegressionTsetlinMachine>,
+    /// Last `lags` values of the training series
+    history: Vec<f64>,
+}
+
+impl Forecaster {
+    /// Create a forecaster using `lags` past values and `num_clauses` clauses
+    pub fn new(lags: usize, num_clauses: usize) -> Self {
+        assert!(lags > 0, "At least one lag is required");
+
+        Self {
+            lags,
+            bins: 10,
+            num_clauses,
+            specificity: 3.0,
+            threshold: None,
+            seed: None,
+            encoder: ThermometerEncoder::new(10),
+            machine: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Set the number of quantile bins per lag and delta (default: 10)
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.encoder = ThermometerEncoder::new(bins);
+        self.bins = bins;
+        self
+    }
+
+    /// Set the specificity of the regression machine (default: 3.0)
+    pub fn specificity(mut self, specificity: f64) -> Self {
+        self.specificity = specificity;
+        self
+    }
+
+    /// Set the threshold of the regression machine (default: half the clauses)
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Seed the regression machine for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Raw lags and deltas before each position from `lags` on (rows x columns)
+    fn lag_features(&self, series: &[f64]) -> Array2<f64> {
+        let rows = series.len() + 1 - self.lags;
+        Array2::from_shape_fn((rows, 2 * self.lags - 1), |(row, column)| {
+            let window = &series[row..row + self.lags];
+            match column.checked_sub(self.lags) {
+                None => window[column],
+                Some(delta) => window[delta + 1] - window[delta],
+            }
+        })
+    }
+
+    /// Train on a series, replacing any earlier training
+    ///
+    /// Every position after the first `lags` values is one sample. The
+    /// series' last values become the starting point of [`forecast`](Self::forecast).
+    pub fn fit(&mut self, series: &[f64], epochs: usize) {
+        assert!(series.len() > self.lags, "Series must be longer than the number of lags");
+        assert!(series.iter().all(|value| value.is_finite()), "Series values must be finite");
+
+        let raw = self.lag_features(&series[..series.len() - 1]);
+        let features = self.encoder.fit_transform(&raw);
+        let targets = Array1::from(series[self.lags..].to_vec());
+
+        let threshold = self.threshold.unwrap_or((self.num_clauses as f64 / 2.0).max(1.0));
+        let mut machine = RegressionTsetlinMachine::new(
+            features.ncols(),
+            self.num_clauses,
+            self.specificity,
+            threshold,
+        );
+        if let Some(seed) = self.seed {
+            machine = machine.seed(seed);
+        }
+        machine.fit(&features, &targets, epochs);
+
+        self.machine = Some(machine);
+        self.history = series[series.len() - self.lags..].to_vec();
+    }
+
+    /// Predict the value following the last `lags` values of `recent`
+    pub fn predict_next(&self, recent: &[f64]) -> f64 {
+        let machine = self.machine.as_ref().expect("Forecaster must be fitted first");
+        assert!(recent.len() >= self.lags, "At least `lags` recent values are required");
+
+        let window = &recent[recent.len() - self.lags..];
+        let encoded = self.encoder.transform(&self.lag_features(window));
+        machine.predict_single(encoded.row(0).as_slice().expect("Encoded rows are contiguous"))
+    }
+
+    /// Forecast `horizon` steps after `recent`, feeding back each prediction
+    pub fn forecast_from(&self, recent: &[f64], horizon: usize) -> Vec<f64> {
+        let mut values = recent.to_vec();
+        for _ in 0..horizon {
+            let next = self.predict_next(&values);
+            values.push(next);
+        }
+        values.split_off(recent.len())
+    }
+
+    /// Forecast `horizon` steps after the end of the training series
+    pub fn forecast(&self, horizon: usize) -> Vec<f64> {
+        self.forecast_from(&self.history, horizon)
+    }
+
+    /// Number of past values used per prediction
+    pub fn lags(&self) -> usize {
+        self.lags
+    }
+
+    /// Quantile bins per lag and delta
+    pub fn num_bins(&self) -> usize {
+        self.bins
+    }
+
+    /// Regression machine, once fitted
+    pub fn machine(&self) -> Option<&RegressionTsetlinMachine> {
+        self.machine.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lag_features_hold_values_and_deltas() {
+        let forecaster = Forecaster::new(3, 10);
+        let features = forecaster.lag_features(&[1.0, 4.0, 2.0, 7.0]);
+        assert_eq!(features.dim(), (2, 5));
+        assert_eq!(features.row(0).to_vec(), vec![1.0, 4.0, 2.0, 3.0, -2.0]);
+        assert_eq!(features.row(1).to_vec(), vec![4.0, 2.0, 7.0, -2.0, 5.0]);
+    }
+
+    #[test]
+    fn test_forecast_is_recursive() {
+        let series: Vec<f64> = (0..80).map(|t| [0.0, 2.0, 4.0, 2.0][t % 4]).collect();
+        let mut forecaster = Forecaster::new(2, 40).bins(4).seed(5);
+        forecaster.fit(&series, 50);
+
+        let forecast = forecaster.forecast(3);
+        let first = forecaster.predict_next(&series);
+        assert_eq!(forecast[0], first);
+        let mut extended = series.clone();
+        extended.push(first);
+        assert_eq!(forecast[1], forecaster.predict_next(&extended));
+        assert_eq!(forecaster.forecast_from(&series, 3), forecast);
+
+        // Each value follows from the last two, so the cycle continues
+        assert_eq!(forecast, vec![0.0, 2.0, 4.0]);
+    }
+}
@@ -0,0 +1,74 @@
+//! Clause bank heatmaps for plotting
+//!
+//! A heatmap is a features x clauses matrix with one cell per literal of a
+//! clause: either the state counter of the literal's automaton or its include
+//! flag. Plain and negated literals get separate matrices. Taking heatmaps at
+//! several points of training shows how inclusion patterns form. With the
+//! `json` feature, `TsetlinMachine::heatmap_json` writes both matrices for
+//! plotting tools outside Rust.
+
+use crate::clause::Literal;
+use crate::machine::TsetlinMachine;
+use ndarray::Array2;
+
+/// What a heatmap cell holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapValues {
+    /// Automaton state counter (positive when the literal is included)
+    States,
+    /// 1 for included literals, 0 otherwise
+    IncludeFlags,
+}
+
+impl TsetlinMachine {
+    /// Features x clauses matrix of the plain or negated literals
+    ///
+    /// # Arguments
+    /// * `negated` - Whether the cells describe `NOT x` literals
+    /// * `values` - Automaton states or include flags
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::clause::Literal;
+    /// use tsetlin::heatmap::HeatmapValues;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let mut machine = TsetlinMachine::with_defaults(3, 4);
+    /// let literal = Literal { feature: 2, negated: false };
+    /// machine.clause_bank_mut().clause_mut(1).set_included(literal, true);
+    ///
+    /// let flags = machine.heatmap(false, HeatmapValues::IncludeFlags);
+    /// assert_eq!(flags.dim(), (3, 4));
+    /// assert_eq!(flags[[2, 1]], 1);
+    /// ```
+    pub fn heatmap(&self, negated: bool, values: HeatmapValues) -> Array2<i32> {
+        let clauses = self.clause_bank().clauses();
+        Array2::from_shape_fn((self.num_features(), clauses.len()), |(feature, clause)| {
+            let state = clauses[clause].literal_state(Literal { feature, negated });
+            match values {
+                HeatmapValues::States => state,
+                HeatmapValues::IncludeFlags => i32::from(state > 0),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_heatmap() {
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        let literal = Literal { feature: 1, negated: true };
+        machine.clause_bank_mut().clause_mut(2).set_literal_state(literal, 7);
+
+        let states = machine.heatmap(true, HeatmapValues::States);
+        assert_eq!(states[[1, 2]], 7);
+        let other = Literal { feature: 0, negated: true };
+        assert_eq!(states[[0, 2]], machine.clause_bank().clauses()[2].literal_state(other));
+        assert_eq!(states.dim(), (2, 4));
+        assert_eq!(machine.heatmap(true, HeatmapValues::IncludeFlags).sum(), 1);
+        assert_eq!(machine.heatmap(false, HeatmapValues::IncludeFlags).sum(), 0);
+    }
+}
@@ -0,0 +1,202 @@
+//! Multi-threaded batch inference for serving
+//!
+//! A [`Predictor`] owns a pool of worker threads and a set of
+//! [`CompiledMachine`]s. Batches submitted with [`Predictor::submit`] are
+//! queued on a channel, scored by the next free worker, and delivered through
+//! the returned [`PendingBatch`], so a request handler can submit work and
+//! wait for it without blocking other requests. Only the standard library is
+//! used; an async service can wait on a `PendingBatch` from a blocking task.
+
+use crate::compiled::CompiledMachine;
+use ndarray::{Array1, Array2};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// A batch waiting for a worker
+struct Job {
+    /// Model scoring the batch
+    model: Arc<CompiledMachine>,
+    /// Samples of the batch
+    features: Array2<bool>,
+    /// Where the vote sums go
+    reply: Sender<Array1<i32>>,
+}
+
+/// Identifier of a model registered with a [`Predictor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModelId(usize);
+
+/// Vote sums of a submitted batch, available once a worker has scored it
+#[derive(Debug)]
+pub struct PendingBatch {
+    /// Receives the vote sums
+    receiver: Receiver<Array1<i32>>,
+}
+
+impl PendingBatch {
+    /// Block until the batch is scored and return its vote sums
+    pub fn wait_votes(self) -> Array1<i32> {
+        self.receiver.recv().expect("Predictor worker stopped before scoring the batch")
+    }
+
+    /// Block until the batch is scored and return its predictions
+    pub fn wait(self) -> Array1<bool> {
+        self.wait_votes().mapv(|vote| vote > 0)
+    }
+
+    /// Vote sums if the batch is already scored, without blocking
+    ///
+    /// Returns `None` while the batch is queued or being scored; after the
+    /// votes have been returned once, later calls return `None` too.
+    pub fn try_votes(&self) -> Option<Array1<i32>> {
+        match self.receiver.try_recv() {
+            Ok(votes) => Some(votes),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                panic!("Predictor worker stopped before scoring the batch")
+            }
+        }
+    }
+}
+
+/// Worker pool scoring batches with compiled models
+///
+/// Dropping the predictor finishes the queued batches and joins the workers.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::inference::Predictor;
+/// use tsetlin::TsetlinMachine;
+///
+/// let machine = TsetlinMachine::with_defaults(2, 10);
+/// let mut predictor = Predictor::spawn(2);
+/// let model = predictor.add_model(machine.compile());
+///
+/// let pending = predictor.submit(model, array![[true, false], [false, true]]);
+/// assert_eq!(pending.wait(), array![false, false]);
+/// ```
+pub struct Predictor {
+    /// Queue of batches, closed on drop
+    sender: Option<Sender<Job>>,
+    /// Worker threads
+    workers: Vec<JoinHandle<()>>,
+    /// Registered models, indexed by `ModelId`
+    models: Vec<Arc<CompiledMachine>>,
+}
+
+impl Predictor {
+    /// Start `num_threads` workers with no models
+    pub fn spawn(num_threads: usize) -> Self {
+        assert!(num_threads > 0, "A predictor needs at least one worker thread");
+
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..num_threads)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    // The lock is released before scoring, so workers run in parallel
+                    let job = receiver.lock().expect("Predictor queue poisoned").recv();
+                    let Ok(job) = job else { break };
+                    // The submitter may have dropped its `PendingBatch`
+                    let _ = job.reply.send(job.model.predict_votes(&job.features));
+                })
+            })
+            .collect();
+
+        Self { sender: Some(sender), workers, models: Vec::new() }
+    }
+
+    /// Register a model and return its identifier
+    pub fn add_model(&mut self, model: CompiledMachine) -> ModelId {
+        self.models.push(Arc::new(model));
+        ModelId(self.models.len() - 1)
+    }
+
+    /// Queue a batch for scoring by `model`
+    pub fn submit(&self, model: ModelId, features: Array2<bool>) -> PendingBatch {
+        let model = Arc::clone(&self.models[model.0]);
+        assert_eq!(
+            features.ncols(),
+            model.num_features(),
+            "Batch must have the model's number of features"
+        );
+
+        let (reply, receiver) = mpsc::channel();
+        self.sender
+            .as_ref()
+            .expect("Predictor is shut down")
+            .send(Job { model, features, reply })
+            .expect("Predictor workers stopped");
+        PendingBatch { receiver }
+    }
+
+    /// Registered model
+    pub fn model(&self, model: ModelId) -> &CompiledMachine {
+        &self.models[model.0]
+    }
+
+    /// Number of registered models
+    pub fn num_models(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Number of worker threads
+    pub fn num_threads(&self) -> usize {
+        self.workers.len()
+    }
+}
+
+impl Drop for Predictor {
+    fn drop(&mut self) {
+        // Closing the queue lets every worker finish and exit
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::copier;
+    use ndarray::array;
+
+    #[test]
+    fn test_batches_use_their_model() {
+        let mut predictor = Predictor::spawn(3);
+        let first = predictor.add_model(copier(2, 0).compile());
+        let second = predictor.add_model(copier(2, 1).compile());
+        assert_eq!(predictor.num_models(), 2);
+
+        let features = array![[true, false], [false, true]];
+        let pending: Vec<PendingBatch> = (0..20)
+            .map(|i| predictor.submit(if i % 2 == 0 { first } else { second }, features.clone()))
+            .collect();
+        for (i, batch) in pending.into_iter().enumerate() {
+            let expected = if i % 2 == 0 { array![1, -1] } else { array![-1, 1] };
+            assert_eq!(batch.wait_votes(), expected);
+        }
+    }
+
+    #[test]
+    fn test_drop_finishes_queued_batches() {
+        let mut predictor = Predictor::spawn(1);
+        let model = predictor.add_model(copier(2, 0).compile());
+        let pending = predictor.submit(model, array![[true, true]]);
+        drop(predictor);
+
+        assert_eq!(pending.try_votes(), Some(array![1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch must have the model's number of features")]
+    fn test_feature_mismatch() {
+        let mut predictor = Predictor::spawn(1);
+        let model = predictor.add_model(copier(2, 0).compile());
+        predictor.submit(model, array![[true]]);
+    }
+}
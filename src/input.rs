@@ -0,0 +1,266 @@
+//! Boolean sample containers accepted by training and prediction
+//!
+//! [`AsBoolSamples`] abstracts over row-major collections of boolean samples
+//! so `fit`, `predict` and friends can read data where it already lives
+//! instead of forcing a copy into an owned `Array2<bool>`.
+
+use ndarray::{Array2, ArrayView2};
+
+/// A collection of equally long boolean samples
+///
+/// Containers are `Sync` so the `parallel` feature can read samples from
+/// several threads without copying the input.
+pub trait AsBoolSamples: Sync {
+    /// Number of samples
+    fn num_samples(&self) -> usize;
+
+    /// Number of features per sample
+    fn num_features(&self) -> usize;
+
+    /// Copy sample `index` into `buffer`, replacing its contents
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>);
+
+    /// Sample `index` as a vector
+    fn sample(&self, index: usize) -> Vec<bool> {
+        let mut buffer = Vec::with_capacity(self.num_features());
+        self.sample_into(index, &mut buffer);
+        buffer
+    }
+
+    /// Copy all samples into an owned matrix (samples x features)
+    fn to_array(&self) -> Array2<bool> {
+        let mut array = Array2::from_elem((self.num_samples(), self.num_features()), false);
+        let mut buffer = Vec::with_capacity(self.num_features());
+        for (index, mut row) in array.rows_mut().into_iter().enumerate() {
+            self.sample_into(index, &mut buffer);
+            for (out, &bit) in row.iter_mut().zip(&buffer) {
+                *out = bit;
+            }
+        }
+        array
+    }
+}
+
+impl AsBoolSamples for Array2<bool> {
+    fn num_samples(&self) -> usize {
+        self.nrows()
+    }
+
+    fn num_features(&self) -> usize {
+        self.ncols()
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        buffer.clear();
+        buffer.extend(self.row(index).iter().copied());
+    }
+
+    fn to_array(&self) -> Array2<bool> {
+        self.clone()
+    }
+}
+
+impl AsBoolSamples for ArrayView2<'_, bool> {
+    fn num_samples(&self) -> usize {
+        self.nrows()
+    }
+
+    fn num_features(&self) -> usize {
+        self.ncols()
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        buffer.clear();
+        buffer.extend(self.row(index).iter().copied());
+    }
+
+    fn to_array(&self) -> Array2<bool> {
+        self.to_owned()
+    }
+}
+
+/// Copy a row of 0/1 bytes into `buffer`
+fn bytes_into<'a>(row: impl Iterator<Item = &'a u8>, buffer: &mut Vec<bool>) {
+    buffer.clear();
+    buffer.extend(row.map(|&byte| {
+        assert!(byte <= 1, "Byte features must be 0 or 1");
+        byte == 1
+    }));
+}
+
+/// 0/1 bytes, as binarized datasets are usually stored on disk
+///
+/// Samples are converted row by row, so no boolean copy of the whole matrix
+/// is made. Any other byte value panics.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::TsetlinMachine;
+///
+/// let features = array![[1_u8, 0], [0, 1]];
+/// let mut machine = TsetlinMachine::with_defaults(2, 10);
+/// machine.fit(&features, &array![1_u8, 0], 5);
+/// assert_eq!(machine.predict(&features).len(), 2);
+/// ```
+impl AsBoolSamples for Array2<u8> {
+    fn num_samples(&self) -> usize {
+        self.nrows()
+    }
+
+    fn num_features(&self) -> usize {
+        self.ncols()
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        bytes_into(self.row(index).iter(), buffer);
+    }
+}
+
+impl AsBoolSamples for ArrayView2<'_, u8> {
+    fn num_samples(&self) -> usize {
+        self.nrows()
+    }
+
+    fn num_features(&self) -> usize {
+        self.ncols()
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        bytes_into(self.row(index).iter(), buffer);
+    }
+}
+
+impl AsBoolSamples for [Vec<bool>] {
+    fn num_samples(&self) -> usize {
+        self.len()
+    }
+
+    /// Length of the first sample (0 when empty); all samples must match it
+    fn num_features(&self) -> usize {
+        self.first().map_or(0, Vec::len)
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        assert_eq!(
+            self[index].len(),
+            self.num_features(),
+            "All samples must have the same number of features"
+        );
+        buffer.clear();
+        buffer.extend_from_slice(&self[index]);
+    }
+}
+
+impl AsBoolSamples for Vec<Vec<bool>> {
+    fn num_samples(&self) -> usize {
+        self.as_slice().num_samples()
+    }
+
+    fn num_features(&self) -> usize {
+        self.as_slice().num_features()
+    }
+
+    fn sample_into(&self, index: usize, buffer: &mut Vec<bool>) {
+        self.as_slice().sample_into(index, buffer);
+    }
+}
+
+/// Label types accepted for binary classification
+///
+/// Integer-coded labels can be passed to `fit` and `evaluate` as they are,
+/// without building a boolean copy of the label array first. Integer labels
+/// must be 0 (negative) or 1 (positive).
+pub trait BinaryLabel: Copy {
+    /// The label as a class (true for positive)
+    fn to_bool(self) -> bool;
+
+    /// The label of a class
+    fn from_bool(positive: bool) -> Self;
+}
+
+impl BinaryLabel for bool {
+    fn to_bool(self) -> bool {
+        self
+    }
+
+    fn from_bool(positive: bool) -> Self {
+        positive
+    }
+}
+
+impl BinaryLabel for u8 {
+    fn to_bool(self) -> bool {
+        assert!(self <= 1, "Integer labels must be 0 or 1");
+        self == 1
+    }
+
+    fn from_bool(positive: bool) -> Self {
+        u8::from(positive)
+    }
+}
+
+impl BinaryLabel for i8 {
+    fn to_bool(self) -> bool {
+        assert!(self == 0 || self == 1, "Integer labels must be 0 or 1");
+        self == 1
+    }
+
+    fn from_bool(positive: bool) -> Self {
+        i8::from(positive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_containers_agree() {
+        let array = array![[true, false, true], [false, false, true]];
+        let rows = vec![vec![true, false, true], vec![false, false, true]];
+
+        assert_eq!(rows.num_samples(), 2);
+        assert_eq!(rows.num_features(), 3);
+        assert_eq!(rows.to_array(), array);
+        assert_eq!(rows.as_slice().sample(1), array.sample(1));
+        assert_eq!(array.view().to_array(), array);
+    }
+
+    #[test]
+    fn test_transposed_view() {
+        let array = array![[true, false], [true, true], [false, false]];
+        let view = array.t();
+        assert_eq!(view.num_samples(), 2);
+        assert_eq!(view.sample(0), vec![true, true, false]);
+    }
+
+    #[test]
+    #[should_panic(expected = "All samples must have the same number of features")]
+    fn test_ragged_rows() {
+        let rows = vec![vec![true, false], vec![true]];
+        rows.sample(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Integer labels must be 0 or 1")]
+    fn test_invalid_integer_label() {
+        (-1_i8).to_bool();
+    }
+
+    #[test]
+    fn test_byte_matrix_matches_booleans() {
+        let bytes = array![[1_u8, 0, 1], [0, 0, 1]];
+        let booleans = bytes.mapv(|byte| byte == 1);
+        assert_eq!(bytes.to_array(), booleans);
+        assert_eq!(bytes.view().sample(1), booleans.sample(1));
+        assert_eq!(bytes.num_features(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Byte features must be 0 or 1")]
+    fn test_invalid_byte_feature() {
+        array![[0_u8, 2]].sample(0);
+    }
+}
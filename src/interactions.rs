@@ -0,0 +1,152 @@
+//! Feature interaction discovery
+//!
+//! Features that appear together in a clause are used jointly by the model.
+//! Counting how often pairs and triples of features co-occur in the included
+//! literals of clauses, weighted by how precise those clauses are on a
+//! labelled dataset, yields a report of the interactions the model relies on.
+
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2};
+use std::collections::{BTreeSet, HashMap};
+
+/// A group of features that co-occur in clauses
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interaction {
+    /// Feature indices, ascending
+    pub features: Vec<usize>,
+    /// Number of clauses containing all of the features
+    pub clauses: usize,
+    /// Sum of the precision of those clauses
+    pub score: f64,
+}
+
+/// Mine feature pairs and triples that co-occur in clauses
+///
+/// Each clause contributes its precision on `features`/`labels` to every
+/// combination of 2 to `max_order` distinct features among its included
+/// literals (negated or not). Results are sorted by descending score, then by
+/// feature indices.
+///
+/// # Arguments
+/// * `machine` - Trained machine
+/// * `features` - Labelled data used to measure clause precision
+/// * `labels` - Labels of `features`
+/// * `max_order` - Largest interaction size (2 for pairs, 3 for triples)
+///
+/// # Example
+/// ```
+/// use tsetlin::{interactions, TsetlinMachine, generate_xor_dataset};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let machine = TsetlinMachine::with_defaults(2, 10);
+/// // Untrained clauses include no literals, so there are no interactions
+/// assert!(interactions::feature_interactions(&machine, &features, &labels, 3).is_empty());
+/// ```
+pub fn feature_interactions(
+    machine: &TsetlinMachine,
+    features: &Array2<bool>,
+    labels: &Array1<bool>,
+    max_order: usize,
+) -> Vec<Interaction> {
+    assert!(max_order >= 2, "Interactions need at least two features");
+
+    let stats = machine.clause_stats(features, labels);
+    let mut found: HashMap<Vec<usize>, (usize, f64)> = HashMap::new();
+    for (clause, stat) in machine.clause_bank().clauses().iter().zip(&stats) {
+        let clause_features: Vec<usize> = clause
+            .included_literals()
+            .iter()
+            .map(|literal| literal.feature)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        for group in combinations(&clause_features, max_order) {
+            let entry = found.entry(group).or_insert((0, 0.0));
+            entry.0 += 1;
+            entry.1 += stat.precision();
+        }
+    }
+
+    let mut interactions: Vec<Interaction> = found
+        .into_iter()
+        .map(|(features, (clauses, score))| Interaction {
+            features,
+            clauses,
+            score,
+        })
+        .collect();
+    interactions.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.features.cmp(&b.features))
+    });
+    interactions
+}
+
+/// All subsets of `items` with 2 to `max_order` elements, in ascending order
+fn combinations(items: &[usize], max_order: usize) -> Vec<Vec<usize>> {
+    let mut groups = Vec::new();
+    let mut current = Vec::new();
+    extend(items, 0, max_order, &mut current, &mut groups);
+    groups
+}
+
+/// Recursive helper of `combinations`
+fn extend(
+    items: &[usize],
+    start: usize,
+    max_order: usize,
+    current: &mut Vec<usize>,
+    groups: &mut Vec<Vec<usize>>,
+) {
+    for i in start..items.len() {
+        current.push(items[i]);
+        if current.len() >= 2 {
+            groups.push(current.clone());
+        }
+        if current.len() < max_order {
+            extend(items, i + 1, max_order, current, groups);
+        }
+        current.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(&[1, 4, 7], 3),
+            vec![vec![1, 4], vec![1, 4, 7], vec![1, 7], vec![4, 7]]
+        );
+        assert_eq!(combinations(&[1, 4, 7], 2).len(), 3);
+        assert!(combinations(&[3], 3).is_empty());
+    }
+
+    #[test]
+    fn test_interactions_weighted_by_precision() {
+        let mut machine = TsetlinMachine::with_defaults(3, 4);
+        let bank = machine.clause_bank_mut();
+        // Positive clause x0 AND x1 (always right), positive clause x0 AND NOT x1 AND x2
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 1, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 1, negated: true }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 2, negated: false }, true);
+
+        let features = array![[true, true, false], [true, false, true], [true, false, true]];
+        let labels = array![true, false, true];
+        let found = feature_interactions(&machine, &features, &labels, 3);
+
+        assert_eq!(found[0], Interaction { features: vec![0, 1], clauses: 2, score: 1.5 });
+        assert_eq!(found.len(), 4);
+        let triple = found.iter().find(|interaction| interaction.features.len() == 3).unwrap();
+        assert_eq!((triple.clauses, triple.score), (1, 0.5));
+        assert_eq!(feature_interactions(&machine, &features, &labels, 2).len(), 3);
+    }
+}
@@ -0,0 +1,949 @@
+//! JSON model export for introspection and dashboards
+//!
+//! The JSON schema records hyperparameters and, for every clause, its
+//! polarity, vote weight and included literals. Automaton states are not
+//! stored, so a model loaded with [`TsetlinMachine::from_json`] predicts like
+//! the exported one but resumes training from shallow automaton states.
+//!
+//! ```json
+//! {
+//!   "format": "tsetlin",
+//!   "version": 1,
+//!   "num_features": 2,
+//!   "num_clauses": 2,
+//!   "num_states": 100,
+//!   "specificity": 2,
+//!   "threshold": 1,
+//!   "clauses": [
+//!     {"polarity": "positive", "weight": 1, "literals": [{"feature": 0, "negated": false}]},
+//!     {"polarity": "negative", "weight": 1, "literals": []}
+//!   ]
+//! }
+//! ```
+//!
+//! [`MultiClassTsetlinMachine`] models use the format tag `tsetlin-multiclass`
+//! and replace `num_clauses`/`clauses` with `num_classes` and a `classes`
+//! array of `{"num_clauses", "clauses"}` entries.
+//!
+//! Heatmaps from [`TsetlinMachine::heatmap_json`] use the format tag
+//! `tsetlin-heatmap` and are export-only.
+//!
+//! Experiment results from [`ExperimentResults::to_json`] use the format tag
+//! `tsetlin-experiment` and are export-only.
+//!
+//! [`Dataset`]s use the format tag `tsetlin-dataset` and store every sample
+//! as a string of `0`/`1` characters next to the labels and metadata.
+//!
+//! The crate has no JSON dependency; a small reader and writer live here.
+
+use crate::clause::{ClauseBank, Literal};
+use crate::config::Binarization;
+use crate::dataset::Dataset;
+use crate::experiments::ExperimentResults;
+use crate::heatmap::HeatmapValues;
+use crate::machine::TsetlinMachine;
+use crate::multiclass::MultiClassTsetlinMachine;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+/// Version of the JSON schema written by `to_json`
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Error raised when a JSON document cannot be read as a model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    message: String,
+}
+
+impl JsonError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid model JSON: {}", self.message)
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// A parsed JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Look up a required object field
+    pub(crate) fn field(&self, name: &str) -> Result<&Value, JsonError> {
+        match self {
+            Value::Object(fields) => fields
+                .get(name)
+                .ok_or_else(|| JsonError::new(format!("missing field `{name}`"))),
+            _ => Err(JsonError::new(format!("expected an object with field `{name}`"))),
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Result<f64, JsonError> {
+        match self {
+            Value::Number(number) => Ok(*number),
+            _ => Err(JsonError::new("expected a number")),
+        }
+    }
+
+    pub(crate) fn as_usize(&self) -> Result<usize, JsonError> {
+        let number = self.as_f64()?;
+        if number < 0.0 || number.fract() != 0.0 {
+            return Err(JsonError::new(format!("expected a non-negative integer, got {number}")));
+        }
+        Ok(number as usize)
+    }
+
+    pub(crate) fn as_bool(&self) -> Result<bool, JsonError> {
+        match self {
+            Value::Bool(value) => Ok(*value),
+            _ => Err(JsonError::new("expected a boolean")),
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Result<&str, JsonError> {
+        match self {
+            Value::String(value) => Ok(value),
+            _ => Err(JsonError::new("expected a string")),
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Result<&[Value], JsonError> {
+        match self {
+            Value::Array(values) => Ok(values),
+            _ => Err(JsonError::new("expected an array")),
+        }
+    }
+}
+
+/// Parse a complete JSON document
+pub(crate) fn parse(input: &str) -> Result<Value, JsonError> {
+    let mut parser = Parser {
+        bytes: input.as_bytes(),
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position != parser.bytes.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+/// Deepest nesting of arrays and objects the parser accepts
+const MAX_DEPTH: usize = 128;
+
+/// Recursive descent JSON parser
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// Arrays and objects currently open, bounded by `MAX_DEPTH`
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> JsonError {
+        JsonError::new(format!("{message} at byte {}", self.position))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.bytes.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", byte as char)))
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, JsonError> {
+        if self.bytes[self.position..].starts_with(keyword.as_bytes()) {
+            self.position += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, JsonError> {
+        self.skip_whitespace();
+        match self.bytes.get(self.position) {
+            Some(&open @ (b'{' | b'[')) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error("nesting too deep"));
+                }
+                self.depth += 1;
+                let value = if open == b'{' { self.object() } else { self.array() };
+                self.depth -= 1;
+                value
+            }
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b't') => self.keyword("true", Value::Bool(true)),
+            Some(b'f') => self.keyword("false", Value::Bool(false)),
+            Some(b'n') => self.keyword("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'{')?;
+        let mut fields = BTreeMap::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&b'}') {
+            self.position += 1;
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            let value = self.value()?;
+            fields.insert(key, value);
+
+            self.skip_whitespace();
+            match self.bytes.get(self.position) {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(Value::Object(fields));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&b']') {
+            self.position += 1;
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.bytes.get(self.position) {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Value::Array(values));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let byte = *self
+                .bytes
+                .get(self.position)
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.position += 1;
+
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = *self
+                        .bytes
+                        .get(self.position)
+                        .ok_or_else(|| self.error("unterminated escape"))?;
+                    self.position += 1;
+                    let character = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let hex = self
+            .bytes
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.position += 4;
+        char::from_u32(hex).ok_or_else(|| self.error("unsupported unicode escape"))
+    }
+
+    fn number(&mut self) -> Result<Value, JsonError> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.bytes.get(self.position) {
+            self.position += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.position])
+            .ok()
+            .and_then(|text| text.parse::<f64>().ok())
+            .map(Value::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+/// Write a string as a quoted JSON string
+pub(crate) fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Append the clauses of a bank as a JSON array, one clause per line
+fn write_clauses(out: &mut String, bank: &ClauseBank, indent: &str) {
+    out.push('[');
+    for (index, (clause, &polarity)) in bank.clauses().iter().zip(bank.polarities()).enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        write!(out, "\n{indent}  {{\"polarity\": ").unwrap();
+        write_string(out, if polarity { "positive" } else { "negative" });
+        write!(out, ", \"weight\": {}, \"literals\": [", bank.weights()[index]).unwrap();
+        for (i, literal) in clause.included_literals().iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write!(
+                out,
+                "{{\"feature\": {}, \"negated\": {}}}",
+                literal.feature, literal.negated
+            )
+            .unwrap();
+        }
+        out.push_str("]}");
+    }
+    write!(out, "\n{indent}]").unwrap();
+}
+
+/// Rebuild a clause bank from a JSON clause array
+///
+/// Included literals are restored in the shallowest include state.
+fn read_clauses(
+    entries: &[Value],
+    num_features: usize,
+    num_states: u32,
+) -> Result<ClauseBank, JsonError> {
+    let mut bank = ClauseBank::new(num_features, entries.len(), num_states);
+    for (index, entry) in entries.iter().enumerate() {
+        let polarity = match entry.field("polarity")?.as_str()? {
+            "positive" => true,
+            "negative" => false,
+            other => return Err(JsonError::new(format!("unknown polarity `{other}`"))),
+        };
+        bank.set_polarity(index, polarity);
+        let weight = entry.field("weight")?.as_usize()?;
+        let weight = u32::try_from(weight)
+            .ok()
+            .filter(|&weight| weight > 0)
+            .ok_or_else(|| JsonError::new(format!("invalid weight {weight}")))?;
+        bank.set_weight(index, weight);
+
+        for literal in entry.field("literals")?.as_array()? {
+            let feature = literal.field("feature")?.as_usize()?;
+            if feature >= num_features {
+                return Err(JsonError::new(format!("feature {feature} out of range")));
+            }
+            let negated = literal.field("negated")?.as_bool()?;
+            bank.clause_mut(index)
+                .set_included(Literal { feature, negated }, true);
+        }
+    }
+    Ok(bank)
+}
+
+/// Check the format tag and schema version of a model document
+fn check_header(document: &Value, format: &str) -> Result<(), JsonError> {
+    if document.field("format")?.as_str()? != format {
+        return Err(JsonError::new(format!("not a {format} model")));
+    }
+    let version = document.field("version")?.as_usize()?;
+    if version != SCHEMA_VERSION as usize {
+        return Err(JsonError::new(format!("unsupported schema version {version}")));
+    }
+    Ok(())
+}
+
+/// Name of heatmap cell values in the JSON document
+fn heatmap_values_name(values: HeatmapValues) -> &'static str {
+    match values {
+        HeatmapValues::States => "states",
+        HeatmapValues::IncludeFlags => "include_flags",
+    }
+}
+
+/// Read the `num_states` field
+fn read_num_states(document: &Value) -> Result<u32, JsonError> {
+    u32::try_from(document.field("num_states")?.as_usize()?)
+        .map_err(|_| JsonError::new("`num_states` is too large"))
+}
+
+/// Read the `threshold` field, which must be positive
+fn read_threshold(document: &Value) -> Result<f64, JsonError> {
+    let threshold = document.field("threshold")?.as_f64()?;
+    if threshold > 0.0 {
+        Ok(threshold)
+    } else {
+        Err(JsonError::new(format!("`threshold` must be positive, got {threshold}")))
+    }
+}
+
+impl TsetlinMachine {
+    /// Export hyperparameters and learned clauses as JSON
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(2, 4);
+    /// let json = machine.to_json();
+    /// let restored = TsetlinMachine::from_json(&json).unwrap();
+    /// assert_eq!(restored.num_clauses(), 4);
+    /// ```
+    pub fn to_json(&self) -> String {
+        let bank = self.clause_bank();
+        let mut out = String::from("{\n  \"format\": ");
+        write_string(&mut out, "tsetlin");
+        out.push_str(",\n");
+        writeln!(out, "  \"version\": {SCHEMA_VERSION},").unwrap();
+        writeln!(out, "  \"num_features\": {},", self.num_features()).unwrap();
+        writeln!(out, "  \"num_clauses\": {},", self.num_clauses()).unwrap();
+        writeln!(out, "  \"num_states\": {},", bank.num_states()).unwrap();
+        writeln!(out, "  \"specificity\": {},", self.specificity()).unwrap();
+        writeln!(out, "  \"threshold\": {},", self.threshold()).unwrap();
+        out.push_str("  \"clauses\": ");
+        write_clauses(&mut out, bank, "  ");
+        out.push_str("\n}\n");
+        out
+    }
+
+    /// Load a machine from the JSON produced by `to_json`
+    ///
+    /// Included literals are restored in the shallowest include state, so the
+    /// loaded machine predicts like the exported one.
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        let document = parse(json)?;
+        check_header(&document, "tsetlin")?;
+
+        let num_features = document.field("num_features")?.as_usize()?;
+        let num_clauses = document.field("num_clauses")?.as_usize()?;
+        let num_states = read_num_states(&document)?;
+        let specificity = document.field("specificity")?.as_f64()?;
+        let threshold = read_threshold(&document)?;
+        let clauses = document.field("clauses")?.as_array()?;
+        if clauses.len() != num_clauses {
+            return Err(JsonError::new("clause count does not match `num_clauses`"));
+        }
+
+        let bank = read_clauses(clauses, num_features, num_states)?;
+        Ok(TsetlinMachine::from_clause_bank(bank, specificity, threshold))
+    }
+
+    /// Both heatmaps and the clause polarities as a JSON document
+    ///
+    /// The document uses the format tag `tsetlin-heatmap` and stores the
+    /// matrices as arrays of feature rows under `plain` and `negated`.
+    pub fn heatmap_json(&self, values: HeatmapValues) -> String {
+        let mut out = String::from("{\n  \"format\": ");
+        write_string(&mut out, "tsetlin-heatmap");
+        out.push_str(",\n");
+        writeln!(out, "  \"version\": {SCHEMA_VERSION},").unwrap();
+        out.push_str("  \"values\": ");
+        write_string(&mut out, heatmap_values_name(values));
+        out.push_str(",\n");
+        writeln!(out, "  \"num_features\": {},", self.num_features()).unwrap();
+        writeln!(out, "  \"num_clauses\": {},", self.num_clauses()).unwrap();
+        let polarities: Vec<&str> = self
+            .clause_bank()
+            .polarities()
+            .iter()
+            .map(|&polarity| if polarity { "\"positive\"" } else { "\"negative\"" })
+            .collect();
+        writeln!(out, "  \"polarities\": [{}],", polarities.join(", ")).unwrap();
+        for negated in [false, true] {
+            let name = if negated { "negated" } else { "plain" };
+            write!(out, "  \"{name}\": [").unwrap();
+            for (i, row) in self.heatmap(negated, values).rows().into_iter().enumerate() {
+                let cells: Vec<String> = row.iter().map(i32::to_string).collect();
+                out.push_str(if i > 0 { ",\n    " } else { "\n    " });
+                write!(out, "[{}]", cells.join(", ")).unwrap();
+            }
+            out.push_str(if negated { "\n  ]\n" } else { "\n  ],\n" });
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl MultiClassTsetlinMachine {
+    /// Export hyperparameters and the clauses of every class as JSON
+    ///
+    /// The document uses the format tag `tsetlin-multiclass` and stores one
+    /// `{"num_clauses", "clauses"}` entry per class, so per-class clause
+    /// budgets survive a round trip.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\n  \"format\": ");
+        write_string(&mut out, "tsetlin-multiclass");
+        out.push_str(",\n");
+        writeln!(out, "  \"version\": {SCHEMA_VERSION},").unwrap();
+        writeln!(out, "  \"num_features\": {},", self.num_features()).unwrap();
+        writeln!(out, "  \"num_classes\": {},", self.num_classes()).unwrap();
+        writeln!(out, "  \"num_states\": {},", self.class_bank(0).num_states()).unwrap();
+        writeln!(out, "  \"specificity\": {},", self.specificity()).unwrap();
+        writeln!(out, "  \"threshold\": {},", self.threshold()).unwrap();
+        out.push_str("  \"classes\": [");
+        for class in 0..self.num_classes() {
+            if class > 0 {
+                out.push(',');
+            }
+            let bank = self.class_bank(class);
+            write!(out, "\n    {{\"num_clauses\": {}, \"clauses\": ", bank.num_clauses()).unwrap();
+            write_clauses(&mut out, bank, "    ");
+            out.push('}');
+        }
+        out.push_str("\n  ]\n}\n");
+        out
+    }
+
+    /// Load a machine from the JSON produced by `to_json`
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        let document = parse(json)?;
+        check_header(&document, "tsetlin-multiclass")?;
+
+        let num_features = document.field("num_features")?.as_usize()?;
+        let num_classes = document.field("num_classes")?.as_usize()?;
+        let num_states = read_num_states(&document)?;
+        let specificity = document.field("specificity")?.as_f64()?;
+        let threshold = read_threshold(&document)?;
+        let classes = document.field("classes")?.as_array()?;
+        if classes.len() != num_classes || num_classes < 2 {
+            return Err(JsonError::new("class count does not match `num_classes`"));
+        }
+
+        let mut class_banks = Vec::with_capacity(num_classes);
+        for class in classes {
+            let num_clauses = class.field("num_clauses")?.as_usize()?;
+            if num_clauses == 0 || !num_clauses.is_multiple_of(2) {
+                return Err(JsonError::new("`num_clauses` must be even and positive for every class"));
+            }
+            let clauses = class.field("clauses")?.as_array()?;
+            if clauses.len() != num_clauses {
+                return Err(JsonError::new("clause count does not match `num_clauses`"));
+            }
+            class_banks.push(read_clauses(clauses, num_features, num_states)?);
+        }
+        Ok(MultiClassTsetlinMachine::from_class_banks(
+            class_banks,
+            specificity,
+            threshold,
+        ))
+    }
+}
+
+impl ExperimentResults {
+    /// Results, configuration and per-run records as a JSON document
+    ///
+    /// Model hashes are written as 16-digit hex strings, since JSON numbers
+    /// cannot hold every `u64`.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::{Array1, Array2};
+    /// use tsetlin::experiments::Experiment;
+    /// use tsetlin::TsetlinConfig;
+    ///
+    /// let features = Array2::from_shape_fn((20, 2), |(i, j)| ((i >> j) & 1) as f64);
+    /// let labels = Array1::from_shape_fn(20, |i| i % 2 == 1);
+    /// let experiment = Experiment::new("parity", "in-memory", TsetlinConfig::default());
+    /// let json = experiment.run_on(&features, &labels).unwrap().to_json();
+    /// assert!(json.contains("\"format\": \"tsetlin-experiment\""));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let config = &self.config;
+        let mut out = String::from("{\n  \"format\": ");
+        write_string(&mut out, "tsetlin-experiment");
+        out.push_str(",\n");
+        writeln!(out, "  \"version\": {SCHEMA_VERSION},").unwrap();
+        out.push_str("  \"name\": ");
+        write_string(&mut out, &self.name);
+        out.push_str(",\n  \"crate_version\": ");
+        write_string(&mut out, self.crate_version);
+        out.push_str(",\n  \"data\": ");
+        write_string(&mut out, &self.data);
+        out.push_str(",\n");
+        writeln!(out, "  \"num_samples\": {},", self.num_samples).unwrap();
+        writeln!(out, "  \"num_features\": {},", self.num_features).unwrap();
+        writeln!(out, "  \"num_test\": {},", self.num_test).unwrap();
+        writeln!(out, "  \"num_clauses\": {},", config.num_clauses).unwrap();
+        writeln!(out, "  \"num_states\": {},", config.num_states).unwrap();
+        writeln!(out, "  \"specificity\": {},", config.specificity).unwrap();
+        writeln!(out, "  \"threshold\": {},", config.threshold).unwrap();
+        writeln!(out, "  \"epochs\": {},", config.epochs).unwrap();
+        out.push_str("  \"encoding\": ");
+        write_encoding(&mut out, config.binarization);
+        out.push_str(",\n  \"runs\": [");
+        for (i, run) in self.runs.iter().enumerate() {
+            out.push_str(if i > 0 { ",\n    " } else { "\n    " });
+            write!(
+                out,
+                "{{\"seed\": {}, \"train_accuracy\": {}, \"test_accuracy\": {}, \
+                 \"test_f1\": {}, \"test_balanced_accuracy\": {}, \"fit_seconds\": {}, \
+                 \"predict_seconds\": {}, \"included_literals\": {}, \
+                 \"model_hash\": \"{:016x}\"}}",
+                run.seed,
+                run.train_accuracy,
+                run.test_accuracy,
+                run.test_f1,
+                run.test_balanced_accuracy,
+                run.fit_seconds,
+                run.predict_seconds,
+                run.included_literals,
+                run.model_hash
+            )
+            .unwrap();
+        }
+        out.push_str(if self.runs.is_empty() { "]\n" } else { "\n  ]\n" });
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Write a binarization setting as a JSON object
+fn write_encoding(out: &mut String, encoding: Binarization) {
+    match encoding {
+        Binarization::None => out.push_str("{\"method\": \"none\"}"),
+        Binarization::Thermometer { bins } => {
+            write!(out, "{{\"method\": \"thermometer\", \"bins\": {bins}}}").unwrap()
+        }
+        Binarization::Gray { bins } => {
+            write!(out, "{{\"method\": \"gray\", \"bins\": {bins}}}").unwrap()
+        }
+        Binarization::Hybrid { bins, dimensions } => write!(
+            out,
+            "{{\"method\": \"hybrid\", \"bins\": {bins}, \"dimensions\": {dimensions}}}"
+        )
+        .unwrap(),
+    }
+}
+
+/// Read a binarization setting written by `write_encoding`
+fn read_encoding(value: &Value) -> Result<Binarization, JsonError> {
+    Ok(match value.field("method")?.as_str()? {
+        "none" => Binarization::None,
+        "thermometer" => Binarization::Thermometer {
+            bins: value.field("bins")?.as_usize()?,
+        },
+        "gray" => Binarization::Gray {
+            bins: value.field("bins")?.as_usize()?,
+        },
+        "hybrid" => Binarization::Hybrid {
+            bins: value.field("bins")?.as_usize()?,
+            dimensions: value.field("dimensions")?.as_usize()?,
+        },
+        other => return Err(JsonError::new(format!("unknown encoding `{other}`"))),
+    })
+}
+
+/// Format booleans as a string of `0`/`1` characters
+fn bit_string<'a>(bits: impl IntoIterator<Item = &'a bool>) -> String {
+    bits.into_iter().map(|&bit| if bit { '1' } else { '0' }).collect()
+}
+
+/// Parse a string of `0`/`1` characters
+fn read_bits(value: &Value) -> Result<Vec<bool>, JsonError> {
+    value
+        .as_str()?
+        .chars()
+        .map(|bit| match bit {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            other => Err(JsonError::new(format!("unexpected bit `{other}`"))),
+        })
+        .collect()
+}
+
+impl Dataset {
+    /// Export samples, labels and metadata as JSON
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::dataset::Dataset;
+    ///
+    /// let dataset = Dataset::new(array![[true, false], [false, true]], array![true, false])
+    ///     .with_feature_names(["rain", "sun"]);
+    /// let restored = Dataset::from_json(&dataset.to_json()).unwrap();
+    /// assert_eq!(restored, dataset);
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\n  \"format\": ");
+        write_string(&mut out, "tsetlin-dataset");
+        out.push_str(",\n");
+        writeln!(out, "  \"version\": {SCHEMA_VERSION},").unwrap();
+        out.push_str("  \"feature_names\": [");
+        for (i, name) in self.feature_names().iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write_string(&mut out, name);
+        }
+        out.push_str("],\n  \"class_names\": [");
+        write_string(&mut out, &self.class_names()[0]);
+        out.push_str(", ");
+        write_string(&mut out, &self.class_names()[1]);
+        out.push_str("],\n  \"encoding\": ");
+        write_encoding(&mut out, self.encoding());
+        out.push_str(",\n  \"labels\": ");
+        write_string(&mut out, &bit_string(self.labels()));
+        out.push_str(",\n  \"samples\": [");
+        for (i, row) in self.features().rows().into_iter().enumerate() {
+            out.push_str(if i > 0 { ",\n    " } else { "\n    " });
+            write_string(&mut out, &bit_string(row));
+        }
+        out.push_str("\n  ]\n}\n");
+        out
+    }
+
+    /// Load a dataset from the JSON produced by `to_json`
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        let document = parse(json)?;
+        check_header(&document, "tsetlin-dataset")?;
+
+        let feature_names = document
+            .field("feature_names")?
+            .as_array()?
+            .iter()
+            .map(|name| name.as_str().map(str::to_string))
+            .collect::<Result<Vec<_>, _>>()?;
+        let class_names = document.field("class_names")?.as_array()?;
+        let [negative, positive] = class_names else {
+            return Err(JsonError::new("`class_names` must hold two names"));
+        };
+        let labels = read_bits(document.field("labels")?)?;
+        let samples = document.field("samples")?.as_array()?;
+        if samples.len() != labels.len() {
+            return Err(JsonError::new("sample count does not match the labels"));
+        }
+
+        let mut values = Vec::with_capacity(samples.len() * feature_names.len());
+        for sample in samples {
+            let row = read_bits(sample)?;
+            if row.len() != feature_names.len() {
+                return Err(JsonError::new("sample length does not match `feature_names`"));
+            }
+            values.extend(row);
+        }
+        let features = ndarray::Array2::from_shape_vec((labels.len(), feature_names.len()), values)
+            .map_err(|error| JsonError::new(error.to_string()))?;
+
+        Ok(Dataset::new(features, labels.into())
+            .with_feature_names(feature_names)
+            .with_class_names(negative.as_str()?, positive.as_str()?)
+            .with_encoding(read_encoding(document.field("encoding")?)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        let value = parse(r#"{"a": [1, -2.5e1, true, null], "b": "x\"é\n"}"#).unwrap();
+        assert_eq!(
+            value.field("a").unwrap().as_array().unwrap(),
+            &[Value::Number(1.0), Value::Number(-25.0), Value::Bool(true), Value::Null]
+        );
+        assert_eq!(value.field("b").unwrap().as_str().unwrap(), "x\"é\n");
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("{} extra").is_err());
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_DEPTH + 1)).unwrap_err().to_string(),
+            format!("invalid model JSON: nesting too deep at byte {MAX_DEPTH}")
+        );
+        // Deep enough to overflow the stack without the limit
+        assert!(parse(&"[".repeat(200_000)).is_err());
+        assert!(parse(&"{\"a\":".repeat(200_000)).is_err());
+    }
+
+    #[test]
+    fn test_write_string_round_trip() {
+        let mut out = String::new();
+        write_string(&mut out, "tab\there \"quoted\" \\ \u{1}");
+        assert_eq!(parse(&out).unwrap().as_str().unwrap(), "tab\there \"quoted\" \\ \u{1}");
+    }
+
+    #[test]
+    fn test_model_round_trip() {
+        let mut machine = TsetlinMachine::new(3, 4, 3.5, 2.0);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 2, negated: true }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 1, negated: false }, true);
+        bank.set_weight(3, 4);
+
+        let restored = TsetlinMachine::from_json(&machine.to_json()).unwrap();
+        assert_eq!(restored.num_features(), 3);
+        assert_eq!(restored.specificity(), 3.5);
+        assert_eq!(restored.threshold(), 2.0);
+        assert_eq!(restored.rules(), machine.rules());
+        assert_eq!(restored.clause_bank().polarities(), machine.clause_bank().polarities());
+        assert_eq!(restored.clause_bank().weights(), &[1, 1, 1, 4]);
+    }
+
+    #[test]
+    fn test_invalid_models() {
+        assert!(TsetlinMachine::from_json("{\"format\": \"other\"}").is_err());
+
+        let machine = TsetlinMachine::with_defaults(2, 2);
+        let json = machine.to_json().replace("\"num_clauses\": 2", "\"num_clauses\": 4");
+        let error = TsetlinMachine::from_json(&json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid model JSON: clause count does not match `num_clauses`"
+        );
+
+        for threshold in ["0", "-2.5"] {
+            let json = machine
+                .to_json()
+                .replace("\"threshold\": 1,", &format!("\"threshold\": {threshold},"));
+            let error = TsetlinMachine::from_json(&json).unwrap_err();
+            assert!(error.to_string().contains("`threshold` must be positive"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_multiclass_round_trip() {
+        let mut machine = MultiClassTsetlinMachine::with_class_clauses(3, &[2, 6, 4], 3.0, 5.0);
+        machine
+            .class_bank_mut(1)
+            .clause_mut(4)
+            .set_included(Literal { feature: 2, negated: false }, true);
+
+        let restored = MultiClassTsetlinMachine::from_json(&machine.to_json()).unwrap();
+        assert_eq!(restored.clauses_per_class(), vec![2, 6, 4]);
+        assert_eq!(restored.threshold(), 5.0);
+        let features = ndarray::array![[false, false, true], [true, true, false]];
+        assert_eq!(restored.predict_votes(&features), machine.predict_votes(&features));
+
+        let binary = TsetlinMachine::with_defaults(2, 2).to_json();
+        assert!(MultiClassTsetlinMachine::from_json(&binary).is_err());
+
+        let json = machine.to_json();
+        let zero_threshold = json.replace("\"threshold\": 5,", "\"threshold\": 0,");
+        assert!(MultiClassTsetlinMachine::from_json(&zero_threshold).is_err());
+        let empty_class = json.replace("{\"num_clauses\": 2,", "{\"num_clauses\": 0,");
+        let error = MultiClassTsetlinMachine::from_json(&empty_class).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid model JSON: `num_clauses` must be even and positive for every class"
+        );
+    }
+
+    #[test]
+    fn test_dataset_round_trip() {
+        let dataset = Dataset::new(
+            ndarray::array![[true, false, true], [false, false, true]],
+            ndarray::array![false, true],
+        )
+        .with_feature_names(["a \"quoted\" name", "b", "c"])
+        .with_class_names("benign", "malignant")
+        .with_encoding(Binarization::Hybrid { bins: 4, dimensions: 8 });
+
+        let json = dataset.to_json();
+        assert!(json.contains("\"101\""));
+        assert_eq!(Dataset::from_json(&json).unwrap(), dataset);
+
+        let broken = json.replace("\"101\"", "\"10\"");
+        assert!(Dataset::from_json(&broken).is_err());
+    }
+
+    #[test]
+    fn test_heatmap_json() {
+        let mut machine = TsetlinMachine::with_defaults(2, 2);
+        let literal = Literal { feature: 0, negated: false };
+        machine.clause_bank_mut().clause_mut(1).set_included(literal, true);
+
+        let json = machine.heatmap_json(HeatmapValues::IncludeFlags);
+        let document = parse(&json).unwrap();
+        assert_eq!(document.field("values").unwrap().as_str().unwrap(), "include_flags");
+        assert!(json.contains("\"polarities\": [\"positive\", \"negative\"],"));
+        assert!(json.contains("\"plain\": [\n    [0, 1],\n    [0, 0]\n  ],"));
+        assert!(json.contains("\"negated\": [\n    [0, 0],\n    [0, 0]\n  ]\n}"));
+    }
+
+    #[test]
+    fn test_experiment_json() {
+        use crate::experiments::Experiment;
+
+        let features = ndarray::Array2::from_shape_fn((10, 2), |(i, j)| ((i >> j) & 1) as f64);
+        let labels = ndarray::Array1::from_shape_fn(10, |i| i % 2 == 0);
+        let mut experiment = Experiment::new("quote \"q\"", "data.csv", Default::default());
+        experiment.seeds = vec![1, 2];
+        let results = experiment.run_on(&features, &labels).unwrap();
+
+        let document = parse(&results.to_json()).unwrap();
+        assert_eq!(document.field("name").unwrap().as_str().unwrap(), "quote \"q\"");
+        assert_eq!(document.field("num_test").unwrap().as_usize().unwrap(), 2);
+        let runs = document.field("runs").unwrap().as_array().unwrap();
+        assert_eq!(runs.len(), 2);
+        let hash = runs[1].field("model_hash").unwrap().as_str().unwrap();
+        assert_eq!(hash, format!("{:016x}", results.runs[1].model_hash));
+    }
+}
@@ -0,0 +1,226 @@
+//! Sliding-window sequence labeling
+//!
+//! A [`SlidingWindow`] turns every timestep of a sequence into one sample:
+//! the features of the steps from `before` steps earlier to `after` steps
+//! later, concatenated oldest first. An ordinary [`TsetlinMachine`] trained on
+//! these windows then labels each position, which suits event detection in
+//! logs and sensor streams. Windows reaching past either end of the sequence
+//! are completed according to a [`Padding`] policy.
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2};
+
+/// How windows are completed past the ends of a sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Padding {
+    /// Missing steps have all features false
+    #[default]
+    False,
+    /// Missing steps repeat the first or last step
+    Edge,
+    /// Missing steps have all features false, and every window step gets one
+    /// extra feature that is true when the step lies outside the sequence
+    Marked,
+}
+
+/// Window of neighbouring timesteps around each position
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::labeling::{Padding, SlidingWindow};
+///
+/// let window = SlidingWindow::new(1, 1).padding(Padding::Edge);
+/// let windows = window.windows(&array![[true], [false]]);
+/// assert_eq!(windows, array![[true, true, false], [true, false, false]]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlidingWindow {
+    /// Steps before the labelled position
+    before: usize,
+    /// Steps after the labelled position
+    after: usize,
+    /// Completion past the ends of the sequence
+    padding: Padding,
+}
+
+impl SlidingWindow {
+    /// Window of `before` earlier and `after` later steps around each position
+    pub fn new(before: usize, after: usize) -> Self {
+        Self { before, after, padding: Padding::False }
+    }
+
+    /// Set the padding policy (default: `Padding::False`)
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Number of steps in a window
+    pub fn len(&self) -> usize {
+        self.before + 1 + self.after
+    }
+
+    /// Whether the window is empty; never true, as it holds the position itself
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Number of window features for `step_features` features per timestep
+    pub fn num_features(&self, step_features: usize) -> usize {
+        let marker = usize::from(self.padding == Padding::Marked);
+        self.len() * (step_features + marker)
+    }
+
+    /// One window per timestep of `sequence` (timesteps x window features)
+    pub fn windows<S: AsBoolSamples + ?Sized>(&self, sequence: &S) -> Array2<bool> {
+        let (steps, step_features) = (sequence.num_samples(), sequence.num_features());
+        let width = self.num_features(step_features) / self.len();
+        let mut windows = Array2::from_elem((steps, self.num_features(step_features)), false);
+        let rows: Vec<Vec<bool>> = (0..steps).map(|t| sequence.sample(t)).collect();
+
+        for t in 0..steps {
+            for offset in 0..self.len() {
+                let source = (t + offset).checked_sub(self.before).filter(|&s| s < steps);
+                let start = offset * width;
+                let edge = if offset < self.before { 0 } else { steps - 1 };
+                let step = match (source, self.padding) {
+                    (Some(source), _) => &rows[source],
+                    (None, Padding::Edge) => &rows[edge],
+                    (None, Padding::Marked) => {
+                        windows[[t, start + step_features]] = true;
+                        continue;
+                    }
+                    (None, Padding::False) => continue,
+                };
+                for (j, &bit) in step.iter().enumerate() {
+                    windows[[t, start + j]] = bit;
+                }
+            }
+        }
+        windows
+    }
+
+    /// Train `machine` to label every position of the sequences
+    ///
+    /// The windows of all sequences are stacked into one training set.
+    ///
+    /// # Arguments
+    /// * `machine` - Machine with `num_features(step_features)` features
+    /// * `sequences` - Training sequences, each timesteps x features
+    /// * `labels` - Label of every timestep of each sequence
+    /// * `epochs` - Number of training epochs
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::labeling::SlidingWindow;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let window = SlidingWindow::new(1, 1);
+    /// let sequences = vec![array![[false], [true], [false]], array![[true], [true]]];
+    /// let labels = vec![array![false, true, false], array![true, false]];
+    ///
+    /// let mut machine = TsetlinMachine::with_defaults(window.num_features(1), 10).seed(1);
+    /// window.fit(&mut machine, &sequences, &labels, 10);
+    /// assert_eq!(window.label(&machine, &sequences[0]).len(), 3);
+    /// ```
+    pub fn fit<S: AsBoolSamples>(
+        &self,
+        machine: &mut TsetlinMachine,
+        sequences: &[S],
+        labels: &[Array1<bool>],
+        epochs: usize,
+    ) {
+        assert_eq!(sequences.len(), labels.len(), "Every sequence needs its labels");
+        let width = machine.num_features();
+        let mut features = Vec::new();
+        let mut targets = Vec::new();
+        for (sequence, labels) in sequences.iter().zip(labels) {
+            assert_eq!(
+                self.num_features(sequence.num_features()),
+                width,
+                "Machine must have one feature per window feature"
+            );
+            assert_eq!(
+                sequence.num_samples(),
+                labels.len(),
+                "Sequences need one label per timestep"
+            );
+            features.extend(self.windows(sequence));
+            targets.extend(labels.iter().copied());
+        }
+
+        let features = Array2::from_shape_vec((targets.len(), width), features)
+            .expect("Window features match the machine");
+        machine.fit(&features, &Array1::from(targets), epochs);
+    }
+
+    /// Vote sum at every position of `sequence`
+    pub fn label_votes<S: AsBoolSamples + ?Sized>(
+        &self,
+        machine: &TsetlinMachine,
+        sequence: &S,
+    ) -> Array1<i32> {
+        machine.predict_votes(&self.windows(sequence))
+    }
+
+    /// Label of every position of `sequence`
+    pub fn label<S: AsBoolSamples + ?Sized>(
+        &self,
+        machine: &TsetlinMachine,
+        sequence: &S,
+    ) -> Array1<bool> {
+        machine.predict(&self.windows(sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    #[test]
+    fn test_padding_policies() {
+        let sequence = array![[true, false], [false, true], [true, true]];
+
+        let windows = SlidingWindow::new(1, 0).windows(&sequence);
+        assert_eq!(windows.row(0).to_vec(), vec![false, false, true, false]);
+
+        let windows = SlidingWindow::new(0, 1).padding(Padding::Edge).windows(&sequence);
+        assert_eq!(windows.row(2).to_vec(), vec![true, true, true, true]);
+
+        let window = SlidingWindow::new(1, 1).padding(Padding::Marked);
+        assert_eq!(window.num_features(2), 9);
+        let windows = window.windows(&sequence);
+        assert_eq!(windows.dim(), (3, 9));
+        let expected = [false, false, true, true, false, false, false, true, false];
+        assert_eq!(windows.row(0).to_vec(), expected);
+        let expected = [false, true, false, true, true, false, false, false, true];
+        assert_eq!(windows.row(2).to_vec(), expected);
+    }
+
+    #[test]
+    fn test_labels_follow_the_next_step() {
+        // Label a position when the step after it fires
+        let window = SlidingWindow::new(0, 1);
+        let mut machine = TsetlinMachine::with_defaults(window.num_features(1), 2);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 1, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 1, negated: true }, true);
+
+        let sequence = array![[false], [false], [true], [false]];
+        assert_eq!(window.label_votes(&machine, &sequence), array![-1, 1, -1, -1]);
+        assert_eq!(window.label(&machine, &sequence), array![false, true, false, false]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sequences need one label per timestep")]
+    fn test_label_length_mismatch() {
+        let window = SlidingWindow::new(1, 1);
+        let mut machine = TsetlinMachine::with_defaults(window.num_features(1), 2);
+        window.fit(&mut machine, &[array![[true], [false]]], &[array![true]], 1);
+    }
+}
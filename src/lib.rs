@@ -47,12 +47,69 @@
 //!
 //! The algorithm learns interpretable rules that can be analyzed and understood.
 
+pub mod anomaly;
+pub mod augment;
+pub mod autoencoder;
 pub mod automaton;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod binarize;
+pub mod bitmatrix;
+pub mod boosting;
+pub mod callback;
+pub mod checkpoint;
 pub mod clause;
+pub mod clustering;
+pub mod compiled;
+pub mod config;
+pub mod convolution;
+pub mod coverage;
+pub mod dataset;
+#[cfg(feature = "datasets")]
+pub mod datasets;
+pub mod decomposition;
+pub mod delta;
+pub mod ensemble;
+pub mod experiments;
+pub mod explanation;
+pub mod export;
+pub mod feature_selection;
+pub mod forecast;
+pub mod heatmap;
+pub mod inference;
+pub mod input;
+pub mod interactions;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod labeling;
 pub mod machine;
+pub mod metrics;
+pub mod multiclass;
+pub mod multitask;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod regression;
+pub mod robustness;
+pub mod rng;
+pub mod rules;
+pub mod sampler;
+pub mod schedule;
+pub mod sequence;
+pub mod text;
+pub mod trajectory;
+pub mod tuning;
+#[cfg(test)]
+mod testing;
 
 // Re-export main types
+pub use anomaly::AnomalyTsetlinMachine;
+pub use autoencoder::TsetlinAutoencoder;
+pub use config::TsetlinConfig;
+pub use convolution::ConvolutionalTsetlinMachine;
 pub use machine::TsetlinMachine;
+pub use multiclass::MultiClassTsetlinMachine;
+pub use multitask::MultiTaskTsetlinMachine;
+pub use sequence::SequenceTsetlinMachine;
 
 /// Generate a simple XOR dataset for testing
 pub fn generate_xor_dataset() -> (ndarray::Array2<bool>, ndarray::Array1<bool>) {
@@ -69,6 +126,7 @@ pub fn generate_xor_dataset() -> (ndarray::Array2<bool>, ndarray::Array1<bool>)
 }
 
 #[cfg(test)]
+#[allow(clippy::manual_range_contains)]
 mod tests {
     use super::*;
 
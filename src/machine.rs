@@ -1,8 +1,141 @@
 //! Main Tsetlin Machine implementation
 
-use crate::clause::ClauseBank;
+use crate::augment::BitFlip;
+use crate::automaton::TsetlinAutomaton;
+use crate::callback::Callback;
+use crate::clause::{
+    Aggregation, Clause, ClauseBank, ClauseStats, Literal, PolarityScheme, WeightLearning,
+};
+use crate::dataset::DatasetStats;
+use crate::input::{AsBoolSamples, BinaryLabel};
+use crate::metrics::{GroupedReport, Metric};
+use crate::rules::{self, Rule};
+use crate::sampler::Sampler;
+use crate::schedule::Schedule;
 use ndarray::{Array1, Array2};
-use rand::{prelude::SliceRandom, SeedableRng};
+use rand::{prelude::SliceRandom, Rng, SeedableRng};
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::{ControlFlow, Range};
+use std::time::Instant;
+
+/// Settings for growing the clause bank when training accuracy plateaus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoGrow {
+    /// Upper bound on the number of clauses
+    pub max_clauses: usize,
+    /// Epochs without training accuracy improvement before a clause pair is added
+    pub patience: usize,
+}
+
+/// Settings for periodically replacing low-precision clauses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PruneRegrow {
+    /// Clauses whose training precision falls below this value are replaced
+    pub min_precision: f64,
+    /// Number of epochs between prune-and-regrow passes
+    pub interval: usize,
+}
+
+/// Bound on the demographic parity difference kept during training
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairnessConstraint {
+    /// Boolean feature marking membership of the protected group
+    pub protected_feature: usize,
+    /// Largest tolerated difference in positive prediction rate between the groups
+    pub max_disparity: f64,
+}
+
+/// Fairness and accuracy of the training predictions after one epoch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairnessEpoch {
+    /// Positive prediction rate inside the protected group minus the rate outside it
+    pub disparity: f64,
+    /// Training accuracy
+    pub accuracy: f64,
+    /// Probability of withholding gap-widening feedback in the next epoch
+    pub strength: f64,
+}
+
+/// Change of the fairness feedback strength per epoch
+const FAIRNESS_STEP: f64 = 0.1;
+
+/// Limits on a training run
+///
+/// `fit` accepts a plain epoch count as well, which converts to a budget
+/// without a time limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrainBudget {
+    /// Maximum number of epochs
+    pub max_epochs: usize,
+    /// Wall-clock limit in seconds, checked before every epoch
+    pub max_seconds: Option<f64>,
+}
+
+impl TrainBudget {
+    /// Budget of `max_epochs` epochs and `max_seconds` seconds
+    pub fn new(max_epochs: usize, max_seconds: f64) -> Self {
+        assert!(max_seconds >= 0.0, "Time limit must not be negative");
+        Self { max_epochs, max_seconds: Some(max_seconds) }
+    }
+}
+
+impl From<usize> for TrainBudget {
+    fn from(max_epochs: usize) -> Self {
+        Self { max_epochs, max_seconds: None }
+    }
+}
+
+/// Training progress stored in checkpoints (see [`crate::checkpoint`])
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Progress {
+    /// Number of epochs run by `fit`
+    pub(crate) epochs_trained: usize,
+    /// Raw state of the random number generator
+    pub(crate) rng_state: [u64; 4],
+    /// Sample order of the last epoch
+    pub(crate) sample_order: Vec<usize>,
+    /// Best training accuracy and epochs without improvement (auto-grow)
+    pub(crate) growth_state: (f64, usize),
+    /// Current (possibly annealed) threshold
+    pub(crate) threshold: f64,
+    /// Threshold used in each epoch
+    pub(crate) threshold_history: Vec<f64>,
+    /// Include-boundary crossings in each epoch
+    pub(crate) churn_history: Vec<usize>,
+    /// Include-boundary crossings of each clause in the last epoch
+    pub(crate) clause_churn: Vec<usize>,
+    /// Privacy budget consumed so far
+    pub(crate) privacy_spent: f64,
+    /// Disparity, accuracy and feedback strength of each constrained epoch
+    pub(crate) fairness_history: Vec<FairnessEpoch>,
+    /// Clauses replaced by prune-and-regrow
+    pub(crate) num_regrown: usize,
+    /// Clauses flipped by prune-and-regrow
+    pub(crate) num_flipped: usize,
+}
+
+/// Memory used by a machine, and how much of it inference needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// Bytes held by the Tsetlin automata
+    pub automata_bytes: usize,
+    /// Bytes of per-clause bookkeeping (polarity, weight, frozen flag, feature subset)
+    pub clause_bytes: usize,
+    /// Total number of included literals
+    pub included_literals: usize,
+    /// Number of clauses with at least one included literal
+    pub non_empty_clauses: usize,
+    /// Bytes of the inference-only model built by `compile`
+    pub compiled_bytes: usize,
+}
+
+impl MemoryFootprint {
+    /// Bytes held by the trainable machine
+    pub fn total_bytes(&self) -> usize {
+        self.automata_bytes + self.clause_bytes
+    }
+}
 
 /// Main Tsetlin Machine implementation
 #[derive(Debug, Clone)]
@@ -18,7 +151,55 @@ pub struct TsetlinMachine {
     /// Decision threshold
     threshold: f64,
     /// Random number generator
-    rng: rand::rngs::StdRng,
+    rng: crate::rng::TsetlinRng,
+    /// Clause growth settings, if enabled
+    auto_grow: Option<AutoGrow>,
+    /// Prune-and-regrow settings, if enabled
+    prune_regrow: Option<PruneRegrow>,
+    /// Total number of clauses replaced by prune-and-regrow
+    num_regrown: usize,
+    /// Whether clauses with negative net precision are flipped
+    polarity_flip: bool,
+    /// Total number of clauses flipped by `polarity_flip`
+    num_flipped: usize,
+    /// Strength of the per-epoch diversity penalty, if enabled
+    diversity_penalty: Option<f64>,
+    /// Number of epochs run by `fit`
+    epochs_trained: usize,
+    /// Seed of the random number generator, if set
+    seed: Option<u64>,
+    /// Bit-flip augmentation applied to training samples, if enabled
+    augmentation: Option<BitFlip>,
+    /// Stratified mini-batch size, if enabled
+    batch_size: Option<usize>,
+    /// Whether mini-batch clause outputs are computed bit-parallel
+    bit_parallel: bool,
+    /// Epoch sample ordering, if not the default shuffle
+    sampler: Option<Box<dyn Sampler>>,
+    /// Randomized-response privacy parameter of feedback targets, if enabled
+    privacy_epsilon: Option<f64>,
+    /// Privacy budget consumed by training so far
+    privacy_spent: f64,
+    /// Demographic parity bound, if enabled
+    fairness: Option<FairnessConstraint>,
+    /// Disparity, accuracy and feedback strength of each constrained epoch
+    fairness_history: Vec<FairnessEpoch>,
+    /// Per-epoch threshold, if annealed
+    threshold_schedule: Option<Schedule>,
+    /// Threshold used in each epoch run by `fit`
+    threshold_history: Vec<f64>,
+    /// Threshold as a fraction of `num_clauses / 2`, if automatic
+    threshold_fraction: Option<f64>,
+    /// Literals crossing the include boundary in each epoch run by `fit`
+    churn_history: Vec<usize>,
+    /// Literals of each clause crossing the include boundary in the last epoch
+    clause_churn: Vec<usize>,
+    /// Validation metric whose best epoch `fit_with_validation` restores, if enabled
+    keep_best: Option<Metric>,
+    /// Sample order of the last epoch, shuffled again by the next one
+    sample_order: Vec<usize>,
+    /// Best training accuracy so far and epochs without improvement (auto-grow)
+    growth_state: (f64, usize),
 }
 
 impl TsetlinMachine {
@@ -28,7 +209,7 @@ impl TsetlinMachine {
     /// * `num_features` - Number of input features
     /// * `num_clauses` - Number of clauses (must be even)
     /// * `specificity` - Specificity parameter (default: 2.0)
-    /// * `threshold` - Decision threshold (default: 1.0)
+    /// * `threshold` - Decision threshold (default: 1.0, must be positive)
     ///
     /// # Example
     /// ```
@@ -36,185 +217,2754 @@ impl TsetlinMachine {
     /// let machine = TsetlinMachine::new(10, 100, 2.0, 1.0);
     /// ```
     pub fn new(num_features: usize, num_clauses: usize, specificity: f64, threshold: f64) -> Self {
-        assert!(num_clauses % 2 == 0, "Number of clauses must be even");
+        assert!(num_clauses.is_multiple_of(2), "Number of clauses must be even");
         
         let clause_bank = ClauseBank::new(num_features, num_clauses, 100);
-        let rng = rand::rngs::StdRng::from_entropy();
-        
+        Self::from_clause_bank(clause_bank, specificity, threshold)
+    }
+
+    /// Create a machine with hyperparameters chosen from the training data
+    ///
+    /// Rules of thumb from the Tsetlin machine literature:
+    /// * about `10 * sqrt(num_features)` clauses, no more than half the number
+    ///   of samples, between 10 and 2000
+    /// * a threshold of about the square root of the number of clauses
+    /// * a specificity growing with `log2(num_features)`, between 2 and 10
+    /// * 255 automaton states for more than 10 000 samples, 100 otherwise
+    /// * the minority class gets the larger share of the clauses, up to three
+    ///   quarters on strongly imbalanced data
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::Array1;
+    /// use tsetlin::dataset::DatasetStats;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let labels = Array1::from_shape_fn(1000, |i| i % 10 == 0);
+    /// let machine = TsetlinMachine::auto(100, &DatasetStats::from_labels(&labels));
+    /// assert_eq!(machine.num_clauses(), 100);
+    /// assert_eq!(machine.clause_bank().polarity_counts(), (75, 25));
+    /// ```
+    pub fn auto(num_features: usize, stats: &DatasetStats) -> Self {
+        assert!(num_features > 0, "Number of features must be positive");
+
+        let features = num_features as f64;
+        let num_clauses = ((10.0 * features.sqrt()).ceil() as usize)
+            .min(stats.num_samples / 2)
+            .clamp(10, 2000)
+            .next_multiple_of(2);
+        let threshold = (num_clauses as f64).sqrt().round();
+        let specificity = (1.0 + features.log2()).clamp(2.0, 10.0);
+        let num_states = if stats.num_samples > 10_000 { 255 } else { 100 };
+
+        let positive_share = (1.0 - stats.positive_fraction).clamp(0.25, 0.75);
+        let num_positive = (positive_share * num_clauses as f64).round() as usize;
+        let clause_bank = ClauseBank::with_polarity_budget(
+            num_features,
+            num_positive,
+            num_clauses - num_positive,
+            num_states,
+        );
+        Self::from_clause_bank(clause_bank, specificity, threshold)
+    }
+
+    /// Create a machine with separate numbers of positive and negative clauses
+    ///
+    /// Skewed problems often need more clauses for one class than the other,
+    /// e.g. 300 positive and 100 negative clauses. See
+    /// [`ClauseBank::with_polarity_budget`].
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// let machine = TsetlinMachine::with_polarity_budget(10, 300, 100, 3.9, 15.0);
+    /// assert_eq!(machine.num_clauses(), 400);
+    /// assert_eq!(machine.clause_bank().polarity_counts(), (300, 100));
+    /// ```
+    pub fn with_polarity_budget(
+        num_features: usize,
+        num_positive: usize,
+        num_negative: usize,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        let clause_bank =
+            ClauseBank::with_polarity_budget(num_features, num_positive, num_negative, 100);
+        Self::from_clause_bank(clause_bank, specificity, threshold)
+    }
+
+    /// Assemble a machine around an existing clause bank
+    pub(crate) fn from_clause_bank(clause_bank: ClauseBank, specificity: f64, threshold: f64) -> Self {
+        assert!(threshold > 0.0, "Threshold must be positive");
+
         Self {
+            num_features: clause_bank.num_features(),
+            num_clauses: clause_bank.num_clauses(),
             clause_bank,
-            num_features,
-            num_clauses,
             specificity,
             threshold,
-            rng,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+            auto_grow: None,
+            prune_regrow: None,
+            num_regrown: 0,
+            polarity_flip: false,
+            num_flipped: 0,
+            diversity_penalty: None,
+            epochs_trained: 0,
+            seed: None,
+            augmentation: None,
+            batch_size: None,
+            bit_parallel: false,
+            sampler: None,
+            privacy_epsilon: None,
+            privacy_spent: 0.0,
+            fairness: None,
+            fairness_history: Vec::new(),
+            threshold_schedule: None,
+            threshold_history: Vec::new(),
+            threshold_fraction: None,
+            churn_history: Vec::new(),
+            clause_churn: Vec::new(),
+            keep_best: None,
+            sample_order: Vec::new(),
+            growth_state: (f64::NEG_INFINITY, 0),
+        }
+    }
+
+    /// Training progress for a checkpoint
+    pub(crate) fn progress(&self) -> Progress {
+        Progress {
+            epochs_trained: self.epochs_trained,
+            rng_state: self.rng.state(),
+            sample_order: self.sample_order.clone(),
+            growth_state: self.growth_state,
+            threshold: self.threshold,
+            threshold_history: self.threshold_history.clone(),
+            churn_history: self.churn_history.clone(),
+            clause_churn: self.clause_churn.clone(),
+            privacy_spent: self.privacy_spent,
+            fairness_history: self.fairness_history.clone(),
+            num_regrown: self.num_regrown,
+            num_flipped: self.num_flipped,
         }
     }
 
+    /// Replace the clauses and training progress with a checkpoint's
+    pub(crate) fn restore(&mut self, clause_bank: ClauseBank, progress: Progress) {
+        self.num_clauses = clause_bank.num_clauses();
+        self.clause_bank = clause_bank;
+        self.epochs_trained = progress.epochs_trained;
+        self.rng = crate::rng::TsetlinRng::from_state(progress.rng_state);
+        self.sample_order = progress.sample_order;
+        self.growth_state = progress.growth_state;
+        self.threshold = progress.threshold;
+        self.threshold_history = progress.threshold_history;
+        self.churn_history = progress.churn_history;
+        self.clause_churn = progress.clause_churn;
+        self.privacy_spent = progress.privacy_spent;
+        self.fairness_history = progress.fairness_history;
+        self.num_regrown = progress.num_regrown;
+        self.num_flipped = progress.num_flipped;
+        self.rescale_threshold();
+    }
+
     /// Create a new Tsetlin machine with default parameters
     pub fn with_defaults(num_features: usize, num_clauses: usize) -> Self {
         Self::new(num_features, num_clauses, 2.0, 1.0)
     }
 
-    /// Train the Tsetlin machine on a dataset
+    /// Create a new Tsetlin machine initialized from a trained one
+    ///
+    /// The automaton states are copied from `other` so fine-tuning on a new but
+    /// related dataset starts from the learned clauses. The most specific
+    /// `freeze_fraction` of each polarity's clauses are frozen: they keep voting
+    /// but receive no feedback while fine-tuning.
     ///
     /// # Arguments
-    /// * `features` - Feature matrix (samples x features)
-    /// * `labels` - Target labels
-    /// * `epochs` - Number of training epochs
+    /// * `other` - Trained machine to copy
+    /// * `new_threshold` - Decision threshold used for fine-tuning
+    /// * `freeze_fraction` - Fraction of clauses to freeze (0.0 to 1.0)
     ///
     /// # Example
     /// ```
     /// use tsetlin::TsetlinMachine;
-    /// use ndarray::{Array1, Array2};
-    /// 
-    /// let features = Array2::from_shape_vec((4, 2), vec![
-    ///     true, false, false, true, true, true, false, false
-    /// ]).unwrap();
-    /// let labels = Array1::from_vec(vec![true, false, true, false]);
-    /// 
-    /// let mut machine = TsetlinMachine::with_defaults(2, 10);
-    /// machine.fit(&features, &labels, 100);
+    /// let pretrained = TsetlinMachine::with_defaults(10, 20);
+    /// let machine = TsetlinMachine::from_pretrained(&pretrained, 2.0, 0.5);
+    /// assert_eq!(machine.num_frozen(), 10);
     /// ```
-    pub fn fit(&mut self, features: &Array2<bool>, labels: &Array1<bool>, epochs: usize) {
-        assert_eq!(features.nrows(), labels.len());
-        assert_eq!(features.ncols(), self.num_features);
-        
-        let num_samples = features.nrows();
-        let mut indices: Vec<usize> = (0..num_samples).collect();
-        
-        for _ in 0..epochs {
-            // Shuffle samples
-            indices.shuffle(&mut self.rng);
-            
-            // Train on each sample
-            for &idx in &indices {
-                let sample_features = features.row(idx).to_vec();
-                let target = labels[idx];
-                
-                self.clause_bank.update(
-                    &sample_features,
-                    target,
-                    self.threshold,
-                    self.specificity,
-                    &mut self.rng,
-                );
-            }
+    pub fn from_pretrained(other: &TsetlinMachine, new_threshold: f64, freeze_fraction: f64) -> Self {
+        let mut clause_bank = other.clause_bank.clone();
+        clause_bank.unfreeze_all();
+        clause_bank.freeze_most_specific(freeze_fraction);
+
+        let mut machine = Self::from_clause_bank(clause_bank, other.specificity, new_threshold);
+        machine.auto_grow = other.auto_grow;
+        machine.prune_regrow = other.prune_regrow;
+        machine.polarity_flip = other.polarity_flip;
+        machine.diversity_penalty = other.diversity_penalty;
+        machine.epochs_trained = other.epochs_trained;
+        machine.augmentation = other.augmentation;
+        machine.batch_size = other.batch_size;
+        machine.bit_parallel = other.bit_parallel;
+        machine.sampler = other.sampler.clone();
+        machine.privacy_epsilon = other.privacy_epsilon;
+        machine.privacy_spent = other.privacy_spent;
+        machine.fairness = other.fairness;
+        machine.fairness_history = other.fairness_history.clone();
+        machine.threshold_history = other.threshold_history.clone();
+        machine.churn_history = other.churn_history.clone();
+        machine.clause_churn = other.clause_churn.clone();
+        machine.keep_best = other.keep_best;
+        if let Some(seed) = other.seed {
+            machine = machine.seed(seed);
         }
+        machine
     }
 
-    /// Make predictions on a dataset
+    /// Grow the clause bank during training when accuracy plateaus
     ///
-    /// # Arguments
-    /// * `features` - Feature matrix (samples x features)
+    /// After every epoch `fit` measures training accuracy. When it has not
+    /// improved for `patience` epochs, a randomly initialized pair of clauses
+    /// (one per polarity) is added, until `max_clauses` is reached.
     ///
-    /// # Returns
-    /// Array of boolean predictions
-    pub fn predict(&self, features: &Array2<bool>) -> Array1<bool> {
-        assert_eq!(features.ncols(), self.num_features);
-        
-        let mut predictions = Array1::from_elem(features.nrows(), false);
-        
-        for (i, row) in features.rows().into_iter().enumerate() {
-            let sample_features = row.to_vec();
-            let vote = self.clause_bank.vote(&sample_features);
-            predictions[i] = vote > 0;
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 2).auto_grow(20, 3);
+    /// machine.fit(&features, &labels, 50);
+    /// assert!(machine.num_clauses() <= 20);
+    /// ```
+    pub fn auto_grow(mut self, max_clauses: usize, patience: usize) -> Self {
+        assert!(max_clauses.is_multiple_of(2), "Maximum number of clauses must be even");
+        assert!(
+            max_clauses >= self.num_clauses,
+            "Maximum number of clauses must not be below the current count"
+        );
+        assert!(patience > 0, "Patience must be positive");
+
+        self.auto_grow = Some(AutoGrow { max_clauses, patience });
+        self
+    }
+
+    /// Periodically replace clauses with low precision on the training data
+    ///
+    /// Every `interval` epochs `fit` computes each clause's precision (the
+    /// fraction of its firings that voted for the correct class) and replaces
+    /// clauses below `min_precision`, including clauses that never fire, with
+    /// randomly initialized ones of the same polarity. This keeps capacity
+    /// focused on patterns the model still gets wrong.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 20).prune_and_regrow(0.5, 10);
+    /// machine.fit(&features, &labels, 50);
+    /// println!("Replaced {} clauses", machine.num_regrown());
+    /// ```
+    pub fn prune_and_regrow(mut self, min_precision: f64, interval: usize) -> Self {
+        assert!((0.0..=1.0).contains(&min_precision), "Minimum precision must be in [0, 1]");
+        assert!(interval > 0, "Interval must be positive");
+
+        self.prune_regrow = Some(PruneRegrow { min_precision, interval });
+        self
+    }
+
+    /// Flip chronically wrong clauses instead of replacing them (experimental)
+    ///
+    /// A clause whose [net precision](ClauseStats::net_precision) on the
+    /// training data is negative, i.e. that fires but votes for the wrong
+    /// class more often than for the right one, has its polarity flipped. The
+    /// flipped clause keeps its learned literals, and its net precision
+    /// changes sign. With [`prune_and_regrow`](Self::prune_and_regrow) the
+    /// check runs at each regrow interval, before low-precision clauses are
+    /// replaced; on its own it runs after every epoch.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 20).polarity_flip(true);
+    /// machine.fit(&features, &labels, 50);
+    /// println!("Flipped {} clauses", machine.num_flipped());
+    /// ```
+    pub fn polarity_flip(mut self, enabled: bool) -> Self {
+        self.polarity_flip = enabled;
+        self
+    }
+
+    /// Replace every clause whose precision on a dataset is below `min_precision`
+    ///
+    /// With [`polarity_flip`](Self::polarity_flip) enabled, clauses with a
+    /// negative net precision are flipped instead. Frozen clauses are left
+    /// untouched. Returns the number of replaced clauses.
+    pub fn regrow_below<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<bool>,
+        min_precision: f64,
+    ) -> usize {
+        let stats = self.clause_stats(features, labels);
+        let mut replaced = 0;
+        for (index, stat) in stats.iter().enumerate() {
+            if self.clause_bank.is_frozen(index) || self.flip_if_wrong(index, stat) {
+                continue;
+            }
+            if stat.precision() < min_precision {
+                self.clause_bank.reinitialize(index, &mut self.rng);
+                replaced += 1;
+            }
         }
-        
-        predictions
+
+        self.num_regrown += replaced;
+        replaced
     }
 
-    /// Make a prediction on a single sample
-    pub fn predict_single(&self, features: &[bool]) -> bool {
-        assert_eq!(features.len(), self.num_features);
-        
-        let vote = self.clause_bank.vote(features);
-        vote > 0
+    /// Flip clause `index` if polarity flips are enabled, it is not frozen and
+    /// its net precision is negative; returns whether it was flipped
+    fn flip_if_wrong(&mut self, index: usize, stat: &ClauseStats) -> bool {
+        let frozen = self.clause_bank.is_frozen(index);
+        if !self.polarity_flip || frozen || stat.net_precision() >= 0.0 {
+            return false;
+        }
+        let polarity = self.clause_bank.polarities()[index];
+        self.clause_bank.set_polarity(index, !polarity);
+        self.num_flipped += 1;
+        true
     }
 
-    /// Evaluate the model on a dataset
+    /// Penalize redundant clauses at the end of every training epoch
     ///
-    /// # Arguments
-    /// * `features` - Feature matrix
-    /// * `labels` - Target labels
+    /// Literals shared with the most similar clause of the same polarity are
+    /// pushed towards exclusion with probability `strength * similarity`. See
+    /// [`ClauseBank::apply_diversity_penalty`].
+    pub fn diversity_penalty(mut self, strength: f64) -> Self {
+        assert!((0.0..=1.0).contains(&strength), "Penalty strength must be in [0, 1]");
+
+        self.diversity_penalty = Some(strength);
+        self
+    }
+
+    /// Seed the random number generator for reproducible training
     ///
-    /// # Returns
-    /// Accuracy score (0.0 to 1.0)
-    pub fn evaluate(&self, features: &Array2<bool>, labels: &Array1<bool>) -> f64 {
-        let predictions = self.predict(features);
-        let correct = predictions
-            .iter()
-            .zip(labels.iter())
-            .filter(|(&pred, &actual)| pred == actual)
-            .count();
-        
-        correct as f64 / labels.len() as f64
+    /// Two machines with the same seed, hyperparameters and data train
+    /// identically, including any augmentation.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{TsetlinMachine, generate_xor_dataset};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut a = TsetlinMachine::with_defaults(2, 10).seed(42);
+    /// let mut b = TsetlinMachine::with_defaults(2, 10).seed(42);
+    /// a.fit(&features, &labels, 20);
+    /// b.fit(&features, &labels, 20);
+    /// assert_eq!(a.rules(), b.rules());
+    /// ```
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self.seed = Some(seed);
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Augment every training sample with random bit flips during `fit`
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use tsetlin::augment::BitFlip;
+    /// let machine = TsetlinMachine::with_defaults(10, 20).seed(1).augment(BitFlip::new(0.05));
+    /// ```
+    pub fn augment(mut self, augmentation: BitFlip) -> Self {
+        self.augmentation = Some(augmentation);
+        self
+    }
 
-    #[test]
-    fn test_machine_creation() {
-        let machine = TsetlinMachine::new(5, 10, 2.0, 1.0);
-        assert_eq!(machine.num_features, 5);
-        assert_eq!(machine.num_clauses, 10);
+    /// Train on stratified mini-batches of `batch_size` samples
+    ///
+    /// Each epoch the classes are shuffled separately and interleaved so
+    /// every batch has about the class ratio of the whole dataset. Within a
+    /// batch all vote sums, and with them all feedback decisions, are computed
+    /// before any clause changes (see [`ClauseBank::update_batch`]), which
+    /// smooths convergence on imbalanced data.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1).batch_size(2);
+    /// machine.fit(&features, &labels, 10);
+    /// ```
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must be positive");
+        self.batch_size = Some(batch_size);
+        self
     }
 
-    #[test]
-    fn test_machine_with_defaults() {
-        let machine = TsetlinMachine::with_defaults(5, 10);
-        assert_eq!(machine.num_features, 5);
-        assert_eq!(machine.num_clauses, 10);
+    /// Evaluate the clauses of each mini-batch bit-parallel over its samples
+    ///
+    /// Only affects training with [`batch_size`](Self::batch_size). Clause
+    /// outputs for 64 samples are computed in one pass over the automata, and
+    /// the feedback of every sample in the batch uses those pre-batch outputs
+    /// (see [`ClauseBank::update_batch_packed`]). Large batches train faster;
+    /// the learned model differs from plain mini-batches.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10)
+    ///     .seed(1)
+    ///     .batch_size(64)
+    ///     .bit_parallel_batches(true);
+    /// machine.fit(&features, &labels, 10);
+    /// ```
+    pub fn bit_parallel_batches(mut self, enabled: bool) -> Self {
+        self.bit_parallel = enabled;
+        self
     }
 
-    #[test]
-    fn test_machine_training() {
-        let features = Array2::from_shape_vec((4, 2), vec![
-            true, false, false, true, true, true, false, false
-        ]).unwrap();
-        let labels = Array1::from_vec(vec![true, false, true, false]);
-        
-        let mut machine = TsetlinMachine::with_defaults(2, 10);
-        machine.fit(&features, &labels, 10);
-        
-        let predictions = machine.predict(&features);
-        assert_eq!(predictions.len(), 4);
+    /// Choose the samples and their order for every training epoch
+    ///
+    /// Replaces the default per-epoch shuffle (and the stratified order of
+    /// mini-batch training). The sampler is told how many epochs the machine
+    /// has been trained for, so curricula continue across `fit` calls.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::sampler::Sequential;
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1).sampler(Sequential);
+    /// machine.fit(&features, &labels, 10);
+    /// ```
+    pub fn sampler(mut self, sampler: impl Sampler + 'static) -> Self {
+        self.sampler = Some(Box::new(sampler));
+        self
     }
 
-    #[test]
-    fn test_machine_prediction() {
-        let features = Array2::from_shape_vec((2, 2), vec![
-            true, false, false, true
-        ]).unwrap();
-        let labels = Array1::from_vec(vec![true, false]);
-        
-        let mut machine = TsetlinMachine::with_defaults(2, 10);
-        machine.fit(&features, &labels, 10);
-        
-        let prediction = machine.predict_single(&[true, false]);
-        assert!(prediction == true || prediction == false);
+    /// Give label-private feedback by randomized response
+    ///
+    /// At the start of every epoch each label is released once, flipped with
+    /// probability `1 / (1 + e^epsilon)`. Everything in the epoch that reads
+    /// labels (feedback targets, stratified batches, samplers, prune-and-regrow,
+    /// auto-grow and the fairness constraint) reads that released copy, so an
+    /// epoch is `epsilon`-differentially private with respect to the labels.
+    /// By sequential composition `fit` spends `epsilon` per epoch and
+    /// [`partial_fit`](Self::partial_fit) `epsilon` per sample; see
+    /// [`privacy_spent`](Self::privacy_spent). Only labels are protected, not
+    /// features, and callbacks still receive the true labels.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).differential_privacy(1.0);
+    /// machine.fit(&features, &labels, 3);
+    /// assert_eq!(machine.privacy_spent(), 3.0);
+    /// ```
+    pub fn differential_privacy(mut self, epsilon: f64) -> Self {
+        assert!(epsilon > 0.0 && epsilon.is_finite(), "Privacy epsilon must be positive and finite");
+        self.privacy_epsilon = Some(epsilon);
+        self
     }
 
-    #[test]
-    fn test_machine_evaluation() {
-        let features = Array2::from_shape_vec((4, 2), vec![
-            true, false, false, true, true, true, false, false
-        ]).unwrap();
-        let labels = Array1::from_vec(vec![true, false, true, false]);
-        
-        let mut machine = TsetlinMachine::with_defaults(2, 20);
-        machine.fit(&features, &labels, 50);
-        
-        let accuracy = machine.evaluate(&features, &labels);
-        assert!(accuracy >= 0.0 && accuracy <= 1.0);
+    /// Keep the demographic parity difference of the predictions under a bound
+    ///
+    /// After every epoch `fit` predicts the training samples and compares the
+    /// positive prediction rate of samples with `protected_feature` set to
+    /// that of the others. While the difference exceeds `max_disparity`, the
+    /// probability of withholding feedback that widens the gap (positive
+    /// targets in the favoured group, negative targets in the other) rises by
+    /// 0.1 per epoch, and it falls again once the bound holds. Each epoch's
+    /// disparity, accuracy and strength are recorded in
+    /// [`fairness_history`](Self::fairness_history) to show the trade-off.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).fairness_constraint(0, 0.2);
+    /// machine.fit(&features, &labels, 5);
+    /// for epoch in machine.fairness_history() {
+    ///     println!("disparity {:+.2}, accuracy {:.2}", epoch.disparity, epoch.accuracy);
+    /// }
+    /// ```
+    pub fn fairness_constraint(mut self, protected_feature: usize, max_disparity: f64) -> Self {
+        assert!(protected_feature < self.num_features, "Protected feature out of range");
+        assert!((0.0..=1.0).contains(&max_disparity), "Maximum disparity must be in [0, 1]");
+
+        self.fairness = Some(FairnessConstraint { protected_feature, max_disparity });
+        self
     }
 
-    #[test]
-    #[should_panic(expected = "Number of clauses must be even")]
-    fn test_machine_odd_clauses() {
-        TsetlinMachine::with_defaults(5, 9);
+    /// Learn integer clause weights during `fit`
+    ///
+    /// See [`WeightLearning`]. Weighted clauses often reach the same accuracy
+    /// with several times fewer clauses.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use tsetlin::clause::WeightLearning;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(10, 20)
+    ///     .weight_learning(WeightLearning::Integer { max_weight: 16 });
+    /// assert_eq!(machine.clause_bank().weights(), &[1; 20]);
+    /// ```
+    pub fn weight_learning(mut self, weight_learning: WeightLearning) -> Self {
+        self.clause_bank.set_weight_learning(weight_learning);
+        self
+    }
+
+    /// Restore the best validation epoch at the end of `fit_with_validation`
+    ///
+    /// Validation scores of a Tsetlin machine oscillate between epochs, so the
+    /// last epoch is rarely the best one. With this option the clause bank of
+    /// the epoch with the highest `metric` (the earliest on ties) is kept and
+    /// restored once training ends.
+    pub fn keep_best(mut self, metric: Metric) -> Self {
+        self.keep_best = Some(metric);
+        self
+    }
+
+    /// Let the threshold follow a schedule over the epochs of `fit`
+    ///
+    /// The threshold of each epoch is `schedule.value(epoch)`, with epochs
+    /// counted across calls to `fit`, and is recorded in
+    /// [`threshold_history`](Self::threshold_history). Raising `T` as
+    /// training progresses keeps refining the votes of a settled model.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::schedule::Schedule;
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10)
+    ///     .threshold_schedule(Schedule::Linear { start: 2.0, end: 8.0, epochs: 3 });
+    /// machine.fit(&features, &labels, 5);
+    /// assert_eq!(machine.threshold_history(), &[2.0, 4.0, 6.0, 8.0, 8.0]);
+    /// assert_eq!(machine.threshold(), 8.0);
+    /// ```
+    pub fn threshold_schedule(mut self, schedule: Schedule) -> Self {
+        assert!(
+            self.threshold_fraction.is_none(),
+            "Threshold cannot follow both a schedule and the clause count"
+        );
+
+        self.threshold_schedule = Some(schedule);
+        self
+    }
+
+    /// Keep the threshold at `fraction * num_clauses / 2`
+    ///
+    /// The vote sum of a machine lies within `±num_clauses / 2` (with unit
+    /// weights), so a fixed `T` that suits one clause count is too strict or
+    /// too loose for another. In this mode `T` is set from the clause count
+    /// now and rescaled whenever training changes the count, e.g. when
+    /// [`auto_grow`](Self::auto_grow) adds clauses. Fractions of about 0.05 to
+    /// 0.3 are typical.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(10, 200).threshold_auto(0.1);
+    /// assert_eq!(machine.threshold(), 10.0);
+    /// ```
+    pub fn threshold_auto(mut self, fraction: f64) -> Self {
+        assert!(fraction > 0.0 && fraction.is_finite(), "Threshold fraction must be positive");
+        assert!(
+            self.threshold_schedule.is_none(),
+            "Threshold cannot follow both a schedule and the clause count"
+        );
+
+        self.threshold_fraction = Some(fraction);
+        self.rescale_threshold();
+        self
+    }
+
+    /// Threshold fraction of `threshold_auto`, if enabled
+    pub fn threshold_fraction(&self) -> Option<f64> {
+        self.threshold_fraction
+    }
+
+    /// Recompute an automatic threshold from the current clause count
+    fn rescale_threshold(&mut self) {
+        if let Some(fraction) = self.threshold_fraction {
+            self.threshold = fraction * self.num_clauses as f64 / 2.0;
+        }
+    }
+
+    /// Choose which clauses vote for the positive class
+    ///
+    /// By default the first half of the clauses is positive. Skewed problems
+    /// can give one class more clauses with [`PolarityScheme::Ratio`].
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use tsetlin::clause::PolarityScheme;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(10, 4).polarity_scheme(PolarityScheme::Ratio(0.75));
+    /// assert_eq!(machine.clause_bank().polarities(), &[true, true, true, false]);
+    /// ```
+    pub fn polarity_scheme(mut self, scheme: PolarityScheme) -> Self {
+        self.clause_bank.set_polarity_scheme(&scheme);
+        self
+    }
+
+    /// Never let any clause use `feature`, plain or negated
+    ///
+    /// Enforced during feedback (see [`ClauseBank::forbid_literal`]), so a
+    /// protected attribute is guaranteed to play no part in any rule.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1).always_exclude(1);
+    /// machine.fit(&features, &labels, 20);
+    /// assert_eq!(machine.feature_usage()[1], 0);
+    /// ```
+    pub fn always_exclude(mut self, feature: usize) -> Self {
+        for negated in [false, true] {
+            self.clause_bank.forbid_literal(Literal { feature, negated });
+        }
+        self
+    }
+
+    /// Only let clauses use `feature` as a plain (non-negated) literal
+    ///
+    /// Rules may then require the feature to be set but never to be unset.
+    pub fn require_positive_only(mut self, feature: usize) -> Self {
+        self.clause_bank.forbid_literal(Literal { feature, negated: true });
+        self
+    }
+
+    /// Keep clauses consistent with thermometer-encoded ordinal columns
+    ///
+    /// No clause may require a value to reach one threshold while staying
+    /// below a lower one, so every rule describes a plausible value range of
+    /// each column (see [`ClauseBank::set_ordinal_groups`]).
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::binarize::{ThermometerEncoder, Transform};
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let mut encoder = ThermometerEncoder::new(4);
+    /// let features = encoder.fit_transform(&array![[1.0], [2.0], [3.0], [4.0]]);
+    /// let machine = TsetlinMachine::with_defaults(features.ncols(), 10)
+    ///     .ordinal_groups(encoder.feature_groups());
+    /// assert_eq!(machine.clause_bank().ordinal_groups(), &[0..3]);
+    /// ```
+    pub fn ordinal_groups(mut self, groups: impl IntoIterator<Item = Range<usize>>) -> Self {
+        self.clause_bank.set_ordinal_groups(groups);
+        self
+    }
+
+    /// Tolerate label noise by sparing clauses from suspected mislabels
+    ///
+    /// When the vote sum already reaches the threshold in favour of the
+    /// opposite class, the sample is likely mislabelled and its Type II
+    /// feedback is withheld with probability `tolerance`. A tolerance close to
+    /// the dataset's known label-noise rate is a good starting point. See
+    /// [`ClauseBank::set_noise_tolerance`].
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// let machine = TsetlinMachine::with_defaults(10, 20).noise_tolerance(0.1);
+    /// assert_eq!(machine.clause_bank().noise_tolerance(), 0.1);
+    /// ```
+    pub fn noise_tolerance(mut self, tolerance: f64) -> Self {
+        self.clause_bank.set_noise_tolerance(tolerance);
+        self
+    }
+
+    /// Restrict every clause to `features_per_clause` randomly chosen features
+    ///
+    /// Subsets are drawn from the machine's random generator, so call this
+    /// after [`seed`](Self::seed) for reproducible subsets. See
+    /// [`ClauseBank::set_feature_bagging`].
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// let machine = TsetlinMachine::with_defaults(1000, 20).seed(7).feature_bagging(50);
+    /// let clause = &machine.clause_bank().clauses()[0];
+    /// assert_eq!(clause.feature_subset().unwrap().len(), 50);
+    /// ```
+    pub fn feature_bagging(mut self, features_per_clause: usize) -> Self {
+        self.clause_bank
+            .set_feature_bagging(Some(features_per_clause), &mut self.rng);
+        self
+    }
+
+    /// Pairwise Jaccard similarity between the literal sets of all clauses
+    pub fn clause_similarity(&self) -> Array2<f64> {
+        self.clause_bank.similarity_matrix()
+    }
+
+    /// Overall clause diversity (0.0 for identical clauses, 1.0 for disjoint ones)
+    pub fn clause_diversity(&self) -> f64 {
+        self.clause_bank.diversity()
+    }
+
+    /// Rules learned by the clauses, one per trained clause
+    ///
+    /// Clauses without included literals are untrained and skipped.
+    pub fn rules(&self) -> Vec<Rule> {
+        self.clause_bank
+            .clauses()
+            .iter()
+            .zip(self.clause_bank.polarities().iter())
+            .map(|(clause, &polarity)| Rule {
+                polarity,
+                literals: clause.included_literals(),
+            })
+            .filter(|rule| !rule.literals.is_empty())
+            .collect()
+    }
+
+    /// Minimal rule set describing the learned logic of each polarity
+    ///
+    /// See [`rules::simplify`] for how the rules are minimized.
+    pub fn rules_simplified(&self) -> Vec<Rule> {
+        rules::simplify(&self.rules())
+    }
+
+    /// Reset clauses that duplicate another clause of the same polarity
+    ///
+    /// Returns the number of reset clauses. See [`ClauseBank::deduplicate`].
+    pub fn deduplicate(&mut self) -> usize {
+        self.clause_bank.deduplicate()
+    }
+
+    /// Firing statistics of every clause on a labelled dataset
+    pub fn clause_stats<S: AsBoolSamples + ?Sized>(
+        &self,
+        features: &S,
+        labels: &Array1<bool>,
+    ) -> Vec<ClauseStats> {
+        assert_eq!(features.num_features(), self.num_features);
+
+        self.clause_bank.clause_stats(features, labels)
+    }
+
+    /// Total number of clauses replaced by prune-and-regrow
+    pub fn num_regrown(&self) -> usize {
+        self.num_regrown
+    }
+
+    /// Total number of clauses flipped by [`polarity_flip`](Self::polarity_flip)
+    pub fn num_flipped(&self) -> usize {
+        self.num_flipped
+    }
+
+    /// Grow the input space by `additional` features
+    ///
+    /// Fresh exclude-state automata are appended to every clause for the new
+    /// columns, so a trained model can keep learning after the input schema
+    /// gains features. Predictions are unchanged until training includes the
+    /// new literals. New columns must be appended after the existing ones.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10);
+    /// machine.extend_features(3);
+    /// assert_eq!(machine.num_features(), 5);
+    /// ```
+    pub fn extend_features(&mut self, additional: usize) {
+        self.clause_bank.extend_features(additional);
+        self.num_features += additional;
+    }
+
+    /// Train the Tsetlin machine on a dataset
+    ///
+    /// Training stops after the budget's epochs or once its time limit has
+    /// passed, keeping what was learned so far. Returns the number of epochs
+    /// completed.
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix (samples x features)
+    /// * `labels` - Target labels: booleans or 0/1 integers (see [`BinaryLabel`])
+    /// * `budget` - Number of training epochs, or a [`TrainBudget`]
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use ndarray::{Array1, Array2};
+    /// 
+    /// let features = Array2::from_shape_vec((4, 2), vec![
+    ///     true, false, false, true, true, true, false, false
+    /// ]).unwrap();
+    /// let labels = Array1::from_vec(vec![true, false, true, false]);
+    /// 
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10);
+    /// assert_eq!(machine.fit(&features, &labels, 100), 100);
+    ///
+    /// let integer_labels: Array1<u8> = Array1::from_vec(vec![1, 0, 1, 0]);
+    /// machine.fit(&features, &integer_labels, 10);
+    /// ```
+    pub fn fit<S: AsBoolSamples + ?Sized, L: BinaryLabel>(
+        &mut self,
+        features: &S,
+        labels: &Array1<L>,
+        budget: impl Into<TrainBudget>,
+    ) -> usize {
+        let labels = labels.mapv(L::to_bool);
+        self.train(features, &labels, None, budget.into(), &mut |_| ControlFlow::Continue(()))
+    }
+
+    /// Train like [`fit`](Self::fit), calling `callback` after every epoch
+    ///
+    /// Training stops early when the callback returns `ControlFlow::Break`.
+    /// Returns the number of epochs completed. See
+    /// [`ProgressCallback`](crate::callback::ProgressCallback) for per-epoch
+    /// metrics.
+    pub fn fit_with_callback<S: AsBoolSamples, L: BinaryLabel>(
+        &mut self,
+        features: &S,
+        labels: &Array1<L>,
+        budget: impl Into<TrainBudget>,
+        callback: &mut dyn Callback,
+    ) -> usize {
+        let labels = labels.mapv(L::to_bool);
+        let mut on_epoch =
+            |machine: &TsetlinMachine| callback.on_epoch_end(machine, features, &labels);
+        self.train(features, &labels, None, budget.into(), &mut on_epoch)
+    }
+
+    /// Train like [`fit`](Self::fit), scoring validation data after every epoch
+    ///
+    /// Returns the validation score of each completed epoch, using the
+    /// [`keep_best`](Self::keep_best) metric or accuracy if none is set. With
+    /// `keep_best`, the clause bank of the best epoch is restored at the end.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::metrics::Metric;
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).keep_best(Metric::F1);
+    /// let scores = machine.fit_with_validation(&features, &labels, &features, &labels, 10);
+    /// assert_eq!(scores.len(), 10);
+    /// ```
+    pub fn fit_with_validation<S, V>(
+        &mut self,
+        features: &S,
+        labels: &Array1<bool>,
+        val_features: &V,
+        val_labels: &Array1<bool>,
+        budget: impl Into<TrainBudget>,
+    ) -> Vec<f64>
+    where
+        S: AsBoolSamples + ?Sized,
+        V: AsBoolSamples + ?Sized,
+    {
+        assert_eq!(val_features.num_samples(), val_labels.len());
+
+        let metric = self.keep_best.unwrap_or_default();
+        let keep_best = self.keep_best.is_some();
+        let mut scores = Vec::new();
+        let mut best: Option<(f64, ClauseBank)> = None;
+        let mut on_epoch = |machine: &TsetlinMachine| {
+            let score = metric.score(&machine.predict(val_features), val_labels);
+            if keep_best && best.as_ref().is_none_or(|&(best_score, _)| score > best_score) {
+                best = Some((score, machine.clause_bank.clone()));
+            }
+            scores.push(score);
+            ControlFlow::Continue(())
+        };
+        self.train(features, labels, None, budget.into(), &mut on_epoch);
+
+        if let Some((_, clause_bank)) = best {
+            self.num_clauses = clause_bank.num_clauses();
+            self.clause_bank = clause_bank;
+            self.rescale_threshold();
+        }
+        scores
+    }
+
+    /// Train the model on soft targets
+    ///
+    /// Each target is the probability that the sample belongs to the positive
+    /// class. Every sample gives positive-class feedback with probability
+    /// `p` per clause and negative-class feedback with probability `1 - p`,
+    /// so hard 0/1 targets train exactly like [`TsetlinMachine::fit`]. Useful
+    /// for distilling another model or smoothing labels.
+    ///
+    /// # Arguments
+    /// * `features` - Training feature matrix (samples x features)
+    /// * `targets` - Positive-class probability of each sample (0.0 to 1.0)
+    /// * `epochs` - Number of training epochs
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use ndarray::array;
+    ///
+    /// let features = array![[true, false], [false, true]];
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10);
+    /// machine.fit_soft(&features, &array![0.9, 0.2], 10);
+    /// ```
+    pub fn fit_soft<S: AsBoolSamples + ?Sized>(&mut self, features: &S, targets: &Array1<f64>, epochs: usize) {
+        assert!(
+            targets.iter().all(|target| (0.0..=1.0).contains(target)),
+            "Soft targets must be probabilities in [0, 1]"
+        );
+
+        let labels = targets.mapv(|target| target >= 0.5);
+        self.train(
+            features,
+            &labels,
+            Some(targets),
+            epochs.into(),
+            &mut |_| ControlFlow::Continue(()),
+        );
+    }
+
+    /// Train on a single sample, for online learning from a stream
+    ///
+    /// Applies the same feedback as one step of `fit` (including any bit-flip
+    /// augmentation and randomized response) but runs no per-epoch hooks and
+    /// does not count an epoch. Under
+    /// [`differential_privacy`](Self::differential_privacy) every call spends
+    /// `epsilon`.
+    pub fn partial_fit(&mut self, sample: &[bool], label: bool) {
+        assert_eq!(sample.len(), self.num_features);
+
+        let mut sample = sample.to_vec();
+        if let Some(augmentation) = self.augmentation {
+            augmentation.flip_sample(&mut sample, &mut self.rng);
+        }
+        let target = label != self.privacy_flip();
+        if let Some(epsilon) = self.privacy_epsilon {
+            self.privacy_spent += epsilon;
+        }
+        self.clause_bank
+            .update(&sample, target, self.threshold, self.specificity, &mut self.rng);
+    }
+
+    /// Prequential (test-then-train) evaluation on a stream
+    ///
+    /// Every sample is first predicted and then trained on with
+    /// [`partial_fit`](Self::partial_fit), so each prediction is made on data
+    /// the machine has not seen yet. Returns, after each sample, the accuracy
+    /// of the last `window` predictions (fewer at the start of the stream).
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let stream = (0..100).map(|i| (vec![i % 2 == 0, i % 3 == 0], i % 2 == 0));
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1);
+    /// let curve = machine.evaluate_prequential(stream, 20);
+    /// assert_eq!(curve.len(), 100);
+    /// ```
+    pub fn evaluate_prequential<I, S>(&mut self, stream: I, window: usize) -> Vec<f64>
+    where
+        I: IntoIterator<Item = (S, bool)>,
+        S: AsRef<[bool]>,
+    {
+        assert!(window > 0, "Window must hold at least one prediction");
+
+        let mut recent = VecDeque::with_capacity(window);
+        let mut correct = 0;
+        let mut curve = Vec::new();
+        for (sample, label) in stream {
+            let sample = sample.as_ref();
+            let hit = self.predict_single(sample) == label;
+            if recent.len() == window && recent.pop_front() == Some(true) {
+                correct -= 1;
+            }
+            recent.push_back(hit);
+            correct += usize::from(hit);
+            curve.push(correct as f64 / recent.len() as f64);
+
+            self.partial_fit(sample, label);
+        }
+        curve
+    }
+
+    /// Shared training loop of `fit`, `fit_with_callback` and `fit_soft`
+    ///
+    /// `labels` drive the per-epoch hooks; `soft_targets`, when given, replace
+    /// them for clause feedback. `on_epoch` runs after every epoch and may
+    /// stop training.
+    fn train<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<bool>,
+        soft_targets: Option<&Array1<f64>>,
+        budget: TrainBudget,
+        on_epoch: &mut dyn FnMut(&TsetlinMachine) -> ControlFlow<()>,
+    ) -> usize {
+        assert_eq!(features.num_samples(), labels.len());
+        assert_eq!(features.num_features(), self.num_features);
+
+        let start = Instant::now();
+        
+        let num_samples = features.num_samples();
+        // Continue from the previous call's order so split runs match one long run
+        let mut indices = std::mem::take(&mut self.sample_order);
+        if indices.len() != num_samples {
+            indices = (0..num_samples).collect();
+        }
+
+        // Reused for every sample instead of allocating one vector each
+        let mut sample_features = Vec::with_capacity(self.num_features);
+        let mut completed = 0;
+        while completed < budget.max_epochs {
+            if budget
+                .max_seconds
+                .is_some_and(|seconds| start.elapsed().as_secs_f64() >= seconds)
+            {
+                break;
+            }
+            completed += 1;
+            if let Some(schedule) = self.threshold_schedule {
+                self.threshold = schedule.value(self.epochs_trained);
+                assert!(self.threshold > 0.0, "Scheduled threshold must be positive");
+            }
+            self.threshold_history.push(self.threshold);
+            let included: Vec<Vec<Literal>> =
+                self.clause_bank.clauses().iter().map(Clause::included_literals).collect();
+            let released = self
+                .privacy_epsilon
+                .map(|epsilon| self.release_labels(labels, soft_targets, epsilon));
+            let (labels, soft_targets) = match &released {
+                Some((labels, soft_targets)) => (labels, soft_targets.as_ref()),
+                None => (labels, soft_targets),
+            };
+
+            if self.sampler.as_ref().is_some_and(|s| s.wants_margins(self.epochs_trained)) {
+                let margins = self.vote_margins(features, labels);
+                if let Some(sampler) = &mut self.sampler {
+                    sampler.set_margins(&margins, self.epochs_trained);
+                }
+            }
+            if let Some(sampler) = &mut self.sampler {
+                indices = sampler.order(labels, self.epochs_trained, &mut self.rng);
+                assert!(
+                    indices.iter().all(|&idx| idx < num_samples),
+                    "Sampler returned an out-of-range sample index"
+                );
+            }
+
+            if let Some(batch_size) = self.batch_size {
+                if self.sampler.is_none() {
+                    indices = stratified_order(labels, &mut self.rng);
+                }
+                for batch in indices.chunks(batch_size) {
+                    self.train_batch(features, batch, labels, soft_targets);
+                }
+                self.end_epoch(features, labels, &included);
+                if on_epoch(self).is_break() {
+                    break;
+                }
+                continue;
+            }
+
+            // Shuffle samples
+            if self.sampler.is_none() {
+                indices.shuffle(&mut self.rng);
+            }
+            
+            // Train on each sample
+            for &idx in &indices {
+                features.sample_into(idx, &mut sample_features);
+                if self.fairness_skip(&sample_features, labels[idx]) {
+                    continue;
+                }
+                if let Some(augmentation) = self.augmentation {
+                    augmentation.flip_sample(&mut sample_features, &mut self.rng);
+                }
+
+                match soft_targets {
+                    None => self.clause_bank.update(
+                        &sample_features,
+                        labels[idx],
+                        self.threshold,
+                        self.specificity,
+                        &mut self.rng,
+                    ),
+                    Some(targets) => {
+                        let probability = targets[idx];
+                        for (target, strength) in [(true, probability), (false, 1.0 - probability)] {
+                            if strength > 0.0 {
+                                self.clause_bank.update_scaled(
+                                    &sample_features,
+                                    target,
+                                    strength,
+                                    self.threshold,
+                                    self.specificity,
+                                    &mut self.rng,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            self.end_epoch(features, labels, &included);
+            if on_epoch(self).is_break() {
+                break;
+            }
+        }
+
+        self.sample_order = indices;
+        completed
+    }
+
+    /// Whether randomized response flips the next feedback target
+    fn privacy_flip(&mut self) -> bool {
+        self.privacy_epsilon.is_some_and(|epsilon| {
+            let flip_probability = 1.0 / (1.0 + epsilon.exp());
+            self.rng.gen::<f64>() < flip_probability
+        })
+    }
+
+    /// Whether the fairness constraint withholds feedback on a sample
+    ///
+    /// Only the rate of the last epoch matters: feedback that would widen its
+    /// disparity is skipped with the current strength.
+    fn fairness_skip(&mut self, sample: &[bool], label: bool) -> bool {
+        let (Some(constraint), Some(last)) = (self.fairness, self.fairness_history.last()) else {
+            return false;
+        };
+        if last.strength == 0.0 {
+            return false;
+        }
+        let favoured = sample[constraint.protected_feature] == (last.disparity > 0.0);
+        label == favoured && self.rng.gen::<f64>() < last.strength
+    }
+
+    /// Measure the disparity of the training predictions and adapt the fairness strength
+    fn record_fairness<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<bool>,
+        constraint: FairnessConstraint,
+    ) {
+        let mut sample = Vec::with_capacity(self.num_features);
+        let groups: Vec<bool> = (0..features.num_samples())
+            .map(|idx| {
+                features.sample_into(idx, &mut sample);
+                sample[constraint.protected_feature]
+            })
+            .collect();
+        let report = GroupedReport::new(&self.predict(features), labels, &groups);
+        let disparity = match (report.get(&true), report.get(&false)) {
+            (Some(inside), Some(outside)) => inside.positive_rate - outside.positive_rate,
+            _ => 0.0,
+        };
+
+        let strength = self.fairness_history.last().map_or(0.0, |last| last.strength);
+        let strength = if disparity.abs() > constraint.max_disparity {
+            (strength + FAIRNESS_STEP).min(1.0)
+        } else {
+            (strength - FAIRNESS_STEP).max(0.0)
+        };
+        let accuracy = report.overall().accuracy;
+        self.fairness_history.push(FairnessEpoch { disparity, accuracy, strength });
+    }
+
+    /// Release every label once by randomized response and spend `epsilon`
+    ///
+    /// A flipped sample also has its soft target mirrored, so hard and soft
+    /// targets stay consistent.
+    fn release_labels(
+        &mut self,
+        labels: &Array1<bool>,
+        soft_targets: Option<&Array1<f64>>,
+        epsilon: f64,
+    ) -> (Array1<bool>, Option<Array1<f64>>) {
+        let flips: Vec<bool> = (0..labels.len()).map(|_| self.privacy_flip()).collect();
+        let labels = Array1::from_shape_fn(labels.len(), |idx| labels[idx] != flips[idx]);
+        let soft_targets = soft_targets.map(|targets| {
+            Array1::from_shape_fn(targets.len(), |idx| {
+                if flips[idx] { 1.0 - targets[idx] } else { targets[idx] }
+            })
+        });
+        self.privacy_spent += epsilon;
+        (labels, soft_targets)
+    }
+
+    /// Vote sum of each sample, signed so that correct votes are positive
+    fn vote_margins<S: AsBoolSamples + ?Sized>(&self, features: &S, labels: &Array1<bool>) -> Vec<i32> {
+        let votes = self.predict_votes(features);
+        votes
+            .iter()
+            .zip(labels)
+            .map(|(&vote, &label)| if label { vote } else { -vote })
+            .collect()
+    }
+
+    /// Give feedback for one mini-batch of sample indices
+    fn train_batch<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        batch: &[usize],
+        labels: &Array1<bool>,
+        soft_targets: Option<&Array1<f64>>,
+    ) {
+        let mut kept = Vec::with_capacity(batch.len());
+        let mut samples = Vec::with_capacity(batch.len());
+        for &idx in batch {
+            let mut sample = features.sample(idx);
+            if self.fairness_skip(&sample, labels[idx]) {
+                continue;
+            }
+            if let Some(augmentation) = self.augmentation {
+                augmentation.flip_sample(&mut sample, &mut self.rng);
+            }
+            kept.push(idx);
+            samples.push(sample);
+        }
+
+        let mut entries: Vec<(&[bool], bool, f64)> = Vec::with_capacity(2 * kept.len());
+        for (&idx, sample) in kept.iter().zip(&samples) {
+            match soft_targets {
+                None => entries.push((sample, labels[idx], 1.0)),
+                Some(targets) => {
+                    let probability = targets[idx];
+                    for (target, strength) in [(true, probability), (false, 1.0 - probability)] {
+                        if strength > 0.0 {
+                            entries.push((sample, target, strength));
+                        }
+                    }
+                }
+            }
+        }
+        if self.bit_parallel {
+            self.clause_bank
+                .update_batch_packed(&entries, self.threshold, self.specificity, &mut self.rng);
+        } else {
+            self.clause_bank
+                .update_batch(&entries, self.threshold, self.specificity, &mut self.rng);
+        }
+    }
+
+    /// Per-epoch hooks: churn, clause growth, prune-and-regrow, diversity penalty and fairness
+    ///
+    /// `included` holds the included literals of every clause at the start of
+    /// the epoch.
+    fn end_epoch<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        labels: &Array1<bool>,
+        included: &[Vec<Literal>],
+    ) {
+        self.clause_churn = self.clause_bank.include_churn(included);
+        self.churn_history.push(self.clause_churn.iter().sum());
+
+        if let Some(growth) = self.auto_grow {
+            let accuracy = self.evaluate(features, labels);
+            let (best_accuracy, epochs_without_improvement) = &mut self.growth_state;
+            if accuracy > *best_accuracy {
+                *best_accuracy = accuracy;
+                *epochs_without_improvement = 0;
+            } else {
+                *epochs_without_improvement += 1;
+            }
+
+            if *epochs_without_improvement >= growth.patience
+                && self.num_clauses + 2 <= growth.max_clauses
+            {
+                *epochs_without_improvement = 0;
+                self.clause_bank.add_clause_pair(&mut self.rng);
+                self.num_clauses += 2;
+                self.rescale_threshold();
+            }
+        }
+
+        if let Some(regrow) = self.prune_regrow {
+            if (self.epochs_trained + 1).is_multiple_of(regrow.interval) {
+                self.regrow_below(features, labels, regrow.min_precision);
+            }
+        } else if self.polarity_flip {
+            let stats = self.clause_stats(features, labels);
+            for (index, stat) in stats.iter().enumerate() {
+                self.flip_if_wrong(index, stat);
+            }
+        }
+
+        if let Some(strength) = self.diversity_penalty {
+            self.clause_bank.apply_diversity_penalty(strength, &mut self.rng);
+        }
+
+        if let Some(constraint) = self.fairness {
+            self.record_fairness(features, labels, constraint);
+        }
+
+        self.epochs_trained += 1;
+    }
+
+    /// Make predictions on a dataset
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix (samples x features)
+    ///
+    /// # Returns
+    /// Array of boolean predictions
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<bool> {
+        assert_eq!(features.num_features(), self.num_features);
+
+        #[cfg(feature = "parallel")]
+        if features.num_samples() >= crate::parallel::MIN_PARALLEL_ROWS {
+            return self.predict_votes(features).mapv(|vote| vote > 0);
+        }
+        
+        let mut predictions = Array1::from_elem(features.num_samples(), false);
+        let mut sample_features = Vec::with_capacity(self.num_features);
+        
+        for (i, prediction) in predictions.iter_mut().enumerate() {
+            features.sample_into(i, &mut sample_features);
+            let vote = self.clause_bank.vote(&sample_features);
+            *prediction = vote > 0;
+        }
+        
+        predictions
+    }
+
+    /// Predict every sample as a label of type `L`, e.g. 0/1 as `u8`
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(2, 10);
+    /// let predictions = machine.predict_labels::<_, u8>(&array![[true, false]]);
+    /// assert_eq!(predictions, array![0]);
+    /// ```
+    pub fn predict_labels<S: AsBoolSamples + ?Sized, L: BinaryLabel>(
+        &self,
+        features: &S,
+    ) -> Array1<L> {
+        self.predict(features).mapv(L::from_bool)
+    }
+
+    /// Make a prediction on a single sample
+    pub fn predict_single(&self, features: &[bool]) -> bool {
+        assert_eq!(features.len(), self.num_features);
+        
+        let vote = self.clause_bank.vote(features);
+        vote > 0
+    }
+
+    /// Compute the raw vote sum for each sample in a dataset
+    ///
+    /// Positive sums favour the positive class; `predict` thresholds them at zero.
+    pub fn predict_votes<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<i32> {
+        assert_eq!(features.num_features(), self.num_features);
+
+        #[cfg(feature = "parallel")]
+        if features.num_samples() >= crate::parallel::MIN_PARALLEL_ROWS {
+            let bank = &self.clause_bank;
+            return crate::parallel::vote_rows(features, |row| bank.vote(row));
+        }
+
+        (0..features.num_samples())
+            .map(|i| self.clause_bank.vote(&features.sample(i)))
+            .collect()
+    }
+
+    /// Positive-class probability of each sample
+    ///
+    /// The vote sum is clipped to `[-threshold, threshold]`, the range in
+    /// which training shapes it, and mapped linearly onto `[0, 1]`. Check the
+    /// mapping with [`metrics::calibration_curve`](crate::metrics::calibration_curve)
+    /// before relying on it.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use ndarray::array;
+    ///
+    /// // Untrained: the empty clauses' votes cancel out
+    /// let machine = TsetlinMachine::new(2, 10, 3.0, 5.0);
+    /// assert_eq!(machine.predict_proba(&array![[true, false]]), array![0.5]);
+    /// ```
+    pub fn predict_proba<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<f64> {
+        self.predict_votes(features).mapv(|vote| {
+            let clipped = f64::from(vote).clamp(-self.threshold, self.threshold);
+            (clipped + self.threshold) / (2.0 * self.threshold)
+        })
+    }
+
+    /// Compute the raw vote sum for a single sample
+    pub fn vote_single(&self, features: &[bool]) -> i32 {
+        assert_eq!(features.len(), self.num_features);
+
+        self.clause_bank.vote(features)
+    }
+
+    /// Make predictions with some features unavailable
+    ///
+    /// Features with `mask[i] == false` are treated as unknown: neither of
+    /// their literals is satisfied, so clauses that use them do not fire.
+    /// Comparing against `predict` measures the effect of ablating features,
+    /// and models can still be served when some inputs are missing.
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix (samples x features)
+    /// * `mask` - Per-feature availability (true = known)
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// use ndarray::array;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(3, 10);
+    /// let predictions = machine.predict_masked(&array![[true, false, true]], &[true, false, true]);
+    /// assert_eq!(predictions.len(), 1);
+    /// ```
+    pub fn predict_masked(&self, features: &Array2<bool>, mask: &[bool]) -> Array1<bool> {
+        self.predict_votes_masked(features, mask).mapv(|vote| vote > 0)
+    }
+
+    /// Raw vote sums with some features unavailable (see `predict_masked`)
+    pub fn predict_votes_masked(&self, features: &Array2<bool>, mask: &[bool]) -> Array1<i32> {
+        assert_eq!(features.ncols(), self.num_features);
+        assert_eq!(mask.len(), self.num_features, "Mask length must match the number of features");
+
+        features
+            .rows()
+            .into_iter()
+            .map(|row| self.clause_bank.vote_masked(&row.to_vec(), mask))
+            .collect()
+    }
+
+    /// Evaluate the model on a dataset
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix
+    /// * `labels` - Target labels
+    ///
+    /// # Returns
+    /// Accuracy score (0.0 to 1.0)
+    pub fn evaluate<S: AsBoolSamples + ?Sized, L: BinaryLabel>(
+        &self,
+        features: &S,
+        labels: &Array1<L>,
+    ) -> f64 {
+        let predictions = self.predict(features);
+        let correct = predictions
+            .iter()
+            .zip(labels.iter())
+            .filter(|(&pred, &actual)| pred == actual.to_bool())
+            .count();
+        
+        correct as f64 / labels.len() as f64
+    }
+
+    /// Number of clauses frozen by `from_pretrained`
+    pub fn num_frozen(&self) -> usize {
+        self.clause_bank.num_frozen()
+    }
+
+    /// Unfreeze every clause so all of them receive feedback again
+    pub fn unfreeze_all(&mut self) {
+        self.clause_bank.unfreeze_all();
+    }
+
+    /// Clause bank containing all clauses
+    pub fn clause_bank(&self) -> &ClauseBank {
+        &self.clause_bank
+    }
+
+    /// Mutable access to the clause bank for direct clause edits
+    pub fn clause_bank_mut(&mut self) -> &mut ClauseBank {
+        &mut self.clause_bank
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Number of clauses
+    pub fn num_clauses(&self) -> usize {
+        self.num_clauses
+    }
+
+    /// Specificity parameter
+    pub fn specificity(&self) -> f64 {
+        self.specificity
+    }
+
+    /// Decision threshold
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Threshold used in each epoch run by `fit`
+    pub fn threshold_history(&self) -> &[f64] {
+        &self.threshold_history
+    }
+
+    /// Literals that crossed the include boundary in each epoch run by `fit`
+    ///
+    /// Counts inclusions and exclusions caused by feedback, before the
+    /// per-epoch hooks such as prune-and-regrow. A count that stays near zero
+    /// means the clauses have converged; a high count means they still
+    /// oscillate.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1);
+    /// machine.fit(&features, &labels, 5);
+    /// assert_eq!(machine.churn_history().len(), 5);
+    /// assert_eq!(machine.clause_churn().len(), 10);
+    /// ```
+    pub fn churn_history(&self) -> &[usize] {
+        &self.churn_history
+    }
+
+    /// Literals of each clause that crossed the include boundary in the last epoch
+    pub fn clause_churn(&self) -> &[usize] {
+        &self.clause_churn
+    }
+
+    /// Seed of the random number generator, if set
+    pub fn random_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Randomized-response epsilon of feedback targets, if private training is enabled
+    pub fn privacy_epsilon(&self) -> Option<f64> {
+        self.privacy_epsilon
+    }
+
+    /// Demographic parity bound of `fit`, if enabled
+    pub fn fairness(&self) -> Option<FairnessConstraint> {
+        self.fairness
+    }
+
+    /// Disparity, training accuracy and feedback strength after each constrained epoch
+    pub fn fairness_history(&self) -> &[FairnessEpoch] {
+        &self.fairness_history
+    }
+
+    /// Total privacy budget spent by training under sequential composition
+    pub fn privacy_spent(&self) -> f64 {
+        self.privacy_spent
+    }
+
+    /// Mini-batch size used by `fit`, if training in batches
+    pub fn training_batch_size(&self) -> Option<usize> {
+        self.batch_size
+    }
+
+    /// Number of epochs run by `fit` (including those of a pretrained source)
+    pub fn epochs_trained(&self) -> usize {
+        self.epochs_trained
+    }
+
+    /// Total number of included literals across all clauses
+    pub fn num_included_literals(&self) -> usize {
+        self.clause_bank.clauses().iter().map(Clause::num_included).sum()
+    }
+
+    /// Approximate memory held by the clause bank, in bytes
+    pub fn estimated_memory_bytes(&self) -> usize {
+        self.memory_footprint().total_bytes()
+    }
+
+    /// Memory used by the automata and clauses, with model size statistics
+    ///
+    /// Lets embedded users check whether a model, or its compiled
+    /// inference-only form, fits their RAM budget before exporting it.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(64, 10);
+    /// let footprint = machine.memory_footprint();
+    /// assert_eq!(footprint.non_empty_clauses, 0);
+    /// assert!(footprint.compiled_bytes < footprint.total_bytes());
+    /// ```
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let clauses = self.clause_bank.clauses();
+        let num_automata: usize = clauses.iter().map(|clause| 2 * clause.num_features()).sum();
+        let subset_features: usize = clauses
+            .iter()
+            .map(|clause| clause.feature_subset().map_or(0, <[usize]>::len))
+            .sum();
+        let per_clause = std::mem::size_of::<Clause>()
+            + 2 * std::mem::size_of::<bool>()
+            + std::mem::size_of::<u32>();
+
+        MemoryFootprint {
+            automata_bytes: num_automata * std::mem::size_of::<TsetlinAutomaton>(),
+            clause_bytes: clauses.len() * per_clause
+                + subset_features * std::mem::size_of::<usize>(),
+            included_literals: self.num_included_literals(),
+            non_empty_clauses: clauses.iter().filter(|clause| clause.num_included() > 0).count(),
+            compiled_bytes: self.compile().memory_bytes(),
+        }
+    }
+
+    /// Check internal invariants of the machine and its clause bank
+    ///
+    /// Automaton states must lie within `±num_states`, the per-clause vectors
+    /// must match the number of clauses and every clause must cover the
+    /// machine's features (see [`ClauseBank::debug_validate`]). Building with
+    /// the `validate` feature runs the bank checks after every update, which
+    /// makes property tests and fuzzing of the training loop practical.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10);
+    /// machine.fit(&features, &labels, 5);
+    /// assert_eq!(machine.debug_validate(), Ok(()));
+    /// ```
+    pub fn debug_validate(&self) -> Result<(), String> {
+        if self.clause_bank.num_clauses() != self.num_clauses {
+            return Err(format!(
+                "machine expects {} clauses but the bank has {}",
+                self.num_clauses,
+                self.clause_bank.num_clauses()
+            ));
+        }
+        if self.clause_bank.num_features() != self.num_features {
+            return Err(format!(
+                "machine expects {} features but the bank has {}",
+                self.num_features,
+                self.clause_bank.num_features()
+            ));
+        }
+        self.clause_bank.debug_validate()
+    }
+
+    /// Stable digest of the hyperparameters and learned state
+    ///
+    /// Covers the feature and clause counts, specificity, threshold, number
+    /// of states, weight learning, every clause's polarity, weight, frozen
+    /// flag, feature subset and automaton states, and the clause bank's
+    /// settings: noise tolerance, bag aggregation, forbidden literals, ordinal
+    /// groups and feature-bagging size. Everything is hashed with 64-bit
+    /// FNV-1a. Training options of the machine and the random number
+    /// generator are not included, so two machines with the same learned model
+    /// hash alike on every platform. Print it with `{:016x}` for registries
+    /// and CI logs.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let machine = TsetlinMachine::with_defaults(4, 10);
+    /// assert_eq!(machine.state_hash(), machine.clone().state_hash());
+    /// assert_ne!(machine.state_hash(), TsetlinMachine::with_defaults(4, 12).state_hash());
+    /// ```
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        let bank = &self.clause_bank;
+        hasher.write_u64(self.num_features as u64);
+        hasher.write_u64(self.num_clauses as u64);
+        hasher.write_u64(self.specificity.to_bits());
+        hasher.write_u64(self.threshold.to_bits());
+        hasher.write_u64(u64::from(bank.num_states()));
+        hasher.write_u64(match bank.weight_learning() {
+            WeightLearning::None => 0,
+            WeightLearning::Integer { max_weight } => 1 + u64::from(max_weight),
+        });
+
+        for (index, clause) in bank.clauses().iter().enumerate() {
+            hasher.write_u64(u64::from(bank.polarities()[index]));
+            hasher.write_u64(u64::from(bank.weights()[index]));
+            hasher.write_u64(u64::from(bank.is_frozen(index)));
+            match clause.feature_subset() {
+                None => hasher.write_u64(u64::MAX),
+                Some(subset) => {
+                    hasher.write_u64(subset.len() as u64);
+                    subset.iter().for_each(|&feature| hasher.write_u64(feature as u64));
+                }
+            }
+            for feature in 0..clause.num_features() {
+                for negated in [false, true] {
+                    let state = clause.literal_state(Literal { feature, negated });
+                    hasher.write_u64(u64::from(state as u32));
+                }
+            }
+        }
+
+        hasher.write_u64(bank.noise_tolerance().to_bits());
+        hasher.write_u64(match bank.aggregation() {
+            Aggregation::Sum => 0,
+            Aggregation::OrPool => 1,
+            Aggregation::MaxMargin => 2,
+        });
+        hasher.write_u64(bank.forbidden_literals().len() as u64);
+        for literal in bank.forbidden_literals() {
+            hasher.write_u64(2 * literal.feature as u64 + u64::from(literal.negated));
+        }
+        hasher.write_u64(bank.ordinal_groups().len() as u64);
+        for group in bank.ordinal_groups() {
+            hasher.write_u64(group.start as u64);
+            hasher.write_u64(group.end as u64);
+        }
+        hasher.write_u64(bank.features_per_clause().map_or(u64::MAX, |size| size as u64));
+        hasher.finish()
+    }
+
+    /// Human-readable model summary
+    ///
+    /// Lists the feature and clause counts, hyperparameters, number of included
+    /// literals, estimated memory and training status. `Display` prints the same
+    /// text.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::TsetlinMachine;
+    /// let machine = TsetlinMachine::with_defaults(4, 10);
+    /// assert!(machine.summary().contains("Training status:    untrained"));
+    /// ```
+    pub fn summary(&self) -> String {
+        let (num_positive, num_negative) = self.clause_bank.polarity_counts();
+        let num_literals = self.num_included_literals();
+        let status = if self.epochs_trained > 0 {
+            format!("trained for {} epochs", self.epochs_trained)
+        } else if num_literals > 0 {
+            "trained (epochs unknown)".to_string()
+        } else {
+            "untrained".to_string()
+        };
+
+        let mut lines = vec![
+            "TsetlinMachine".to_string(),
+            "=".repeat(40),
+            format!("Features:           {}", self.num_features),
+            format!(
+                "Clauses:            {} ({} positive, {} negative)",
+                self.num_clauses,
+                num_positive,
+                num_negative
+            ),
+            format!("Specificity:        {}", self.specificity),
+            match self.threshold_fraction {
+                Some(fraction) => format!(
+                    "Threshold:          {} (auto, {fraction} x clauses / 2)",
+                    self.threshold
+                ),
+                None => format!("Threshold:          {}", self.threshold),
+            },
+            format!("States per action:  {}", self.clause_bank.num_states()),
+            format!(
+                "Included literals:  {} of {}",
+                num_literals,
+                2 * self.num_features * self.num_clauses
+            ),
+        ];
+        if self.num_frozen() > 0 {
+            lines.push(format!("Frozen clauses:     {}", self.num_frozen()));
+        }
+        if let Some(growth) = self.auto_grow {
+            lines.push(format!(
+                "Auto-grow:          up to {} clauses, patience {}",
+                growth.max_clauses, growth.patience
+            ));
+        }
+        if let Some(regrow) = self.prune_regrow {
+            lines.push(format!(
+                "Prune and regrow:   precision < {} every {} epochs",
+                regrow.min_precision, regrow.interval
+            ));
+        }
+        if self.polarity_flip {
+            lines.push(format!("Polarity flips:     {}", self.num_flipped));
+        }
+        if !self.clause_bank.forbidden_literals().is_empty() {
+            let forbidden: Vec<String> = self
+                .clause_bank
+                .forbidden_literals()
+                .iter()
+                .map(|literal| {
+                    let not = if literal.negated { "NOT " } else { "" };
+                    format!("{not}x{}", literal.feature)
+                })
+                .collect();
+            lines.push(format!("Forbidden literals: {}", forbidden.join(", ")));
+        }
+        if !self.clause_bank.ordinal_groups().is_empty() {
+            lines.push(format!(
+                "Ordinal groups:     {}",
+                self.clause_bank.ordinal_groups().len()
+            ));
+        }
+        if let Some(schedule) = self.threshold_schedule {
+            lines.push(format!("Threshold schedule: {schedule}"));
+        }
+        if let Some(metric) = self.keep_best {
+            lines.push(format!("Keep best:          {metric:?} on validation data"));
+        }
+        if let Some(strength) = self.diversity_penalty {
+            lines.push(format!("Diversity penalty:  {strength}"));
+        }
+        if let Some(augmentation) = self.augmentation {
+            lines.push(format!("Bit-flip rate:      {}", augmentation.probability));
+        }
+        if let Some(batch_size) = self.batch_size {
+            let packing = if self.bit_parallel { " (bit-parallel)" } else { "" };
+            lines.push(format!("Batch size:         {batch_size}{packing}"));
+        }
+        if let Some(epsilon) = self.privacy_epsilon {
+            lines.push(format!(
+                "Label privacy:      epsilon {epsilon} per release (spent {})",
+                self.privacy_spent
+            ));
+        }
+        if let Some(constraint) = self.fairness {
+            let disparity = self.fairness_history.last().map_or(0.0, |last| last.disparity);
+            lines.push(format!(
+                "Fairness:           |disparity| <= {} on feature {} (last {disparity:+.3})",
+                constraint.max_disparity, constraint.protected_feature
+            ));
+        }
+        if let Some(seed) = self.seed {
+            lines.push(format!("Seed:               {seed}"));
+        }
+        if let WeightLearning::Integer { max_weight } = self.clause_bank.weight_learning() {
+            let total: u64 = self.clause_bank.weights().iter().map(|&w| u64::from(w)).sum();
+            lines.push(format!(
+                "Weight learning:    integer up to {max_weight} (total weight {total})"
+            ));
+        }
+        if let Some(size) = self.clause_bank.features_per_clause() {
+            lines.push(format!("Feature bagging:    {size} features per clause"));
+        }
+        if self.clause_bank.noise_tolerance() > 0.0 {
+            lines.push(format!("Noise tolerance:    {}", self.clause_bank.noise_tolerance()));
+        }
+        lines.push(format!(
+            "Estimated memory:   {}",
+            format_bytes(self.estimated_memory_bytes())
+        ));
+        lines.push(format!("Training status:    {status}"));
+        lines.join("\n")
+    }
+}
+
+/// 64-bit FNV-1a hash over little-endian words, identical on every platform
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Shuffle each class and interleave them in proportion
+///
+/// Every prefix of the order, and so every consecutive mini-batch, holds
+/// about the same class ratio as the whole dataset.
+fn stratified_order<R: rand::Rng>(labels: &Array1<bool>, rng: &mut R) -> Vec<usize> {
+    let (mut positive, mut negative): (Vec<usize>, Vec<usize>) =
+        (0..labels.len()).partition(|&i| labels[i]);
+    positive.shuffle(rng);
+    negative.shuffle(rng);
+
+    let mut order = Vec::with_capacity(labels.len());
+    let (mut p, mut n) = (0, 0);
+    while p < positive.len() || n < negative.len() {
+        // Take from the class that is furthest behind its share
+        let take_positive = n == negative.len()
+            || (p < positive.len() && p * negative.len() <= n * positive.len());
+        if take_positive {
+            order.push(positive[p]);
+            p += 1;
+        } else {
+            order.push(negative[n]);
+            n += 1;
+        }
+    }
+    order
+}
+
+impl fmt::Display for TsetlinMachine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+/// Format a byte count with a binary unit
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampler::Weighted;
+
+    #[test]
+    fn test_machine_creation() {
+        let machine = TsetlinMachine::new(5, 10, 2.0, 1.0);
+        assert_eq!(machine.num_features, 5);
+        assert_eq!(machine.num_clauses, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold must be positive")]
+    fn test_machine_rejects_zero_threshold() {
+        TsetlinMachine::new(2, 10, 2.0, 0.0);
+    }
+
+    #[test]
+    fn test_machine_with_defaults() {
+        let machine = TsetlinMachine::with_defaults(5, 10);
+        assert_eq!(machine.num_features, 5);
+        assert_eq!(machine.num_clauses, 10);
+    }
+
+    #[test]
+    fn test_machine_training() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+        
+        let mut machine = TsetlinMachine::with_defaults(2, 10);
+        machine.fit(&features, &labels, 10);
+        
+        let predictions = machine.predict(&features);
+        assert_eq!(predictions.len(), 4);
+    }
+
+    #[test]
+    #[allow(clippy::bool_comparison)]
+    fn test_machine_prediction() {
+        let features = Array2::from_shape_vec((2, 2), vec![
+            true, false, false, true
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false]);
+        
+        let mut machine = TsetlinMachine::with_defaults(2, 10);
+        machine.fit(&features, &labels, 10);
+        
+        let prediction = machine.predict_single(&[true, false]);
+        assert!(prediction == true || prediction == false);
+    }
+
+    #[test]
+    #[allow(clippy::manual_range_contains)]
+    fn test_machine_evaluation() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+        
+        let mut machine = TsetlinMachine::with_defaults(2, 20);
+        machine.fit(&features, &labels, 50);
+        
+        let accuracy = machine.evaluate(&features, &labels);
+        assert!(accuracy >= 0.0 && accuracy <= 1.0);
+    }
+
+    #[test]
+    fn test_machine_votes_match_predictions() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 10);
+        machine.fit(&features, &labels, 10);
+
+        let votes = machine.predict_votes(&features);
+        let predictions = machine.predict(&features);
+        for (&vote, &prediction) in votes.iter().zip(predictions.iter()) {
+            assert_eq!(vote > 0, prediction);
+        }
+        assert_eq!(machine.vote_single(&[true, false]), votes[0]);
+    }
+
+    #[test]
+    fn test_from_pretrained() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut pretrained = TsetlinMachine::with_defaults(2, 10);
+        pretrained.fit(&features, &labels, 10);
+
+        let mut machine = TsetlinMachine::from_pretrained(&pretrained, 3.0, 0.4);
+        assert_eq!(machine.threshold(), 3.0);
+        assert_eq!(machine.num_frozen(), 4);
+        assert_eq!(machine.predict_votes(&features), pretrained.predict_votes(&features));
+
+        machine.fit(&features, &labels, 5);
+        machine.unfreeze_all();
+        assert_eq!(machine.num_frozen(), 0);
+    }
+
+    #[test]
+    fn test_extend_features() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 10);
+        machine.fit(&features, &labels, 10);
+        let vote = machine.vote_single(&[true, false]);
+
+        machine.extend_features(1);
+        assert_eq!(machine.num_features(), 3);
+        assert_eq!(machine.vote_single(&[true, false, true]), vote);
+        assert_eq!(machine.vote_single(&[true, false, false]), vote);
+
+        let extended = Array2::from_shape_vec((4, 3), vec![
+            true, false, true, false, true, false, true, true, true, false, false, false
+        ]).unwrap();
+        machine.fit(&extended, &labels, 10);
+        assert_eq!(machine.predict(&extended).len(), 4);
+    }
+
+    #[test]
+    fn test_auto_grow() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 2).auto_grow(6, 1);
+        machine.fit(&features, &labels, 30);
+
+        assert!(machine.num_clauses() >= 2 && machine.num_clauses() <= 6);
+        assert_eq!(machine.clause_bank().num_clauses(), machine.num_clauses());
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum number of clauses must not be below the current count")]
+    fn test_auto_grow_below_current() {
+        let _ = TsetlinMachine::with_defaults(2, 10).auto_grow(4, 1);
+    }
+
+    #[test]
+    fn test_prune_and_regrow() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 10).prune_and_regrow(0.5, 5);
+        machine.fit(&features, &labels, 20);
+        assert_eq!(machine.num_clauses(), 10);
+
+        // A precision above 1.0 is impossible, so every clause gets replaced
+        let before = machine.num_regrown();
+        assert_eq!(machine.regrow_below(&features, &labels, 1.1), 10);
+        assert_eq!(machine.num_regrown(), before + 10);
+        assert_eq!(machine.clause_stats(&features, &labels).len(), 10);
+    }
+
+    #[test]
+    fn test_regrow_skips_frozen_clauses() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1);
+        let literal = Literal { feature: 1, negated: false };
+        machine.clause_bank_mut().clause_mut(0).set_included(literal, true);
+        machine.clause_bank_mut().set_frozen(0, true);
+
+        assert_eq!(machine.regrow_below(&features, &labels, 1.1), 9);
+        assert!(machine.clause_bank().is_frozen(0));
+        assert_eq!(machine.clause_bank().clauses()[0].included_literals(), vec![literal]);
+    }
+
+    #[test]
+    fn test_auto_hyperparameters() {
+        let balanced = DatasetStats { num_samples: 4, positive_fraction: 0.5 };
+        let machine = TsetlinMachine::auto(2, &balanced);
+        assert_eq!(machine.num_clauses(), 10);
+        assert_eq!(machine.clause_bank().polarity_counts(), (5, 5));
+        assert_eq!(machine.clause_bank().num_states(), 100);
+
+        let large = DatasetStats { num_samples: 60_000, positive_fraction: 0.8 };
+        let machine = TsetlinMachine::auto(784, &large);
+        assert_eq!(machine.num_clauses(), 280);
+        assert_eq!(machine.clause_bank().polarity_counts(), (70, 210));
+        assert_eq!(machine.clause_bank().num_states(), 255);
+        assert_eq!((machine.specificity(), machine.threshold()), (10.0, 17.0));
+    }
+
+    #[test]
+    fn test_threshold_schedule_spans_fit_calls() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let schedule = Schedule::Step { start: 1.0, factor: 2.0, every: 2 };
+        let mut machine = TsetlinMachine::with_defaults(2, 4).seed(3).threshold_schedule(schedule);
+        machine.fit(&features, &labels, 3);
+        machine.fit(&features, &labels, 2);
+
+        assert_eq!(machine.threshold_history(), &[1.0, 1.0, 2.0, 2.0, 4.0]);
+        assert!(machine.summary().contains("Threshold schedule: 1, x2 every 2 epochs"));
+    }
+
+    #[test]
+    fn test_literal_constraints_hold_during_training() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let seeded = |machine: TsetlinMachine| {
+            let mut machine = machine.seed(2);
+            let bank = machine.clause_bank_mut();
+            bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+            bank.clause_mut(2).set_included(Literal { feature: 0, negated: true }, true);
+            bank.clause_mut(3).set_included(Literal { feature: 1, negated: false }, true);
+            machine
+        };
+
+        let mut free = seeded(TsetlinMachine::with_defaults(2, 4));
+        free.fit(&features, &labels, 20);
+        assert!(free.feature_usage()[1] > 0);
+
+        let mut constrained =
+            seeded(TsetlinMachine::with_defaults(2, 4).always_exclude(1).require_positive_only(0));
+        constrained.fit(&features, &labels, 20);
+        assert_eq!(constrained.feature_usage()[1], 0);
+        let negated_x0 = Literal { feature: 0, negated: true };
+        assert!(constrained.clause_bank().clauses().iter().all(|clause| {
+            !clause.included_literals().contains(&negated_x0)
+        }));
+        assert!(constrained.summary().contains("Forbidden literals: NOT x0, x1, NOT x1"));
+    }
+
+    #[test]
+    fn test_churn_tracks_include_changes() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4).seed(3);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 1, negated: true }, true);
+        machine.fit(&features, &labels, 20);
+
+        let history = machine.churn_history();
+        assert_eq!(history.len(), 20);
+        assert!(history.iter().any(|&churn| churn > 0));
+        assert_eq!(machine.clause_churn().iter().sum::<usize>(), history[19]);
+    }
+
+    #[test]
+    fn test_integer_labels_train_like_booleans() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let build = || {
+            let mut machine = TsetlinMachine::with_defaults(2, 4).seed(4);
+            let x0 = Literal { feature: 0, negated: false };
+            machine.clause_bank_mut().clause_mut(0).set_included(x0, true);
+            machine
+        };
+        let mut boolean = build();
+        boolean.fit(&features, &labels, 5);
+        let mut integer = build();
+        integer.fit(&features, &labels.mapv(u8::from), 5);
+        let mut signed = build();
+        signed.fit(&features, &labels.mapv(i8::from), 5);
+
+        assert_ne!(boolean.state_hash(), build().state_hash());
+        assert_eq!(integer.state_hash(), boolean.state_hash());
+        assert_eq!(signed.state_hash(), boolean.state_hash());
+        let accuracy = boolean.evaluate(&features, &labels);
+        assert_eq!(integer.evaluate(&features, &labels.mapv(u8::from)), accuracy);
+        let predictions = boolean.predict(&features).mapv(i8::from);
+        assert_eq!(boolean.predict_labels::<_, i8>(&features), predictions);
+    }
+
+    #[test]
+    fn test_train_budget() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4).seed(1);
+
+        assert_eq!(machine.fit(&features, &labels, TrainBudget::new(5, 60.0)), 5);
+        // An exhausted time limit stops before the first epoch
+        assert_eq!(machine.fit(&features, &labels, TrainBudget::new(5, 0.0)), 0);
+        assert_eq!(machine.epochs_trained(), 5);
+    }
+
+    #[test]
+    fn test_keep_best_restores_best_epoch() {
+        // The clauses start out predicting the validation labels, and training
+        // on the opposite labels unlearns them
+        let features = Array2::from_shape_vec((4, 1), vec![true, false, true, false]).unwrap();
+        let train = Array1::from_vec(vec![false, true, false, true]);
+        let validation = Array1::from_vec(vec![true, false, true, false]);
+        let build = |keep_best: Option<Metric>| {
+            let mut machine = TsetlinMachine::with_defaults(1, 4).seed(0);
+            machine.keep_best = keep_best;
+            let bank = machine.clause_bank_mut();
+            bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+            bank.clause_mut(2).set_included(Literal { feature: 0, negated: true }, true);
+            machine
+        };
+
+        let mut plain = build(None);
+        let scores = plain.fit_with_validation(&features, &train, &features, &validation, 8);
+        assert_eq!((scores[0], scores[7]), (1.0, 0.5));
+        assert_eq!(plain.evaluate(&features, &validation), 0.5);
+
+        let mut kept = build(Some(Metric::Accuracy));
+        assert_eq!(kept.fit_with_validation(&features, &train, &features, &validation, 8), scores);
+        assert_eq!(kept.evaluate(&features, &validation), 1.0);
+        assert_eq!(kept.epochs_trained(), 8);
+    }
+
+    #[test]
+    fn test_polarity_flip() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        // Clause 0 (positive) fires on NOT x0, which only negative samples have
+        let mut machine = TsetlinMachine::with_defaults(2, 4).polarity_flip(true);
+        let literal = Literal { feature: 0, negated: true };
+        machine.clause_bank_mut().clause_mut(0).set_included(literal, true);
+        assert_eq!(machine.clause_stats(&features, &labels)[0].precision(), 0.0);
+
+        // Empty clauses fire everywhere with precision 0.5 and are replaced
+        assert_eq!(machine.regrow_below(&features, &labels, 0.6), 3);
+        assert_eq!(machine.num_flipped(), 1);
+        assert!(!machine.clause_bank().polarities()[0]);
+        assert_eq!(machine.clause_bank().clauses()[0].included_literals(), vec![literal]);
+        assert_eq!(machine.clause_stats(&features, &labels)[0].precision(), 1.0);
+    }
+
+    #[test]
+    fn test_polarity_flip_without_regrow() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        // A fully included literal survives one epoch of feedback
+        let mut machine = TsetlinMachine::with_defaults(2, 4).seed(1).polarity_flip(true);
+        let literal = Literal { feature: 0, negated: true };
+        machine.clause_bank_mut().clause_mut(0).set_literal_state(literal, 100);
+        assert_eq!(machine.clause_stats(&features, &labels)[0].net_precision(), -1.0);
+
+        machine.fit(&features, &labels, 1);
+        // Empty clauses have a net precision of 0 and are kept
+        assert_eq!(machine.num_flipped(), 1);
+        assert_eq!(machine.num_regrown(), 0);
+        assert_eq!(machine.clause_bank().polarities(), &[false, true, false, false]);
+        assert_eq!(machine.clause_stats(&features, &labels)[0].net_precision(), 1.0);
+    }
+
+    #[test]
+    fn test_polarity_flip_skips_frozen_clauses() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 4).seed(1).polarity_flip(true);
+        let literal = Literal { feature: 0, negated: true };
+        machine.clause_bank_mut().clause_mut(0).set_included(literal, true);
+        machine.clause_bank_mut().set_frozen(0, true);
+
+        machine.fit(&features, &labels, 1);
+        assert_eq!(machine.num_flipped(), 0);
+        assert!(machine.clause_bank().polarities()[0]);
+        assert_eq!(machine.clause_stats(&features, &labels)[0].net_precision(), -1.0);
+    }
+
+    #[test]
+    fn test_diversity_metrics() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, false, true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 10).diversity_penalty(0.5);
+        assert_eq!(machine.clause_diversity(), 0.0);
+
+        machine.fit(&features, &labels, 10);
+        assert_eq!(machine.clause_similarity().dim(), (10, 10));
+        assert!((0.0..=1.0).contains(&machine.clause_diversity()));
+    }
+
+    #[test]
+    fn test_rules_export() {
+        let machine = TsetlinMachine::with_defaults(2, 10);
+        // Untrained clauses produce no rules
+        assert!(machine.rules().is_empty());
+        assert!(machine.rules_simplified().is_empty());
+
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 10).prune_and_regrow(1.0, 1);
+        machine.fit(&features, &labels, 5);
+        assert!(machine.rules_simplified().len() <= machine.rules().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of clauses must be even")]
+    fn test_machine_odd_clauses() {
+        TsetlinMachine::with_defaults(5, 9);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut machine = TsetlinMachine::with_defaults(3, 4).diversity_penalty(0.2);
+        machine
+            .clause_bank_mut()
+            .clause_mut(0)
+            .set_included(Literal { feature: 1, negated: true }, true);
+
+        let summary = machine.to_string();
+        assert_eq!(summary, machine.summary());
+        assert!(summary.contains("Features:           3"));
+        assert!(summary.contains("Clauses:            4 (2 positive, 2 negative)"));
+        assert!(summary.contains("Included literals:  1 of 24"));
+        assert!(summary.contains("Diversity penalty:  0.2"));
+        assert!(summary.contains("Training status:    trained (epochs unknown)"));
+    }
+
+    #[test]
+    fn test_summary_training_status() {
+        let features = Array2::from_shape_vec((2, 2), vec![true, false, false, true]).unwrap();
+        let labels = Array1::from_vec(vec![true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        assert!(machine.summary().ends_with("untrained"));
+        machine.fit(&features, &labels, 3);
+        machine.fit(&features, &labels, 2);
+        assert_eq!(machine.epochs_trained(), 5);
+        assert!(machine.summary().ends_with("trained for 5 epochs"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_fit_soft() {
+        let features = Array2::from_shape_vec((2, 2), vec![true, false, false, true]).unwrap();
+
+        let mut machine = TsetlinMachine::with_defaults(2, 10);
+        machine.fit_soft(&features, &Array1::from_vec(vec![0.8, 0.0]), 4);
+        assert_eq!(machine.epochs_trained(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Soft targets must be probabilities in [0, 1]")]
+    fn test_fit_soft_rejects_invalid_targets() {
+        let features = Array2::from_shape_vec((1, 2), vec![true, false]).unwrap();
+        TsetlinMachine::with_defaults(2, 10).fit_soft(&features, &Array1::from_vec(vec![1.5]), 1);
+    }
+
+    #[test]
+    fn test_seeded_augmented_training() {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let labels = Array1::from_vec(vec![true, true, false, false]);
+
+        let train = || {
+            let mut machine = TsetlinMachine::with_defaults(2, 10)
+                .seed(9)
+                .augment(BitFlip::new(0.3));
+            machine.fit(&features, &labels, 30);
+            machine
+        };
+        let (a, b) = (train(), train());
+        assert_eq!(a.random_seed(), Some(9));
+        assert_eq!(a.rules(), b.rules());
+        assert_eq!(a.predict_votes(&features), b.predict_votes(&features));
+    }
+
+    #[test]
+    fn test_predict_masked() {
+        let mut machine = TsetlinMachine::with_defaults(2, 2);
+        machine
+            .clause_bank_mut()
+            .clause_mut(1)
+            .set_included(Literal { feature: 1, negated: false }, true);
+
+        // The negative clause needs x1; hiding x1 silences it
+        let features = Array2::from_shape_vec((1, 2), vec![false, true]).unwrap();
+        assert_eq!(machine.predict_votes(&features)[0], 0);
+        assert_eq!(machine.predict_votes_masked(&features, &[true, false])[0], 1);
+        assert!(machine.predict_masked(&features, &[true, false])[0]);
+        let unmasked = machine.predict_votes_masked(&features, &[true, true]);
+        assert_eq!(unmasked, machine.predict_votes(&features));
+    }
+
+    #[test]
+    fn test_generic_containers() {
+        let rows = vec![vec![true, false], vec![false, true]];
+        let array = Array2::from_shape_vec((2, 2), vec![true, false, false, true]).unwrap();
+        let labels = Array1::from_vec(vec![true, false]);
+
+        let mut machine = TsetlinMachine::with_defaults(2, 4);
+        machine
+            .clause_bank_mut()
+            .clause_mut(0)
+            .set_included(Literal { feature: 0, negated: false }, true);
+        assert_eq!(machine.predict(&rows), machine.predict(&array));
+        assert_eq!(machine.predict_votes(rows.as_slice()), machine.predict_votes(&array.view()));
+        assert_eq!(machine.evaluate(&rows, &labels), machine.evaluate(&array, &labels));
+
+        machine.fit(&rows, &labels, 2);
+        machine.fit(&array.view(), &labels, 2);
+        assert_eq!(machine.epochs_trained(), 4);
+    }
+
+    #[test]
+    fn test_weighted_votes() {
+        let mut machine = TsetlinMachine::with_defaults(2, 4)
+            .weight_learning(WeightLearning::Integer { max_weight: 8 });
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.set_weight(0, 5);
+
+        // Empty clauses: +1 (clause 1) -1 -1 (clauses 2, 3); clause 0 adds 5
+        assert_eq!(machine.vote_single(&[true, false]), 4);
+        assert_eq!(machine.vote_single(&[false, false]), -1);
+        assert_eq!(machine.compile().vote_single(&[true, false]), 4);
+        assert!(machine.summary().contains("integer up to 8 (total weight 8)"));
+    }
+
+    #[test]
+    fn test_stratified_order() {
+        let labels = Array1::from_vec(vec![true, false, false, false, true, false, false, false]);
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(3);
+        let order = stratified_order(&labels, &mut rng);
+
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..8).collect::<Vec<_>>());
+        // Every batch of four holds one positive and three negatives
+        for batch in order.chunks(4) {
+            assert_eq!(batch.iter().filter(|&&i| labels[i]).count(), 1);
+        }
+    }
+
+    /// Sampler that always returns the same indices
+    #[derive(Debug, Clone)]
+    struct Fixed(Vec<usize>);
+
+    impl Sampler for Fixed {
+        fn order(&mut self, _: &Array1<bool>, _: usize, _: &mut dyn rand::RngCore) -> Vec<usize> {
+            self.0.clone()
+        }
+
+        fn clone_box(&self) -> Box<dyn Sampler> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_custom_sampler() {
+        let (features, labels) = crate::generate_xor_dataset();
+        // Only sample 0 has a weight, so training only ever sees one class
+        let mut machine = TsetlinMachine::with_defaults(2, 10)
+            .seed(2)
+            .sampler(Weighted::new(vec![1.0, 0.0, 0.0, 0.0]));
+        machine.fit(&features, &labels, 20);
+        let copy = machine.clone();
+
+        assert_eq!(machine.epochs_trained(), 20);
+        assert_eq!(copy.rules(), machine.rules());
+    }
+
+    #[test]
+    #[should_panic(expected = "Sampler returned an out-of-range sample index")]
+    fn test_sampler_out_of_range() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 10).sampler(Fixed(vec![0, 4]));
+        machine.fit(&features, &labels, 1);
+    }
+
+    #[test]
+    fn test_prequential_window() {
+        // Clause 1 (negative) fires on `!x0`, so the machine predicts `x0`
+        let mut trained = TsetlinMachine::with_defaults(1, 2);
+        trained
+            .clause_bank_mut()
+            .clause_mut(1)
+            .set_included(Literal { feature: 0, negated: true }, true);
+        // Freezing every clause keeps predictions fixed while streaming
+        let mut machine = TsetlinMachine::from_pretrained(&trained, 1.0, 1.0);
+
+        let stream = vec![
+            (vec![true], true),
+            (vec![false], true),
+            (vec![false], false),
+            (vec![true], true),
+        ];
+        assert_eq!(machine.evaluate_prequential(stream, 2), vec![1.0, 0.5, 0.5, 1.0]);
+        assert_eq!(machine.epochs_trained(), 0);
+    }
+
+    #[test]
+    fn test_memory_footprint() {
+        let mut machine = TsetlinMachine::with_defaults(3, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 2, negated: true }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 1, negated: false }, true);
+
+        let footprint = machine.memory_footprint();
+        assert_eq!(footprint.automata_bytes, 24 * std::mem::size_of::<TsetlinAutomaton>());
+        assert_eq!(footprint.included_literals, 3);
+        assert_eq!(footprint.non_empty_clauses, 2);
+        assert_eq!(footprint.compiled_bytes, machine.compile().memory_bytes());
+        assert_eq!(machine.estimated_memory_bytes(), footprint.total_bytes());
+    }
+
+    #[test]
+    fn test_privacy_budget() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 10)
+            .seed(3)
+            .differential_privacy(0.5)
+            .sampler(Fixed(vec![0, 1, 1, 2]));
+        machine.fit(&features, &labels, 4);
+
+        // Sample 1 is used twice per epoch, but its label is released once
+        assert_eq!(machine.privacy_spent(), 2.0);
+        assert!(machine.summary().contains("epsilon 0.5 per release (spent 2)"));
+
+        let mut batched = TsetlinMachine::with_defaults(2, 10)
+            .differential_privacy(0.5)
+            .batch_size(2);
+        batched.fit(&features, &labels, 2);
+        assert_eq!(batched.privacy_spent(), 1.0);
+    }
+
+    #[test]
+    fn test_partial_fit_spends_privacy() {
+        let mut machine = TsetlinMachine::with_defaults(2, 10).seed(3).differential_privacy(0.5);
+        machine.partial_fit(&[true, false], true);
+        assert_eq!(machine.privacy_spent(), 0.5);
+        machine.partial_fit(&[false, true], false);
+        assert_eq!(machine.privacy_spent(), 1.0);
+
+        let stream = (0..4).map(|i| (vec![i % 2 == 0, true], i % 2 == 0));
+        machine.evaluate_prequential(stream, 2);
+        assert_eq!(machine.privacy_spent(), 3.0);
+    }
+
+    #[test]
+    fn test_seeded_state_snapshot() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::new(2, 4, 3.0, 2.0).seed(2024);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 1, negated: true }, true);
+        machine.fit(&features, &labels, 25);
+
+        let states: Vec<Vec<i32>> = machine
+            .clause_bank()
+            .clauses()
+            .iter()
+            .map(|clause| {
+                (0..2)
+                    .flat_map(|feature| [false, true].map(|negated| Literal { feature, negated }))
+                    .map(|literal| clause.literal_state(literal))
+                    .collect()
+            })
+            .collect();
+        // The pinned generator makes these exact states part of the crate's contract
+        assert_eq!(
+            states,
+            vec![
+                vec![0, -99, -100, -100],
+                vec![-100, -100, -100, -100],
+                vec![-79, -100, -82, 16],
+                vec![-100, -100, -100, -100],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let machine = TsetlinMachine::new(3, 4, 3.0, 2.0);
+        let hash = machine.state_hash();
+        // Pinned so that stored fingerprints stay valid across releases
+        assert_eq!(hash, 0x090f_4417_9dd9_e246);
+        assert_eq!(hash, TsetlinMachine::new(3, 4, 3.0, 2.0).seed(5).state_hash());
+
+        let mut changed = machine.clone();
+        changed
+            .clause_bank_mut()
+            .clause_mut(1)
+            .set_literal_state(Literal { feature: 2, negated: true }, -99);
+        assert_ne!(changed.state_hash(), hash);
+        assert_ne!(TsetlinMachine::new(3, 4, 3.5, 2.0).state_hash(), hash);
+    }
+
+    #[test]
+    fn test_state_hash_covers_bank_settings() {
+        let base = || TsetlinMachine::new(4, 4, 3.0, 2.0).seed(1);
+        let variants = [
+            base().noise_tolerance(0.1),
+            base().always_exclude(1),
+            base().require_positive_only(1),
+            base().ordinal_groups([0..2, 2..4]),
+            base().ordinal_groups([0..3, 3..4]),
+            base().feature_bagging(4),
+            base().weight_learning(WeightLearning::Integer { max_weight: 8 }),
+            base().polarity_scheme(PolarityScheme::Interleaved),
+            {
+                let mut machine = base();
+                machine.clause_bank_mut().set_aggregation(Aggregation::OrPool);
+                machine
+            },
+            {
+                let mut machine = base();
+                machine.clause_bank_mut().set_aggregation(Aggregation::MaxMargin);
+                machine
+            },
+        ];
+
+        let mut hashes: Vec<u64> = variants.iter().map(TsetlinMachine::state_hash).collect();
+        hashes.push(base().state_hash());
+        let count = hashes.len();
+        hashes.sort_unstable();
+        hashes.dedup();
+        assert_eq!(hashes.len(), count);
+    }
+
+    #[test]
+    fn test_batch_training() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 10).seed(4).batch_size(3);
+        machine.fit(&features, &labels, 5);
+
+        assert_eq!(machine.epochs_trained(), 5);
+        assert_eq!(machine.training_batch_size(), Some(3));
+        assert!(machine.summary().contains("Batch size:         3"));
+    }
+
+    #[test]
+    fn test_bit_parallel_batches() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let build = || {
+            let mut machine = TsetlinMachine::with_defaults(2, 4).seed(4).batch_size(4);
+            let bank = machine.clause_bank_mut();
+            bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+            bank.clause_mut(3).set_included(Literal { feature: 1, negated: true }, true);
+            machine
+        };
+        let mut machine = build().bit_parallel_batches(true);
+        machine.fit(&features, &labels, 5);
+
+        assert_ne!(machine.state_hash(), build().state_hash());
+        assert!(machine.summary().contains("Batch size:         4 (bit-parallel)"));
+    }
+
+    #[test]
+    fn test_threshold_auto_follows_clause_count() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine =
+            TsetlinMachine::with_defaults(2, 4).threshold_auto(0.5).auto_grow(12, 1).seed(2);
+        assert_eq!(machine.threshold(), 1.0);
+
+        machine.fit(&features, &labels, 20);
+        assert!(machine.num_clauses() > 4);
+        assert_eq!(machine.threshold(), 0.5 * machine.num_clauses() as f64 / 2.0);
+        assert!(machine.threshold_history().windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(machine.summary().contains("(auto, 0.5 x clauses / 2)"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold cannot follow both a schedule and the clause count")]
+    fn test_threshold_auto_conflicts_with_schedule() {
+        let schedule = Schedule::Linear { start: 2.0, end: 8.0, epochs: 3 };
+        let machine = TsetlinMachine::with_defaults(2, 4).threshold_schedule(schedule);
+        let _ = machine.threshold_auto(0.5);
+    }
+
+    #[test]
+    fn test_fairness_history_tracks_disparity() {
+        // Positive clause x0 outvotes the empty negative clause: predictions copy x0
+        let mut source = TsetlinMachine::with_defaults(2, 2);
+        let bank = source.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.set_weight(0, 2);
+        // Every clause frozen, so the disparity stays at 1
+        let mut machine = TsetlinMachine::from_pretrained(&source, 1.0, 1.0)
+            .fairness_constraint(0, 0.2)
+            .seed(4);
+
+        let (features, labels) = crate::generate_xor_dataset();
+        machine.fit(&features, &labels, 12);
+        let history = machine.fairness_history();
+        assert_eq!(history.len(), 12);
+        assert!(history.iter().all(|epoch| epoch.disparity == 1.0));
+        assert_eq!(history[0].accuracy, machine.evaluate(&features, &labels));
+        assert!((history[2].strength - 0.3).abs() < 1e-12);
+        assert_eq!(history[11].strength, 1.0);
+        assert!(machine.summary().contains("|disparity| <= 0.2 on feature 0 (last +1.000)"));
+    }
+
+    #[test]
+    fn test_fairness_skips_gap_widening_feedback() {
+        let mut machine = TsetlinMachine::with_defaults(2, 2).fairness_constraint(1, 0.1);
+        assert!(!machine.fairness_skip(&[true, true], true));
+
+        // The protected group gets more positive predictions
+        let epoch = FairnessEpoch { disparity: 0.5, accuracy: 0.5, strength: 1.0 };
+        machine.fairness_history.push(epoch);
+        assert!(machine.fairness_skip(&[false, true], true));
+        assert!(!machine.fairness_skip(&[false, true], false));
+        assert!(machine.fairness_skip(&[true, false], false));
+        assert!(!machine.fairness_skip(&[true, false], true));
     }
 }
\ No newline at end of file
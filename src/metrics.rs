@@ -0,0 +1,929 @@
+//! Evaluation metrics and statistical tests
+
+use crate::input::{AsBoolSamples, BinaryLabel};
+use crate::machine::TsetlinMachine;
+use crate::multiclass::MultiClassTsetlinMachine;
+use ndarray::{Array1, Array2};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Discordant pair count below which McNemar's test uses the exact binomial distribution
+const EXACT_LIMIT: usize = 25;
+
+/// Result of McNemar's test between two models
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct McNemarResult {
+    /// Samples only the first model classifies correctly
+    pub a_only_correct: usize,
+    /// Samples only the second model classifies correctly
+    pub b_only_correct: usize,
+    /// Continuity-corrected chi-squared statistic
+    pub statistic: f64,
+    /// Two-sided p-value
+    pub p_value: f64,
+}
+
+impl McNemarResult {
+    /// Whether the difference between the models is significant at level `alpha`
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// McNemar's paired test of two models on the same test set
+///
+/// Only the samples on which exactly one model is correct carry information
+/// about which model is better. A small p-value means the difference in
+/// accuracy is unlikely to be noise; with fewer than 25 such samples the
+/// exact binomial test is used instead of the chi-squared approximation.
+///
+/// # Arguments
+/// * `model_a` - First model
+/// * `model_b` - Second model
+/// * `features` - Test feature matrix (samples x features)
+/// * `labels` - Test labels
+///
+/// # Example
+/// ```
+/// use tsetlin::{metrics, TsetlinMachine, generate_xor_dataset};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let a = TsetlinMachine::with_defaults(2, 10);
+/// let b = TsetlinMachine::with_defaults(2, 20);
+/// let result = metrics::mcnemar_test(&a, &b, &features, &labels);
+/// assert!(!result.is_significant(0.05));
+/// ```
+pub fn mcnemar_test(
+    model_a: &TsetlinMachine,
+    model_b: &TsetlinMachine,
+    features: &Array2<bool>,
+    labels: &Array1<bool>,
+) -> McNemarResult {
+    mcnemar_from_predictions(&model_a.predict(features), &model_b.predict(features), labels)
+}
+
+/// McNemar's test on precomputed predictions of two models
+pub fn mcnemar_from_predictions(
+    predictions_a: &Array1<bool>,
+    predictions_b: &Array1<bool>,
+    labels: &Array1<bool>,
+) -> McNemarResult {
+    assert_eq!(predictions_a.len(), labels.len());
+    assert_eq!(predictions_b.len(), labels.len());
+
+    let mut a_only_correct = 0;
+    let mut b_only_correct = 0;
+    for ((&a, &b), &label) in predictions_a.iter().zip(predictions_b).zip(labels) {
+        match (a == label, b == label) {
+            (true, false) => a_only_correct += 1,
+            (false, true) => b_only_correct += 1,
+            _ => {}
+        }
+    }
+
+    let discordant = a_only_correct + b_only_correct;
+    let statistic = if discordant == 0 {
+        0.0
+    } else {
+        let difference = (a_only_correct as f64 - b_only_correct as f64).abs() - 1.0;
+        difference.max(0.0).powi(2) / discordant as f64
+    };
+    let p_value = if discordant < EXACT_LIMIT {
+        binomial_two_sided(a_only_correct.min(b_only_correct), discordant)
+    } else {
+        chi_squared_1_sf(statistic)
+    };
+
+    McNemarResult {
+        a_only_correct,
+        b_only_correct,
+        statistic,
+        p_value,
+    }
+}
+
+/// Receiver operating characteristic of vote scores
+///
+/// Point `i` classifies samples with a vote of at least `thresholds[i]` as
+/// positive. Thresholds decrease along the curve, which starts at (0, 0) and
+/// ends at (1, 1). The machine's own decision rule is the threshold 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RocCurve {
+    /// False positive rate at each threshold
+    pub false_positive_rate: Vec<f64>,
+    /// True positive rate (recall) at each threshold
+    pub true_positive_rate: Vec<f64>,
+    /// Minimum vote classified as positive
+    pub thresholds: Vec<i32>,
+}
+
+impl RocCurve {
+    /// Area under the curve: the probability that a random positive sample
+    /// outvotes a random negative one (ties count half)
+    pub fn auc(&self) -> f64 {
+        trapezoid(&self.false_positive_rate, &self.true_positive_rate)
+    }
+}
+
+/// Precision-recall trade-off of vote scores
+///
+/// Point `i` classifies samples with a vote of at least `thresholds[i]` as
+/// positive; thresholds decrease and recall increases along the curve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrCurve {
+    /// Precision at each threshold
+    pub precision: Vec<f64>,
+    /// Recall at each threshold
+    pub recall: Vec<f64>,
+    /// Minimum vote classified as positive
+    pub thresholds: Vec<i32>,
+}
+
+impl PrCurve {
+    /// Trapezoidal area under the curve
+    pub fn auc(&self) -> f64 {
+        trapezoid(&self.recall, &self.precision)
+    }
+
+    /// Average precision: precision weighted by the recall gained at each step
+    pub fn average_precision(&self) -> f64 {
+        let mut previous_recall = 0.0;
+        let mut total = 0.0;
+        for (&precision, &recall) in self.precision.iter().zip(&self.recall) {
+            total += (recall - previous_recall) * precision;
+            previous_recall = recall;
+        }
+        total
+    }
+}
+
+/// True and false positive counts at each distinct vote, from highest vote down
+fn cumulative_counts(votes: &Array1<i32>, labels: &Array1<bool>) -> Vec<(i32, usize, usize)> {
+    assert_eq!(votes.len(), labels.len());
+
+    let mut scored: Vec<(i32, bool)> = votes.iter().copied().zip(labels.iter().copied()).collect();
+    scored.sort_unstable_by_key(|&(vote, _)| std::cmp::Reverse(vote));
+
+    let mut counts: Vec<(i32, usize, usize)> = Vec::new();
+    let (mut true_positives, mut false_positives) = (0, 0);
+    for (i, &(vote, label)) in scored.iter().enumerate() {
+        if label {
+            true_positives += 1;
+        } else {
+            false_positives += 1;
+        }
+        if scored.get(i + 1).is_none_or(|&(next, _)| next != vote) {
+            counts.push((vote, true_positives, false_positives));
+        }
+    }
+    counts
+}
+
+/// ROC curve of vote scores (see [`TsetlinMachine::predict_votes`])
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::metrics;
+///
+/// let roc = metrics::roc_curve(&array![3, 1, -2, 1], &array![true, true, false, false]);
+/// assert_eq!(roc.thresholds, vec![4, 3, 1, -2]);
+/// assert_eq!(roc.auc(), 0.875);
+/// ```
+pub fn roc_curve(votes: &Array1<i32>, labels: &Array1<bool>) -> RocCurve {
+    let counts = cumulative_counts(votes, labels);
+    let positives = labels.iter().filter(|&&label| label).count();
+    let negatives = labels.len() - positives;
+
+    let first = counts.first().map_or(1, |&(vote, _, _)| vote.saturating_add(1));
+    let mut curve = RocCurve {
+        false_positive_rate: vec![0.0],
+        true_positive_rate: vec![0.0],
+        thresholds: vec![first],
+    };
+    for (vote, true_positives, false_positives) in counts {
+        curve.false_positive_rate.push(ratio(false_positives, negatives));
+        curve.true_positive_rate.push(ratio(true_positives, positives));
+        curve.thresholds.push(vote);
+    }
+    curve
+}
+
+/// Precision-recall curve of vote scores (see [`TsetlinMachine::predict_votes`])
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::metrics;
+///
+/// let pr = metrics::pr_curve(&array![3, 1, -2, 1], &array![true, true, false, false]);
+/// assert_eq!(pr.precision, vec![1.0, 2.0 / 3.0, 0.5]);
+/// assert_eq!(pr.recall, vec![0.5, 1.0, 1.0]);
+/// assert!((pr.average_precision() - 5.0 / 6.0).abs() < 1e-12);
+/// ```
+pub fn pr_curve(votes: &Array1<i32>, labels: &Array1<bool>) -> PrCurve {
+    let positives = labels.iter().filter(|&&label| label).count();
+    let mut curve = PrCurve {
+        precision: Vec::new(),
+        recall: Vec::new(),
+        thresholds: Vec::new(),
+    };
+    for (vote, true_positives, false_positives) in cumulative_counts(votes, labels) {
+        curve.precision.push(ratio(true_positives, true_positives + false_positives));
+        curve.recall.push(ratio(true_positives, positives));
+        curve.thresholds.push(vote);
+    }
+    curve
+}
+
+/// Area under the piecewise linear curve through the points `(x, y)`
+fn trapezoid(x: &[f64], y: &[f64]) -> f64 {
+    x.windows(2)
+        .zip(y.windows(2))
+        .map(|(x, y)| (x[1] - x[0]) * (y[0] + y[1]) / 2.0)
+        .sum()
+}
+
+/// Reliability diagram data: predicted probability against observed frequency
+///
+/// Only bins containing predictions are listed. For a well calibrated model
+/// `fraction_positive` is close to `mean_predicted` in every bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationCurve {
+    /// Mean predicted probability in each bin
+    pub mean_predicted: Vec<f64>,
+    /// Fraction of positive samples in each bin
+    pub fraction_positive: Vec<f64>,
+    /// Number of samples in each bin
+    pub counts: Vec<usize>,
+}
+
+impl CalibrationCurve {
+    /// Expected calibration error: mean gap between prediction and frequency,
+    /// weighted by bin size
+    pub fn expected_calibration_error(&self) -> f64 {
+        let total: usize = self.counts.iter().sum();
+        let gaps: f64 = self
+            .mean_predicted
+            .iter()
+            .zip(&self.fraction_positive)
+            .zip(&self.counts)
+            .map(|((predicted, observed), &count)| (predicted - observed).abs() * count as f64)
+            .sum();
+        gaps / total.max(1) as f64
+    }
+}
+
+/// Reliability diagram over `bins` equal-width probability bins
+///
+/// # Arguments
+/// * `probabilities` - Predicted positive-class probabilities, e.g. from
+///   [`TsetlinMachine::predict_proba`]
+/// * `labels` - True labels
+/// * `bins` - Number of bins covering [0, 1]
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use tsetlin::metrics;
+///
+/// let curve = metrics::calibration_curve(
+///     &array![0.1, 0.2, 0.8, 0.9],
+///     &array![false, false, true, false],
+///     2,
+/// );
+/// assert_eq!(curve.counts, vec![2, 2]);
+/// assert_eq!(curve.fraction_positive, vec![0.0, 0.5]);
+/// ```
+pub fn calibration_curve(
+    probabilities: &Array1<f64>,
+    labels: &Array1<bool>,
+    bins: usize,
+) -> CalibrationCurve {
+    assert_eq!(probabilities.len(), labels.len());
+    assert!(bins > 0, "There must be at least one bin");
+    assert_probabilities(probabilities);
+
+    let mut sums = vec![(0.0, 0, 0); bins];
+    for (&probability, &label) in probabilities.iter().zip(labels) {
+        let bin = ((probability * bins as f64) as usize).min(bins - 1);
+        sums[bin].0 += probability;
+        sums[bin].1 += usize::from(label);
+        sums[bin].2 += 1;
+    }
+
+    let mut curve = CalibrationCurve {
+        mean_predicted: Vec::new(),
+        fraction_positive: Vec::new(),
+        counts: Vec::new(),
+    };
+    for (sum, positives, count) in sums.into_iter().filter(|&(_, _, count)| count > 0) {
+        curve.mean_predicted.push(sum / count as f64);
+        curve.fraction_positive.push(positives as f64 / count as f64);
+        curve.counts.push(count);
+    }
+    curve
+}
+
+/// Brier score: mean squared difference between probability and outcome
+///
+/// 0 is perfect; always predicting 0.5 scores 0.25.
+pub fn brier_score(probabilities: &Array1<f64>, labels: &Array1<bool>) -> f64 {
+    assert_eq!(probabilities.len(), labels.len());
+    assert_probabilities(probabilities);
+
+    let total: f64 = probabilities
+        .iter()
+        .zip(labels)
+        .map(|(&probability, &label)| (probability - f64::from(u8::from(label))).powi(2))
+        .sum();
+    total / labels.len().max(1) as f64
+}
+
+/// Panic unless every value is a probability
+fn assert_probabilities(probabilities: &Array1<f64>) {
+    assert!(
+        probabilities.iter().all(|p| (0.0..=1.0).contains(p)),
+        "Probabilities must be in [0, 1]"
+    );
+}
+
+/// Binary validation score, e.g. for [`TsetlinMachine::keep_best`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Metric {
+    /// Fraction of correct predictions
+    #[default]
+    Accuracy,
+    /// F1 score of the positive class
+    F1,
+    /// Mean recall of the two classes
+    BalancedAccuracy,
+}
+
+impl Metric {
+    /// Score binary predictions against true labels
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::metrics::Metric;
+    ///
+    /// let predictions = array![true, true, false, false];
+    /// let labels = array![true, false, false, false];
+    /// assert_eq!(Metric::Accuracy.score(&predictions, &labels), 0.75);
+    /// assert!((Metric::F1.score(&predictions, &labels) - 2.0 / 3.0).abs() < 1e-12);
+    /// ```
+    pub fn score(&self, predictions: &Array1<bool>, labels: &Array1<bool>) -> f64 {
+        let report =
+            ClassificationReport::new(&predictions.mapv(usize::from), &labels.mapv(usize::from), 2);
+        match self {
+            Metric::Accuracy => report.accuracy(),
+            Metric::F1 => report.f1(1),
+            Metric::BalancedAccuracy => report.balanced_accuracy(),
+        }
+    }
+}
+
+/// Per-class and averaged scores of a multi-class classifier
+///
+/// Built from a confusion matrix whose rows are the true classes and whose
+/// columns are the predicted classes. Scores of classes without predictions
+/// or samples count as 0. Its `Display` output is a per-class report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassificationReport {
+    /// Sample counts by true class (rows) and predicted class (columns)
+    confusion: Array2<usize>,
+}
+
+impl ClassificationReport {
+    /// Tally predictions against true labels
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::metrics::ClassificationReport;
+    ///
+    /// let report = ClassificationReport::new(&array![0, 1, 2, 2], &array![0, 1, 1, 2], 3);
+    /// assert_eq!(report.accuracy(), 0.75);
+    /// assert_eq!(report.recall(1), 0.5);
+    /// println!("{report}");
+    /// ```
+    pub fn new(predictions: &Array1<usize>, labels: &Array1<usize>, num_classes: usize) -> Self {
+        assert_eq!(predictions.len(), labels.len());
+
+        let mut confusion = Array2::zeros((num_classes, num_classes));
+        for (&predicted, &actual) in predictions.iter().zip(labels) {
+            assert!(
+                predicted < num_classes && actual < num_classes,
+                "Class index out of range"
+            );
+            confusion[[actual, predicted]] += 1;
+        }
+        Self { confusion }
+    }
+
+    /// Score a multi-class machine on labelled data
+    pub fn from_machine<S: AsBoolSamples + ?Sized>(
+        machine: &MultiClassTsetlinMachine,
+        features: &S,
+        labels: &Array1<usize>,
+    ) -> Self {
+        Self::new(&machine.predict(features), labels, machine.num_classes())
+    }
+
+    /// Sample counts by true class (rows) and predicted class (columns)
+    pub fn confusion_matrix(&self) -> &Array2<usize> {
+        &self.confusion
+    }
+
+    /// Number of classes
+    pub fn num_classes(&self) -> usize {
+        self.confusion.nrows()
+    }
+
+    /// Number of samples
+    pub fn total(&self) -> usize {
+        self.confusion.sum()
+    }
+
+    /// Number of samples of a class
+    pub fn support(&self, class: usize) -> usize {
+        self.confusion.row(class).sum()
+    }
+
+    /// Fraction of the predictions of a class that are correct
+    pub fn precision(&self, class: usize) -> f64 {
+        ratio(self.confusion[[class, class]], self.confusion.column(class).sum())
+    }
+
+    /// Fraction of the samples of a class that are predicted correctly
+    pub fn recall(&self, class: usize) -> f64 {
+        ratio(self.confusion[[class, class]], self.support(class))
+    }
+
+    /// Harmonic mean of precision and recall of a class
+    pub fn f1(&self, class: usize) -> f64 {
+        let (precision, recall) = (self.precision(class), self.recall(class));
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+
+    /// Fraction of all samples predicted correctly
+    pub fn accuracy(&self) -> f64 {
+        ratio(self.confusion.diag().sum(), self.total())
+    }
+
+    /// Unweighted mean of the per-class F1 scores
+    pub fn macro_f1(&self) -> f64 {
+        self.class_mean(|class| self.f1(class))
+    }
+
+    /// F1 of the pooled counts of all classes
+    ///
+    /// Every sample has exactly one prediction, so this equals the accuracy.
+    pub fn micro_f1(&self) -> f64 {
+        self.accuracy()
+    }
+
+    /// Mean of the per-class F1 scores weighted by class support
+    pub fn weighted_f1(&self) -> f64 {
+        let weighted: f64 = (0..self.num_classes())
+            .map(|class| self.f1(class) * self.support(class) as f64)
+            .sum();
+        weighted / self.total().max(1) as f64
+    }
+
+    /// Unweighted mean of the per-class recalls
+    pub fn balanced_accuracy(&self) -> f64 {
+        self.class_mean(|class| self.recall(class))
+    }
+
+    /// Cohen's kappa: agreement between predictions and labels beyond chance
+    ///
+    /// 1 is perfect agreement and 0 is what random predictions with the same
+    /// class frequencies achieve.
+    pub fn cohen_kappa(&self) -> f64 {
+        let total = self.total() as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        let observed = self.accuracy();
+        let expected: f64 = (0..self.num_classes())
+            .map(|class| {
+                self.support(class) as f64 * self.confusion.column(class).sum() as f64
+            })
+            .sum::<f64>()
+            / (total * total);
+        if expected >= 1.0 {
+            0.0
+        } else {
+            (observed - expected) / (1.0 - expected)
+        }
+    }
+
+    /// Mean of a per-class score over all classes
+    fn class_mean(&self, score: impl Fn(usize) -> f64) -> f64 {
+        let num_classes = self.num_classes();
+        (0..num_classes).map(score).sum::<f64>() / num_classes.max(1) as f64
+    }
+}
+
+impl fmt::Display for ClassificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>8} {:>9} {:>9} {:>9} {:>9}",
+            "class", "precision", "recall", "f1", "support"
+        )?;
+        for class in 0..self.num_classes() {
+            writeln!(
+                f,
+                "{:>8} {:>9.3} {:>9.3} {:>9.3} {:>9}",
+                class,
+                self.precision(class),
+                self.recall(class),
+                self.f1(class),
+                self.support(class)
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:>28} {:>9.3} {:>9}", "accuracy", self.accuracy(), self.total())?;
+        writeln!(f, "{:>28} {:>9.3}", "macro f1", self.macro_f1())?;
+        writeln!(f, "{:>28} {:>9.3}", "micro f1", self.micro_f1())?;
+        writeln!(f, "{:>28} {:>9.3}", "weighted f1", self.weighted_f1())?;
+        writeln!(f, "{:>28} {:>9.3}", "balanced accuracy", self.balanced_accuracy())?;
+        write!(f, "{:>28} {:>9.3}", "cohen kappa", self.cohen_kappa())
+    }
+}
+
+/// Binary scores of one group of samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupScores<G> {
+    /// Group label
+    pub group: G,
+    /// Number of samples in the group
+    pub support: usize,
+    /// Fraction of correct predictions
+    pub accuracy: f64,
+    /// F1 score of the positive class
+    pub f1: f64,
+    /// Fraction of samples predicted positive
+    pub positive_rate: f64,
+}
+
+impl<G> GroupScores<G> {
+    /// Score the predictions of one group
+    fn new(group: G, predictions: &[bool], labels: &[bool]) -> Self {
+        let report = ClassificationReport::new(
+            &predictions.iter().map(|&p| usize::from(p)).collect(),
+            &labels.iter().map(|&l| usize::from(l)).collect(),
+            2,
+        );
+        let positives = predictions.iter().filter(|&&p| p).count();
+        Self {
+            group,
+            support: predictions.len(),
+            accuracy: report.accuracy(),
+            f1: report.f1(1),
+            positive_rate: ratio(positives, predictions.len()),
+        }
+    }
+}
+
+/// Binary scores broken down by group, e.g. per demographic slice or device type
+///
+/// Groups are listed in ascending order of their labels. Its `Display`
+/// output is a per-group table followed by the overall scores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupedReport<G> {
+    /// Scores of every group
+    groups: Vec<GroupScores<G>>,
+    /// Scores over all samples
+    overall: GroupScores<()>,
+}
+
+impl<G: Clone + Ord> GroupedReport<G> {
+    /// Score predictions per group
+    ///
+    /// # Arguments
+    /// * `predictions` - Predicted classes
+    /// * `labels` - True classes
+    /// * `groups` - Group label of every sample
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::metrics::GroupedReport;
+    ///
+    /// let predictions = array![true, true, false, false];
+    /// let labels = array![true, false, false, true];
+    /// let report = GroupedReport::new(&predictions, &labels, &["a", "a", "b", "b"]);
+    /// assert_eq!(report.get(&"a").unwrap().positive_rate, 1.0);
+    /// assert_eq!(report.accuracy_gap(), 0.0);
+    /// println!("{report}");
+    /// ```
+    pub fn new(predictions: &Array1<bool>, labels: &Array1<bool>, groups: &[G]) -> Self {
+        assert_eq!(predictions.len(), labels.len());
+        assert_eq!(groups.len(), labels.len(), "Every sample needs a group");
+
+        let mut by_group: BTreeMap<&G, (Vec<bool>, Vec<bool>)> = BTreeMap::new();
+        for ((&predicted, &actual), group) in predictions.iter().zip(labels).zip(groups) {
+            let (group_predictions, group_labels) = by_group.entry(group).or_default();
+            group_predictions.push(predicted);
+            group_labels.push(actual);
+        }
+        let groups = by_group
+            .into_iter()
+            .map(|(group, (predictions, labels))| {
+                GroupScores::new(group.clone(), &predictions, &labels)
+            })
+            .collect();
+        let overall = GroupScores::new((), &predictions.to_vec(), &labels.to_vec());
+        Self { groups, overall }
+    }
+
+    /// Scores of every group, in ascending order of the group labels
+    pub fn groups(&self) -> &[GroupScores<G>] {
+        &self.groups
+    }
+
+    /// Scores of one group, if it has samples
+    pub fn get(&self, group: &G) -> Option<&GroupScores<G>> {
+        self.groups
+            .binary_search_by(|scores| scores.group.cmp(group))
+            .ok()
+            .map(|index| &self.groups[index])
+    }
+
+    /// Scores over all samples
+    pub fn overall(&self) -> &GroupScores<()> {
+        &self.overall
+    }
+
+    /// Difference between the highest and lowest group accuracy
+    pub fn accuracy_gap(&self) -> f64 {
+        self.gap(|scores| scores.accuracy)
+    }
+
+    /// Difference between the highest and lowest positive prediction rate
+    ///
+    /// This is the demographic parity difference when the groups are the
+    /// values of a protected attribute.
+    pub fn positive_rate_gap(&self) -> f64 {
+        self.gap(|scores| scores.positive_rate)
+    }
+
+    /// Range of a per-group score
+    fn gap(&self, score: impl Fn(&GroupScores<G>) -> f64) -> f64 {
+        let (min, max) = self.groups.iter().map(score).fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), value| (min.min(value), max.max(value)),
+        );
+        if self.groups.is_empty() {
+            0.0
+        } else {
+            max - min
+        }
+    }
+}
+
+impl<G: fmt::Display> fmt::Display for GroupedReport<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>12} {:>9} {:>9} {:>9} {:>9}",
+            "group", "accuracy", "f1", "positive", "support"
+        )?;
+        for scores in &self.groups {
+            writeln!(
+                f,
+                "{:>12} {:>9.3} {:>9.3} {:>9.3} {:>9}",
+                scores.group.to_string(),
+                scores.accuracy,
+                scores.f1,
+                scores.positive_rate,
+                scores.support
+            )?;
+        }
+        let overall = &self.overall;
+        write!(
+            f,
+            "{:>12} {:>9.3} {:>9.3} {:>9.3} {:>9}",
+            "overall", overall.accuracy, overall.f1, overall.positive_rate, overall.support
+        )
+    }
+}
+
+impl TsetlinMachine {
+    /// Accuracy and F1 score per group of samples
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix (samples x features)
+    /// * `labels` - Target labels
+    /// * `groups` - Group label of every sample, e.g. a demographic slice
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let machine = TsetlinMachine::with_defaults(2, 10);
+    /// let report = machine.evaluate_grouped(&features, &labels, &["x", "x", "y", "y"]);
+    /// assert_eq!(report.groups().len(), 2);
+    /// assert_eq!(report.overall().accuracy, machine.evaluate(&features, &labels));
+    /// ```
+    pub fn evaluate_grouped<S, L, G>(
+        &self,
+        features: &S,
+        labels: &Array1<L>,
+        groups: &[G],
+    ) -> GroupedReport<G>
+    where
+        S: AsBoolSamples + ?Sized,
+        L: BinaryLabel,
+        G: Clone + Ord,
+    {
+        GroupedReport::new(&self.predict(features), &labels.mapv(L::to_bool), groups)
+    }
+}
+
+/// `numerator / denominator`, or 0 when the denominator is 0
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Two-sided exact p-value of `k` or fewer successes out of `n` fair coin flips
+fn binomial_two_sided(k: usize, n: usize) -> f64 {
+    let mut coefficient = 1.0;
+    let mut tail = 0.0;
+    for i in 0..=k {
+        if i > 0 {
+            coefficient *= (n - i + 1) as f64 / i as f64;
+        }
+        tail += coefficient;
+    }
+    (2.0 * tail * 0.5f64.powi(n as i32)).min(1.0)
+}
+
+/// Survival function of the chi-squared distribution with one degree of freedom
+fn chi_squared_1_sf(statistic: f64) -> f64 {
+    erfc((statistic / 2.0).sqrt())
+}
+
+/// Complementary error function (fractional error below 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let value = t * polynomial.exp();
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_erfc() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-6);
+        assert!((erfc(1.0) - 0.157_299_207).abs() < 1e-6);
+        assert!((erfc(-1.0) - 1.842_700_793).abs() < 1e-6);
+        // P(chi2 > 3.841) = 0.05 for one degree of freedom
+        assert!((chi_squared_1_sf(3.841_459) - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_exact_binomial() {
+        assert_eq!(binomial_two_sided(0, 0), 1.0);
+        // 2 * (1 + 10) / 1024
+        assert!((binomial_two_sided(1, 10) - 22.0 / 1024.0).abs() < 1e-12);
+        assert_eq!(binomial_two_sided(5, 10), 1.0);
+    }
+
+    #[test]
+    fn test_classification_report() {
+        // Class 0: 2/2 right; class 1: 1 of 2 right (one predicted as 2); class 2: 1/1
+        let report = ClassificationReport::new(&array![0, 0, 1, 2, 2], &array![0, 0, 1, 1, 2], 3);
+
+        assert_eq!(report.confusion_matrix()[[1, 2]], 1);
+        assert_eq!(report.precision(2), 0.5);
+        assert!((report.macro_f1() - (1.0 + 2.0 / 3.0 + 2.0 / 3.0) / 3.0).abs() < 1e-12);
+        assert_eq!(report.micro_f1(), 0.8);
+        assert!((report.weighted_f1() - (2.0 + 4.0 / 3.0 + 2.0 / 3.0) / 5.0).abs() < 1e-12);
+        assert!((report.balanced_accuracy() - 2.5 / 3.0).abs() < 1e-12);
+        // Chance agreement: (2*2 + 2*1 + 1*2) / 25 = 0.32
+        assert!((report.cohen_kappa() - (0.8 - 0.32) / 0.68).abs() < 1e-12);
+
+        let text = report.to_string();
+        assert!(text.contains("weighted f1"));
+        assert_eq!(text.lines().count(), 11);
+    }
+
+    #[test]
+    fn test_roc_auc_matches_pairwise_ranking() {
+        let votes = array![5, 2, 2, 0, -1, -3, 2];
+        let labels = array![true, true, false, true, false, false, true];
+        let roc = roc_curve(&votes, &labels);
+
+        // Count positive/negative pairs won by the positive, ties half
+        let scored: Vec<(i32, bool)> = votes.iter().copied().zip(labels.iter().copied()).collect();
+        let mut wins = 0.0;
+        for &(positive, _) in scored.iter().filter(|&&(_, label)| label) {
+            for &(negative, _) in scored.iter().filter(|&&(_, label)| !label) {
+                wins += match positive.cmp(&negative) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                };
+            }
+        }
+        assert!((roc.auc() - wins / 12.0).abs() < 1e-12);
+        assert_eq!(roc.true_positive_rate.last(), Some(&1.0));
+        assert_eq!(roc.false_positive_rate.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn test_perfect_pr_curve() {
+        let pr = pr_curve(&array![4, 3, -1, -2], &array![true, true, false, false]);
+        assert_eq!(pr.average_precision(), 1.0);
+        assert_eq!(pr.thresholds, vec![4, 3, -1, -2]);
+        assert_eq!(pr.precision[1], 1.0);
+    }
+
+    #[test]
+    fn test_calibration_and_brier() {
+        let labels = array![true, false, true, true];
+        let probabilities = array![1.0, 0.0, 0.75, 0.25];
+        let curve = calibration_curve(&probabilities, &labels, 4);
+
+        // 1.0 falls into the last bin
+        assert_eq!(curve.mean_predicted, vec![0.0, 0.25, 0.875]);
+        assert_eq!(curve.fraction_positive, vec![0.0, 1.0, 1.0]);
+        assert!((curve.expected_calibration_error() - (0.75 + 2.0 * 0.125) / 4.0).abs() < 1e-12);
+        assert!((brier_score(&probabilities, &labels) - (0.0625 + 0.5625) / 4.0).abs() < 1e-12);
+        assert_eq!(brier_score(&array![0.5, 0.5], &array![true, false]), 0.25);
+    }
+
+    #[test]
+    fn test_identical_predictions() {
+        let predictions = array![true, false, true];
+        let labels = array![true, true, true];
+        let result = mcnemar_from_predictions(&predictions, &predictions, &labels);
+
+        assert_eq!((result.a_only_correct, result.b_only_correct), (0, 0));
+        assert_eq!(result.statistic, 0.0);
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn test_significant_difference() {
+        // Model A is right on all 40 samples, model B on the first 10 only
+        let labels = Array1::from_elem(40, true);
+        let a = labels.clone();
+        let b = Array1::from_shape_fn(40, |i| i < 10);
+        let result = mcnemar_from_predictions(&a, &b, &labels);
+
+        assert_eq!((result.a_only_correct, result.b_only_correct), (30, 0));
+        assert!((result.statistic - 29.0 * 29.0 / 30.0).abs() < 1e-12);
+        assert!(result.is_significant(0.001));
+    }
+
+    #[test]
+    fn test_grouped_report() {
+        let predictions = array![true, true, false, true, false, false];
+        let labels = array![true, false, false, true, true, false];
+        let report = GroupedReport::new(&predictions, &labels, &[2, 1, 2, 1, 2, 2]);
+
+        let groups: Vec<i32> = report.groups().iter().map(|scores| scores.group).collect();
+        assert_eq!(groups, vec![1, 2]);
+        let first = report.get(&1).unwrap();
+        assert_eq!((first.support, first.accuracy, first.positive_rate), (2, 0.5, 1.0));
+        let second = report.get(&2).unwrap();
+        assert_eq!((second.support, second.accuracy, second.positive_rate), (4, 0.75, 0.25));
+        assert!((second.f1 - 2.0 / 3.0).abs() < 1e-12);
+        assert!(report.get(&3).is_none());
+        assert_eq!(report.accuracy_gap(), 0.25);
+        assert_eq!(report.positive_rate_gap(), 0.75);
+        assert_eq!(report.overall().support, 6);
+        assert_eq!(report.to_string().lines().count(), 4);
+    }
+}
@@ -0,0 +1,564 @@
+//! Multi-class Tsetlin machine
+//!
+//! Each class owns a clause bank whose votes measure the evidence for that
+//! class; the prediction is the class with the highest vote sum. Training
+//! pushes the bank of the true class towards firing and the bank of one
+//! randomly chosen other class towards not firing. With logged bandit
+//! feedback instead of labels, `fit_bandit` trains the classes as the
+//! actions of a policy.
+
+use crate::clause::ClauseBank;
+use crate::input::AsBoolSamples;
+use ndarray::{Array1, Array2, ArrayView1};
+use rand::{prelude::SliceRandom, Rng, SeedableRng};
+
+/// A class ranked by its vote sum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClassCandidate {
+    /// Class index
+    pub class: usize,
+    /// Vote sum of the class
+    pub votes: i32,
+    /// Lead over the next-ranked class (0 for the last class)
+    pub margin: i32,
+}
+
+/// Index of the highest vote sum, preferring the lowest index on ties
+pub fn argmax(votes: ArrayView1<'_, i32>) -> usize {
+    let mut best = 0;
+    for (class, &vote) in votes.iter().enumerate() {
+        if vote > votes[best] {
+            best = class;
+        }
+    }
+    best
+}
+
+/// The `k` highest-voted classes, best first, with their vote margins
+///
+/// Ties are ranked by class index. The margin of each candidate is measured
+/// against the next class in the full ranking, so the last of the `k`
+/// candidates shows how far it is ahead of the first excluded class.
+pub fn top_k(votes: ArrayView1<'_, i32>, k: usize) -> Vec<ClassCandidate> {
+    let mut ranked: Vec<usize> = (0..votes.len()).collect();
+    ranked.sort_by_key(|&class| (std::cmp::Reverse(votes[class]), class));
+
+    ranked
+        .iter()
+        .enumerate()
+        .take(k)
+        .map(|(rank, &class)| ClassCandidate {
+            class,
+            votes: votes[class],
+            margin: ranked
+                .get(rank + 1)
+                .map_or(0, |&next| votes[class] - votes[next]),
+        })
+        .collect()
+}
+
+/// Tsetlin machine for more than two classes
+#[derive(Debug, Clone)]
+pub struct MultiClassTsetlinMachine {
+    /// One clause bank per class
+    class_banks: Vec<ClauseBank>,
+    /// Number of input features
+    num_features: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Decision threshold
+    threshold: f64,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl MultiClassTsetlinMachine {
+    /// Create a new multi-class machine
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `num_classes` - Number of classes (at least two)
+    /// * `clauses_per_class` - Number of clauses of each class (must be even)
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::multiclass::MultiClassTsetlinMachine;
+    /// let machine = MultiClassTsetlinMachine::new(10, 3, 20, 2.0, 10.0);
+    /// assert_eq!(machine.num_classes(), 3);
+    /// ```
+    pub fn new(
+        num_features: usize,
+        num_classes: usize,
+        clauses_per_class: usize,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        Self::with_class_clauses(
+            num_features,
+            &vec![clauses_per_class; num_classes],
+            specificity,
+            threshold,
+        )
+    }
+
+    /// Create a multi-class machine with a separate clause budget per class
+    ///
+    /// Rare or hard classes can be given more clauses than easy ones.
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `clauses_per_class` - Number of clauses of each class (each even and positive)
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::multiclass::MultiClassTsetlinMachine;
+    /// let machine = MultiClassTsetlinMachine::with_class_clauses(10, &[20, 20, 60], 2.0, 10.0);
+    /// assert_eq!(machine.clauses_per_class(), vec![20, 20, 60]);
+    /// ```
+    pub fn with_class_clauses(
+        num_features: usize,
+        clauses_per_class: &[usize],
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        assert!(clauses_per_class.len() >= 2, "At least two classes are required");
+        assert!(
+            clauses_per_class
+                .iter()
+                .all(|&clauses| clauses > 0 && clauses.is_multiple_of(2)),
+            "Number of clauses must be even and positive for every class"
+        );
+
+        let class_banks = clauses_per_class
+            .iter()
+            .map(|&clauses| ClauseBank::new(num_features, clauses, 100))
+            .collect();
+        Self::from_class_banks(class_banks, specificity, threshold)
+    }
+
+    /// Assemble a machine around existing clause banks
+    pub(crate) fn from_class_banks(
+        class_banks: Vec<ClauseBank>,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        assert!(class_banks.len() >= 2, "At least two classes are required");
+        assert!(
+            class_banks
+                .iter()
+                .all(|bank| bank.num_clauses() > 0 && bank.num_clauses().is_multiple_of(2)),
+            "Number of clauses must be even and positive for every class"
+        );
+        assert!(threshold > 0.0, "Threshold must be positive");
+
+        Self {
+            num_features: class_banks[0].num_features(),
+            class_banks,
+            specificity,
+            threshold,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a new multi-class machine with default parameters
+    pub fn with_defaults(num_features: usize, num_classes: usize, clauses_per_class: usize) -> Self {
+        Self::new(num_features, num_classes, clauses_per_class, 2.0, 1.0)
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Train the machine
+    ///
+    /// # Arguments
+    /// * `features` - Training feature matrix (samples x features)
+    /// * `labels` - Class index of each sample
+    /// * `epochs` - Number of training epochs
+    pub fn fit<S: AsBoolSamples + ?Sized>(&mut self, features: &S, labels: &Array1<usize>, epochs: usize) {
+        assert_eq!(features.num_samples(), labels.len());
+        assert_eq!(features.num_features(), self.num_features);
+        assert!(
+            labels.iter().all(|&label| label < self.num_classes()),
+            "Labels must be smaller than the number of classes"
+        );
+
+        let mut indices: Vec<usize> = (0..features.num_samples()).collect();
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+
+            for &idx in &indices {
+                let sample = features.sample(idx);
+                let label = labels[idx];
+
+                self.class_banks[label].update(
+                    &sample,
+                    true,
+                    self.threshold,
+                    self.specificity,
+                    &mut self.rng,
+                );
+
+                let num_others = self.num_classes() - 1;
+                let mut other = crate::rng::gen_index(&mut self.rng, num_others);
+                if other >= label {
+                    other += 1;
+                }
+                self.class_banks[other].update(
+                    &sample,
+                    false,
+                    self.threshold,
+                    self.specificity,
+                    &mut self.rng,
+                );
+            }
+        }
+    }
+
+    /// Train a policy from logged contextual bandit feedback
+    ///
+    /// Classes are actions. Each entry records a context, the action taken
+    /// in it and the reward observed; there is no label saying which action
+    /// would have been best. Only the bank of the taken action learns: a
+    /// positive reward gives it Type I feedback, as if the action were the
+    /// correct class, and a negative reward Type II. Each update happens
+    /// with probability `|reward|`, and zero rewards are ignored. `predict`
+    /// then returns the greedy action, and the clauses of each action read
+    /// as the contexts in which it pays off.
+    ///
+    /// # Arguments
+    /// * `contexts` - Context of each interaction (samples x features)
+    /// * `actions` - Action taken in each context
+    /// * `rewards` - Observed rewards, scaled to `-1.0..=1.0`
+    /// * `epochs` - Number of passes over the log
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::multiclass::MultiClassTsetlinMachine;
+    ///
+    /// let contexts = array![[true, false], [false, true], [true, false]];
+    /// let actions = array![0, 1, 1];
+    /// let rewards = array![1.0, 1.0, -0.5];
+    ///
+    /// let mut policy = MultiClassTsetlinMachine::with_defaults(2, 2, 10);
+    /// policy.fit_bandit(&contexts, &actions, &rewards, 10);
+    /// let action = policy.predict_single(&[true, false]);
+    /// assert!(action < 2);
+    /// ```
+    pub fn fit_bandit<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        contexts: &S,
+        actions: &Array1<usize>,
+        rewards: &Array1<f64>,
+        epochs: usize,
+    ) {
+        assert_eq!(contexts.num_samples(), actions.len());
+        assert_eq!(contexts.num_samples(), rewards.len());
+        assert_eq!(contexts.num_features(), self.num_features);
+        assert!(
+            actions.iter().all(|&action| action < self.num_classes()),
+            "Actions must be smaller than the number of classes"
+        );
+        assert!(
+            rewards.iter().all(|reward| (-1.0..=1.0).contains(reward)),
+            "Rewards must be scaled to [-1, 1]"
+        );
+
+        let mut indices: Vec<usize> = (0..contexts.num_samples()).collect();
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+
+            for &idx in &indices {
+                self.update_bandit(&contexts.sample(idx), actions[idx], rewards[idx]);
+            }
+        }
+    }
+
+    /// Learn from a single bandit interaction, for online policies
+    ///
+    /// Applies the same feedback as one step of
+    /// [`fit_bandit`](Self::fit_bandit).
+    pub fn update_bandit(&mut self, context: &[bool], action: usize, reward: f64) {
+        assert_eq!(context.len(), self.num_features);
+        assert!((-1.0..=1.0).contains(&reward), "Rewards must be scaled to [-1, 1]");
+        if reward == 0.0 {
+            return;
+        }
+
+        self.class_banks[action].update_scaled(
+            context,
+            reward > 0.0,
+            reward.abs(),
+            self.threshold,
+            self.specificity,
+            &mut self.rng,
+        );
+    }
+
+    /// Epsilon-greedy action for a context
+    ///
+    /// With probability `epsilon` a uniformly random action is explored,
+    /// otherwise the action with the highest vote sum is taken.
+    pub fn choose_action(&mut self, context: &[bool], epsilon: f64) -> usize {
+        assert!((0.0..=1.0).contains(&epsilon), "Epsilon must be in [0, 1]");
+
+        if epsilon > 0.0 && self.rng.gen::<f64>() < epsilon {
+            crate::rng::gen_index(&mut self.rng, self.class_banks.len())
+        } else {
+            self.predict_single(context)
+        }
+    }
+
+    /// Vote sum of every class for a single sample
+    pub fn vote_single(&self, features: &[bool]) -> Array1<i32> {
+        assert_eq!(features.len(), self.num_features);
+
+        self.class_banks.iter().map(|bank| bank.vote(features)).collect()
+    }
+
+    /// Vote sums for a dataset (samples x classes)
+    pub fn predict_votes<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array2<i32> {
+        assert_eq!(features.num_features(), self.num_features);
+
+        let mut votes = Array2::zeros((features.num_samples(), self.num_classes()));
+        for (index, mut out) in votes.rows_mut().into_iter().enumerate() {
+            out.assign(&self.vote_single(&features.sample(index)));
+        }
+        votes
+    }
+
+    /// Predicted class of a single sample
+    pub fn predict_single(&self, features: &[bool]) -> usize {
+        argmax(self.vote_single(features).view())
+    }
+
+    /// Predicted classes for a dataset
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<usize> {
+        self.predict_votes(features)
+            .rows()
+            .into_iter()
+            .map(argmax)
+            .collect()
+    }
+
+    /// The `k` most likely classes per sample, best first
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::multiclass::MultiClassTsetlinMachine;
+    ///
+    /// let machine = MultiClassTsetlinMachine::with_defaults(2, 4, 10);
+    /// let ranked = machine.predict_topk(&array![[true, false]], 2);
+    /// assert_eq!(ranked[0].len(), 2);
+    /// ```
+    pub fn predict_topk<S: AsBoolSamples + ?Sized>(&self, features: &S, k: usize) -> Vec<Vec<ClassCandidate>> {
+        self.predict_votes(features)
+            .rows()
+            .into_iter()
+            .map(|votes| top_k(votes, k))
+            .collect()
+    }
+
+    /// Calculate accuracy on a dataset
+    pub fn evaluate<S: AsBoolSamples + ?Sized>(&self, features: &S, labels: &Array1<usize>) -> f64 {
+        let predictions = self.predict(features);
+        let correct = predictions
+            .iter()
+            .zip(labels.iter())
+            .filter(|(&pred, &actual)| pred == actual)
+            .count();
+
+        correct as f64 / labels.len() as f64
+    }
+
+    /// Add a class with `num_clauses` fresh clauses and return its index
+    ///
+    /// Existing classes keep their clauses, so a trained machine can learn
+    /// a category that only appears later: train on data including the new
+    /// class index, ideally mixed with samples of the old classes.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::multiclass::MultiClassTsetlinMachine;
+    ///
+    /// let mut machine = MultiClassTsetlinMachine::with_defaults(2, 2, 10);
+    /// machine.fit(&array![[true, false], [false, true]], &array![0, 1], 5);
+    ///
+    /// let new_class = machine.add_class(10);
+    /// assert_eq!(new_class, 2);
+    /// machine.fit(&array![[true, false], [false, true], [true, true]], &array![0, 1, 2], 5);
+    /// ```
+    pub fn add_class(&mut self, num_clauses: usize) -> usize {
+        assert!(
+            num_clauses > 0 && num_clauses.is_multiple_of(2),
+            "Number of clauses must be even and positive for every class"
+        );
+
+        let num_states = self.class_banks[0].num_states();
+        self.class_banks.push(ClauseBank::new(self.num_features, num_clauses, num_states));
+        self.class_banks.len() - 1
+    }
+
+    /// Clause bank of a class
+    pub fn class_bank(&self, class: usize) -> &ClauseBank {
+        &self.class_banks[class]
+    }
+
+    /// Mutable access to the clause bank of a class
+    pub fn class_bank_mut(&mut self, class: usize) -> &mut ClauseBank {
+        &mut self.class_banks[class]
+    }
+
+    /// Number of classes
+    pub fn num_classes(&self) -> usize {
+        self.class_banks.len()
+    }
+
+    /// Number of clauses of each class
+    pub fn clauses_per_class(&self) -> Vec<usize> {
+        self.class_banks.iter().map(ClauseBank::num_clauses).collect()
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Specificity parameter
+    pub fn specificity(&self) -> f64 {
+        self.specificity
+    }
+
+    /// Decision threshold
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    #[test]
+    fn test_argmax_ties() {
+        assert_eq!(argmax(array![1, 3, 3, -2].view()), 1);
+        assert_eq!(argmax(array![-5, -7].view()), 0);
+    }
+
+    #[test]
+    fn test_top_k_margins() {
+        let ranked = top_k(array![2, 7, -1, 4].view(), 3);
+        assert_eq!(
+            ranked,
+            vec![
+                ClassCandidate { class: 1, votes: 7, margin: 3 },
+                ClassCandidate { class: 3, votes: 4, margin: 2 },
+                ClassCandidate { class: 0, votes: 2, margin: 3 },
+            ]
+        );
+        assert_eq!(top_k(array![1, 1].view(), 5).len(), 2);
+        assert_eq!(top_k(array![1, 1].view(), 5)[1].margin, 0);
+    }
+
+    #[test]
+    fn test_predict_from_clauses() {
+        let mut machine = MultiClassTsetlinMachine::with_defaults(2, 3, 2);
+        // Class 1 votes for x0 and against x1, class 2 the other way round
+        let x0 = Literal { feature: 0, negated: false };
+        let x1 = Literal { feature: 1, negated: false };
+        machine.class_bank_mut(1).clause_mut(0).set_included(x0, true);
+        machine.class_bank_mut(1).clause_mut(1).set_included(x1, true);
+        machine.class_bank_mut(2).clause_mut(0).set_included(x1, true);
+        machine.class_bank_mut(2).clause_mut(1).set_included(x0, true);
+
+        let features = array![[true, false], [false, true], [false, false]];
+        assert_eq!(machine.predict(&features), array![1, 2, 0]);
+        assert_eq!(machine.predict_votes(&features).row(0).to_vec(), vec![0, 1, -1]);
+
+        let ranked = machine.predict_topk(&features, 1);
+        assert_eq!(ranked[1], vec![ClassCandidate { class: 2, votes: 1, margin: 1 }]);
+    }
+
+    #[test]
+    fn test_fit_learns_separable_classes() {
+        let features = array![[true, false], [false, true], [true, true]];
+        let labels = array![0, 1, 2];
+        let mut machine = MultiClassTsetlinMachine::new(2, 3, 8, 4.0, 4.0).seed(2);
+        for class in 0..3 {
+            crate::testing::spread_bank_literals(machine.class_bank_mut(class));
+        }
+        machine.fit(&features, &labels, 50);
+
+        assert_eq!(machine.evaluate(&features, &labels), 1.0);
+    }
+
+    #[test]
+    fn test_class_clause_budgets() {
+        let machine = MultiClassTsetlinMachine::with_class_clauses(4, &[2, 8, 4], 2.0, 5.0);
+        assert_eq!(machine.num_classes(), 3);
+        assert_eq!(machine.clauses_per_class(), vec![2, 8, 4]);
+        assert_eq!(machine.class_bank(1).num_clauses(), 8);
+    }
+
+    #[test]
+    fn test_add_class_keeps_learning() {
+        let mut machine = MultiClassTsetlinMachine::with_defaults(2, 2, 2);
+        let x0 = Literal { feature: 0, negated: false };
+        machine.class_bank_mut(1).clause_mut(0).set_included(x0, true);
+
+        assert_eq!(machine.add_class(4), 2);
+        assert_eq!(machine.clauses_per_class(), vec![2, 2, 4]);
+        assert_eq!(machine.class_bank(1).clauses()[0].included_literals(), vec![x0]);
+        assert_eq!(machine.predict_votes(&array![[false, false]]).row(0).to_vec(), vec![0, -1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of clauses must be even and positive for every class")]
+    fn test_odd_class_budget() {
+        MultiClassTsetlinMachine::with_class_clauses(4, &[2, 3], 2.0, 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold must be positive")]
+    fn test_zero_threshold() {
+        MultiClassTsetlinMachine::with_class_clauses(4, &[2, 2], 2.0, 0.0);
+    }
+
+    #[test]
+    fn test_bandit_rewards_drive_feedback() {
+        let x0 = Literal { feature: 0, negated: false };
+        let mut machine = MultiClassTsetlinMachine::with_defaults(2, 2, 2).seed(3);
+        machine.class_bank_mut(0).clause_mut(0).set_included(x0, true);
+        machine.class_bank_mut(1).clause_mut(0).set_included(x0, true);
+        let contexts = array![[true, false]];
+
+        // Zero rewards give no feedback
+        machine.fit_bandit(&contexts, &array![0], &array![0.0], 20);
+        assert_eq!(machine.class_bank(0).clauses()[0].literal_state(x0), 1);
+
+        // A rewarded action reinforces its literal, a punished one drops it
+        machine.fit_bandit(&contexts, &array![0], &array![1.0], 20);
+        assert!(machine.class_bank(0).clauses()[0].literal_state(x0) > 1);
+        machine.fit_bandit(&contexts, &array![1], &array![-1.0], 200);
+        assert!(machine.class_bank(1).clauses()[0].included_literals().is_empty());
+        assert_eq!(machine.choose_action(&[true, false], 0.0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rewards must be scaled to [-1, 1]")]
+    fn test_bandit_reward_range() {
+        let mut machine = MultiClassTsetlinMachine::with_defaults(2, 2, 2);
+        machine.fit_bandit(&array![[true, false]], &array![0], &array![2.0], 1);
+    }
+}
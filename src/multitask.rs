@@ -0,0 +1,330 @@
+//! Multi-task Tsetlin machine with a shared clause bank
+//!
+//! Several related binary tasks are trained against one set of clauses. Each
+//! task keeps its own integer weight per clause: the sign is the clause's
+//! polarity for that task and the magnitude is how strongly it votes. Clauses
+//! learned from tasks with plenty of data can therefore be reused by tasks
+//! that only have a few labelled samples.
+
+use crate::clause::Clause;
+use ndarray::{Array1, Array2, ArrayView1};
+use rand::{prelude::SliceRandom, SeedableRng};
+
+/// Tsetlin machine that shares its clauses across several binary tasks
+#[derive(Debug, Clone)]
+pub struct MultiTaskTsetlinMachine {
+    /// Clauses shared by all tasks
+    clauses: Vec<Clause>,
+    /// Per-task clause weights (tasks x clauses), sign gives the polarity
+    weights: Array2<i32>,
+    /// Number of input features
+    num_features: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Decision threshold
+    threshold: f64,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl MultiTaskTsetlinMachine {
+    /// Create a new multi-task machine
+    ///
+    /// Every task starts with the usual layout: the first half of the clauses
+    /// vote for the positive class and the second half against it.
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `num_clauses` - Number of shared clauses (must be even)
+    /// * `num_tasks` - Number of binary tasks
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::multitask::MultiTaskTsetlinMachine;
+    /// let machine = MultiTaskTsetlinMachine::new(10, 100, 3, 2.0, 10.0);
+    /// assert_eq!(machine.num_tasks(), 3);
+    /// ```
+    pub fn new(
+        num_features: usize,
+        num_clauses: usize,
+        num_tasks: usize,
+        specificity: f64,
+        threshold: f64,
+    ) -> Self {
+        assert!(num_clauses.is_multiple_of(2), "Number of clauses must be even");
+        assert!(num_tasks > 0, "At least one task is required");
+
+        let clauses = (0..num_clauses)
+            .map(|_| Clause::new(num_features, 100))
+            .collect();
+        let weights = Array2::from_shape_fn((num_tasks, num_clauses), |(_, clause)| {
+            if clause < num_clauses / 2 {
+                1
+            } else {
+                -1
+            }
+        });
+
+        Self {
+            clauses,
+            weights,
+            num_features,
+            specificity,
+            threshold,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a new multi-task machine with default parameters
+    pub fn with_defaults(num_features: usize, num_clauses: usize, num_tasks: usize) -> Self {
+        Self::new(num_features, num_clauses, num_tasks, 2.0, 1.0)
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Train on one dataset per task
+    ///
+    /// `tasks[t]` holds the features and labels for task `t`. Every epoch the
+    /// samples of all tasks are shuffled together, so clauses receive feedback
+    /// from every task in turn.
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::multitask::MultiTaskTsetlinMachine;
+    /// use ndarray::{Array1, Array2};
+    ///
+    /// let features = Array2::from_shape_vec((4, 2), vec![
+    ///     true, false, false, true, true, true, false, false
+    /// ]).unwrap();
+    /// let first = Array1::from_vec(vec![true, false, true, false]);
+    /// let second = Array1::from_vec(vec![true, true, false, false]);
+    ///
+    /// let mut machine = MultiTaskTsetlinMachine::with_defaults(2, 20, 2);
+    /// machine.fit(&[(&features, &first), (&features, &second)], 50);
+    /// let predictions = machine.predict(1, &features);
+    /// assert_eq!(predictions.len(), 4);
+    /// ```
+    pub fn fit(&mut self, tasks: &[(&Array2<bool>, &Array1<bool>)], epochs: usize) {
+        assert_eq!(tasks.len(), self.num_tasks(), "One dataset per task is required");
+        for (features, labels) in tasks {
+            assert_eq!(features.nrows(), labels.len());
+            assert_eq!(features.ncols(), self.num_features);
+        }
+
+        let mut order: Vec<(usize, usize)> = tasks
+            .iter()
+            .enumerate()
+            .flat_map(|(task, (features, _))| (0..features.nrows()).map(move |idx| (task, idx)))
+            .collect();
+
+        for _ in 0..epochs {
+            order.shuffle(&mut self.rng);
+
+            for &(task, idx) in &order {
+                let (features, labels) = tasks[task];
+                let sample_features = features.row(idx).to_vec();
+                self.update(task, &sample_features, labels[idx]);
+            }
+        }
+    }
+
+    /// Train a single task, leaving the weights of other tasks untouched
+    pub fn fit_task(
+        &mut self,
+        task: usize,
+        features: &Array2<bool>,
+        labels: &Array1<bool>,
+        epochs: usize,
+    ) {
+        assert!(task < self.num_tasks(), "Task index out of range");
+        assert_eq!(features.nrows(), labels.len());
+        assert_eq!(features.ncols(), self.num_features);
+
+        let mut indices: Vec<usize> = (0..features.nrows()).collect();
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+
+            for &idx in &indices {
+                let sample_features = features.row(idx).to_vec();
+                self.update(task, &sample_features, labels[idx]);
+            }
+        }
+    }
+
+    /// Compute the weighted vote sum of a task for each sample
+    pub fn predict_votes(&self, task: usize, features: &Array2<bool>) -> Array1<i32> {
+        assert_eq!(features.ncols(), self.num_features);
+
+        features
+            .rows()
+            .into_iter()
+            .map(|row| self.vote(task, &row.to_vec()))
+            .collect()
+    }
+
+    /// Make predictions for a task on a dataset
+    pub fn predict(&self, task: usize, features: &Array2<bool>) -> Array1<bool> {
+        self.predict_votes(task, features).mapv(|vote| vote > 0)
+    }
+
+    /// Make a prediction for a task on a single sample
+    pub fn predict_single(&self, task: usize, features: &[bool]) -> bool {
+        assert_eq!(features.len(), self.num_features);
+
+        self.vote(task, features) > 0
+    }
+
+    /// Accuracy of a task on a dataset (0.0 to 1.0)
+    pub fn evaluate(&self, task: usize, features: &Array2<bool>, labels: &Array1<bool>) -> f64 {
+        let predictions = self.predict(task, features);
+        let correct = predictions
+            .iter()
+            .zip(labels.iter())
+            .filter(|(&pred, &actual)| pred == actual)
+            .count();
+
+        correct as f64 / labels.len() as f64
+    }
+
+    /// Clause weights of a task; the sign is each clause's polarity
+    pub fn task_weights(&self, task: usize) -> ArrayView1<'_, i32> {
+        self.weights.row(task)
+    }
+
+    /// Number of tasks
+    pub fn num_tasks(&self) -> usize {
+        self.weights.nrows()
+    }
+
+    /// Number of shared clauses
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Weighted vote sum of a task for a single sample
+    fn vote(&self, task: usize, input: &[bool]) -> i32 {
+        self.clauses
+            .iter()
+            .zip(self.weights.row(task).iter())
+            .filter(|(clause, _)| clause.evaluate(input))
+            .map(|(_, &weight)| weight)
+            .sum()
+    }
+
+    /// Apply feedback for one labelled sample of a task
+    fn update(&mut self, task: usize, input: &[bool], target: bool) {
+        let vote_sum = self.vote(task, input);
+        let should_update = if target {
+            vote_sum < self.threshold as i32
+        } else {
+            vote_sum > -(self.threshold as i32)
+        };
+        if !should_update {
+            return;
+        }
+
+        let mut weights = self.weights.row_mut(task);
+        for (clause, weight) in self.clauses.iter_mut().zip(weights.iter_mut()) {
+            let clause_output = clause.evaluate(input);
+            let polarity = *weight > 0;
+            let clause_target = if polarity { target } else { !target };
+            clause.update(input, clause_target, clause_output, self.specificity, &mut self.rng);
+
+            // Firing clauses that agree with the label gain weight for this
+            // task; disagreeing ones lose it and flip polarity at zero
+            if clause_output {
+                let sign = weight.signum();
+                if polarity == target {
+                    *weight = weight.saturating_add(sign);
+                } else if *weight == sign {
+                    *weight = -sign;
+                } else {
+                    *weight -= sign;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> (Array2<bool>, Array1<bool>, Array1<bool>) {
+        let features = Array2::from_shape_vec((4, 2), vec![
+            true, false, false, true, true, true, false, false
+        ]).unwrap();
+        let first = Array1::from_vec(vec![true, false, true, false]);
+        let second = Array1::from_vec(vec![false, true, false, true]);
+        (features, first, second)
+    }
+
+    #[test]
+    fn test_multitask_creation() {
+        let machine = MultiTaskTsetlinMachine::new(5, 10, 3, 2.0, 1.0);
+        assert_eq!(machine.num_features(), 5);
+        assert_eq!(machine.num_clauses(), 10);
+        assert_eq!(machine.num_tasks(), 3);
+        assert_eq!(machine.task_weights(2).iter().filter(|&&w| w > 0).count(), 5);
+    }
+
+    #[test]
+    fn test_multitask_training() {
+        let (features, first, second) = dataset();
+        let mut machine = MultiTaskTsetlinMachine::new(2, 16, 2, 4.0, 4.0).seed(5);
+        let (positive, negative) = machine.clauses.split_at_mut(8);
+        crate::testing::spread_literals(positive);
+        crate::testing::spread_literals(negative);
+        machine.fit(&[(&features, &first), (&features, &second)], 50);
+
+        assert_eq!(machine.evaluate(0, &features, &first), 1.0);
+        assert_eq!(machine.evaluate(1, &features, &second), 1.0);
+        for task in 0..2 {
+            assert!(machine.task_weights(task).iter().all(|&w| w != 0));
+        }
+    }
+
+    #[test]
+    fn test_seeded_training_is_reproducible() {
+        let (features, first, second) = dataset();
+        let train = || {
+            let mut machine = MultiTaskTsetlinMachine::with_defaults(2, 20, 2).seed(11);
+            machine.fit(&[(&features, &first), (&features, &second)], 20);
+            machine
+        };
+        let (a, b) = (train(), train());
+        for task in 0..2 {
+            assert_eq!(a.task_weights(task), b.task_weights(task));
+            assert_eq!(a.predict_votes(task, &features), b.predict_votes(task, &features));
+        }
+    }
+
+    #[test]
+    fn test_fit_single_task() {
+        let (features, first, _) = dataset();
+        let mut machine = MultiTaskTsetlinMachine::with_defaults(2, 20, 2);
+        let untouched = machine.task_weights(1).to_owned();
+
+        machine.fit_task(0, &features, &first, 20);
+        assert_eq!(machine.task_weights(1), untouched);
+    }
+
+    #[test]
+    #[should_panic(expected = "One dataset per task is required")]
+    fn test_missing_task_dataset() {
+        let (features, first, _) = dataset();
+        let mut machine = MultiTaskTsetlinMachine::with_defaults(2, 20, 2);
+        machine.fit(&[(&features, &first)], 1);
+    }
+}
@@ -0,0 +1,75 @@
+//! Row-parallel scoring of large sample matrices
+//!
+//! With the `parallel` feature, `TsetlinMachine::predict`, `predict_votes`
+//! and `evaluate` and `CompiledMachine::predict_votes` split matrices of at
+//! least [`MIN_PARALLEL_ROWS`] samples into row blocks and vote on them with
+//! `ndarray::parallel`, which runs the blocks on rayon's global thread pool.
+//! Smaller inputs stay on the calling thread, where scheduling would cost
+//! more than it saves. Results are identical to the sequential path.
+//! Training is unaffected.
+
+use crate::input::AsBoolSamples;
+use ndarray::parallel::prelude::*;
+use ndarray::{Array1, Axis};
+
+/// Fewest samples scored in parallel
+pub const MIN_PARALLEL_ROWS: usize = 1024;
+
+/// Samples voted on by one parallel task
+const BLOCK_ROWS: usize = MIN_PARALLEL_ROWS / 4;
+
+/// Apply `vote` to every sample of `features`, voting on row blocks in parallel
+///
+/// Each block reads its rows with `sample_into`, so the input is never
+/// copied as a whole.
+pub(crate) fn vote_rows<S, F>(features: &S, vote: F) -> Array1<i32>
+where
+    S: AsBoolSamples + ?Sized,
+    F: Fn(&[bool]) -> i32 + Sync,
+{
+    let mut votes = Array1::zeros(features.num_samples());
+    votes
+        .axis_chunks_iter_mut(Axis(0), BLOCK_ROWS)
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(index, mut out)| {
+            let mut buffer = Vec::with_capacity(features.num_features());
+            for (row, out) in (index * BLOCK_ROWS..).zip(out.iter_mut()) {
+                features.sample_into(row, &mut buffer);
+                *out = vote(&buffer);
+            }
+        });
+    votes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitmatrix::BitMatrix;
+    use crate::clause::Literal;
+    use crate::machine::TsetlinMachine;
+    use ndarray::Array2;
+
+    #[test]
+    fn test_parallel_votes_match_sequential() {
+        let mut machine = TsetlinMachine::with_defaults(3, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 2, negated: true }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 1, negated: false }, true);
+
+        let rows = 3 * MIN_PARALLEL_ROWS + 7;
+        let features = Array2::from_shape_fn((rows, 3), |(i, j)| (i * 7 + j * 3) % 5 < 2);
+        let expected: Array1<i32> =
+            features.rows().into_iter().map(|row| machine.vote_single(&row.to_vec())).collect();
+
+        assert_eq!(machine.predict_votes(&features), expected);
+        assert_eq!(machine.compile().predict_votes(&features), expected);
+        // Column-major rows are not contiguous but read the same samples
+        let (transposed, bank) = (features.t().to_owned(), machine.clause_bank());
+        assert_eq!(vote_rows(&transposed.t(), |row| bank.vote(row)), expected);
+        // Packed inputs are read row by row, never unpacked as a whole
+        let packed = BitMatrix::from_array(&features);
+        assert_eq!(machine.predict_votes(&packed), expected);
+    }
+}
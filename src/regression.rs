@@ -0,0 +1,287 @@
+//! Regression Tsetlin machine for real-valued targets
+//!
+//! Every clause votes for a larger output. The prediction is the number of
+//! firing clauses, capped at the threshold `T`, divided by `T` and mapped
+//! onto the target range. Clauses without included literals do not vote, so
+//! an untrained machine predicts the bottom of the range. Training gives
+//! each clause feedback with probability `|error| / T`, where the error is
+//! measured in clause votes: Type I when the prediction is too low, which
+//! grows clauses that match the sample, and Type II when it is too high,
+//! which adds a literal that is false on the sample to every firing clause.
+//! Unlike the classification machines, feedback follows the standard rules
+//! per literal: Type I also forgets literals with probability `1 / s`.
+
+use crate::clause::{Clause, Literal};
+use crate::input::AsBoolSamples;
+use ndarray::Array1;
+use rand::{prelude::SliceRandom, Rng, SeedableRng};
+
+/// Tsetlin machine predicting a real value from boolean features
+#[derive(Debug, Clone)]
+pub struct RegressionTsetlinMachine {
+    /// Clauses, all voting for a larger output
+    clauses: Vec<Clause>,
+    /// Number of input features
+    num_features: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Number of firing clauses that predicts the top of the target range
+    threshold: f64,
+    /// Smallest and largest target, set by the first `fit` unless given
+    target_range: Option<(f64, f64)>,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl RegressionTsetlinMachine {
+    /// Create a new regression machine
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of input features
+    /// * `num_clauses` - Number of clauses
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Firing clauses at the top of the target range
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::regression::RegressionTsetlinMachine;
+    ///
+    /// let features = array![[true, false], [false, true], [true, true]];
+    /// let targets = array![1.0, 3.0, 2.0];
+    ///
+    /// let mut machine = RegressionTsetlinMachine::new(2, 20, 3.0, 10.0).seed(1);
+    /// machine.fit(&features, &targets, 20);
+    /// let predictions = machine.predict(&features);
+    /// assert!(predictions.iter().all(|&p| (1.0..=3.0).contains(&p)));
+    /// ```
+    pub fn new(num_features: usize, num_clauses: usize, specificity: f64, threshold: f64) -> Self {
+        assert!(threshold > 0.0, "Threshold must be positive");
+
+        Self {
+            clauses: (0..num_clauses).map(|_| boundary_clause(num_features)).collect(),
+            num_features,
+            specificity,
+            threshold,
+            target_range: None,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a regression machine with specificity 3.0 and threshold half the clauses
+    pub fn with_defaults(num_features: usize, num_clauses: usize) -> Self {
+        Self::new(num_features, num_clauses, 3.0, (num_clauses as f64 / 2.0).max(1.0))
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Fix the target range instead of taking it from the first training set
+    pub fn target_range(mut self, min: f64, max: f64) -> Self {
+        assert!(min <= max, "Target range must satisfy min <= max");
+        self.target_range = Some((min, max));
+        self
+    }
+
+    /// Train the machine
+    ///
+    /// # Arguments
+    /// * `features` - Feature matrix (samples x features)
+    /// * `targets` - Real-valued target of each sample
+    /// * `epochs` - Number of training epochs
+    pub fn fit<S: AsBoolSamples + ?Sized>(
+        &mut self,
+        features: &S,
+        targets: &Array1<f64>,
+        epochs: usize,
+    ) {
+        assert_eq!(features.num_samples(), targets.len());
+        assert_eq!(features.num_features(), self.num_features);
+        assert!(targets.iter().all(|target| target.is_finite()), "Targets must be finite");
+        if self.target_range.is_none() && !targets.is_empty() {
+            let min = targets.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = targets.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            self.target_range = Some((min, max));
+        }
+
+        let mut indices: Vec<usize> = (0..targets.len()).collect();
+        let mut sample = Vec::with_capacity(self.num_features);
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+            for &idx in &indices {
+                features.sample_into(idx, &mut sample);
+                self.update(&sample, targets[idx]);
+            }
+        }
+    }
+
+    /// Give every clause feedback towards `target` on one sample
+    fn update(&mut self, sample: &[bool], target: f64) {
+        let error = self.scale(target) * self.threshold - self.vote_single(sample) as f64;
+        let probability = (error.abs() / self.threshold).min(1.0);
+        for clause in &mut self.clauses {
+            if self.rng.gen::<f64>() < probability {
+                literal_feedback(clause, sample, error > 0.0, self.specificity, &mut self.rng);
+            }
+        }
+    }
+
+    /// Position of `target` in the target range, from 0.0 to 1.0
+    fn scale(&self, target: f64) -> f64 {
+        match self.target_range {
+            Some((min, max)) if max > min => ((target - min) / (max - min)).clamp(0.0, 1.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Number of firing clauses, capped at the threshold
+    pub fn vote_single(&self, features: &[bool]) -> usize {
+        let votes = self.clauses.iter().filter(|clause| fires(clause, features)).count();
+        votes.min(self.threshold as usize)
+    }
+
+    /// Predict the target of a single sample
+    pub fn predict_single(&self, features: &[bool]) -> f64 {
+        let fraction = self.vote_single(features) as f64 / self.threshold;
+        match self.target_range {
+            Some((min, max)) => min + fraction.min(1.0) * (max - min),
+            None => 0.0,
+        }
+    }
+
+    /// Predict the target of every sample
+    pub fn predict<S: AsBoolSamples + ?Sized>(&self, features: &S) -> Array1<f64> {
+        let mut sample = Vec::with_capacity(self.num_features);
+        (0..features.num_samples())
+            .map(|idx| {
+                features.sample_into(idx, &mut sample);
+                self.predict_single(&sample)
+            })
+            .collect()
+    }
+
+    /// Mean absolute error of the predictions
+    pub fn mean_absolute_error<S: AsBoolSamples + ?Sized>(
+        &self,
+        features: &S,
+        targets: &Array1<f64>,
+    ) -> f64 {
+        let predictions = self.predict(features);
+        let total: f64 = predictions.iter().zip(targets).map(|(p, t)| (p - t).abs()).sum();
+        total / targets.len() as f64
+    }
+
+    /// Smallest and largest predictable target, once known
+    pub fn range(&self) -> Option<(f64, f64)> {
+        self.target_range
+    }
+
+    /// All clauses
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+
+    /// Mutable access to a clause for direct edits
+    pub fn clause_mut(&mut self, index: usize) -> &mut Clause {
+        &mut self.clauses[index]
+    }
+
+    /// Number of input features
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Number of clauses
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Firing clauses at the top of the target range
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
+
+/// Standard Type I (`type_i`) or Type II feedback to every literal of a clause
+///
+/// During training an empty clause fires, so Type I feedback can grow it.
+pub(crate) fn literal_feedback<R: Rng>(
+    clause: &mut Clause,
+    sample: &[bool],
+    type_i: bool,
+    specificity: f64,
+    rng: &mut R,
+) {
+    let output = clause.evaluate(sample);
+    for (feature, &bit) in sample.iter().enumerate() {
+        for negated in [false, true] {
+            let literal = Literal { feature, negated };
+            let value = bit != negated;
+            let state = clause.literal_state(literal);
+            let step = if type_i && output && value {
+                // Type Ia: include literals of a sample the clause matches
+                i32::from(rng.gen::<f64>() < (specificity - 1.0) / specificity)
+            } else if type_i {
+                // Type Ib: forget everything else
+                -i32::from(rng.gen::<f64>() < 1.0 / specificity)
+            } else {
+                // Type II: include a false literal so the clause stops firing
+                i32::from(output && !value && state <= 0)
+            };
+            if step != 0 {
+                clause.set_literal_state(literal, state + step);
+            }
+        }
+    }
+}
+
+/// Clause with every literal one step from being included
+pub(crate) fn boundary_clause(num_features: usize) -> Clause {
+    let mut clause = Clause::new(num_features, 100);
+    for feature in 0..num_features {
+        for negated in [false, true] {
+            clause.set_literal_state(Literal { feature, negated }, 0);
+        }
+    }
+    clause
+}
+
+/// Whether a clause votes on `input`; clauses without literals never do
+pub(crate) fn fires(clause: &Clause, input: &[bool]) -> bool {
+    clause.num_included() > 0 && clause.evaluate(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_prediction_maps_votes_to_range() {
+        let mut machine = RegressionTsetlinMachine::new(2, 4, 3.0, 2.0).target_range(10.0, 20.0);
+        assert_eq!(machine.predict_single(&[true, true]), 10.0);
+
+        machine.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        machine.clause_mut(1).set_included(Literal { feature: 1, negated: false }, true);
+        machine.clause_mut(2).set_included(Literal { feature: 1, negated: false }, true);
+        let predictions = machine.predict(&array![[false, false], [true, false], [true, true]]);
+        // Three firing clauses are capped at the threshold of two
+        assert_eq!(predictions, array![10.0, 15.0, 20.0]);
+    }
+
+    #[test]
+    fn test_learns_from_scratch() {
+        let features = array![[true, false], [false, true], [false, false], [true, true]];
+        let targets = array![4.0, 0.0, 0.0, 4.0];
+        let mut machine = RegressionTsetlinMachine::new(2, 20, 3.0, 8.0).seed(3);
+        let before = machine.mean_absolute_error(&features, &targets);
+        machine.fit(&features, &targets, 200);
+
+        assert_eq!(machine.range(), Some((0.0, 4.0)));
+        assert_eq!(before, 2.0);
+        assert_eq!(machine.predict(&features), targets);
+    }
+}
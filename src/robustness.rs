@@ -0,0 +1,196 @@
+//! Adversarial robustness against bit-flip attacks
+//!
+//! A prediction can only change when a flipped bit belongs to a literal of
+//! some clause, so the search for adversarial flips is restricted to the
+//! features that trained clauses actually use. Within that set
+//! [`flip_attack`] tries every combination of 1, 2, ... flips up to a budget
+//! and returns the smallest one that changes the prediction.
+
+use crate::machine::TsetlinMachine;
+use ndarray::Array2;
+use std::collections::BTreeSet;
+
+/// Result of a bit-flip attack on one sample
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlipAttack {
+    /// Features whose flip changes the prediction
+    pub flipped: Vec<usize>,
+}
+
+impl FlipAttack {
+    /// Number of flipped bits
+    pub fn num_flips(&self) -> usize {
+        self.flipped.len()
+    }
+}
+
+/// Find the smallest set of bit flips that changes the prediction of `sample`
+///
+/// Returns `None` if no combination of at most `budget` flips succeeds.
+///
+/// # Arguments
+/// * `machine` - Trained machine
+/// * `sample` - Input to attack
+/// * `budget` - Maximum number of flipped bits
+///
+/// # Example
+/// ```
+/// use tsetlin::{robustness, TsetlinMachine};
+///
+/// // Untrained clauses use no features, so no flip changes the prediction
+/// let machine = TsetlinMachine::with_defaults(3, 10);
+/// assert_eq!(robustness::flip_attack(&machine, &[true, false, true], 3), None);
+/// ```
+pub fn flip_attack(machine: &TsetlinMachine, sample: &[bool], budget: usize) -> Option<FlipAttack> {
+    assert_eq!(sample.len(), machine.num_features());
+
+    let original = machine.predict_single(sample);
+    let candidates = relevant_features(machine);
+    let mut perturbed = sample.to_vec();
+
+    for size in 1..=budget.min(candidates.len()) {
+        // Walk all combinations of `size` candidates in lexicographic order
+        let mut combination: Vec<usize> = (0..size).collect();
+        loop {
+            for &i in &combination {
+                perturbed[candidates[i]] = !sample[candidates[i]];
+            }
+            let changed = machine.predict_single(&perturbed) != original;
+            for &i in &combination {
+                perturbed[candidates[i]] = sample[candidates[i]];
+            }
+            if changed {
+                return Some(FlipAttack {
+                    flipped: combination.iter().map(|&i| candidates[i]).collect(),
+                });
+            }
+
+            match (0..size).rev().find(|&i| combination[i] < candidates.len() - size + i) {
+                Some(position) => {
+                    combination[position] += 1;
+                    for i in position + 1..size {
+                        combination[i] = combination[i - 1] + 1;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+    None
+}
+
+/// Features used by at least one included literal, in ascending order
+fn relevant_features(machine: &TsetlinMachine) -> Vec<usize> {
+    let features: BTreeSet<usize> = machine
+        .clause_bank()
+        .clauses()
+        .iter()
+        .flat_map(|clause| clause.included_literals())
+        .map(|literal| literal.feature)
+        .collect();
+    features.into_iter().collect()
+}
+
+/// Empirical robustness radius over a dataset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RobustnessReport {
+    /// Minimal number of flips per sample, `None` if the budget did not suffice
+    pub radii: Vec<Option<usize>>,
+    /// Flip budget of the attack
+    pub budget: usize,
+}
+
+impl RobustnessReport {
+    /// Fraction of samples whose prediction survives every attack of at most `flips` bits
+    pub fn robust_fraction(&self, flips: usize) -> f64 {
+        if self.radii.is_empty() {
+            return 0.0;
+        }
+        let robust = self
+            .radii
+            .iter()
+            .filter(|radius| radius.is_none_or(|radius| radius > flips))
+            .count();
+        robust as f64 / self.radii.len() as f64
+    }
+
+    /// Mean radius, counting unbroken samples as `budget + 1` (a lower bound)
+    pub fn mean_radius(&self) -> f64 {
+        if self.radii.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self
+            .radii
+            .iter()
+            .map(|radius| radius.unwrap_or(self.budget + 1))
+            .sum();
+        total as f64 / self.radii.len() as f64
+    }
+
+    /// Smallest radius over the dataset, `None` if no sample was broken
+    pub fn min_radius(&self) -> Option<usize> {
+        self.radii.iter().flatten().min().copied()
+    }
+}
+
+/// Attack every sample of a dataset with at most `budget` flips
+pub fn robustness_radius(
+    machine: &TsetlinMachine,
+    features: &Array2<bool>,
+    budget: usize,
+) -> RobustnessReport {
+    let radii = features
+        .rows()
+        .into_iter()
+        .map(|row| flip_attack(machine, &row.to_vec(), budget).map(|attack| attack.num_flips()))
+        .collect();
+
+    RobustnessReport { radii, budget }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    /// Positive clause `x0 AND x1`, negative clause `x2`
+    fn machine() -> TsetlinMachine {
+        let mut machine = TsetlinMachine::with_defaults(4, 2);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(0).set_included(Literal { feature: 1, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 2, negated: false }, true);
+        machine
+    }
+
+    #[test]
+    fn test_relevant_features() {
+        assert_eq!(relevant_features(&machine()), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_minimal_attack() {
+        let machine = machine();
+        // Predicted positive; flipping x0 or x1 breaks the positive clause
+        let attack = flip_attack(&machine, &[true, true, false, false], 3).unwrap();
+        assert_eq!(attack.flipped, vec![0]);
+
+        // Predicted negative; both x0 and x1 must be set and x2 cleared
+        let attack = flip_attack(&machine, &[false, false, true, true], 3).unwrap();
+        assert_eq!(attack.flipped, vec![0, 1, 2]);
+        assert_eq!(flip_attack(&machine, &[false, false, true, true], 2), None);
+    }
+
+    #[test]
+    fn test_robustness_report() {
+        let features = array![[true, true, false, false], [false, false, true, true]];
+        let report = robustness_radius(&machine(), &features, 2);
+
+        assert_eq!(report.radii, vec![Some(1), None]);
+        assert_eq!(report.min_radius(), Some(1));
+        assert_eq!(report.robust_fraction(0), 1.0);
+        assert_eq!(report.robust_fraction(1), 0.5);
+        assert_eq!(report.mean_radius(), 2.0);
+    }
+}
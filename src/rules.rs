@@ -0,0 +1,283 @@
+//! Human-readable rules extracted from trained clauses
+//!
+//! Every trained clause is a conjunction of literals that votes for or against
+//! the positive class. Read per polarity, the clauses form a DNF: "vote for the
+//! positive class if any positive rule matches". [`simplify`] minimizes such a
+//! rule set with a Quine–McCluskey style pass, removing contradictory,
+//! duplicated and absorbed terms and merging terms that differ in one literal.
+
+use crate::clause::Literal;
+use std::fmt;
+
+/// A conjunction of literals voting for one class
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// True if the rule votes for the positive class
+    pub polarity: bool,
+    /// Literals that must all hold, ordered by feature
+    pub literals: Vec<Literal>,
+}
+
+impl Rule {
+    /// Check whether the rule matches an input
+    pub fn matches(&self, input: &[bool]) -> bool {
+        self.literals
+            .iter()
+            .all(|literal| input[literal.feature] != literal.negated)
+    }
+
+    /// Check whether the rule requires a feature to be both true and false
+    pub fn is_contradictory(&self) -> bool {
+        self.literals
+            .windows(2)
+            .any(|pair| pair[0].feature == pair[1].feature && pair[0].negated != pair[1].negated)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.literals.is_empty() {
+            return write!(f, "TRUE");
+        }
+
+        for (i, literal) in self.literals.iter().enumerate() {
+            if i > 0 {
+                write!(f, " AND ")?;
+            }
+            if literal.negated {
+                write!(f, "NOT ")?;
+            }
+            write!(f, "x{}", literal.feature)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered if-else rules: the first matching rule decides the class
+///
+/// Built from a trained machine by
+/// [`export::to_decision_list`](crate::export::to_decision_list).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionList {
+    /// Rules in evaluation order; a matching rule predicts its polarity
+    pub rules: Vec<Rule>,
+    /// Class predicted when no rule matches
+    pub default: bool,
+    /// Fraction of the extraction samples on which the list agrees with the machine
+    pub fidelity: f64,
+}
+
+impl DecisionList {
+    /// Predict the class of one input
+    pub fn predict_single(&self, input: &[bool]) -> bool {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(input))
+            .map_or(self.default, |rule| rule.polarity)
+    }
+}
+
+impl fmt::Display for DecisionList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let class = |positive: bool| if positive { "positive" } else { "negative" };
+        for (i, rule) in self.rules.iter().enumerate() {
+            let keyword = if i == 0 { "IF" } else { "ELSE IF" };
+            writeln!(f, "{keyword} {rule} THEN {}", class(rule.polarity))?;
+        }
+        if self.rules.is_empty() {
+            write!(f, "{}", class(self.default))
+        } else {
+            write!(f, "ELSE {}", class(self.default))
+        }
+    }
+}
+
+/// Minimize a rule set, treating the rules of each polarity as a DNF
+///
+/// The result matches exactly the same inputs per polarity as the original
+/// rules, but no longer preserves how many rules fire, so it describes the
+/// learned logic rather than the vote counts. Positive rules come first.
+///
+/// # Example
+/// ```
+/// use tsetlin::clause::Literal;
+/// use tsetlin::rules::{simplify, Rule};
+///
+/// let x0 = Literal { feature: 0, negated: false };
+/// let x1 = Literal { feature: 1, negated: false };
+/// let not_x1 = Literal { feature: 1, negated: true };
+///
+/// // (x0 AND x1) OR (x0 AND NOT x1) is just x0
+/// let rules = vec![
+///     Rule { polarity: true, literals: vec![x0, x1] },
+///     Rule { polarity: true, literals: vec![x0, not_x1] },
+/// ];
+/// assert_eq!(simplify(&rules), vec![Rule { polarity: true, literals: vec![x0] }]);
+/// ```
+pub fn simplify(rules: &[Rule]) -> Vec<Rule> {
+    let mut simplified = Vec::new();
+    for polarity in [true, false] {
+        let terms: Vec<Vec<Literal>> = rules
+            .iter()
+            .filter(|rule| rule.polarity == polarity)
+            .map(|rule| normalized(&rule.literals))
+            .collect();
+
+        simplified.extend(
+            minimize(terms)
+                .into_iter()
+                .map(|literals| Rule { polarity, literals }),
+        );
+    }
+    simplified
+}
+
+/// Sorted, duplicate-free copy of a term
+fn normalized(literals: &[Literal]) -> Vec<Literal> {
+    let mut literals = literals.to_vec();
+    literals.sort();
+    literals.dedup();
+    literals
+}
+
+/// Minimize a DNF given as a list of sorted terms
+fn minimize(mut terms: Vec<Vec<Literal>>) -> Vec<Vec<Literal>> {
+    loop {
+        // Contradictory terms never match
+        terms.retain(|term| {
+            !term
+                .windows(2)
+                .any(|pair| pair[0].feature == pair[1].feature)
+        });
+        terms.sort();
+        terms.dedup();
+
+        // Absorption: a term implied by a shorter one adds nothing
+        let absorbed: Vec<bool> = terms
+            .iter()
+            .enumerate()
+            .map(|(i, term)| {
+                terms
+                    .iter()
+                    .enumerate()
+                    .any(|(j, other)| i != j && other.len() < term.len() && is_subset(other, term))
+            })
+            .collect();
+        let mut kept: Vec<Vec<Literal>> = terms
+            .into_iter()
+            .zip(absorbed)
+            .filter(|(_, absorbed)| !absorbed)
+            .map(|(term, _)| term)
+            .collect();
+
+        // Merging: (A AND x) OR (A AND NOT x) is A
+        match find_mergeable(&kept) {
+            Some((i, j, position)) => {
+                let mut merged = kept[i].clone();
+                merged.remove(position);
+                kept.swap_remove(j.max(i));
+                kept.swap_remove(j.min(i));
+                kept.push(merged);
+                terms = kept;
+            }
+            None => return kept,
+        }
+    }
+}
+
+/// Check whether every literal of `small` appears in `large` (both sorted)
+fn is_subset(small: &[Literal], large: &[Literal]) -> bool {
+    small.iter().all(|literal| large.binary_search(literal).is_ok())
+}
+
+/// Find two terms that differ only in the sign of one literal
+fn find_mergeable(terms: &[Vec<Literal>]) -> Option<(usize, usize, usize)> {
+    for i in 0..terms.len() {
+        for j in (i + 1)..terms.len() {
+            let (a, b) = (&terms[i], &terms[j]);
+            if a.len() != b.len() {
+                continue;
+            }
+
+            let mut differing = a.iter().zip(b.iter()).enumerate().filter(|(_, (x, y))| x != y);
+            if let (Some((position, (x, y))), None) = (differing.next(), differing.next()) {
+                if x.feature == y.feature {
+                    return Some((i, j, position));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit(feature: usize, negated: bool) -> Literal {
+        Literal { feature, negated }
+    }
+
+    fn positive(literals: Vec<Literal>) -> Rule {
+        Rule { polarity: true, literals }
+    }
+
+    #[test]
+    fn test_rule_display_and_matching() {
+        let rule = positive(vec![lit(0, false), lit(2, true)]);
+        assert_eq!(rule.to_string(), "x0 AND NOT x2");
+        assert!(rule.matches(&[true, true, false]));
+        assert!(!rule.matches(&[true, true, true]));
+        assert_eq!(positive(vec![]).to_string(), "TRUE");
+    }
+
+    #[test]
+    fn test_simplify_absorption_and_duplicates() {
+        let rules = vec![
+            positive(vec![lit(0, false), lit(1, false)]),
+            positive(vec![lit(0, false)]),
+            positive(vec![lit(0, false)]),
+            Rule { polarity: false, literals: vec![lit(3, true)] },
+        ];
+        assert_eq!(simplify(&rules), vec![
+            positive(vec![lit(0, false)]),
+            Rule { polarity: false, literals: vec![lit(3, true)] },
+        ]);
+    }
+
+    #[test]
+    fn test_simplify_contradictions() {
+        let rules = vec![positive(vec![lit(1, false), lit(1, true)])];
+        assert!(rules[0].is_contradictory());
+        assert!(simplify(&rules).is_empty());
+    }
+
+    #[test]
+    fn test_simplify_repeated_merging() {
+        // All four combinations of x0 and x1 together with x2 reduce to x2
+        let rules = vec![
+            positive(vec![lit(0, false), lit(1, false), lit(2, false)]),
+            positive(vec![lit(0, false), lit(1, true), lit(2, false)]),
+            positive(vec![lit(0, true), lit(1, false), lit(2, false)]),
+            positive(vec![lit(0, true), lit(1, true), lit(2, false)]),
+        ];
+        assert_eq!(simplify(&rules), vec![positive(vec![lit(2, false)])]);
+    }
+
+    #[test]
+    fn test_simplify_preserves_semantics() {
+        let rules = vec![
+            positive(vec![lit(0, false), lit(1, true)]),
+            positive(vec![lit(0, false), lit(1, false), lit(2, true)]),
+            positive(vec![lit(2, true), lit(0, false)]),
+        ];
+        let simplified = simplify(&rules);
+
+        for bits in 0..8u8 {
+            let input: Vec<bool> = (0..3).map(|i| bits & (1 << i) != 0).collect();
+            let original = rules.iter().any(|rule| rule.matches(&input));
+            let minimized = simplified.iter().any(|rule| rule.matches(&input));
+            assert_eq!(original, minimized);
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Sample ordering for training epochs
+//!
+//! At the start of every epoch `fit` asks its [`Sampler`] which samples to
+//! train on and in which order (see [`TsetlinMachine::sampler`]). Without a
+//! sampler the samples are shuffled, as with [`Shuffled`].
+//!
+//! [`TsetlinMachine::sampler`]: crate::TsetlinMachine::sampler
+
+use ndarray::Array1;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::SliceRandom;
+use rand::RngCore;
+use std::fmt::Debug;
+
+/// Strategy choosing the training samples of each epoch
+pub trait Sampler: Debug {
+    /// Sample indices to train on, in order
+    ///
+    /// # Arguments
+    /// * `labels` - Labels of the training samples
+    /// * `epoch` - Number of epochs the machine has been trained for so far
+    /// * `rng` - The machine's random number generator
+    fn order(&mut self, labels: &Array1<bool>, epoch: usize, rng: &mut dyn RngCore) -> Vec<usize>;
+
+    /// Boxed copy of the sampler, so machines holding one can be cloned
+    fn clone_box(&self) -> Box<dyn Sampler>;
+
+    /// Whether the sampler needs fresh vote margins before ordering `epoch`
+    fn wants_margins(&self, _epoch: usize) -> bool {
+        false
+    }
+
+    /// Receive the current vote margin of every sample
+    ///
+    /// A margin is the vote sum signed by the label, so it is positive when
+    /// the sample is classified correctly and large when it is easy. Called
+    /// before [`order`](Self::order) whenever
+    /// [`wants_margins`](Self::wants_margins) returns true.
+    fn set_margins(&mut self, _margins: &[i32], _epoch: usize) {}
+}
+
+impl Clone for Box<dyn Sampler> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Every sample once per epoch, in index order
+///
+/// Useful for deterministic debugging: training depends on the random number
+/// generator only through feedback, not through the sample order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sequential;
+
+impl Sampler for Sequential {
+    fn order(&mut self, labels: &Array1<bool>, _epoch: usize, _rng: &mut dyn RngCore) -> Vec<usize> {
+        (0..labels.len()).collect()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(*self)
+    }
+}
+
+/// Every sample once per epoch, in a fresh random order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shuffled {
+    /// Order of the previous epoch, shuffled again each epoch
+    order: Vec<usize>,
+}
+
+impl Sampler for Shuffled {
+    fn order(&mut self, labels: &Array1<bool>, _epoch: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        if self.order.len() != labels.len() {
+            self.order = (0..labels.len()).collect();
+        }
+        self.order.shuffle(rng);
+        self.order.clone()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/// Samples drawn with replacement in proportion to fixed weights
+///
+/// Each epoch draws as many samples as there are weights, so heavily weighted
+/// samples are seen several times and zero-weight samples never.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weighted {
+    /// Non-negative weight of each sample
+    weights: Vec<f64>,
+}
+
+impl Weighted {
+    /// Create a weighted sampler
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use rand::SeedableRng;
+    /// use tsetlin::sampler::{Sampler, Weighted};
+    ///
+    /// let mut sampler = Weighted::new(vec![0.0, 1.0, 0.0]);
+    /// let mut rng = tsetlin::rng::TsetlinRng::seed_from_u64(0);
+    /// assert_eq!(sampler.order(&array![true, false, true], 0, &mut rng), vec![1, 1, 1]);
+    /// ```
+    pub fn new(weights: Vec<f64>) -> Self {
+        assert!(
+            weights.iter().all(|&w| w >= 0.0 && w.is_finite()),
+            "Sample weights must be finite and non-negative"
+        );
+        assert!(weights.iter().any(|&w| w > 0.0), "At least one sample weight must be positive");
+
+        Self { weights }
+    }
+
+    /// Weight of each sample
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+}
+
+impl Sampler for Weighted {
+    fn order(&mut self, labels: &Array1<bool>, _epoch: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        assert_eq!(labels.len(), self.weights.len(), "There must be one weight per sample");
+
+        let distribution = WeightedIndex::new(&self.weights).expect("weights checked in new");
+        (0..self.weights.len()).map(|_| distribution.sample(rng)).collect()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/// Easy samples first, harder ones added as training progresses
+///
+/// Samples are ranked by a difficulty score. In epoch `e` the easiest
+/// `(e + 1) / warmup_epochs` fraction of them is trained on, shuffled; from
+/// epoch `warmup_epochs - 1` on every sample is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curriculum {
+    /// Sample indices from easiest to hardest
+    ranking: Vec<usize>,
+    /// Number of epochs until every sample is included
+    warmup_epochs: usize,
+}
+
+impl Curriculum {
+    /// Create a curriculum from per-sample difficulty scores (lower is easier)
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use rand::SeedableRng;
+    /// use tsetlin::sampler::{Curriculum, Sampler};
+    ///
+    /// let mut sampler = Curriculum::new(&[0.9, 0.1, 0.5, 0.3], 2);
+    /// let mut rng = tsetlin::rng::TsetlinRng::seed_from_u64(0);
+    /// let labels = array![true, false, true, false];
+    ///
+    /// let mut first = sampler.order(&labels, 0, &mut rng);
+    /// first.sort_unstable();
+    /// assert_eq!(first, vec![1, 3]);
+    /// assert_eq!(sampler.order(&labels, 1, &mut rng).len(), 4);
+    /// ```
+    pub fn new(difficulty: &[f64], warmup_epochs: usize) -> Self {
+        assert!(warmup_epochs > 0, "Warmup must last at least one epoch");
+        assert!(
+            difficulty.iter().all(|d| !d.is_nan()),
+            "Difficulty scores must not be NaN"
+        );
+
+        let mut ranking: Vec<usize> = (0..difficulty.len()).collect();
+        ranking.sort_by(|&a, &b| difficulty[a].total_cmp(&difficulty[b]));
+        Self { ranking, warmup_epochs }
+    }
+
+    /// Sample indices from easiest to hardest
+    pub fn ranking(&self) -> &[usize] {
+        &self.ranking
+    }
+
+    /// Number of samples trained on in an epoch
+    pub fn active_samples(&self, epoch: usize) -> usize {
+        let stage = (epoch + 1).min(self.warmup_epochs);
+        (self.ranking.len() * stage).div_ceil(self.warmup_epochs)
+    }
+}
+
+impl Sampler for Curriculum {
+    fn order(&mut self, labels: &Array1<bool>, epoch: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        assert_eq!(labels.len(), self.ranking.len(), "There must be one difficulty per sample");
+
+        let mut order = self.ranking[..self.active_samples(epoch)].to_vec();
+        order.shuffle(rng);
+        order
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+/// Direction of a [`MarginCurriculum`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarginOrder {
+    /// Largest margins first: confidently correct samples before hard ones
+    #[default]
+    EasyToHard,
+    /// Smallest margins first: misclassified and borderline samples lead the
+    /// epoch (hard-example mining)
+    HardToEasy,
+}
+
+/// Every sample once per epoch, ordered by the machine's current vote margin
+///
+/// The margins are measured every `refresh_interval` epochs and the order is
+/// kept in between. Ties are broken randomly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginCurriculum {
+    /// Direction of the ordering
+    direction: MarginOrder,
+    /// Number of epochs between margin measurements
+    refresh_interval: usize,
+    /// Margins of the last measurement
+    margins: Vec<i32>,
+    /// Epoch of the last measurement
+    refreshed_at: Option<usize>,
+    /// Current order, rebuilt after each measurement
+    ranking: Vec<usize>,
+}
+
+impl MarginCurriculum {
+    /// Create a margin curriculum
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::sampler::{MarginCurriculum, MarginOrder};
+    /// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+    ///
+    /// let (features, labels) = generate_xor_dataset();
+    /// let mut machine = TsetlinMachine::with_defaults(2, 10)
+    ///     .seed(1)
+    ///     .sampler(MarginCurriculum::new(MarginOrder::HardToEasy, 5));
+    /// machine.fit(&features, &labels, 20);
+    /// ```
+    pub fn new(direction: MarginOrder, refresh_interval: usize) -> Self {
+        assert!(refresh_interval > 0, "Refresh interval must be positive");
+
+        Self {
+            direction,
+            refresh_interval,
+            margins: Vec::new(),
+            refreshed_at: None,
+            ranking: Vec::new(),
+        }
+    }
+
+    /// Direction of the ordering
+    pub fn direction(&self) -> MarginOrder {
+        self.direction
+    }
+
+    /// Number of epochs between margin measurements
+    pub fn refresh_interval(&self) -> usize {
+        self.refresh_interval
+    }
+}
+
+impl Sampler for MarginCurriculum {
+    fn order(&mut self, labels: &Array1<bool>, _epoch: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        assert_eq!(
+            self.margins.len(),
+            labels.len(),
+            "Margin curriculum needs one vote margin per sample"
+        );
+
+        if self.ranking.len() != labels.len() {
+            self.ranking = (0..labels.len()).collect();
+            self.ranking.shuffle(rng);
+            // Stable sort keeps the random order among equal margins
+            match self.direction {
+                MarginOrder::EasyToHard => {
+                    self.ranking.sort_by_key(|&i| std::cmp::Reverse(self.margins[i]))
+                }
+                MarginOrder::HardToEasy => self.ranking.sort_by_key(|&i| self.margins[i]),
+            }
+        }
+        self.ranking.clone()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+
+    fn wants_margins(&self, epoch: usize) -> bool {
+        self.refreshed_at
+            .is_none_or(|refreshed| epoch >= refreshed + self.refresh_interval)
+    }
+
+    fn set_margins(&mut self, margins: &[i32], epoch: usize) {
+        self.margins = margins.to_vec();
+        self.refreshed_at = Some(epoch);
+        self.ranking.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_sequential_and_shuffled_cover_all_samples() {
+        let labels = array![true, false, true, false, true];
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(1);
+        assert_eq!(Sequential.order(&labels, 3, &mut rng), vec![0, 1, 2, 3, 4]);
+
+        let mut order = Shuffled::default().order(&labels, 0, &mut rng);
+        order.sort_unstable();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_curriculum_grows_from_easy_samples() {
+        let curriculum = Curriculum::new(&[3.0, 0.0, 2.0, 1.0, 4.0], 3);
+        assert_eq!(curriculum.ranking(), &[1, 3, 2, 0, 4]);
+        assert_eq!(curriculum.active_samples(0), 2);
+        assert_eq!(curriculum.active_samples(1), 4);
+        assert_eq!(curriculum.active_samples(2), 5);
+        assert_eq!(curriculum.active_samples(10), 5);
+    }
+
+    #[test]
+    fn test_margin_curriculum_refresh() {
+        let labels = array![true, false, true, false];
+        let mut rng = crate::rng::TsetlinRng::seed_from_u64(1);
+        let mut sampler = MarginCurriculum::new(MarginOrder::HardToEasy, 3);
+        assert!(sampler.wants_margins(0));
+
+        sampler.set_margins(&[2, -1, 5, 0], 0);
+        assert_eq!(sampler.order(&labels, 0, &mut rng), vec![1, 3, 0, 2]);
+        assert!(!sampler.wants_margins(2));
+        assert!(sampler.wants_margins(3));
+
+        let mut easy_first = MarginCurriculum::new(MarginOrder::EasyToHard, 1);
+        easy_first.set_margins(&[2, -1, 5, 0], 0);
+        assert_eq!(easy_first.order(&labels, 0, &mut rng), vec![2, 0, 3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "At least one sample weight must be positive")]
+    fn test_weighted_rejects_zero_weights() {
+        Weighted::new(vec![0.0, 0.0]);
+    }
+}
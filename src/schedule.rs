@@ -0,0 +1,126 @@
+//! Hyperparameter schedules over training epochs
+//!
+//! A [`Schedule`] gives the value of a hyperparameter at every epoch of
+//! training, counted across calls to `fit`. The threshold `T` is often raised
+//! as training progresses so that votes of a settled model stabilize (see
+//! [`TsetlinMachine::threshold_schedule`](crate::TsetlinMachine::threshold_schedule)).
+
+use std::fmt;
+
+/// Value of a hyperparameter as a function of the epoch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// The same value in every epoch
+    Constant(f64),
+    /// Straight line from `start` to `end` over `epochs`, then `end`
+    Linear {
+        /// Value in the first epoch
+        start: f64,
+        /// Value from epoch `epochs` on
+        end: f64,
+        /// Length of the ramp
+        epochs: usize,
+    },
+    /// Geometric interpolation from `start` to `end` over `epochs`, then `end`
+    ///
+    /// Both values must be positive.
+    Exponential {
+        /// Value in the first epoch
+        start: f64,
+        /// Value from epoch `epochs` on
+        end: f64,
+        /// Length of the ramp
+        epochs: usize,
+    },
+    /// `start` multiplied by `factor` every `every` epochs
+    Step {
+        /// Value in the first epoch
+        start: f64,
+        /// Multiplier applied at each step
+        factor: f64,
+        /// Epochs between steps
+        every: usize,
+    },
+}
+
+impl Schedule {
+    /// Value in `epoch` (counted from 0)
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::schedule::Schedule;
+    ///
+    /// let schedule = Schedule::Linear { start: 5.0, end: 15.0, epochs: 10 };
+    /// assert_eq!(schedule.value(0), 5.0);
+    /// assert_eq!(schedule.value(5), 10.0);
+    /// assert_eq!(schedule.value(50), 15.0);
+    /// ```
+    pub fn value(&self, epoch: usize) -> f64 {
+        match *self {
+            Schedule::Constant(value) => value,
+            Schedule::Linear { start, end, epochs } => {
+                start + (end - start) * progress(epoch, epochs)
+            }
+            Schedule::Exponential { start, end, epochs } => {
+                assert!(start > 0.0 && end > 0.0, "Exponential schedules need positive values");
+                start * (end / start).powf(progress(epoch, epochs))
+            }
+            Schedule::Step { start, factor, every } => {
+                assert!(every > 0, "Step interval must be positive");
+                start * factor.powi(i32::try_from(epoch / every).unwrap_or(i32::MAX))
+            }
+        }
+    }
+}
+
+/// Fraction of a ramp of `epochs` epochs completed in `epoch`
+fn progress(epoch: usize, epochs: usize) -> f64 {
+    if epochs == 0 {
+        1.0
+    } else {
+        epoch.min(epochs) as f64 / epochs as f64
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Schedule::Constant(value) => write!(f, "constant {value}"),
+            Schedule::Linear { start, end, epochs } => {
+                write!(f, "linear {start} -> {end} over {epochs} epochs")
+            }
+            Schedule::Exponential { start, end, epochs } => {
+                write!(f, "exponential {start} -> {end} over {epochs} epochs")
+            }
+            Schedule::Step { start, factor, every } => {
+                write!(f, "{start}, x{factor} every {every} epochs")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_values() {
+        assert_eq!(Schedule::Constant(3.0).value(100), 3.0);
+
+        let exponential = Schedule::Exponential { start: 1.0, end: 100.0, epochs: 2 };
+        assert_eq!(exponential.value(0), 1.0);
+        assert!((exponential.value(1) - 10.0).abs() < 1e-9);
+        assert_eq!(exponential.value(9), 100.0);
+
+        let step = Schedule::Step { start: 4.0, factor: 2.0, every: 3 };
+        let values: Vec<f64> = (0..7).map(|epoch| step.value(epoch)).collect();
+        assert_eq!(values, vec![4.0, 4.0, 4.0, 8.0, 8.0, 8.0, 16.0]);
+    }
+
+    #[test]
+    fn test_empty_ramp_jumps_to_end() {
+        let schedule = Schedule::Linear { start: 1.0, end: 2.0, epochs: 0 };
+        assert_eq!(schedule.value(0), 2.0);
+        assert_eq!(schedule.to_string(), "linear 1 -> 2 over 0 epochs");
+    }
+}
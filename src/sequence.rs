@@ -0,0 +1,237 @@
+//! Recurrent Tsetlin machine for variable-length sequences
+//!
+//! A single clause bank reads the sequence one timestep at a time. The input
+//! of each step is the step's features followed by the outputs of every
+//! clause at the previous step, so clauses can build on what they saw
+//! earlier. The vote sum at the last timestep classifies the sequence, and
+//! training gives feedback on that last step only.
+
+use crate::clause::ClauseBank;
+use crate::input::AsBoolSamples;
+use ndarray::Array1;
+use rand::{prelude::SliceRandom, SeedableRng};
+
+/// Tsetlin machine that classifies sequences of boolean feature vectors
+#[derive(Debug, Clone)]
+pub struct SequenceTsetlinMachine {
+    /// Shared clause bank over step features plus carried clause outputs
+    clause_bank: ClauseBank,
+    /// Number of features per timestep
+    num_features: usize,
+    /// Specificity parameter
+    specificity: f64,
+    /// Decision threshold
+    threshold: f64,
+    /// Random number generator
+    rng: crate::rng::TsetlinRng,
+}
+
+impl SequenceTsetlinMachine {
+    /// Create a new sequence machine
+    ///
+    /// # Arguments
+    /// * `num_features` - Number of features per timestep
+    /// * `num_clauses` - Number of clauses (must be even)
+    /// * `specificity` - Specificity parameter
+    /// * `threshold` - Decision threshold
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::sequence::SequenceTsetlinMachine;
+    /// let machine = SequenceTsetlinMachine::new(4, 10, 2.0, 5.0);
+    /// // Clauses see the 4 step features and the 10 previous clause outputs
+    /// assert_eq!(machine.clause_bank().num_features(), 14);
+    /// ```
+    pub fn new(num_features: usize, num_clauses: usize, specificity: f64, threshold: f64) -> Self {
+        assert!(num_clauses.is_multiple_of(2), "Number of clauses must be even");
+
+        Self {
+            clause_bank: ClauseBank::new(num_features + num_clauses, num_clauses, 100),
+            num_features,
+            specificity,
+            threshold,
+            rng: crate::rng::TsetlinRng::from_entropy(),
+        }
+    }
+
+    /// Create a new sequence machine with default parameters
+    pub fn with_defaults(num_features: usize, num_clauses: usize) -> Self {
+        Self::new(num_features, num_clauses, 2.0, 1.0)
+    }
+
+    /// Seed the random number generator for reproducible training
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = crate::rng::TsetlinRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Clause input at the last timestep
+    ///
+    /// The step's features are followed by the clause outputs of the step
+    /// before; those outputs are all false before the first timestep.
+    pub fn final_input<S: AsBoolSamples + ?Sized>(&self, sequence: &S) -> Vec<bool> {
+        assert!(sequence.num_samples() > 0, "Sequences must have at least one timestep");
+        assert_eq!(sequence.num_features(), self.num_features);
+
+        let num_clauses = self.clause_bank.num_clauses();
+        let mut input = vec![false; self.num_features + num_clauses];
+        let mut step = Vec::with_capacity(self.num_features);
+        for t in 0..sequence.num_samples() {
+            if t > 0 {
+                let outputs: Vec<bool> = self
+                    .clause_bank
+                    .clauses()
+                    .iter()
+                    .map(|clause| clause.evaluate(&input))
+                    .collect();
+                input[self.num_features..].copy_from_slice(&outputs);
+            }
+            sequence.sample_into(t, &mut step);
+            input[..self.num_features].copy_from_slice(&step);
+        }
+        input
+    }
+
+    /// Train the machine
+    ///
+    /// # Arguments
+    /// * `sequences` - Training sequences, each timesteps x features
+    /// * `labels` - Label of each sequence
+    /// * `epochs` - Number of training epochs
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use tsetlin::sequence::SequenceTsetlinMachine;
+    ///
+    /// let sequences = vec![array![[true], [false]], array![[false], [false], [true]]];
+    /// let labels = Array1::from(vec![true, false]);
+    ///
+    /// let mut machine = SequenceTsetlinMachine::with_defaults(1, 10).seed(1);
+    /// machine.fit(&sequences, &labels, 10);
+    /// assert_eq!(machine.predict(&sequences).len(), 2);
+    /// ```
+    pub fn fit<S: AsBoolSamples>(&mut self, sequences: &[S], labels: &Array1<bool>, epochs: usize) {
+        assert_eq!(sequences.len(), labels.len());
+
+        let mut indices: Vec<usize> = (0..sequences.len()).collect();
+        for _ in 0..epochs {
+            indices.shuffle(&mut self.rng);
+
+            for &idx in &indices {
+                let input = self.final_input(&sequences[idx]);
+                self.clause_bank.update(
+                    &input,
+                    labels[idx],
+                    self.threshold,
+                    self.specificity,
+                    &mut self.rng,
+                );
+            }
+        }
+    }
+
+    /// Vote sum of a sequence at its last timestep
+    pub fn vote_single<S: AsBoolSamples + ?Sized>(&self, sequence: &S) -> i32 {
+        self.clause_bank.vote(&self.final_input(sequence))
+    }
+
+    /// Predict the label of a single sequence
+    pub fn predict_single<S: AsBoolSamples + ?Sized>(&self, sequence: &S) -> bool {
+        self.vote_single(sequence) > 0
+    }
+
+    /// Vote sum of each sequence
+    pub fn predict_votes<S: AsBoolSamples>(&self, sequences: &[S]) -> Array1<i32> {
+        sequences.iter().map(|sequence| self.vote_single(sequence)).collect()
+    }
+
+    /// Predict the label of each sequence
+    pub fn predict<S: AsBoolSamples>(&self, sequences: &[S]) -> Array1<bool> {
+        sequences.iter().map(|sequence| self.predict_single(sequence)).collect()
+    }
+
+    /// Accuracy on labelled sequences
+    pub fn evaluate<S: AsBoolSamples>(&self, sequences: &[S], labels: &Array1<bool>) -> f64 {
+        let predictions = self.predict(sequences);
+        let correct = predictions
+            .iter()
+            .zip(labels.iter())
+            .filter(|(&pred, &actual)| pred == actual)
+            .count();
+
+        correct as f64 / labels.len() as f64
+    }
+
+    /// The shared clause bank
+    ///
+    /// Feature `num_features + j` of its clauses is the output of clause `j`
+    /// at the previous timestep.
+    pub fn clause_bank(&self) -> &ClauseBank {
+        &self.clause_bank
+    }
+
+    /// Mutable access to the shared clause bank
+    pub fn clause_bank_mut(&mut self) -> &mut ClauseBank {
+        &mut self.clause_bank
+    }
+
+    /// Number of features per timestep
+    pub fn num_features(&self) -> usize {
+        self.num_features
+    }
+
+    /// Number of clauses
+    pub fn num_clauses(&self) -> usize {
+        self.clause_bank.num_clauses()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    /// Positive clause 0 fires when negative clause 2 (`x0`) fired one step earlier
+    fn machine() -> SequenceTsetlinMachine {
+        let mut machine = SequenceTsetlinMachine::with_defaults(1, 4);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 3, negated: false }, true);
+        bank.clause_mut(2).set_included(Literal { feature: 0, negated: false }, true);
+        machine
+    }
+
+    #[test]
+    fn test_final_input_carries_clause_outputs() {
+        let machine = machine();
+        // Step 0 fires clauses 1, 2 and 3; clause 0 needs a previous step
+        assert_eq!(
+            machine.final_input(&array![[true], [false]]),
+            vec![false, false, true, true, true]
+        );
+        assert_eq!(machine.final_input(&array![[true]]), vec![true, false, false, false, false]);
+    }
+
+    #[test]
+    fn test_predicts_from_earlier_timesteps() {
+        let machine = machine();
+        let sequences = vec![
+            array![[true], [false]],
+            array![[false], [false]],
+            array![[false], [true], [false]],
+            array![[true], [false], [false]],
+        ];
+        let labels = array![true, false, true, false];
+
+        assert_eq!(machine.predict_votes(&sequences), array![1, 0, 1, 0]);
+        assert_eq!(machine.evaluate(&sequences, &labels), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sequences must have at least one timestep")]
+    fn test_empty_sequence() {
+        let machine = SequenceTsetlinMachine::with_defaults(2, 4);
+        machine.predict_single(&ndarray::Array2::from_elem((0, 2), false));
+    }
+}
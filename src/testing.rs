@@ -0,0 +1,49 @@
+//! Fixtures shared by the unit tests of several modules
+
+use crate::clause::{Clause, ClauseBank, Literal};
+use crate::machine::TsetlinMachine;
+
+/// Include one literal in each clause, cycling through every literal
+///
+/// Feedback only refines literals a clause already includes, so tests that
+/// check learning start from this spread and let training drop the literals
+/// that disagree with the labels.
+pub(crate) fn spread_literals<'a>(clauses: impl IntoIterator<Item = &'a mut Clause>) {
+    for (position, clause) in clauses.into_iter().enumerate() {
+        let literal = nth_literal(position, clause.num_features());
+        clause.set_included(literal, true);
+    }
+}
+
+/// [`spread_literals`] over the positive and the negative clauses of a bank
+///
+/// Each polarity cycles through the literals on its own, so every literal
+/// votes both ways when the bank has enough clauses.
+pub(crate) fn spread_bank_literals(bank: &mut ClauseBank) {
+    let polarities = bank.polarities().to_vec();
+    for polarity in [true, false] {
+        let indices = (0..polarities.len()).filter(|&index| polarities[index] == polarity);
+        for (position, index) in indices.enumerate() {
+            let clause = bank.clause_mut(index);
+            let literal = nth_literal(position, clause.num_features());
+            clause.set_included(literal, true);
+        }
+    }
+}
+
+/// Machine whose two clauses predict that `feature` is set
+pub(crate) fn copier(num_features: usize, feature: usize) -> TsetlinMachine {
+    let mut machine = TsetlinMachine::with_defaults(num_features, 2);
+    let bank = machine.clause_bank_mut();
+    bank.clause_mut(0).set_included(Literal { feature, negated: false }, true);
+    bank.clause_mut(1).set_included(Literal { feature, negated: true }, true);
+    machine
+}
+
+/// Literal at `position` in the order x0, ¬x0, x1, ¬x1, ...
+fn nth_literal(position: usize, num_features: usize) -> Literal {
+    Literal {
+        feature: (position / 2) % num_features,
+        negated: position % 2 == 1,
+    }
+}
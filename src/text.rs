@@ -0,0 +1,541 @@
+//! Text classification with a boolean bag of words
+//!
+//! The pipeline used for IMDB-style sentiment benchmarks: a [`Tokenizer`]
+//! splits a document into words or n-grams, a [`Vocabulary`] keeps the words found in
+//! the most training documents, and every document becomes one boolean
+//! feature per vocabulary word that is true when the word occurs in it. A
+//! [`TextClassifier`] bundles the vocabulary with a machine so raw documents
+//! go in and predictions come out. [`load_imdb`] reads the `pos`/`neg`
+//! directories of the Large Movie Review Dataset.
+
+use crate::input::BinaryLabel;
+use crate::machine::TsetlinMachine;
+use ndarray::{Array1, Array2};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io;
+use std::path::Path;
+
+/// Split a document into words with the default [`Tokenizer`]
+///
+/// HTML tags such as the `<br />` line breaks in IMDB reviews are dropped,
+/// and words are lowercase runs of letters, digits and inner apostrophes.
+///
+/// # Example
+/// ```
+/// use tsetlin::text::tokenize;
+///
+/// let tokens = tokenize("Didn't LIKE it.<br />2 stars");
+/// assert_eq!(tokens, vec!["didn't", "like", "it", "2", "stars"]);
+/// ```
+pub fn tokenize(text: &str) -> Vec<String> {
+    Tokenizer::new().tokenize(text)
+}
+
+/// Word normalization applied by a [`Tokenizer`] after lowercasing
+pub trait Stemmer: Debug + Send + Sync {
+    /// Stem of a word; an empty stem drops the word
+    fn stem(&self, word: &str) -> String;
+
+    /// Boxed copy of the stemmer, so tokenizers holding one can be cloned
+    fn clone_box(&self) -> Box<dyn Stemmer>;
+}
+
+impl Clone for Box<dyn Stemmer> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Stemmer removing the first matching suffix of a list
+///
+/// A suffix is only removed when at least `min_stem` characters remain, so
+/// short words are left alone.
+///
+/// # Example
+/// ```
+/// use tsetlin::text::{Stemmer, SuffixStemmer};
+///
+/// let stemmer = SuffixStemmer::english();
+/// assert_eq!(stemmer.stem("boringly"), "boring");
+/// assert_eq!(stemmer.stem("films"), "film");
+/// assert_eq!(stemmer.stem("is"), "is");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuffixStemmer {
+    /// Suffixes tried in order
+    suffixes: Vec<String>,
+    /// Fewest characters left after removing a suffix
+    min_stem: usize,
+}
+
+impl SuffixStemmer {
+    /// Stemmer for the given suffixes, tried in order
+    pub fn new<T: Into<String>>(suffixes: impl IntoIterator<Item = T>, min_stem: usize) -> Self {
+        let suffixes = suffixes.into_iter().map(Into::into).collect();
+        Self { suffixes, min_stem }
+    }
+
+    /// Common English inflection suffixes (`ing`, `ed`, `ly`, `es`, `s`)
+    pub fn english() -> Self {
+        Self::new(["ing", "ed", "ly", "es", "s"], 3)
+    }
+}
+
+impl Stemmer for SuffixStemmer {
+    fn stem(&self, word: &str) -> String {
+        for suffix in &self.suffixes {
+            if let Some(stem) = word.strip_suffix(suffix.as_str()) {
+                if stem.chars().count() >= self.min_stem {
+                    return stem.to_string();
+                }
+            }
+        }
+        word.to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Stemmer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Configurable splitting of documents into tokens
+///
+/// Words are runs of Unicode letters and digits, joined by inner apostrophes
+/// (`'` or `’`, both kept as `'`). By default HTML tags are dropped, words
+/// are lowercased, punctuation is discarded and every word is one token.
+///
+/// # Example
+/// ```
+/// use tsetlin::text::{SuffixStemmer, Tokenizer};
+///
+/// let tokenizer = Tokenizer::new().stemmer(SuffixStemmer::english()).ngrams(1, 2);
+/// assert_eq!(
+///     tokenizer.tokenize("Loved the ENDING"),
+///     vec!["lov", "the", "end", "lov the", "the end"]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+    /// Whether words are lowercased
+    lowercase: bool,
+    /// Whether punctuation is dropped instead of becoming tokens
+    strip_punctuation: bool,
+    /// Whether text between `<` and `>` is dropped
+    strip_html: bool,
+    /// Optional normalization of every word
+    stemmer: Option<Box<dyn Stemmer>>,
+    /// Shortest and longest n-grams produced
+    ngrams: (usize, usize),
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tokenizer {
+    /// Tokenizer with the default settings
+    pub fn new() -> Self {
+        Self {
+            lowercase: true,
+            strip_punctuation: true,
+            strip_html: true,
+            stemmer: None,
+            ngrams: (1, 1),
+        }
+    }
+
+    /// Set whether words are lowercased (default: true)
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Set whether punctuation is dropped (default: true)
+    ///
+    /// When kept, every punctuation or symbol character is its own token, so
+    /// `"great!!"` gives `great`, `!` and `!`.
+    pub fn strip_punctuation(mut self, strip: bool) -> Self {
+        self.strip_punctuation = strip;
+        self
+    }
+
+    /// Set whether HTML tags are dropped (default: true)
+    pub fn strip_html(mut self, strip: bool) -> Self {
+        self.strip_html = strip;
+        self
+    }
+
+    /// Normalize every word with a stemmer (default: none)
+    pub fn stemmer(mut self, stemmer: impl Stemmer + 'static) -> Self {
+        self.stemmer = Some(Box::new(stemmer));
+        self
+    }
+
+    /// Produce every n-gram of `min` to `max` consecutive tokens (default: 1, 1)
+    ///
+    /// The words of an n-gram are joined by single spaces. N-grams are formed
+    /// after stemming and span kept punctuation tokens.
+    pub fn ngrams(mut self, min: usize, max: usize) -> Self {
+        assert!(min >= 1 && min <= max, "N-gram range must satisfy 1 <= min <= max");
+        self.ngrams = (min, max);
+        self
+    }
+
+    /// Tokens of a document, all unigrams first, then bigrams and so on
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut in_tag = false;
+        for c in text.chars() {
+            let c = if c == '’' { '\'' } else { c };
+            let mut closes_tag = false;
+            match c {
+                '<' if self.strip_html => in_tag = true,
+                '>' if in_tag => (in_tag, closes_tag) = (false, true),
+                _ if in_tag => {}
+                _ if c.is_alphanumeric() || (c == '\'' && !word.is_empty()) => {
+                    if self.lowercase {
+                        word.extend(c.to_lowercase());
+                    } else {
+                        word.push(c);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            self.push_word(&mut words, &mut word);
+            if !(in_tag || closes_tag || self.strip_punctuation || c.is_whitespace()) {
+                words.push(c.to_string());
+            }
+        }
+        self.push_word(&mut words, &mut word);
+
+        let (min, max) = self.ngrams;
+        let mut tokens = Vec::new();
+        for n in min..=max.min(words.len()) {
+            tokens.extend(words.windows(n).map(|gram| gram.join(" ")));
+        }
+        tokens
+    }
+
+    /// Move a finished word into `words`, trimmed and stemmed
+    fn push_word(&self, words: &mut Vec<String>, word: &mut String) {
+        let trimmed = word.trim_end_matches('\'');
+        let stemmed = match &self.stemmer {
+            Some(stemmer) if !trimmed.is_empty() => stemmer.stem(trimmed),
+            _ => trimmed.to_string(),
+        };
+        let stemmed = stemmed.trim_end_matches('\'');
+        if !stemmed.is_empty() {
+            words.push(stemmed.to_string());
+        }
+        word.clear();
+    }
+}
+
+/// Words that become bag-of-words features, in feature order
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Vocabulary {
+    /// Word of each feature
+    tokens: Vec<String>,
+    /// Feature index of each word
+    index: HashMap<String, usize>,
+}
+
+impl Vocabulary {
+    /// Vocabulary of the given words, in order; repeated words are kept once
+    pub fn new<T: Into<String>>(tokens: impl IntoIterator<Item = T>) -> Self {
+        let mut vocabulary = Self::default();
+        for token in tokens {
+            let token = token.into();
+            if !vocabulary.index.contains_key(&token) {
+                vocabulary.index.insert(token.clone(), vocabulary.tokens.len());
+                vocabulary.tokens.push(token);
+            }
+        }
+        vocabulary
+    }
+
+    /// Keep the `max_size` words that occur in the most documents
+    ///
+    /// Words found in fewer than `min_documents` documents are dropped. Ties
+    /// are broken alphabetically, so the result does not depend on hashing.
+    ///
+    /// # Arguments
+    /// * `documents` - Tokenized training documents
+    /// * `max_size` - Largest number of words kept
+    /// * `min_documents` - Fewest documents a kept word occurs in
+    ///
+    /// # Example
+    /// ```
+    /// use tsetlin::text::{tokenize, Vocabulary};
+    ///
+    /// let texts = ["good film", "bad film", "good"];
+    /// let documents: Vec<_> = texts.iter().map(|text| tokenize(text)).collect();
+    /// let vocabulary = Vocabulary::fit(&documents, 2, 1);
+    /// assert_eq!(vocabulary.tokens(), &["film", "good"]);
+    /// ```
+    pub fn fit<D: AsRef<[String]>>(documents: &[D], max_size: usize, min_documents: usize) -> Self {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for document in documents {
+            let mut seen: Vec<&str> = document.as_ref().iter().map(String::as_str).collect();
+            seen.sort_unstable();
+            seen.dedup();
+            for token in seen {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+        }
+
+        let mut ranked: Vec<(&str, usize)> =
+            counts.into_iter().filter(|&(_, count)| count >= min_documents).collect();
+        ranked.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        ranked.truncate(max_size);
+        Self::new(ranked.into_iter().map(|(token, _)| token))
+    }
+
+    /// Number of words
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether the vocabulary has no words
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Words in feature order
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Feature index of a word
+    pub fn get(&self, token: &str) -> Option<usize> {
+        self.index.get(token).copied()
+    }
+
+    /// Boolean bag of words of one tokenized document
+    pub fn encode(&self, tokens: &[String]) -> Vec<bool> {
+        let mut features = vec![false; self.len()];
+        for index in tokens.iter().filter_map(|token| self.get(token)) {
+            features[index] = true;
+        }
+        features
+    }
+
+    /// Boolean bag of words of tokenized documents (documents x words)
+    pub fn transform<D: AsRef<[String]>>(&self, documents: &[D]) -> Array2<bool> {
+        let mut features = Array2::from_elem((documents.len(), self.len()), false);
+        for (row, document) in documents.iter().enumerate() {
+            for index in document.as_ref().iter().filter_map(|token| self.get(token)) {
+                features[[row, index]] = true;
+            }
+        }
+        features
+    }
+}
+
+/// Machine classifying raw documents through a vocabulary
+#[derive(Debug, Clone)]
+pub struct TextClassifier {
+    /// Splits documents into tokens
+    tokenizer: Tokenizer,
+    /// Words used as features
+    vocabulary: Vocabulary,
+    /// Machine with one feature per vocabulary word
+    machine: TsetlinMachine,
+}
+
+impl TextClassifier {
+    /// Combine a vocabulary with a machine over its words
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::array;
+    /// use tsetlin::text::{tokenize, TextClassifier, Vocabulary};
+    /// use tsetlin::TsetlinMachine;
+    ///
+    /// let reviews = ["A great film", "Dull and slow", "great acting", "slow plot"];
+    /// let tokens: Vec<_> = reviews.iter().map(|r| tokenize(r)).collect();
+    /// let vocabulary = Vocabulary::fit(&tokens, 5000, 1);
+    /// let machine = TsetlinMachine::new(vocabulary.len(), 20, 3.0, 10.0).seed(7);
+    ///
+    /// let mut classifier = TextClassifier::new(vocabulary, machine);
+    /// classifier.fit(&reviews, &array![true, false, true, false], 10);
+    /// assert_eq!(classifier.predict(&["great plot"]).len(), 1);
+    /// ```
+    pub fn new(vocabulary: Vocabulary, machine: TsetlinMachine) -> Self {
+        assert_eq!(
+            machine.num_features(),
+            vocabulary.len(),
+            "Machine must have one feature per vocabulary word"
+        );
+        Self { tokenizer: Tokenizer::new(), vocabulary, machine }
+    }
+
+    /// Tokenize documents with `tokenizer` (default: `Tokenizer::new()`)
+    ///
+    /// Use the tokenizer that built the vocabulary, or its n-grams and
+    /// stems will never match.
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// Bag-of-words features of raw documents
+    pub fn encode<D: AsRef<str>>(&self, documents: &[D]) -> Array2<bool> {
+        let tokenize = |document: &D| self.tokenizer.tokenize(document.as_ref());
+        let tokens: Vec<Vec<String>> = documents.iter().map(tokenize).collect();
+        self.vocabulary.transform(&tokens)
+    }
+
+    /// Train on raw documents
+    pub fn fit<D: AsRef<str>, L: BinaryLabel>(
+        &mut self,
+        documents: &[D],
+        labels: &Array1<L>,
+        epochs: usize,
+    ) {
+        let features = self.encode(documents);
+        self.machine.fit(&features, labels, epochs);
+    }
+
+    /// Predict raw documents
+    pub fn predict<D: AsRef<str>>(&self, documents: &[D]) -> Array1<bool> {
+        self.machine.predict(&self.encode(documents))
+    }
+
+    /// Vote sums of raw documents
+    pub fn predict_votes<D: AsRef<str>>(&self, documents: &[D]) -> Array1<i32> {
+        self.machine.predict_votes(&self.encode(documents))
+    }
+
+    /// Accuracy on raw documents
+    pub fn evaluate<D: AsRef<str>, L: BinaryLabel>(
+        &self,
+        documents: &[D],
+        labels: &Array1<L>,
+    ) -> f64 {
+        self.machine.evaluate(&self.encode(documents), labels)
+    }
+
+    /// Tokenizer applied to documents
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+
+    /// Words used as features
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    /// Underlying machine
+    pub fn machine(&self) -> &TsetlinMachine {
+        &self.machine
+    }
+
+    /// Mutable access to the underlying machine
+    pub fn machine_mut(&mut self) -> &mut TsetlinMachine {
+        &mut self.machine
+    }
+}
+
+/// Read one split of the Large Movie Review Dataset (IMDB)
+///
+/// `dir` is a split directory such as `aclImdb/train`, holding one review
+/// per file in `pos` and `neg`. Positive reviews come first and are
+/// labelled `true`; files are read in name order.
+pub fn load_imdb(dir: impl AsRef<Path>) -> io::Result<(Vec<String>, Array1<bool>)> {
+    let mut documents = Vec::new();
+    let mut labels = Vec::new();
+    for (class, label) in [("pos", true), ("neg", false)] {
+        let mut paths = std::fs::read_dir(dir.as_ref().join(class))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.sort();
+        for path in paths.into_iter().filter(|path| path.is_file()) {
+            documents.push(std::fs::read_to_string(path)?);
+            labels.push(label);
+        }
+    }
+    Ok((documents, Array1::from(labels)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("'Tis <b>GREAT</b>!! Ünïcode's");
+        assert_eq!(tokens, vec!["tis", "great", "ünïcode's"]);
+        assert!(tokenize("<br /> ... ").is_empty());
+    }
+
+    #[test]
+    fn test_vocabulary_bag_of_words() {
+        let texts = ["the plot the plot", "the acting", "plot twist"];
+        let documents: Vec<Vec<String>> = texts.iter().map(|d| tokenize(d)).collect();
+        let vocabulary = Vocabulary::fit(&documents, 10, 2);
+        assert_eq!(vocabulary.tokens(), &["plot", "the"]);
+        assert_eq!(vocabulary.get("twist"), None);
+        let expected = array![[true, true], [false, true], [true, false]];
+        assert_eq!(vocabulary.transform(&documents), expected);
+        assert_eq!(vocabulary.encode(&tokenize("The end")), vec![false, true]);
+    }
+
+    #[test]
+    fn test_classifier_scores_documents() {
+        let vocabulary = Vocabulary::new(["great", "awful"]);
+        let mut classifier = TextClassifier::new(vocabulary, TsetlinMachine::with_defaults(2, 2));
+        let bank = classifier.machine_mut().clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 1, negated: false }, true);
+
+        let reviews = ["Great!", "awful, just awful", "great cast, awful script"];
+        assert_eq!(classifier.predict_votes(&reviews), array![1, -1, 0]);
+        assert_eq!(classifier.evaluate(&reviews, &array![true, false, false]), 1.0);
+    }
+
+    #[test]
+    fn test_load_imdb() {
+        let dir = std::env::temp_dir().join(format!("tsetlin-imdb-{}", std::process::id()));
+        for (class, name, text) in [("pos", "1_9.txt", "loved it"), ("neg", "0_2.txt", "no")] {
+            std::fs::create_dir_all(dir.join(class)).unwrap();
+            std::fs::write(dir.join(class).join(name), text).unwrap();
+        }
+
+        let (documents, labels) = load_imdb(&dir).unwrap();
+        assert_eq!(documents, vec!["loved it", "no"]);
+        assert_eq!(labels, array![true, false]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tokenizer_options() {
+        let text = "Wow!! <i>Hablé</i> con ÉL’s fans";
+        let raw = Tokenizer::new().lowercase(false).strip_punctuation(false).strip_html(false);
+        let expected = ["Wow", "!", "!", "<", "i", ">", "Hablé", "<", "/", "i", ">", "con", "ÉL's",
+            "fans"];
+        assert_eq!(raw.tokenize(text), expected);
+
+        let stemmed = Tokenizer::new().stemmer(SuffixStemmer::english()).ngrams(2, 3);
+        let expected = ["wow hablé", "hablé con", "con él", "él fan", "wow hablé con",
+            "hablé con él", "con él fan"];
+        assert_eq!(stemmed.tokenize(text), expected);
+        assert!(stemmed.tokenize("one").is_empty());
+    }
+
+    #[test]
+    fn test_classifier_uses_its_tokenizer() {
+        let tokenizer = Tokenizer::new().ngrams(2, 2);
+        let vocabulary = Vocabulary::new(["not good"]);
+        let classifier = TextClassifier::new(vocabulary, TsetlinMachine::with_defaults(1, 2))
+            .with_tokenizer(tokenizer);
+        assert_eq!(classifier.encode(&["Not good.", "good, not"]), array![[true], [false]]);
+    }
+}
@@ -0,0 +1,226 @@
+//! Recording of automaton states during training
+//!
+//! A [`TrajectoryRecorder`] is a [`Callback`] that snapshots the states of a
+//! chosen set of literal automata every few epochs. Snapshots are stored as
+//! one flat list of states, and [`TrajectoryRecorder::to_bytes`] writes each
+//! snapshot as varint differences from the previous one, so slowly moving
+//! automata cost about a byte per state. The trace is meant for studying how
+//! clauses form, e.g. by plotting a literal's state against the epoch.
+
+use crate::callback::Callback;
+use crate::clause::Literal;
+use crate::compiled::{read_i32, read_usize, read_varint, write_varint, zigzag, DecodeError};
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use ndarray::Array1;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+/// Leading bytes of a serialized trace
+const MAGIC: &[u8; 4] = b"TMT1";
+
+/// Snapshots of selected automaton states taken during training
+///
+/// # Example
+/// ```
+/// use tsetlin::clause::Literal;
+/// use tsetlin::trajectory::TrajectoryRecorder;
+/// use tsetlin::{generate_xor_dataset, TsetlinMachine};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1);
+/// let x0 = Literal { feature: 0, negated: false };
+/// let mut recorder = TrajectoryRecorder::new(vec![(0, x0), (5, x0)], 2);
+/// machine.fit_with_callback(&features, &labels, 10, &mut recorder);
+///
+/// assert_eq!(recorder.num_snapshots(), 5);
+/// for (epoch, states) in recorder.iter() {
+///     println!("epoch {epoch}: {states:?}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrajectoryRecorder {
+    /// Clause index and literal of every tracked automaton
+    tracked: Vec<(usize, Literal)>,
+    /// Epochs between snapshots
+    interval: usize,
+    /// Epoch counter of the machine at each snapshot
+    epochs: Vec<usize>,
+    /// States of the tracked automata, one block of `tracked.len()` per snapshot
+    states: Vec<i32>,
+}
+
+impl TrajectoryRecorder {
+    /// Track the given `(clause, literal)` automata every `interval` epochs
+    ///
+    /// Snapshots are taken when the machine's total epoch count is a
+    /// multiple of `interval`.
+    pub fn new(tracked: Vec<(usize, Literal)>, interval: usize) -> Self {
+        assert!(interval > 0, "Snapshot interval must be at least one epoch");
+
+        Self { tracked, interval, epochs: Vec::new(), states: Vec::new() }
+    }
+
+    /// Track every literal automaton of every clause of `machine`
+    pub fn all_literals(machine: &TsetlinMachine, interval: usize) -> Self {
+        let tracked = (0..machine.num_clauses())
+            .flat_map(|clause| {
+                (0..machine.num_features()).flat_map(move |feature| {
+                    [false, true].map(|negated| (clause, Literal { feature, negated }))
+                })
+            })
+            .collect();
+        Self::new(tracked, interval)
+    }
+
+    /// Snapshot the tracked states of `machine` now
+    pub fn record(&mut self, machine: &TsetlinMachine) {
+        let clauses = machine.clause_bank().clauses();
+        for &(clause, literal) in &self.tracked {
+            assert!(clause < clauses.len(), "Tracked clause {clause} does not exist");
+            self.states.push(clauses[clause].literal_state(literal));
+        }
+        self.epochs.push(machine.epochs_trained());
+    }
+
+    /// Tracked `(clause, literal)` automata, in the order of every snapshot
+    pub fn tracked(&self) -> &[(usize, Literal)] {
+        &self.tracked
+    }
+
+    /// Number of snapshots taken
+    pub fn num_snapshots(&self) -> usize {
+        self.epochs.len()
+    }
+
+    /// Epoch and tracked states of every snapshot, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[i32])> + '_ {
+        // `max(1)` keeps `chunks` valid when nothing is tracked
+        let states = self.states.chunks(self.tracked.len().max(1));
+        self.epochs.iter().copied().zip(states.chain(std::iter::repeat(&[][..])))
+    }
+
+    /// Serialize the trace, storing each snapshot as differences from the previous one
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        write_varint(&mut out, self.interval as u64);
+        write_varint(&mut out, self.tracked.len() as u64);
+        for &(clause, literal) in &self.tracked {
+            write_varint(&mut out, clause as u64);
+            write_varint(&mut out, literal.feature as u64);
+            write_varint(&mut out, u64::from(literal.negated));
+        }
+
+        write_varint(&mut out, self.num_snapshots() as u64);
+        let mut previous = vec![0i32; self.tracked.len()];
+        for (epoch, states) in self.iter() {
+            write_varint(&mut out, epoch as u64);
+            for (previous, &state) in previous.iter_mut().zip(states) {
+                write_varint(&mut out, zigzag(state.wrapping_sub(*previous)));
+                *previous = state;
+            }
+        }
+        out
+    }
+
+    /// Read a trace written by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| DecodeError::new("missing trace header"))?;
+        let interval = read_usize(&mut reader)?;
+        if interval == 0 {
+            return Err(DecodeError::new("invalid snapshot interval"));
+        }
+        let num_tracked = read_usize(&mut reader)?;
+        let mut tracked = Vec::with_capacity(num_tracked.min(reader.len()));
+        for _ in 0..num_tracked {
+            let clause = read_usize(&mut reader)?;
+            let feature = read_usize(&mut reader)?;
+            let negated = match read_varint(&mut reader)? {
+                0 => false,
+                1 => true,
+                _ => return Err(DecodeError::new("invalid literal sign")),
+            };
+            tracked.push((clause, Literal { feature, negated }));
+        }
+
+        let mut recorder = Self::new(tracked, interval);
+        let num_snapshots = read_usize(&mut reader)?;
+        let mut previous = vec![0i32; num_tracked];
+        for _ in 0..num_snapshots {
+            recorder.epochs.push(read_usize(&mut reader)?);
+            for previous in &mut previous {
+                *previous = previous.wrapping_add(read_i32(&mut reader)?);
+                recorder.states.push(*previous);
+            }
+        }
+        if !reader.is_empty() {
+            return Err(DecodeError::new("trailing bytes"));
+        }
+        Ok(recorder)
+    }
+
+    /// Write the trace of `to_bytes` to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+}
+
+impl Callback for TrajectoryRecorder {
+    fn on_epoch_end(
+        &mut self,
+        machine: &TsetlinMachine,
+        _features: &dyn AsBoolSamples,
+        _labels: &Array1<bool>,
+    ) -> ControlFlow<()> {
+        if machine.epochs_trained().is_multiple_of(self.interval) {
+            self.record(machine);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_every_interval() {
+        let (features, labels) = crate::generate_xor_dataset();
+        let mut machine = TsetlinMachine::with_defaults(2, 4).seed(3);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(3).set_included(Literal { feature: 1, negated: true }, true);
+
+        let mut recorder = TrajectoryRecorder::all_literals(&machine, 3);
+        assert_eq!(recorder.tracked().len(), 16);
+        machine.fit_with_callback(&features, &labels, 6, &mut recorder);
+
+        let epochs: Vec<usize> = recorder.iter().map(|(epoch, _)| epoch).collect();
+        assert_eq!(epochs, vec![3, 6]);
+        let expected: Vec<i32> = recorder
+            .tracked()
+            .iter()
+            .map(|&(clause, literal)| machine.clause_bank().clauses()[clause].literal_state(literal))
+            .collect();
+        let (_, last) = recorder.iter().last().unwrap();
+        assert_eq!(last, expected.as_slice());
+    }
+
+    #[test]
+    fn test_trace_round_trip() {
+        let literal = Literal { feature: 1, negated: true };
+        let mut machine = TsetlinMachine::with_defaults(2, 2);
+        let mut recorder = TrajectoryRecorder::new(vec![(1, literal)], 1);
+        recorder.record(&machine);
+        machine.clause_bank_mut().clause_mut(1).set_literal_state(literal, 40);
+        recorder.record(&machine);
+
+        let bytes = recorder.to_bytes();
+        assert_eq!(TrajectoryRecorder::from_bytes(&bytes), Ok(recorder.clone()));
+        assert!(TrajectoryRecorder::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let states: Vec<&[i32]> = recorder.iter().map(|(_, states)| states).collect();
+        assert_eq!(states[1], &[40]);
+    }
+}
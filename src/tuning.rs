@@ -0,0 +1,172 @@
+//! Post-training tuning of the decision rule
+
+use crate::input::AsBoolSamples;
+use crate::machine::TsetlinMachine;
+use ndarray::Array1;
+
+/// Validation metrics when predicting `vote > margin`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarginPoint {
+    /// Samples are positive when their vote exceeds this margin
+    pub margin: i32,
+    /// Fraction of correct predictions
+    pub accuracy: f64,
+    /// Fraction of positive predictions that are correct (0 without any)
+    pub precision: f64,
+    /// Fraction of positive samples predicted positive (0 without any)
+    pub recall: f64,
+}
+
+/// Metrics across all integer vote margins and the best margin per metric
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginSweep {
+    /// One point per margin, in increasing order of margin
+    pub points: Vec<MarginPoint>,
+    /// Margin with the highest accuracy
+    pub best_accuracy: i32,
+    /// Margin with the highest precision
+    pub best_precision: i32,
+    /// Margin with the highest recall
+    pub best_recall: i32,
+}
+
+impl MarginSweep {
+    /// Point of a margin inside the swept range
+    pub fn point(&self, margin: i32) -> Option<&MarginPoint> {
+        let first = self.points.first()?.margin;
+        let offset = usize::try_from(i64::from(margin) - i64::from(first)).ok()?;
+        self.points.get(offset)
+    }
+}
+
+/// Evaluate the rule `vote > margin` for every integer margin on validation data
+///
+/// The sweep covers margins from just below the lowest vote, where every
+/// sample is positive, to the highest vote, where none is. Ties between
+/// margins are broken towards the default margin 0.
+///
+/// # Arguments
+/// * `machine` - Trained machine
+/// * `val_x` - Validation features
+/// * `val_y` - Validation labels
+///
+/// # Example
+/// ```
+/// use tsetlin::{generate_xor_dataset, tuning, TsetlinMachine};
+///
+/// let (features, labels) = generate_xor_dataset();
+/// let mut machine = TsetlinMachine::with_defaults(2, 10).seed(1);
+/// machine.fit(&features, &labels, 20);
+///
+/// let sweep = tuning::sweep_margin(&machine, &features, &labels);
+/// let best = sweep.point(sweep.best_accuracy).unwrap();
+/// let votes = machine.predict_votes(&features);
+/// let correct = votes.iter().zip(&labels).filter(|&(&v, &y)| (v > best.margin) == y).count();
+/// assert_eq!(best.accuracy, correct as f64 / labels.len() as f64);
+/// ```
+pub fn sweep_margin<S: AsBoolSamples + ?Sized>(
+    machine: &TsetlinMachine,
+    val_x: &S,
+    val_y: &Array1<bool>,
+) -> MarginSweep {
+    assert_eq!(val_x.num_samples(), val_y.len(), "Features and labels must have the same length");
+    assert!(!val_y.is_empty(), "Margin sweep needs at least one sample");
+
+    let votes = machine.predict_votes(val_x);
+    let lowest = votes.iter().copied().min().unwrap_or(0).saturating_sub(1);
+    let highest = votes.iter().copied().max().unwrap_or(0);
+    let positives = val_y.iter().filter(|&&label| label).count();
+
+    let points: Vec<MarginPoint> = (lowest..=highest)
+        .map(|margin| {
+            let (mut true_positives, mut false_positives) = (0, 0);
+            for (&vote, &label) in votes.iter().zip(val_y) {
+                if vote > margin {
+                    if label {
+                        true_positives += 1;
+                    } else {
+                        false_positives += 1;
+                    }
+                }
+            }
+            let true_negatives = val_y.len() - positives - false_positives;
+            MarginPoint {
+                margin,
+                accuracy: ratio(true_positives + true_negatives, val_y.len()),
+                precision: ratio(true_positives, true_positives + false_positives),
+                recall: ratio(true_positives, positives),
+            }
+        })
+        .collect();
+
+    MarginSweep {
+        best_accuracy: best_margin(&points, |point| point.accuracy),
+        best_precision: best_margin(&points, |point| point.precision),
+        best_recall: best_margin(&points, |point| point.recall),
+        points,
+    }
+}
+
+/// Margin maximizing a metric, preferring margins closer to 0 on ties
+fn best_margin(points: &[MarginPoint], metric: impl Fn(&MarginPoint) -> f64) -> i32 {
+    points
+        .iter()
+        .max_by(|a, b| {
+            metric(a)
+                .total_cmp(&metric(b))
+                .then(b.margin.unsigned_abs().cmp(&a.margin.unsigned_abs()))
+        })
+        .map_or(0, |point| point.margin)
+}
+
+/// `numerator / denominator`, or 0 for an empty denominator
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clause::Literal;
+    use ndarray::array;
+
+    #[test]
+    fn test_sweep_covers_all_margins() {
+        // Clause 0 votes +1 on x0, clause 1 votes -1 on NOT x0
+        let mut machine = TsetlinMachine::new(1, 2, 3.0, 2.0);
+        let bank = machine.clause_bank_mut();
+        bank.clause_mut(0).set_included(Literal { feature: 0, negated: false }, true);
+        bank.clause_mut(1).set_included(Literal { feature: 0, negated: true }, true);
+        bank.set_weight(0, 3);
+
+        let features = array![[true], [true], [false], [false]];
+        let labels = array![true, false, false, false];
+        let sweep = sweep_margin(&machine, &features, &labels);
+
+        let margins: Vec<i32> = sweep.points.iter().map(|point| point.margin).collect();
+        assert_eq!(margins, vec![-2, -1, 0, 1, 2, 3]);
+        let all_positive = sweep.point(-2).unwrap();
+        assert_eq!((all_positive.accuracy, all_positive.recall), (0.25, 1.0));
+        let default = sweep.point(0).unwrap();
+        assert_eq!((default.accuracy, default.precision), (0.75, 0.5));
+        assert_eq!(sweep.point(3).unwrap().precision, 0.0);
+        assert!(sweep.point(4).is_none());
+
+        assert_eq!(sweep.best_accuracy, 0);
+        assert_eq!(sweep.best_precision, 0);
+        assert_eq!(sweep.best_recall, 0);
+    }
+
+    #[test]
+    fn test_ties_prefer_default_margin() {
+        let points: Vec<MarginPoint> = (-3..=3)
+            .map(|margin| MarginPoint { margin, accuracy: 0.5, precision: 0.5, recall: 0.5 })
+            .collect();
+        assert_eq!(best_margin(&points, |point| point.accuracy), 0);
+        assert_eq!(best_margin(&points[4..], |point| point.accuracy), 1);
+    }
+}